@@ -0,0 +1,133 @@
+//! Derive macros of the [CloudEvents Rust SDK](https://docs.rs/cloudevents-sdk), re-exported by
+//! the `cloudevents-sdk` crate with the `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use std::collections::HashMap;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+/// Derive `cloudevents::event::EventDispatch` and `TryFrom<cloudevents::Event>` for an enum
+/// whose variants are annotated with the event type they match, e.g.
+/// `#[cloudevents(ty = "com.example.order.created")]`.
+///
+/// The variants hold either nothing, or a single field deserialized from the data of the event.
+/// See `cloudevents::event::EventDispatch` for an example.
+#[proc_macro_derive(EventDispatch, attributes(cloudevents))]
+pub fn derive_event_dispatch(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "EventDispatch can only be derived for enums",
+            ))
+        }
+    };
+
+    let mut types = Vec::new();
+    let mut seen = HashMap::new();
+    let mut decode_branches = Vec::new();
+    let mut type_arms = Vec::new();
+    for variant in variants {
+        let ty = variant_type(variant)?;
+        if let Some(previous) = seen.insert(ty.value(), &variant.ident) {
+            return Err(syn::Error::new_spanned(
+                &ty,
+                format!(
+                    "the event type `{}` is already matched by the variant `{}`",
+                    ty.value(),
+                    previous
+                ),
+            ));
+        }
+        let ident = &variant.ident;
+        match &variant.fields {
+            Fields::Unit => {
+                decode_branches.push(quote! {
+                    if ::cloudevents::AttributesReader::ty(&event) == #ty {
+                        return ::std::result::Result::Ok(Self::#ident);
+                    }
+                });
+                type_arms.push(quote! { Self::#ident => #ty });
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                decode_branches.push(quote! {
+                    if ::cloudevents::AttributesReader::ty(&event) == #ty {
+                        return <::cloudevents::event::CloudEvent<_> as ::std::convert::TryFrom<_>>::try_from(event)
+                            .map(|event| Self::#ident(event.into_data()))
+                            .map_err(|source| ::cloudevents::event::EventDispatchError::InvalidData {
+                                ty: ::std::string::ToString::to_string(#ty),
+                                source,
+                            });
+                    }
+                });
+                type_arms.push(quote! { Self::#ident(_) => #ty });
+            }
+            fields => {
+                return Err(syn::Error::new_spanned(
+                    fields,
+                    "the variants must have no fields or a single unnamed field holding the data",
+                ))
+            }
+        }
+        types.push(ty);
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::cloudevents::event::EventDispatch for #name #ty_generics #where_clause {
+            const EVENT_TYPES: &'static [&'static str] = &[#(#types),*];
+
+            fn event_type(&self) -> &'static str {
+                match *self {
+                    #(#type_arms,)*
+                }
+            }
+        }
+
+        impl #impl_generics ::std::convert::TryFrom<::cloudevents::Event> for #name #ty_generics #where_clause {
+            type Error = ::cloudevents::event::EventDispatchError;
+
+            fn try_from(event: ::cloudevents::Event) -> ::std::result::Result<Self, Self::Error> {
+                #(#decode_branches)*
+                ::std::result::Result::Err(::cloudevents::event::EventDispatchError::UnknownType {
+                    ty: ::std::string::ToString::to_string(::cloudevents::AttributesReader::ty(&event)),
+                })
+            }
+        }
+    })
+}
+
+/// Read the event type of `variant` from its `#[cloudevents(ty = "...")]` attribute.
+fn variant_type(variant: &syn::Variant) -> syn::Result<LitStr> {
+    let mut ty = None;
+    for attr in variant
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("cloudevents"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("ty") {
+                ty = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported attribute, expected `ty = \"...\"`"))
+            }
+        })?;
+    }
+    ty.ok_or_else(|| {
+        syn::Error::new_spanned(
+            &variant.ident,
+            "missing event type, add `#[cloudevents(ty = \"...\")]` to the variant",
+        )
+    })
+}
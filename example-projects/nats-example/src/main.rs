@@ -1,6 +1,6 @@
 use std::{error::Error, thread};
 
-use cloudevents::binding::nats::{MessageExt, NatsCloudEvent};
+use cloudevents::binding::nats::{ContentMode, MessageExt, NatsCloudEvent};
 use cloudevents::{Event, EventBuilder, EventBuilderV10};
 use serde_json::json;
 
@@ -19,7 +19,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         .build()
         .unwrap();
 
-    let n_msg = NatsCloudEvent::from_event(event).unwrap();
+    let n_msg = NatsCloudEvent::from_event(event, ContentMode::Structured).unwrap();
 
     let sub = nc.subscribe("test").unwrap();
 
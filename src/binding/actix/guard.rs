@@ -0,0 +1,286 @@
+use crate::binding::{is_media_type, percent_decode_header_value, CLOUDEVENTS_JSON_HEADER};
+use actix_web::dev::{self, forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::error::PayloadError;
+use actix_web::guard::{Guard, GuardContext};
+use actix_web::web::{Bytes, BytesMut};
+use actix_web::HttpMessage;
+use futures::future::{ready, LocalBoxFuture, Ready};
+use futures::{stream, FutureExt, StreamExt};
+use http::header::CONTENT_TYPE;
+use http_0_2 as http;
+use serde::Deserialize;
+use std::rc::Rc;
+
+/// Default maximum size of the structured mode requests buffered by [`SniffEventType`].
+const DEFAULT_MAX_SIZE: usize = 256 * 1024;
+
+/// Create a [`Guard`] matching the requests carrying an event of type `ty`, to route the
+/// event types to different handlers.
+///
+/// The type of the binary mode requests is read from the `ce-type` header, while the
+/// structured mode requests match only when the [`SniffEventType`] middleware read their
+/// type beforehand.
+///
+/// ```
+/// use actix_web::{web, App, HttpResponse};
+/// use cloudevents::binding::actix::{ce_type, SniffEventType};
+/// use cloudevents::Event;
+///
+/// let app = App::new()
+///     .wrap(SniffEventType::default())
+///     .service(
+///         web::resource("/")
+///             .guard(ce_type("com.example.order.created"))
+///             .to(|event: Event| async move { HttpResponse::Ok().finish() }),
+///     )
+///     .service(
+///         web::resource("/")
+///             .guard(ce_type("com.example.order.deleted"))
+///             .to(|event: Event| async move { HttpResponse::Accepted().finish() }),
+///     );
+/// ```
+pub fn ce_type(ty: impl Into<String>) -> EventTypeGuard {
+    EventTypeGuard { ty: ty.into() }
+}
+
+/// [`Guard`] created by [`ce_type`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventTypeGuard {
+    ty: String,
+}
+
+impl Guard for EventTypeGuard {
+    fn check(&self, ctx: &GuardContext<'_>) -> bool {
+        if let Some(value) = ctx.head().headers().get("ce-type") {
+            return value
+                .to_str()
+                .ok()
+                .and_then(|v| percent_decode_header_value(v).ok())
+                .is_some_and(|ty| ty == self.ty);
+        }
+        ctx.req_data()
+            .get::<SniffedEventType>()
+            .is_some_and(|sniffed| sniffed.0 == self.ty)
+    }
+}
+
+/// Type of a structured mode event, read by [`SniffEventType`].
+struct SniffedEventType(String);
+
+#[derive(Deserialize)]
+struct TypeOnly {
+    #[serde(rename = "type")]
+    ty: String,
+}
+
+/// Middleware reading the type of the structured mode events, so [`ce_type`] guards can
+/// route them too. Register it with [`App::wrap`](actix_web::App::wrap), as the guards
+/// run once the middlewares of the app were applied.
+///
+/// The body of the requests with the `application/cloudevents+json` content type is buffered
+/// and handed back to the handler, up to a maximum size of 256 KiB by default, the type of
+/// the larger requests being left unknown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SniffEventType {
+    max_size: usize,
+}
+
+impl Default for SniffEventType {
+    fn default() -> Self {
+        SniffEventType {
+            max_size: DEFAULT_MAX_SIZE,
+        }
+    }
+}
+
+impl SniffEventType {
+    /// Buffer the structured mode requests up to `max_size` bytes.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for SniffEventType
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Transform = SniffEventTypeMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(SniffEventTypeMiddleware {
+            service: Rc::new(service),
+            max_size: self.max_size,
+        }))
+    }
+}
+
+/// Service created by the [`SniffEventType`] middleware.
+pub struct SniffEventTypeMiddleware<S> {
+    service: Rc<S>,
+    max_size: usize,
+}
+
+impl<S, B> Service<ServiceRequest> for SniffEventTypeMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let max_size = self.max_size;
+        async move {
+            let structured = req
+                .headers()
+                .get(CONTENT_TYPE)
+                .is_some_and(|v| is_media_type(v, CLOUDEVENTS_JSON_HEADER));
+            if structured && !req.headers().contains_key("ce-type") {
+                sniff(&mut req, max_size).await;
+            }
+            service.call(req).await
+        }
+        .boxed_local()
+    }
+}
+
+/// Read the body of `req` up to `max_size` bytes, recording the type of the event if the whole
+/// body was read, and put it back in front of the rest of the payload.
+async fn sniff(req: &mut ServiceRequest, max_size: usize) {
+    let mut payload = req.take_payload();
+    let mut body = BytesMut::new();
+    let mut error = None;
+    let mut complete = false;
+    while body.len() <= max_size {
+        match payload.next().await {
+            Some(Ok(chunk)) => body.extend_from_slice(&chunk),
+            Some(Err(e)) => {
+                error = Some(e);
+                break;
+            }
+            None => {
+                complete = true;
+                break;
+            }
+        }
+    }
+    let body = body.freeze();
+    if complete {
+        if let Ok(event) = serde_json::from_slice::<TypeOnly>(&body) {
+            req.extensions_mut().insert(SniffedEventType(event.ty));
+        }
+    }
+
+    let head = stream::once(ready(Ok::<Bytes, PayloadError>(body)));
+    let rest = match error {
+        Some(e) => head.chain(stream::once(ready(Err(e)))).boxed_local(),
+        None if complete => head.boxed_local(),
+        None => head.chain(payload).boxed_local(),
+    };
+    req.set_payload(dev::Payload::from(rest));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use crate::{AttributesReader, Event};
+    use actix_web::{test, web, App, HttpResponse};
+
+    fn app() -> App<
+        impl actix_web::dev::ServiceFactory<
+            ServiceRequest,
+            Config = (),
+            Response = ServiceResponse<impl actix_web::body::MessageBody>,
+            Error = actix_web::Error,
+            InitError = (),
+        >,
+    > {
+        App::new()
+            .wrap(SniffEventType::default())
+            .service(
+                web::resource("/")
+                    .guard(ce_type(fixtures::ty()))
+                    .to(|event: Event| async move { HttpResponse::Ok().body(event.id().to_string()) }),
+            )
+            .service(
+                web::resource("/")
+                    .guard(ce_type("other.type"))
+                    .to(|| async { HttpResponse::Accepted().finish() }),
+            )
+    }
+
+    #[actix_rt::test]
+    async fn binary_mode() {
+        let app = test::init_service(app()).await;
+
+        let req = test::TestRequest::post()
+            .insert_header(("ce-specversion", "1.0"))
+            .insert_header(("ce-id", fixtures::id()))
+            .insert_header(("ce-type", fixtures::ty()))
+            .insert_header(("ce-source", fixtures::source()))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        assert_eq!(test::read_body(resp).await, fixtures::id());
+
+        let req = test::TestRequest::post()
+            .insert_header(("ce-type", "other.type"))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 202);
+
+        let req = test::TestRequest::post()
+            .insert_header(("ce-type", "unknown.type"))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 404);
+    }
+
+    #[actix_rt::test]
+    async fn structured_mode() {
+        let app = test::init_service(app()).await;
+
+        let body = serde_json::to_vec(&fixtures::v10::full_json_data()).unwrap();
+        let req = test::TestRequest::post()
+            .insert_header((CONTENT_TYPE, "application/cloudevents+json"))
+            .set_payload(body)
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), 200);
+        assert_eq!(test::read_body(resp).await, fixtures::id());
+
+        let req = test::TestRequest::post()
+            .insert_header((CONTENT_TYPE, "application/cloudevents+json"))
+            .set_payload(r#"{"type": "unknown.type"}"#)
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 404);
+    }
+
+    #[actix_rt::test]
+    async fn structured_mode_too_large() {
+        let app = test::init_service(
+            App::new()
+                .wrap(SniffEventType::default().max_size(8))
+                .service(
+                    web::resource("/")
+                        .guard(ce_type(fixtures::ty()))
+                        .to(|| async { HttpResponse::Ok().finish() }),
+                ),
+        )
+        .await;
+
+        let body = serde_json::to_vec(&fixtures::v10::full_json_data()).unwrap();
+        let req = test::TestRequest::post()
+            .insert_header((CONTENT_TYPE, "application/cloudevents+json"))
+            .set_payload(body)
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), 404);
+    }
+}
@@ -49,6 +49,8 @@
 //! }
 //! ```
 //!
+//! To route the event types to different handlers, see [`ce_type`].
+//!
 //! For more complex applications, use the HTTP response builder extension:
 //!
 //! ```
@@ -74,9 +76,14 @@
 
 #![deny(rustdoc::broken_intra_doc_links)]
 
+mod guard;
 mod server_request;
 mod server_response;
+#[cfg_attr(docsrs, doc(cfg(feature = "actix-ws")))]
+#[cfg(feature = "actix-ws")]
+pub mod ws;
 
+pub use guard::{ce_type, EventTypeGuard, SniffEventType, SniffEventTypeMiddleware};
 pub use server_request::request_to_event;
 pub use server_request::HttpRequestExt;
 pub use server_response::event_to_response;
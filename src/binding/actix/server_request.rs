@@ -1,4 +1,5 @@
-use crate::binding::http_0_2::{to_event, Headers};
+use crate::binding::http_0_2::{to_event_with_limits, Headers};
+use crate::binding::{DuplicateHeaders, Limits};
 use crate::Event;
 use actix_web::dev::Payload;
 use actix_web::web::BytesMut;
@@ -19,7 +20,28 @@ impl<'a> Headers<'a> for actix_http::header::HeaderMap {
     }
 }
 
+/// Turn the headers of `req` and `body` into an [`Event`], with the [`DuplicateHeaders`] policy
+/// and the [`Limits`] registered with [`App::app_data`](actix_web::App::app_data), if any.
+fn to_event(
+    req: &HttpRequest,
+    body: bytes::Bytes,
+) -> std::result::Result<Event, actix_web::error::Error> {
+    let duplicates = req.app_data::<DuplicateHeaders>().copied();
+    let limits = req.app_data::<Limits>().copied();
+    to_event_with_limits(
+        req.headers(),
+        body,
+        duplicates.unwrap_or_default(),
+        limits.unwrap_or_default(),
+    )
+    .map_err(actix_web::error::ErrorBadRequest)
+}
+
 /// Method to transform an incoming [`HttpRequest`] to [`Event`].
+///
+/// The repeated headers carrying an attribute are handled according to the
+/// [`DuplicateHeaders`] policy registered with [`App::app_data`](actix_web::App::app_data), if
+/// any, and the request is rejected if it exceeds the registered [`Limits`].
 pub async fn request_to_event(
     req: &HttpRequest,
     mut payload: web::Payload,
@@ -28,7 +50,7 @@ pub async fn request_to_event(
     while let Some(item) = payload.next().await {
         bytes.extend_from_slice(&item?);
     }
-    to_event(req.headers(), bytes.to_vec()).map_err(actix_web::error::ErrorBadRequest)
+    to_event(req, bytes.freeze())
 }
 
 /// So that an actix-web handler may take an Event parameter
@@ -40,8 +62,7 @@ impl actix_web::FromRequest for Event {
         let request = r.to_owned();
         bytes::Bytes::from_request(&request, p)
             .map(move |bytes| match bytes {
-                Ok(b) => to_event(request.headers(), b.to_vec())
-                    .map_err(actix_web::error::ErrorBadRequest),
+                Ok(b) => to_event(&request, b),
                 Err(e) => Err(e),
             })
             .boxed_local()
@@ -82,6 +103,7 @@ mod tests {
     use actix_web::{test, FromRequest};
 
     use crate::test::fixtures;
+    use crate::AttributesReader;
     use serde_json::json;
 
     async fn to_event(req: &HttpRequest, mut payload: Payload) -> Event {
@@ -106,6 +128,49 @@ mod tests {
         assert_eq!(expected, to_event(&req, payload).await);
     }
 
+    #[actix_rt::test]
+    async fn test_duplicate_headers() {
+        let request = |policy: Option<DuplicateHeaders>| {
+            let request = test::TestRequest::post()
+                .append_header(("ce-specversion", "1.0"))
+                .append_header(("ce-id", "0001"))
+                .append_header(("ce-type", "test_event.test_application"))
+                .append_header(("ce-source", "http://localhost/"))
+                .append_header(("ce-id", "0002"));
+            match policy {
+                Some(policy) => request.app_data(policy),
+                None => request,
+            }
+            .to_http_parts()
+        };
+
+        let (req, mut payload) = request(None);
+        let event = Event::from_request(&req, &mut payload).await.unwrap();
+        assert_eq!(event.id(), "0002");
+
+        let (req, mut payload) = request(Some(DuplicateHeaders::FirstWins));
+        let event = Event::from_request(&req, &mut payload).await.unwrap();
+        assert_eq!(event.id(), "0001");
+
+        let (req, mut payload) = request(Some(DuplicateHeaders::Reject));
+        assert!(Event::from_request(&req, &mut payload).await.is_err());
+    }
+
+    #[actix_rt::test]
+    async fn test_limits() {
+        let (req, mut payload) = test::TestRequest::post()
+            .insert_header(("ce-specversion", "1.0"))
+            .insert_header(("ce-id", "0001"))
+            .insert_header(("ce-type", "test_event.test_application"))
+            .insert_header(("ce-source", "http://localhost/"))
+            .insert_header(("ce-someint", "10"))
+            .app_data(Limits::new().max_extensions(0))
+            .to_http_parts();
+
+        let error = Event::from_request(&req, &mut payload).await.unwrap_err();
+        assert_eq!(error.to_string(), "The message has more than 0 extensions");
+    }
+
     #[actix_rt::test]
     async fn test_request_with_full_data() {
         let expected = fixtures::v10::full_binary_json_data_string_extension();
@@ -117,9 +182,9 @@ mod tests {
             .insert_header(("ce-subject", "cloudevents-sdk"))
             .insert_header(("ce-source", "http://localhost/"))
             .insert_header(("ce-time", fixtures::time().to_rfc3339()))
-            .insert_header(("ce-string_ex", "val"))
-            .insert_header(("ce-int_ex", "10"))
-            .insert_header(("ce-bool_ex", "true"))
+            .insert_header(("ce-stringex", "val"))
+            .insert_header(("ce-intex", "10"))
+            .insert_header(("ce-boolex", "true"))
             .insert_header(("content-type", "application/json"))
             .set_json(fixtures::json_data())
             .to_http_parts();
@@ -136,9 +201,9 @@ mod tests {
             "subject": "cloudevents-sdk",
             "source": "http://localhost/",
             "time": fixtures::time().to_rfc3339(),
-            "string_ex": "val",
-            "int_ex": "10",
-            "bool_ex": "true",
+            "stringex": "val",
+            "intex": "10",
+            "boolex": "true",
             "datacontenttype": "application/json",
             "data": fixtures::json_data()
         });
@@ -0,0 +1,141 @@
+//! Helpers to exchange events over [actix-ws](https://docs.rs/actix-ws) WebSocket sessions,
+//! using the `cloudevents.json` subprotocol of the CloudEvents WebSockets binding: every
+//! message contains one event in structured mode.
+//!
+//! ```
+//! use actix_web::{web, HttpRequest, HttpResponse};
+//! use actix_ws_lib::codec::CodecMessage;
+//! use cloudevents::binding::actix::ws;
+//!
+//! async fn echo(req: HttpRequest, body: web::Payload) -> actix_web::Result<HttpResponse> {
+//!     let (response, mut session, mut stream) = ws::handle(&req, body)?;
+//!
+//!     actix_web::rt::spawn(async move {
+//!         while let Some(Ok(CodecMessage::Item(event))) = stream.recv().await {
+//!             if session.send(&event).await.is_err() {
+//!                 break;
+//!             }
+//!         }
+//!     });
+//!
+//!     Ok(response)
+//! }
+//! ```
+
+use crate::message::{Error, Result};
+use crate::Event;
+use actix_web::{web, HttpRequest, HttpResponse};
+use actix_ws_lib::codec::{
+    CodecMessage, CodecMessageStream, CodecSession, EncodedMessage, MessageCodec,
+};
+use actix_ws_lib::AggregatedMessage;
+
+/// WebSocket subprotocol of the events serialized in the JSON format.
+pub static JSON_SUBPROTOCOL: &str = "cloudevents.json";
+
+/// [`MessageCodec`] encoding the events in JSON text messages, and decoding them from the text
+/// and binary messages.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EventCodec;
+
+impl MessageCodec<Event> for EventCodec {
+    type Error = Error;
+
+    fn encode(&self, event: &Event) -> Result<EncodedMessage> {
+        Ok(EncodedMessage::Text(serde_json::to_string(event)?.into()))
+    }
+
+    fn decode(&self, msg: AggregatedMessage) -> Result<CodecMessage<Event>> {
+        Ok(match msg {
+            AggregatedMessage::Text(text) => CodecMessage::Item(serde_json::from_str(&text)?),
+            AggregatedMessage::Binary(bytes) => CodecMessage::Item(serde_json::from_slice(&bytes)?),
+            AggregatedMessage::Ping(bytes) => CodecMessage::Ping(bytes),
+            AggregatedMessage::Pong(bytes) => CodecMessage::Pong(bytes),
+            AggregatedMessage::Close(reason) => CodecMessage::Close(reason),
+        })
+    }
+}
+
+/// Session sending events with the [`EventCodec`].
+pub type EventSession = CodecSession<Event, EventCodec>;
+
+/// Stream of the events received and decoded with the [`EventCodec`].
+pub type EventStream = CodecMessageStream<Event, EventCodec>;
+
+/// Begin handling a WebSocket session exchanging events, negotiating the
+/// [`JSON_SUBPROTOCOL`] if the client offers it.
+///
+/// Returns the handshake response to send back, the session to send events, and the stream of
+/// the received events.
+pub fn handle(
+    req: &HttpRequest,
+    body: web::Payload,
+) -> std::result::Result<(HttpResponse, EventSession, EventStream), actix_web::Error> {
+    let (response, session, stream) =
+        actix_ws_lib::handle_with_protocols(req, body, &[JSON_SUBPROTOCOL])?;
+    Ok((
+        response,
+        session.with_codec(EventCodec),
+        stream.with_codec(EventCodec),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use actix_web::test::TestRequest;
+    use actix_web::FromRequest;
+
+    #[test]
+    fn encode_decode() {
+        let expected = fixtures::v10::full_json_data_string_extension();
+
+        let text = match EventCodec.encode(&expected).unwrap() {
+            EncodedMessage::Text(text) => text,
+            msg => panic!("unexpected message {:?}", msg),
+        };
+        match EventCodec.decode(AggregatedMessage::Text(text)).unwrap() {
+            CodecMessage::Item(event) => assert_eq!(event, expected),
+            msg => panic!("unexpected message {:?}", msg),
+        }
+
+        let binary = serde_json::to_vec(&expected).unwrap();
+        match EventCodec
+            .decode(AggregatedMessage::Binary(binary.into()))
+            .unwrap()
+        {
+            CodecMessage::Item(event) => assert_eq!(event, expected),
+            msg => panic!("unexpected message {:?}", msg),
+        }
+
+        assert!(EventCodec
+            .decode(AggregatedMessage::Text("{}".into()))
+            .is_err());
+    }
+
+    #[actix_rt::test]
+    async fn handshake_negotiates_subprotocol() {
+        let (req, mut payload) = TestRequest::get()
+            .insert_header(("upgrade", "websocket"))
+            .insert_header(("connection", "upgrade"))
+            .insert_header(("sec-websocket-version", "13"))
+            .insert_header(("sec-websocket-key", "dGhlIHNhbXBsZSBub25jZQ=="))
+            .insert_header((
+                "sec-websocket-protocol",
+                "cloudevents.avro, cloudevents.json",
+            ))
+            .to_http_parts();
+
+        let body = web::Payload::from_request(&req, &mut payload)
+            .await
+            .unwrap();
+        let (response, _, _) = handle(&req, body).unwrap();
+
+        assert_eq!(response.status(), 101);
+        assert_eq!(
+            response.headers().get("sec-websocket-protocol").unwrap(),
+            JSON_SUBPROTOCOL
+        );
+    }
+}
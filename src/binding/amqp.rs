@@ -0,0 +1,151 @@
+//! Mapping of the events to the AMQP 0.9.1 messages, shared by the `lapin` and `amqprs`
+//! bindings, which only convert the header values of their client library.
+
+use crate::binding::CLOUDEVENTS_JSON_HEADER;
+use crate::event::SpecVersion;
+use crate::message::{
+    BinaryDeserializer, BinarySerializer, Encoding, Error, MessageAttributeValue,
+    MessageDeserializer, Result, StructuredDeserializer, StructuredSerializer,
+};
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt::Debug;
+
+pub static HEADER_PREFIX: &str = "cloudEvents:";
+pub static SPEC_VERSION_HEADER: &str = "cloudEvents:specversion";
+pub fn header_prefix(name: &str) -> String {
+    [HEADER_PREFIX, name].concat()
+}
+
+/// Value of a header table of an AMQP 0.9.1 client library.
+pub trait HeaderValue: Debug {
+    fn into_attribute_value(self) -> Result<MessageAttributeValue>;
+}
+
+/// Convert an AMQP timestamp, in seconds since the epoch.
+pub fn timestamp(t: u64) -> Result<MessageAttributeValue> {
+    i64::try_from(t)
+        .ok()
+        .and_then(|t| DateTime::<Utc>::from_timestamp(t, 0))
+        .map(MessageAttributeValue::DateTime)
+        .ok_or_else(|| Error::Other {
+            source: format!("Timestamp out of range: {}", t).into(),
+        })
+}
+
+/// Error of the header values without an attribute counterpart, e.g. tables.
+pub fn unsupported(value: impl Debug) -> Error {
+    Error::Other {
+        source: format!("Unsupported AMQP header value: {:?}", value).into(),
+    }
+}
+
+/// Content type, headers and payload of an AMQP 0.9.1 message, implementing
+/// [`MessageDeserializer`] for the deserializers of the bindings.
+pub struct Message<V> {
+    pub content_type: Option<String>,
+    pub headers: BTreeMap<String, V>,
+    pub payload: Vec<u8>,
+}
+
+impl<V: HeaderValue> BinaryDeserializer for Message<V> {
+    fn deserialize_binary<R: Sized, S: BinarySerializer<R>>(mut self, mut visitor: S) -> Result<R> {
+        if self.encoding() != Encoding::BINARY {
+            return Err(Error::WrongEncoding {});
+        }
+
+        let spec_version = SpecVersion::try_from(
+            self.headers
+                .remove(SPEC_VERSION_HEADER)
+                .unwrap()
+                .into_attribute_value()?
+                .to_string()
+                .as_str(),
+        )
+        .map_err(|e| e.in_attribute(SPEC_VERSION_HEADER))?;
+
+        let attributes = spec_version.attribute_names();
+
+        visitor = visitor.set_spec_version(spec_version)?;
+
+        if let Some(ct) = self.content_type {
+            visitor = visitor.set_attribute("datacontenttype", MessageAttributeValue::String(ct))?
+        }
+
+        for (hn, hv) in self
+            .headers
+            .into_iter()
+            .filter(|(hn, _)| hn.starts_with(HEADER_PREFIX))
+        {
+            let name = &hn[HEADER_PREFIX.len()..];
+
+            if attributes.contains(&name) {
+                visitor = visitor.set_attribute(name, hv.into_attribute_value()?)?
+            } else {
+                visitor = visitor.set_extension(name, hv.into_attribute_value()?)?
+            }
+        }
+
+        if !self.payload.is_empty() {
+            visitor.end_with_data(self.payload)
+        } else {
+            visitor.end()
+        }
+    }
+}
+
+impl<V: HeaderValue> StructuredDeserializer for Message<V> {
+    fn deserialize_structured<R: Sized, S: StructuredSerializer<R>>(self, visitor: S) -> Result<R> {
+        if self.encoding() != Encoding::STRUCTURED {
+            return Err(Error::WrongEncoding {});
+        }
+        visitor.set_structured_event(self.payload)
+    }
+}
+
+impl<V: HeaderValue> MessageDeserializer for Message<V> {
+    fn encoding(&self) -> Encoding {
+        match (
+            self.content_type
+                .as_deref()
+                .map(|s| s.starts_with(CLOUDEVENTS_JSON_HEADER))
+                .unwrap_or(false),
+            self.headers.get(SPEC_VERSION_HEADER),
+        ) {
+            (true, _) => Encoding::STRUCTURED,
+            (_, Some(_)) => Encoding::BINARY,
+            _ => Encoding::UNKNOWN,
+        }
+    }
+}
+
+/// Implement the deserializer traits for a wrapper of a [`Message`], delegating to it.
+macro_rules! impl_deserializer {
+    ($wrapper:ty) => {
+        impl $crate::message::BinaryDeserializer for $wrapper {
+            fn deserialize_binary<R: Sized, V: $crate::message::BinarySerializer<R>>(
+                self,
+                visitor: V,
+            ) -> $crate::message::Result<R> {
+                self.0.deserialize_binary(visitor)
+            }
+        }
+
+        impl $crate::message::StructuredDeserializer for $wrapper {
+            fn deserialize_structured<R: Sized, V: $crate::message::StructuredSerializer<R>>(
+                self,
+                visitor: V,
+            ) -> $crate::message::Result<R> {
+                self.0.deserialize_structured(visitor)
+            }
+        }
+
+        impl $crate::message::MessageDeserializer for $wrapper {
+            fn encoding(&self) -> $crate::message::Encoding {
+                self.0.encoding()
+            }
+        }
+    };
+}
+pub(crate) use impl_deserializer;
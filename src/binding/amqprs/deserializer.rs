@@ -0,0 +1,168 @@
+use amqprs_lib as amqprs;
+
+use crate::binding::amqp::{self, impl_deserializer, HeaderValue};
+use crate::message::{MessageAttributeValue, MessageDeserializer, Result};
+use crate::Event;
+use amqprs::channel::ConsumerMessage;
+use amqprs::{BasicProperties, FieldValue};
+
+/// Wrapper for an AMQP message that implements [`MessageDeserializer`] trait.
+pub struct ConsumerMessageDeserializer(amqp::Message<FieldValue>);
+
+impl ConsumerMessageDeserializer {
+    /// Create a new [`ConsumerMessageDeserializer`] from the message properties and content.
+    pub fn new(properties: &BasicProperties, payload: Vec<u8>) -> ConsumerMessageDeserializer {
+        ConsumerMessageDeserializer(amqp::Message {
+            content_type: properties.content_type().cloned(),
+            headers: properties
+                .headers()
+                .map(|h| {
+                    h.as_ref()
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), v.clone()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            payload,
+        })
+    }
+}
+
+impl_deserializer!(ConsumerMessageDeserializer);
+
+impl HeaderValue for FieldValue {
+    fn into_attribute_value(self) -> Result<MessageAttributeValue> {
+        Ok(match self {
+            FieldValue::t(b) => MessageAttributeValue::Boolean(b),
+            FieldValue::b(i) => MessageAttributeValue::Integer(i.into()),
+            FieldValue::B(i) => MessageAttributeValue::Integer(i.into()),
+            FieldValue::s(i) => MessageAttributeValue::Integer(i.into()),
+            FieldValue::u(i) => MessageAttributeValue::Integer(i.into()),
+            FieldValue::I(i) => MessageAttributeValue::Integer(i.into()),
+            FieldValue::i(i) => MessageAttributeValue::Integer(i.into()),
+            FieldValue::l(i) => MessageAttributeValue::Integer(i),
+            FieldValue::S(s) => MessageAttributeValue::String(s.into()),
+            FieldValue::x(b) => MessageAttributeValue::Binary(b.into()),
+            FieldValue::T(t) => amqp::timestamp(t)?,
+            v => return Err(amqp::unsupported(v)),
+        })
+    }
+}
+
+/// Method to transform the properties and content of an AMQP message to [`Event`],
+/// e.g. from within an [`AsyncConsumer`](amqprs::consumer::AsyncConsumer).
+pub fn message_to_event(properties: &BasicProperties, content: Vec<u8>) -> Result<Event> {
+    MessageDeserializer::into_event(ConsumerMessageDeserializer::new(properties, content))
+}
+
+/// Extension Trait for [`ConsumerMessage`] which acts as a wrapper for the function [`message_to_event()`].
+///
+/// This trait is sealed and cannot be implemented for types outside of this crate.
+pub trait ConsumerMessageExt: private::Sealed {
+    /// Generates [`Event`] from [`ConsumerMessage`].
+    fn to_event(&self) -> Result<Event>;
+}
+
+impl ConsumerMessageExt for ConsumerMessage {
+    fn to_event(&self) -> Result<Event> {
+        message_to_event(
+            self.basic_properties
+                .as_ref()
+                .unwrap_or(&Default::default()),
+            self.content.clone().unwrap_or_default(),
+        )
+    }
+}
+
+mod private {
+    use amqprs_lib as amqprs;
+
+    // Sealing the ConsumerMessageExt
+    pub trait Sealed {}
+    impl Sealed for amqprs::channel::ConsumerMessage {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::amqprs::MessageRecord;
+    use crate::message::{Error, StructuredDeserializer};
+    use crate::test::fixtures;
+    use crate::{EventBuilder, EventBuilderV10};
+    use std::convert::TryInto;
+
+    fn to_event(message_record: MessageRecord) -> Result<Event> {
+        let (properties, payload) = message_record.into_parts();
+        message_to_event(&properties, payload)
+    }
+
+    #[test]
+    fn test_binary_message() {
+        let expected = fixtures::v10::minimal_string_extension();
+
+        let message_record = MessageRecord::from_event(
+            EventBuilderV10::new()
+                .id("0001")
+                .ty("test_event.test_application")
+                .source("http://localhost/")
+                .extension("someint", "10")
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(to_event(message_record).unwrap(), expected)
+    }
+
+    #[test]
+    fn test_binary_message_typed_extensions() {
+        let expected = fixtures::v10::full_binary_json_data_string_extension();
+        let input = EventBuilderV10::from(expected.clone())
+            .extension("intex", 10)
+            .extension("boolex", true)
+            .build()
+            .unwrap();
+
+        let message_record = MessageRecord::from_event(input.clone()).unwrap();
+        let properties = message_record.properties();
+        let headers = properties.headers().unwrap().as_ref();
+
+        assert_eq!(
+            headers.get(&"cloudEvents:intex".try_into().unwrap()),
+            Some(&FieldValue::l(10))
+        );
+        assert_eq!(
+            headers.get(&"cloudEvents:boolex".try_into().unwrap()),
+            Some(&FieldValue::t(true))
+        );
+        assert_eq!(
+            properties.content_type().map(String::as_str),
+            Some("application/json")
+        );
+
+        let actual = to_event(message_record).unwrap();
+
+        assert_eq!(actual.extension("intex"), input.extension("intex"));
+        assert_eq!(actual.extension("boolex"), input.extension("boolex"));
+        assert_eq!(actual.data(), expected.data());
+    }
+
+    #[test]
+    fn test_structured_message() {
+        let expected = fixtures::v10::full_json_data_string_extension();
+
+        let message_record =
+            StructuredDeserializer::deserialize_structured(expected.clone(), MessageRecord::new())
+                .unwrap();
+
+        assert_eq!(to_event(message_record).unwrap(), expected)
+    }
+
+    #[test]
+    fn test_unknown_encoding() {
+        assert!(matches!(
+            to_event(MessageRecord::new()),
+            Err(Error::WrongEncoding {})
+        ))
+    }
+}
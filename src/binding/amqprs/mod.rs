@@ -0,0 +1,62 @@
+//! This library provides AMQP 0.9.1 protocol bindings for CloudEvents
+//! using the [amqprs](https://docs.rs/amqprs) library.
+//!
+//! The wire format is the same as the one used by [`super::lapin`]: in binary mode,
+//! attributes and extensions are written as `cloudEvents:`-prefixed message headers,
+//! while `datacontenttype` is mapped to the `content-type` message property.
+//!
+//! To publish Cloudevents:
+//!
+//! ```
+//! # use amqprs_lib as amqprs;
+//! use cloudevents::Event;
+//! use cloudevents::binding::amqprs::{BasicPublishExt, MessageRecord};
+//! use amqprs::channel::{BasicPublishArguments, Channel};
+//!
+//! # async fn publish(channel: &Channel, event: Event) -> Result<(), Box<dyn std::error::Error>> {
+//! let message_record = MessageRecord::from_event(event)?;
+//!
+//! channel
+//!     .basic_publish_record(
+//!         BasicPublishArguments::new("exchange", "routing_key"),
+//!         message_record,
+//!     )
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! To consume Cloudevents:
+//!
+//! ```
+//! # use amqprs_lib as amqprs;
+//! use cloudevents::binding::amqprs::ConsumerMessageExt;
+//! use amqprs::channel::{BasicConsumeArguments, Channel};
+//!
+//! # async fn consume(channel: &Channel) -> Result<(), Box<dyn std::error::Error>> {
+//! let (_ctag, mut rx) = channel
+//!     .basic_consume_rx(BasicConsumeArguments::new("queue", "consumer").auto_ack(true).finish())
+//!     .await?;
+//!
+//! while let Some(message) = rx.recv().await {
+//!     let event = message.to_event()?;
+//!     println!("Received Event: {}", event);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+#![deny(rustdoc::broken_intra_doc_links)]
+
+mod deserializer;
+mod serializer;
+mod transport;
+
+pub use deserializer::message_to_event;
+pub use deserializer::ConsumerMessageDeserializer;
+pub use deserializer::ConsumerMessageExt;
+
+pub use serializer::BasicPublishExt;
+pub use serializer::MessageRecord;
+
+pub use transport::{AmqprsSink, AmqprsSource};
@@ -0,0 +1,159 @@
+use amqprs_lib as amqprs;
+
+use crate::binding::{
+    amqp::{header_prefix, SPEC_VERSION_HEADER},
+    CLOUDEVENTS_JSON_HEADER,
+};
+use crate::event::SpecVersion;
+use crate::message::{
+    BinaryDeserializer, BinarySerializer, Error, MessageAttributeValue, Result,
+    StructuredSerializer,
+};
+use crate::Event;
+use amqprs::channel::{BasicPublishArguments, Channel};
+use amqprs::{BasicProperties, FieldName, FieldTable, FieldValue, LongStr};
+use async_trait::async_trait;
+use std::convert::TryFrom;
+
+/// This struct contains a serialized CloudEvent message in the AMQP 0.9.1 shape.
+/// Implements [`StructuredSerializer`] & [`BinarySerializer`] traits.
+///
+/// To instantiate a new `MessageRecord` from an [`Event`],
+/// look at [`Self::from_event`] or use [`StructuredDeserializer::deserialize_structured`](crate::message::StructuredDeserializer::deserialize_structured)
+/// or [`BinaryDeserializer::deserialize_binary`].
+pub struct MessageRecord {
+    pub(crate) properties: BasicProperties,
+    pub(crate) headers: FieldTable,
+    pub(crate) payload: Vec<u8>,
+}
+
+impl MessageRecord {
+    /// Create a new empty [`MessageRecord`]
+    pub fn new() -> Self {
+        MessageRecord {
+            properties: BasicProperties::default(),
+            headers: FieldTable::new(),
+            payload: Vec::new(),
+        }
+    }
+
+    /// Create a new [`MessageRecord`], filled with `event` serialized in binary mode.
+    pub fn from_event(event: Event) -> Result<Self> {
+        BinaryDeserializer::deserialize_binary(event, MessageRecord::new())
+    }
+
+    /// Get the [`BasicProperties`] to publish this record with, including the `cloudEvents:*` headers.
+    pub fn properties(&self) -> BasicProperties {
+        let mut properties = self.properties.clone();
+        if !self.headers.as_ref().is_empty() {
+            properties.with_headers(self.headers.clone());
+        }
+        properties
+    }
+
+    /// Get the message body.
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+
+    /// Split this record into the [`BasicProperties`] and the body to pass to
+    /// [`Channel::basic_publish`].
+    pub fn into_parts(self) -> (BasicProperties, Vec<u8>) {
+        (self.properties(), self.payload)
+    }
+
+    fn insert_header(&mut self, name: &str, value: FieldValue) -> Result<()> {
+        let name = FieldName::try_from(name).map_err(|e| Error::Other {
+            source: Box::new(e),
+        })?;
+        self.headers.insert(name, value);
+        Ok(())
+    }
+}
+
+impl Default for MessageRecord {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BinarySerializer<MessageRecord> for MessageRecord {
+    fn set_spec_version(mut self, sv: SpecVersion) -> Result<Self> {
+        self.insert_header(SPEC_VERSION_HEADER, FieldValue::from(sv.as_str()))?;
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        if name == "datacontenttype" {
+            self.properties.with_content_type(&value.to_string());
+        } else {
+            let value = to_field_value(value)?;
+            self.insert_header(&header_prefix(name), value)?;
+        }
+        Ok(self)
+    }
+
+    fn set_extension(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.set_attribute(name, value)
+    }
+
+    fn end_with_data(mut self, bytes: Vec<u8>) -> Result<MessageRecord> {
+        self.payload = bytes;
+        Ok(self)
+    }
+
+    fn end(self) -> Result<MessageRecord> {
+        Ok(self)
+    }
+}
+
+impl StructuredSerializer<MessageRecord> for MessageRecord {
+    fn set_structured_event(mut self, bytes: Vec<u8>) -> Result<MessageRecord> {
+        self.properties.with_content_type(CLOUDEVENTS_JSON_HEADER);
+        self.payload = bytes;
+        Ok(self)
+    }
+}
+
+fn to_field_value(value: MessageAttributeValue) -> Result<FieldValue> {
+    Ok(match value {
+        MessageAttributeValue::Boolean(b) => FieldValue::t(b),
+        MessageAttributeValue::Integer(i) => FieldValue::l(i),
+        v => FieldValue::S(LongStr::try_from(v.to_string()).map_err(|e| Error::Other {
+            source: Box::new(e),
+        })?),
+    })
+}
+
+/// Extension Trait for [`Channel`] to publish a [`MessageRecord`].
+///
+/// This trait is sealed and cannot be implemented for types outside of this crate.
+#[async_trait]
+pub trait BasicPublishExt: private::Sealed {
+    /// Publish `message_record` with the given [`BasicPublishArguments`].
+    async fn basic_publish_record(
+        &self,
+        args: BasicPublishArguments,
+        message_record: MessageRecord,
+    ) -> std::result::Result<(), amqprs::error::Error>;
+}
+
+#[async_trait]
+impl BasicPublishExt for Channel {
+    async fn basic_publish_record(
+        &self,
+        args: BasicPublishArguments,
+        message_record: MessageRecord,
+    ) -> std::result::Result<(), amqprs::error::Error> {
+        let (properties, payload) = message_record.into_parts();
+        self.basic_publish(properties, payload, args).await
+    }
+}
+
+mod private {
+    use amqprs_lib as amqprs;
+
+    // Sealing the BasicPublishExt
+    pub trait Sealed {}
+    impl Sealed for amqprs::channel::Channel {}
+}
@@ -0,0 +1,94 @@
+use amqprs_lib as amqprs;
+
+use super::{BasicPublishExt, ConsumerMessageExt, MessageRecord};
+use crate::transport::{Error, EventSink, EventSource, Result};
+use crate::Event;
+use amqprs::channel::{
+    BasicAckArguments, BasicPublishArguments, BasicRejectArguments, Channel, ConsumerMessage,
+};
+use async_trait::async_trait;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+/// [`EventSink`] publishing the events in binary mode to an exchange.
+pub struct AmqprsSink {
+    channel: Channel,
+    exchange: String,
+    routing_key: String,
+}
+
+impl AmqprsSink {
+    /// Create a new [`AmqprsSink`], publishing the events to `exchange` with `routing_key`.
+    pub fn new(
+        channel: Channel,
+        exchange: impl Into<String>,
+        routing_key: impl Into<String>,
+    ) -> Self {
+        AmqprsSink {
+            channel,
+            exchange: exchange.into(),
+            routing_key: routing_key.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for AmqprsSink {
+    async fn send(&self, event: Event) -> Result<()> {
+        let message_record = MessageRecord::from_event(event)?;
+        self.channel
+            .basic_publish_record(
+                BasicPublishArguments::new(&self.exchange, &self.routing_key),
+                message_record,
+            )
+            .await
+            .map_err(Error::transport)
+    }
+}
+
+/// [`EventSource`] receiving the events of a consumer started with
+/// [`Channel::basic_consume_rx`], acknowledging them by delivery tag.
+///
+/// The deliveries which can't be decoded are rejected without requeuing them, so the broker
+/// routes them to the dead-letter exchange of the queue, if any, instead of redelivering them.
+pub struct AmqprsSource {
+    channel: Channel,
+    receiver: UnboundedReceiver<ConsumerMessage>,
+}
+
+impl AmqprsSource {
+    /// Create a new [`AmqprsSource`] from the `channel` and `receiver` of a consumer.
+    pub fn new(channel: Channel, receiver: UnboundedReceiver<ConsumerMessage>) -> Self {
+        AmqprsSource { channel, receiver }
+    }
+}
+
+#[async_trait]
+impl EventSource for AmqprsSource {
+    type Ack = u64;
+
+    async fn receive(&mut self) -> Option<Result<(Event, u64)>> {
+        let message = self.receiver.recv().await?;
+        let delivery_tag = message
+            .deliver
+            .as_ref()
+            .map(|d| d.delivery_tag())
+            .unwrap_or_default();
+        match message.to_event() {
+            Ok(event) => Some(Ok((event, delivery_tag))),
+            Err(e) => Some(
+                self.channel
+                    .basic_reject(BasicRejectArguments::new(delivery_tag, false))
+                    .await
+                    .map_err(Error::transport)
+                    .and(Err(Error::from(e))),
+            ),
+        }
+    }
+
+    async fn ack(&mut self, ack: u64) -> Result<()> {
+        self.channel
+            .basic_ack(BasicAckArguments::new(ack, false))
+            .await
+            .map_err(Error::transport)
+    }
+}
@@ -6,9 +6,16 @@ use axum_lib as axum;
 use http;
 use http::StatusCode;
 
-use crate::binding::http::to_event;
+use crate::binding::http::to_event_with_limits;
+use crate::binding::{DuplicateHeaders, Limits};
 use crate::event::Event;
 
+/// Extracts an [`Event`] from the request.
+///
+/// The repeated headers carrying an attribute are handled according to the
+/// [`DuplicateHeaders`] policy found in the request extensions, e.g. added with an
+/// [`Extension`](axum::Extension) layer, defaulting to [`DuplicateHeaders::LastWins`]. The
+/// request is rejected if it exceeds the [`Limits`] found in the extensions.
 #[async_trait]
 impl<S> FromRequest<S> for Event
 where
@@ -27,7 +34,15 @@ where
                 .unwrap()
         })?;
 
-        to_event(&parts.headers, body.to_vec()).map_err(|e| {
+        let duplicates = parts.extensions.get::<DuplicateHeaders>().copied();
+        let limits = parts.extensions.get::<Limits>().copied();
+        to_event_with_limits(
+            &parts.headers,
+            body,
+            duplicates.unwrap_or_default(),
+            limits.unwrap_or_default(),
+        )
+        .map_err(|e| {
             Response::builder()
                 .status(StatusCode::BAD_REQUEST)
                 .body(axum::body::Body::from(e.to_string()))
@@ -44,6 +59,7 @@ mod tests {
     use axum::http::{self, Request, StatusCode};
 
     use crate::test::fixtures;
+    use crate::AttributesReader;
 
     #[tokio::test]
     async fn axum_test_request() {
@@ -64,6 +80,55 @@ mod tests {
         assert_eq!(expected, result);
     }
 
+    #[tokio::test]
+    async fn axum_test_duplicate_headers() {
+        let request = |policy: Option<DuplicateHeaders>| {
+            let mut request = Request::builder()
+                .method(http::Method::POST)
+                .header("ce-specversion", "1.0")
+                .header("ce-id", "0001")
+                .header("ce-type", "test_event.test_application")
+                .header("ce-source", "http://localhost/")
+                .header("ce-id", "0002")
+                .body(Body::empty())
+                .unwrap();
+            if let Some(policy) = policy {
+                request.extensions_mut().insert(policy);
+            }
+            request
+        };
+
+        let event = Event::from_request(request(None), &()).await.unwrap();
+        assert_eq!(event.id(), "0002");
+
+        let first_wins = request(Some(DuplicateHeaders::FirstWins));
+        let event = Event::from_request(first_wins, &()).await.unwrap();
+        assert_eq!(event.id(), "0001");
+
+        let reject = request(Some(DuplicateHeaders::Reject));
+        let rejection = Event::from_request(reject, &()).await.unwrap_err();
+        assert_eq!(rejection.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn axum_test_limits() {
+        let mut request = Request::builder()
+            .method(http::Method::POST)
+            .header("ce-specversion", "1.0")
+            .header("ce-id", "0001")
+            .header("ce-type", "test_event.test_application")
+            .header("ce-source", "http://localhost/")
+            .header("ce-someint", "10")
+            .body(Body::empty())
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(Limits::new().max_value_len(8));
+
+        let rejection = Event::from_request(request, &()).await.unwrap_err();
+        assert_eq!(rejection.status(), StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn axum_test_bad_request() {
         let request = Request::builder()
@@ -97,9 +162,9 @@ mod tests {
             .header("ce-source", "http://localhost/")
             .header("ce-subject", "cloudevents-sdk")
             .header("content-type", "application/json")
-            .header("ce-string_ex", "val")
-            .header("ce-int_ex", "10")
-            .header("ce-bool_ex", "true")
+            .header("ce-stringex", "val")
+            .header("ce-intex", "10")
+            .header("ce-boolex", "true")
             .header("ce-time", &fixtures::time().to_rfc3339())
             .body(Body::from(fixtures::json_data_binary()))
             .unwrap();
@@ -0,0 +1,164 @@
+use super::{media_type, CE_ATTRIBUTE_OPTION};
+use crate::event::SpecVersion;
+use crate::message::{
+    BinaryDeserializer, BinarySerializer, Encoding, Error, MessageAttributeValue,
+    MessageDeserializer, Result, StructuredDeserializer, StructuredSerializer,
+};
+use coap_lite::{CoapOption, Packet};
+use std::convert::TryFrom;
+
+/// The `<name>=<value>` attribute options of `packet`.
+fn attributes(packet: &Packet) -> Result<Vec<(&str, &str)>> {
+    packet
+        .get_option(CoapOption::Unknown(CE_ATTRIBUTE_OPTION))
+        .into_iter()
+        .flatten()
+        .map(|value| {
+            let invalid = |source| Error::InvalidHeaderValue {
+                name: format!("option {}", CE_ATTRIBUTE_OPTION),
+                source,
+            };
+            let value = std::str::from_utf8(value).map_err(|e| invalid(Box::new(e)))?;
+            value
+                .split_once('=')
+                .ok_or_else(|| invalid(format!("missing `=` in `{}`", value).into()))
+        })
+        .collect()
+}
+
+impl BinaryDeserializer for Packet {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, mut visitor: V) -> Result<R> {
+        if self.encoding() != Encoding::BINARY {
+            return Err(Error::WrongEncoding {});
+        }
+
+        let attributes = attributes(&self)?;
+        let spec_version = attributes
+            .iter()
+            .find(|(name, _)| *name == "specversion")
+            .map(|(_, value)| SpecVersion::try_from(*value))
+            .unwrap()
+            .map_err(|e| e.in_attribute("specversion"))?;
+
+        let attribute_names = spec_version.attribute_names();
+
+        visitor = visitor.set_spec_version(spec_version)?;
+
+        if let Some(ct) = self.get_content_format().and_then(media_type) {
+            visitor = visitor.set_attribute(
+                "datacontenttype",
+                MessageAttributeValue::String(ct.to_string()),
+            )?
+        }
+
+        for (name, value) in attributes.into_iter().filter(|(n, _)| *n != "specversion") {
+            let value = MessageAttributeValue::String(value.to_string());
+            if attribute_names.contains(&name) {
+                visitor = visitor.set_attribute(name, value)?
+            } else {
+                visitor = visitor.set_extension(name, value)?
+            }
+        }
+
+        if !self.payload.is_empty() {
+            visitor.end_with_data(self.payload)
+        } else {
+            visitor.end()
+        }
+    }
+}
+
+impl StructuredDeserializer for Packet {
+    fn deserialize_structured<R: Sized, V: StructuredSerializer<R>>(self, visitor: V) -> Result<R> {
+        if self.encoding() != Encoding::STRUCTURED {
+            return Err(Error::WrongEncoding {});
+        }
+        visitor.set_structured_event(self.payload)
+    }
+}
+
+impl MessageDeserializer for Packet {
+    fn encoding(&self) -> Encoding {
+        let is_binary = self
+            .get_option(CoapOption::Unknown(CE_ATTRIBUTE_OPTION))
+            .is_some_and(|values| values.iter().any(|v| v.starts_with(b"specversion=")));
+        if is_binary {
+            Encoding::BINARY
+        } else if !self.payload.is_empty() {
+            Encoding::STRUCTURED
+        } else {
+            Encoding::UNKNOWN
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binding::coap::{ContentMode, PacketExt, CE_ATTRIBUTE_OPTION};
+    use crate::message::Error;
+    use crate::test::fixtures;
+    use crate::{AttributesWriter, Event};
+    use coap_lite::{CoapOption, ContentFormat, Packet};
+
+    fn roundtrip(event: Event, mode: ContentMode) -> Packet {
+        let mut packet = Packet::new();
+        packet.set_event(event, mode).unwrap();
+        Packet::from_bytes(&packet.to_bytes().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_binary_roundtrip() {
+        let expected = fixtures::v10::full_binary_json_data_string_extension();
+
+        let packet = roundtrip(expected.clone(), ContentMode::Binary);
+
+        assert_eq!(
+            packet.get_content_format(),
+            Some(ContentFormat::ApplicationJSON)
+        );
+        let options = packet
+            .get_option(CoapOption::Unknown(CE_ATTRIBUTE_OPTION))
+            .unwrap();
+        assert!(options.contains(&b"specversion=1.0".to_vec()));
+        assert!(options.contains(&b"id=0001".to_vec()));
+        assert_eq!(packet.to_event().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_binary_unregistered_content_type() {
+        let mut expected = fixtures::v10::minimal_string_extension();
+        expected.set_datacontenttype(Some("text/csv"));
+
+        let packet = roundtrip(expected.clone(), ContentMode::Binary);
+
+        assert_eq!(packet.get_content_format(), None);
+        assert_eq!(packet.to_event().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_structured_roundtrip() {
+        let expected = fixtures::v10::full_json_data_string_extension();
+
+        let packet = roundtrip(expected.clone(), ContentMode::Structured);
+
+        assert_eq!(
+            packet.get_option(CoapOption::Unknown(CE_ATTRIBUTE_OPTION)),
+            None
+        );
+        assert_eq!(packet.to_event().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_invalid_attribute_option() {
+        let mut packet = Packet::new();
+        packet
+            .set_event(fixtures::v10::minimal(), ContentMode::Binary)
+            .unwrap();
+        packet.add_option(CoapOption::Unknown(CE_ATTRIBUTE_OPTION), b"rogue".to_vec());
+
+        assert!(matches!(
+            packet.to_event(),
+            Err(Error::InvalidHeaderValue { .. })
+        ));
+    }
+}
@@ -0,0 +1,133 @@
+//! This module provides bindings between [cloudevents-sdk](https://docs.rs/cloudevents-sdk) and [coap-lite](https://docs.rs/coap-lite) packets,
+//! for constrained devices speaking CoAP.
+//!
+//! CloudEvents has no CoAP protocol binding spec, so this module uses the following convention:
+//!
+//! * In binary mode, every attribute, including `specversion` and the extensions, is carried by
+//!   a [`CE_ATTRIBUTE_OPTION`] option whose value is the UTF-8 string `<name>=<value>`. The
+//!   option number is in the experimental use range, elective and safe to forward, so the
+//!   endpoints and proxies which don't know it skip it or pass it along.
+//! * `datacontenttype` is mapped to the Content-Format option when the media type has one of
+//!   the registered content formats `text/plain;charset=utf-8`, `application/link-format`,
+//!   `application/xml`, `application/octet-stream`, `application/json` or `application/cbor`,
+//!   and to a [`CE_ATTRIBUTE_OPTION`] otherwise.
+//! * The payload contains the event data.
+//! * In structured mode, the payload contains the JSON serialized event. A packet is deserialized
+//!   in binary mode if it has the `specversion` attribute option, in structured mode otherwise.
+//!
+//! ## Examples
+//! Deserialize a received [coap_lite::Packet](https://docs.rs/coap-lite/latest/coap_lite/struct.Packet.html) into [Event](https://docs.rs/cloudevents-sdk/latest/cloudevents/event/struct.Event.html)
+//! ```
+//!     use cloudevents::binding::coap::PacketExt;
+//!     use coap_lite::Packet;
+//!
+//!     fn receive(buf: &[u8]) {
+//!       let packet = Packet::from_bytes(buf).unwrap();
+//!       let cloud_event = packet.to_event().unwrap();
+//!
+//!       println!("{}", cloud_event.to_string());
+//!     }
+//! ```
+//!
+//! Serialize [Event](https://docs.rs/cloudevents-sdk/latest/cloudevents/event/struct.Event.html) in binary mode into a CoAP POST request
+//! ```
+//!     use cloudevents::binding::coap::{ContentMode, PacketExt};
+//!     use cloudevents::Event;
+//!     use coap_lite::{CoapRequest, RequestType};
+//!     use std::net::SocketAddr;
+//!
+//!     fn request(event: Event) -> Vec<u8> {
+//!       let mut request: CoapRequest<SocketAddr> = CoapRequest::new();
+//!       request.set_method(RequestType::Post);
+//!       request.set_path("/events");
+//!       request.message.set_event(event, ContentMode::Binary).unwrap();
+//!
+//!       request.message.to_bytes().unwrap()
+//!     }
+//! ```
+mod deserializer;
+mod serializer;
+
+pub use serializer::ContentMode;
+
+use crate::binding::instrument;
+use crate::message::{BinaryDeserializer, MessageDeserializer, Result};
+use crate::Event;
+use coap_lite::{CoapOption, ContentFormat, Packet};
+
+/// Number of the option carrying an attribute in binary mode, as `<name>=<value>`.
+pub const CE_ATTRIBUTE_OPTION: u16 = 65000;
+
+/// Media types mapped to the Content-Format option instead of an attribute option.
+static CONTENT_FORMATS: &[(ContentFormat, &str)] = &[
+    (ContentFormat::TextPlain, "text/plain;charset=utf-8"),
+    (
+        ContentFormat::ApplicationLinkFormat,
+        "application/link-format",
+    ),
+    (ContentFormat::ApplicationXML, "application/xml"),
+    (
+        ContentFormat::ApplicationOctetStream,
+        "application/octet-stream",
+    ),
+    (ContentFormat::ApplicationJSON, "application/json"),
+    (ContentFormat::ApplicationCBOR, "application/cbor"),
+];
+
+fn content_format(media_type: &str) -> Option<ContentFormat> {
+    CONTENT_FORMATS
+        .iter()
+        .find(|(_, mt)| mt.eq_ignore_ascii_case(media_type))
+        .map(|(cf, _)| *cf)
+}
+
+fn media_type(content_format: ContentFormat) -> Option<&'static str> {
+    CONTENT_FORMATS
+        .iter()
+        .find(|(cf, _)| *cf == content_format)
+        .map(|(_, mt)| *mt)
+}
+
+/// Extension Trait for [`Packet`] to deserialize and serialize [`Event`]s.
+///
+/// This trait is sealed and cannot be implemented for types outside of this crate.
+pub trait PacketExt: private::Sealed {
+    /// Deserialize the [`Event`] carried by this packet, in binary or structured mode.
+    fn to_event(&self) -> Result<Event>;
+
+    /// Set the payload and the attribute options of this packet to `event`, serialized using
+    /// the given content `mode`. The other options, e.g. the Uri-Path, are left as they are.
+    fn set_event(&mut self, event: Event, mode: ContentMode) -> Result<()>;
+}
+
+impl PacketExt for Packet {
+    fn to_event(&self) -> Result<Event> {
+        instrument::deserialize("coap", || MessageDeserializer::into_event(self.clone()))
+    }
+
+    fn set_event(&mut self, event: Event, mode: ContentMode) -> Result<()> {
+        instrument::serialize("coap", event, |event| {
+            self.clear_option(CoapOption::Unknown(CE_ATTRIBUTE_OPTION));
+            self.clear_option(CoapOption::ContentFormat);
+            match mode {
+                ContentMode::Structured => {
+                    self.payload = serde_json::to_vec(&event)?;
+                    Ok(())
+                }
+                ContentMode::Binary => {
+                    self.payload = Vec::new();
+                    BinaryDeserializer::deserialize_binary(
+                        event,
+                        serializer::PacketSerializer(self),
+                    )
+                }
+            }
+        })
+    }
+}
+
+mod private {
+    // Sealing the PacketExt
+    pub trait Sealed {}
+    impl Sealed for coap_lite::Packet {}
+}
@@ -0,0 +1,53 @@
+use super::{content_format, CE_ATTRIBUTE_OPTION};
+use crate::event::SpecVersion;
+use crate::message::{BinarySerializer, MessageAttributeValue, Result};
+use coap_lite::{CoapOption, Packet};
+
+/// Content mode used to serialize an [Event](crate::Event) into a [`Packet`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentMode {
+    /// The payload contains the JSON serialized [Event](crate::Event).
+    Structured,
+    /// The attributes are mapped to options and the payload contains the event data.
+    Binary,
+}
+
+pub(crate) struct PacketSerializer<'a>(pub(crate) &'a mut Packet);
+
+impl PacketSerializer<'_> {
+    fn add_attribute(&mut self, name: &str, value: &str) {
+        self.0.add_option(
+            CoapOption::Unknown(CE_ATTRIBUTE_OPTION),
+            [name, "=", value].concat().into_bytes(),
+        );
+    }
+}
+
+impl BinarySerializer<()> for PacketSerializer<'_> {
+    fn set_spec_version(mut self, sv: SpecVersion) -> Result<Self> {
+        self.add_attribute("specversion", sv.as_str());
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        let value = value.to_string();
+        match content_format(&value).filter(|_| name == "datacontenttype") {
+            Some(cf) => self.0.set_content_format(cf),
+            None => self.add_attribute(name, &value),
+        }
+        Ok(self)
+    }
+
+    fn set_extension(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.set_attribute(name, value)
+    }
+
+    fn end_with_data(self, bytes: Vec<u8>) -> Result<()> {
+        self.0.payload = bytes;
+        Ok(())
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+use crate::message::{Error, Result};
+
+/// Policy of the HTTP deserializers for the `ce-` headers, `content-type` and
+/// `content-encoding` repeated in a message, e.g. two `ce-id` headers.
+///
+/// The other headers are not checked, as they don't carry attributes.
+///
+/// ```
+/// # #[cfg(feature = "http-binding")]
+/// # {
+/// use cloudevents::binding::http::to_event_with;
+/// use cloudevents::binding::DuplicateHeaders;
+/// use http::{HeaderMap, HeaderValue};
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("ce-specversion", HeaderValue::from_static("1.0"));
+/// headers.insert("ce-id", HeaderValue::from_static("0001"));
+/// headers.insert("ce-type", HeaderValue::from_static("example.test"));
+/// headers.insert("ce-source", HeaderValue::from_static("http://localhost/"));
+/// headers.append("ce-id", HeaderValue::from_static("0002"));
+///
+/// assert!(to_event_with(&headers, Vec::new(), DuplicateHeaders::Reject).is_err());
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateHeaders {
+    /// Fail with [`Error::DuplicateHeader`].
+    Reject,
+    /// Keep the first value of the header.
+    FirstWins,
+    /// Keep the last value of the header.
+    #[default]
+    LastWins,
+}
+
+impl DuplicateHeaders {
+    /// Keep a single value per header name among `headers`, ordered by first occurrence.
+    pub(crate) fn dedup<N, V>(self, headers: impl Iterator<Item = (N, V)>) -> Result<Vec<(N, V)>>
+    where
+        N: PartialEq + AsRef<str>,
+    {
+        let mut kept: Vec<(N, V)> = Vec::new();
+        for (name, value) in headers {
+            match kept.iter_mut().find(|(n, _)| *n == name) {
+                None => kept.push((name, value)),
+                Some(_) if self == DuplicateHeaders::FirstWins => {}
+                Some(header) if self == DuplicateHeaders::LastWins => header.1 = value,
+                Some(_) => {
+                    return Err(Error::DuplicateHeader {
+                        name: name.as_ref().to_string(),
+                    })
+                }
+            }
+        }
+        Ok(kept)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dedup() {
+        let headers = || vec![("ce-id", 1), ("ce-type", 2), ("ce-id", 3)].into_iter();
+
+        assert_eq!(
+            DuplicateHeaders::FirstWins.dedup(headers()).unwrap(),
+            vec![("ce-id", 1), ("ce-type", 2)]
+        );
+        assert_eq!(
+            DuplicateHeaders::LastWins.dedup(headers()).unwrap(),
+            vec![("ce-id", 3), ("ce-type", 2)]
+        );
+        assert!(matches!(
+            DuplicateHeaders::Reject.dedup(headers()),
+            Err(Error::DuplicateHeader { name }) if name == "ce-id"
+        ));
+    }
+}
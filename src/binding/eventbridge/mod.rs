@@ -0,0 +1,236 @@
+//! This module provides conversions between [`Event`] and the
+//! [Amazon EventBridge event envelope](https://docs.aws.amazon.com/eventbridge/latest/userguide/eb-events-structure.html),
+//! so hybrid AWS/Knative systems can translate events at the boundary.
+//!
+//! The attributes are mapped as follows:
+//!
+//! | EventBridge   | CloudEvents                                   |
+//! | ------------- | --------------------------------------------- |
+//! | `id`          | `id`                                          |
+//! | `source`      | `source`                                      |
+//! | `detail-type` | `type`                                        |
+//! | `time`        | `time`                                        |
+//! | `detail`      | `data` (`application/json`)                   |
+//! | `version`     | `version` extension                           |
+//! | `account`     | `account` extension                           |
+//! | `region`      | `region` extension                            |
+//! | `resources`   | `resources` extension, joined with `,`        |
+//!
+//! When converting an [`Event`] to an [`EventBridgeEvent`], the attributes and extensions
+//! with no counterpart in the envelope are dropped.
+//!
+//! ```
+//! use cloudevents::binding::eventbridge::EventBridgeEvent;
+//! use cloudevents::{AttributesReader, Event};
+//! use std::convert::TryFrom;
+//!
+//! let eventbridge: EventBridgeEvent = serde_json::from_str(r#"{
+//!     "version": "0",
+//!     "id": "6a7e8feb-b491-4cf7-a9f1-bf3703467718",
+//!     "detail-type": "EC2 Instance State-change Notification",
+//!     "source": "aws.ec2",
+//!     "account": "111122223333",
+//!     "time": "2017-12-22T18:43:48Z",
+//!     "region": "us-west-1",
+//!     "resources": ["arn:aws:ec2:us-west-1:123456789012:instance/i-1234567890abcdef0"],
+//!     "detail": {"instance-id": "i-1234567890abcdef0", "state": "terminated"}
+//! }"#).unwrap();
+//!
+//! let event = Event::try_from(eventbridge).unwrap();
+//! assert_eq!(event.ty(), "EC2 Instance State-change Notification");
+//! ```
+
+use crate::event::{AttributesReader, Data, ExtensionValue};
+use crate::{Event, EventBuilder, EventBuilderV10};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use snafu::{ResultExt, Snafu};
+use std::convert::TryFrom;
+
+static VERSION_EXTENSION: &str = "version";
+static ACCOUNT_EXTENSION: &str = "account";
+static REGION_EXTENSION: &str = "region";
+static RESOURCES_EXTENSION: &str = "resources";
+
+/// Represents an error during the conversion from/to the EventBridge envelope
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display(
+        "Missing attribute {} required by the EventBridge envelope",
+        attribute_name
+    ))]
+    MissingAttribute { attribute_name: &'static str },
+    #[snafu(display("Event data is not valid json: {}", source))]
+    InvalidData { source: serde_json::Error },
+    #[snafu(display("Error while building the event: {}", source))]
+    #[snafu(context(false))]
+    EventBuilderError {
+        source: crate::event::EventBuilderError,
+    },
+}
+
+/// Result type alias for return values of the EventBridge conversions
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// An event in the Amazon EventBridge envelope.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct EventBridgeEvent {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    pub id: String,
+    pub detail_type: String,
+    pub source: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    pub time: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    #[serde(default)]
+    pub resources: Vec<String>,
+    #[serde(default)]
+    pub detail: Value,
+}
+
+impl TryFrom<EventBridgeEvent> for Event {
+    type Error = Error;
+
+    fn try_from(value: EventBridgeEvent) -> Result<Self> {
+        let mut builder = EventBuilderV10::new()
+            .id(value.id)
+            .ty(value.detail_type)
+            .source(value.source)
+            .time(value.time);
+        if !value.detail.is_null() {
+            builder = builder.data("application/json", value.detail);
+        }
+        if let Some(version) = value.version {
+            builder = builder.extension(VERSION_EXTENSION, version);
+        }
+        if let Some(account) = value.account {
+            builder = builder.extension(ACCOUNT_EXTENSION, account);
+        }
+        if let Some(region) = value.region {
+            builder = builder.extension(REGION_EXTENSION, region);
+        }
+        if !value.resources.is_empty() {
+            builder = builder.extension(RESOURCES_EXTENSION, value.resources.join(","));
+        }
+        Ok(builder.build()?)
+    }
+}
+
+impl TryFrom<Event> for EventBridgeEvent {
+    type Error = Error;
+
+    fn try_from(mut event: Event) -> Result<Self> {
+        let extension_string =
+            |event: &Event, name: &str| event.extension(name).map(ExtensionValue::to_string);
+        let time = *event.time().ok_or(Error::MissingAttribute {
+            attribute_name: "time",
+        })?;
+        let detail = match event.take_data().2 {
+            Some(Data::Json(v)) => v,
+            Some(Data::String(s)) => Value::String(s),
+            Some(Data::Binary(b)) => serde_json::from_slice(&b).context(InvalidDataSnafu)?,
+            Some(Data::Bytes(b)) => serde_json::from_slice(&b).context(InvalidDataSnafu)?,
+            None => Value::Object(Default::default()),
+        };
+
+        Ok(EventBridgeEvent {
+            version: extension_string(&event, VERSION_EXTENSION),
+            id: event.id().to_string(),
+            detail_type: event.ty().to_string(),
+            source: event.source().to_string(),
+            account: extension_string(&event, ACCOUNT_EXTENSION),
+            time,
+            region: extension_string(&event, REGION_EXTENSION),
+            resources: extension_string(&event, RESOURCES_EXTENSION)
+                .map(|r| r.split(',').map(String::from).collect())
+                .unwrap_or_default(),
+            detail,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::AttributesWriter;
+    use crate::test::fixtures;
+    use serde_json::json;
+
+    fn eventbridge_json() -> Value {
+        json!({
+            "version": "0",
+            "id": fixtures::id(),
+            "detail-type": fixtures::ty(),
+            "source": fixtures::source(),
+            "account": "111122223333",
+            "time": fixtures::time(),
+            "region": "us-west-1",
+            "resources": ["arn:aws:ec2:us-west-1:123456789012:instance/i-1", "arn:aws:ec2:us-west-1:123456789012:instance/i-2"],
+            "detail": fixtures::json_data()
+        })
+    }
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id(fixtures::id())
+            .source(fixtures::source())
+            .ty(fixtures::ty())
+            .time(fixtures::time())
+            .data("application/json", fixtures::json_data())
+            .extension("version", "0")
+            .extension("account", "111122223333")
+            .extension("region", "us-west-1")
+            .extension(
+                "resources",
+                "arn:aws:ec2:us-west-1:123456789012:instance/i-1,arn:aws:ec2:us-west-1:123456789012:instance/i-2",
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn eventbridge_to_event() {
+        let eventbridge: EventBridgeEvent = serde_json::from_value(eventbridge_json()).unwrap();
+
+        assert_eq!(Event::try_from(eventbridge).unwrap(), event());
+    }
+
+    #[test]
+    fn event_to_eventbridge() {
+        let eventbridge = EventBridgeEvent::try_from(event()).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(eventbridge).unwrap(),
+            eventbridge_json()
+        );
+    }
+
+    #[test]
+    fn event_without_time_to_eventbridge() {
+        let mut event = event();
+        event.set_time(None as Option<DateTime<Utc>>);
+
+        assert!(matches!(
+            EventBridgeEvent::try_from(event),
+            Err(Error::MissingAttribute {
+                attribute_name: "time"
+            })
+        ));
+    }
+
+    #[test]
+    fn event_with_invalid_data_to_eventbridge() {
+        let mut event = event();
+        event.set_data("text/xml", fixtures::xml_data().into_bytes());
+
+        assert!(matches!(
+            EventBridgeEvent::try_from(event),
+            Err(Error::InvalidData { .. })
+        ));
+    }
+}
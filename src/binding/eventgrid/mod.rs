@@ -0,0 +1,309 @@
+//! This module provides conversions between [`Event`] and the
+//! [Azure Event Grid event schema](https://learn.microsoft.com/azure/event-grid/event-schema),
+//! so subscribers can normalize Event Grid traffic into standard CloudEvents.
+//!
+//! The attributes are mapped as follows, any other field of the Event Grid envelope is dropped:
+//!
+//! | Event Grid        | CloudEvents                  |
+//! | ----------------- | ---------------------------- |
+//! | `id`              | `id`                         |
+//! | `topic`           | `source`                     |
+//! | `eventType`       | `type`                       |
+//! | `subject`         | `subject`                    |
+//! | `eventTime`       | `time`                       |
+//! | `data`            | `data` (`application/json`)  |
+//! | `dataVersion`     | `dataversion` extension      |
+//! | `metadataVersion` | `metadataversion` extension  |
+//!
+//! To normalize a webhook request body, which might either contain Event Grid events
+//! or CloudEvents (in structured or batched mode), use [`to_events`]:
+//!
+//! ```
+//! use cloudevents::binding::eventgrid::to_events;
+//! use cloudevents::AttributesReader;
+//!
+//! let body = br#"[{
+//!     "id": "831e1650-001e-001b-66ab-eeb76e069631",
+//!     "topic": "/subscriptions/sub/resourceGroups/rg/providers/Microsoft.Storage/storageAccounts/account",
+//!     "subject": "/blobServices/default/containers/container/blobs/blob",
+//!     "eventType": "Microsoft.Storage.BlobCreated",
+//!     "eventTime": "2017-06-26T18:41:00.9584103Z",
+//!     "data": {"api": "PutBlockList"},
+//!     "dataVersion": "1",
+//!     "metadataVersion": "1"
+//! }]"#;
+//!
+//! let events = to_events(body).unwrap();
+//! assert_eq!(events[0].ty(), "Microsoft.Storage.BlobCreated");
+//! ```
+//!
+//! Event Grid requires webhooks to complete a validation handshake before delivering events.
+//! With the Event Grid schema, the first event delivered is a
+//! [`SUBSCRIPTION_VALIDATION_EVENT_TYPE`] event, that must be answered with
+//! [`validation_response`]. With the CloudEvents schema, Event Grid performs the
+//! [CloudEvents Web Hook abuse protection](https://github.com/cloudevents/spec/blob/v1.0.2/cloudevents/http-webhook.md#4-abuse-protection)
+//! handshake instead: an `OPTIONS` request carrying the [`WEBHOOK_REQUEST_ORIGIN_HEADER`] header,
+//! that must be answered echoing its value in the [`WEBHOOK_ALLOWED_ORIGIN_HEADER`] header.
+
+use crate::event::{AttributesReader, Data, ExtensionValue};
+use crate::{Event, EventBuilder, EventBuilderV10};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use snafu::{ResultExt, Snafu};
+use std::convert::TryFrom;
+
+/// `eventType` of the event Event Grid sends to validate a webhook subscription.
+pub static SUBSCRIPTION_VALIDATION_EVENT_TYPE: &str =
+    "Microsoft.EventGrid.SubscriptionValidationEvent";
+/// Header carried by the abuse protection `OPTIONS` request.
+pub static WEBHOOK_REQUEST_ORIGIN_HEADER: &str = "webhook-request-origin";
+/// Header to answer the abuse protection `OPTIONS` request with.
+pub static WEBHOOK_ALLOWED_ORIGIN_HEADER: &str = "webhook-allowed-origin";
+
+static DATA_VERSION_EXTENSION: &str = "dataversion";
+static METADATA_VERSION_EXTENSION: &str = "metadataversion";
+
+/// Represents an error during the conversion from/to the Event Grid schema
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display(
+        "Missing attribute {} required by the Event Grid schema",
+        attribute_name
+    ))]
+    MissingAttribute { attribute_name: &'static str },
+    #[snafu(display("Error while serializing/deserializing to json: {}", source))]
+    #[snafu(context(false))]
+    SerdeJsonError { source: serde_json::Error },
+    #[snafu(display("Event data is not valid json: {}", source))]
+    InvalidData { source: serde_json::Error },
+    #[snafu(display("Error while building the event: {}", source))]
+    #[snafu(context(false))]
+    EventBuilderError {
+        source: crate::event::EventBuilderError,
+    },
+}
+
+/// Result type alias for return values of the Event Grid conversions
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// An event in the Azure Event Grid schema.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventGridEvent {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    pub subject: String,
+    pub event_type: String,
+    pub event_time: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata_version: Option<String>,
+}
+
+impl EventGridEvent {
+    /// Returns the validation code if this is a [`SUBSCRIPTION_VALIDATION_EVENT_TYPE`] event.
+    pub fn validation_code(&self) -> Option<&str> {
+        if self.event_type != SUBSCRIPTION_VALIDATION_EVENT_TYPE {
+            return None;
+        }
+        self.data
+            .as_ref()
+            .and_then(|d| d.get("validationCode"))
+            .and_then(Value::as_str)
+    }
+}
+
+/// Body to answer a [`SUBSCRIPTION_VALIDATION_EVENT_TYPE`] event with.
+pub fn validation_response(validation_code: &str) -> Value {
+    serde_json::json!({ "validationResponse": validation_code })
+}
+
+impl TryFrom<EventGridEvent> for Event {
+    type Error = Error;
+
+    fn try_from(value: EventGridEvent) -> Result<Self> {
+        let mut builder = EventBuilderV10::new()
+            .id(value.id)
+            .ty(value.event_type)
+            .source(value.topic.ok_or(Error::MissingAttribute {
+                attribute_name: "topic",
+            })?)
+            .subject(value.subject)
+            .time(value.event_time);
+        if let Some(data) = value.data {
+            builder = builder.data("application/json", data);
+        }
+        if let Some(data_version) = value.data_version {
+            builder = builder.extension(DATA_VERSION_EXTENSION, data_version);
+        }
+        if let Some(metadata_version) = value.metadata_version {
+            builder = builder.extension(METADATA_VERSION_EXTENSION, metadata_version);
+        }
+        Ok(builder.build()?)
+    }
+}
+
+impl TryFrom<Event> for EventGridEvent {
+    type Error = Error;
+
+    fn try_from(mut event: Event) -> Result<Self> {
+        let extension_string =
+            |event: &Event, name: &str| event.extension(name).map(ExtensionValue::to_string);
+        let data_version = extension_string(&event, DATA_VERSION_EXTENSION);
+        let metadata_version = extension_string(&event, METADATA_VERSION_EXTENSION);
+        let event_time = *event.time().ok_or(Error::MissingAttribute {
+            attribute_name: "time",
+        })?;
+        let data = match event.take_data().2 {
+            Some(Data::Json(v)) => Some(v),
+            Some(Data::String(s)) => Some(Value::String(s)),
+            Some(Data::Binary(b)) => Some(serde_json::from_slice(&b).context(InvalidDataSnafu)?),
+            Some(Data::Bytes(b)) => Some(serde_json::from_slice(&b).context(InvalidDataSnafu)?),
+            None => None,
+        };
+
+        Ok(EventGridEvent {
+            id: event.id().to_string(),
+            topic: Some(event.source().to_string()),
+            subject: event.subject().unwrap_or_default().to_string(),
+            event_type: event.ty().to_string(),
+            event_time,
+            data,
+            data_version,
+            metadata_version,
+        })
+    }
+}
+
+/// Parse a webhook request body into a list of [`Event`].
+///
+/// The body can contain a single event or an array of events, each of them either
+/// in the Event Grid schema or in the CloudEvents JSON format.
+pub fn to_events(body: &[u8]) -> Result<Vec<Event>> {
+    let values = match serde_json::from_slice(body)? {
+        Value::Array(values) => values,
+        v => vec![v],
+    };
+    values
+        .into_iter()
+        .map(|v| {
+            if v.get("specversion").is_some() {
+                Ok(serde_json::from_value(v)?)
+            } else {
+                Event::try_from(serde_json::from_value::<EventGridEvent>(v)?)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::AttributesWriter;
+    use crate::test::fixtures;
+    use serde_json::json;
+
+    fn eventgrid_json() -> Value {
+        json!({
+            "id": fixtures::id(),
+            "topic": fixtures::source(),
+            "subject": fixtures::subject(),
+            "eventType": fixtures::ty(),
+            "eventTime": fixtures::time(),
+            "data": fixtures::json_data(),
+            "dataVersion": "1.0",
+            "metadataVersion": "1"
+        })
+    }
+
+    fn event() -> Event {
+        EventBuilderV10::new()
+            .id(fixtures::id())
+            .source(fixtures::source())
+            .ty(fixtures::ty())
+            .subject(fixtures::subject())
+            .time(fixtures::time())
+            .data("application/json", fixtures::json_data())
+            .extension("dataversion", "1.0")
+            .extension("metadataversion", "1")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn eventgrid_to_event() {
+        let eventgrid: EventGridEvent = serde_json::from_value(eventgrid_json()).unwrap();
+
+        assert_eq!(Event::try_from(eventgrid).unwrap(), event());
+    }
+
+    #[test]
+    fn event_to_eventgrid() {
+        let eventgrid = EventGridEvent::try_from(event()).unwrap();
+
+        assert_eq!(serde_json::to_value(eventgrid).unwrap(), eventgrid_json());
+    }
+
+    #[test]
+    fn event_without_time_to_eventgrid() {
+        let mut event = event();
+        event.set_time(None as Option<DateTime<Utc>>);
+
+        assert!(matches!(
+            EventGridEvent::try_from(event),
+            Err(Error::MissingAttribute {
+                attribute_name: "time"
+            })
+        ));
+    }
+
+    #[test]
+    fn to_events_mixed_schemas() {
+        let body = json!([eventgrid_json(), fixtures::v10::full_json_data_json()]).to_string();
+
+        let events = to_events(body.as_bytes()).unwrap();
+
+        assert_eq!(events, vec![event(), fixtures::v10::full_json_data()]);
+    }
+
+    #[test]
+    fn to_events_single_event() {
+        let body = eventgrid_json().to_string();
+
+        assert_eq!(to_events(body.as_bytes()).unwrap(), vec![event()]);
+    }
+
+    #[test]
+    fn subscription_validation() {
+        let eventgrid: EventGridEvent = serde_json::from_value(json!({
+            "id": "2d1781af-3a4c-4d7c-bd0c-e34b19da4e66",
+            "topic": "/subscriptions/xx/resourceGroups/xx/providers/Microsoft.EventGrid/topics/xx",
+            "subject": "",
+            "data": {
+                "validationCode": "512d38b6-c7b8-40c8-89fe-f46f9e9622b6",
+                "validationUrl": "https://rp-eastus2.eventgrid.azure.net:553/eventsubscriptions/xx/validate"
+            },
+            "eventType": "Microsoft.EventGrid.SubscriptionValidationEvent",
+            "eventTime": "2018-01-25T22:12:19.4556811Z",
+            "metadataVersion": "1",
+            "dataVersion": "1"
+        }))
+        .unwrap();
+
+        let code = eventgrid.validation_code().unwrap();
+        assert_eq!(code, "512d38b6-c7b8-40c8-89fe-f46f9e9622b6");
+        assert_eq!(
+            validation_response(code),
+            json!({"validationResponse": "512d38b6-c7b8-40c8-89fe-f46f9e9622b6"})
+        );
+        assert_eq!(
+            EventGridEvent::try_from(event()).unwrap().validation_code(),
+            None
+        );
+    }
+}
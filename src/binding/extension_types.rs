@@ -0,0 +1,188 @@
+use crate::event::{ExtensionTypeError, ExtensionValue, FromExtensionValue};
+use crate::message::Result;
+use crate::Event;
+use std::collections::HashMap;
+
+/// Type of an extension declared in [`ExtensionTypes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensionType {
+    String,
+    Boolean,
+    Integer,
+}
+
+/// Types of the extensions received as strings, e.g. in the headers of binary mode messages,
+/// so they are read with the same type as in the structured mode.
+///
+/// The declared extensions are converted to their type, failing if their value can't be
+/// parsed, while the other extensions are kept as strings unless [`ExtensionTypes::infer`] is
+/// enabled. By default, no extension is converted.
+///
+/// ```
+/// use cloudevents::binding::{ExtensionType, ExtensionTypes};
+/// use cloudevents::event::ExtensionValue;
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+///
+/// let types = ExtensionTypes::new()
+///     .declare("sequence", ExtensionType::Integer)
+///     .infer(true);
+///
+/// let mut event = EventBuilderV10::new()
+///     .id("0001")
+///     .ty("example.test")
+///     .source("http://localhost/")
+///     .extension("sequence", "42")
+///     .extension("sampled", "true")
+///     .build()
+///     .unwrap();
+/// types.apply(&mut event).unwrap();
+///
+/// assert_eq!(event.extension("sequence"), Some(&ExtensionValue::Integer(42)));
+/// assert_eq!(event.extension("sampled"), Some(&ExtensionValue::Boolean(true)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ExtensionTypes {
+    declared: HashMap<String, ExtensionType>,
+    infer: bool,
+}
+
+impl ExtensionTypes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare the type of the extension `name`.
+    pub fn declare(mut self, name: impl Into<String>, ty: ExtensionType) -> Self {
+        self.declared.insert(name.into(), ty);
+        self
+    }
+
+    /// Infer the type of the undeclared extensions: `true` and `false` are read as booleans,
+    /// and the canonical representations of the 32-bit integers, e.g. `-42` but not `042` or
+    /// `+42`, as integers. Disabled by default.
+    pub fn infer(mut self, infer: bool) -> Self {
+        self.infer = infer;
+        self
+    }
+
+    /// Convert the value of the extension `name` to its declared or inferred type.
+    pub fn convert(&self, name: &str, value: ExtensionValue) -> Result<ExtensionValue> {
+        match self.declared.get(name) {
+            Some(ExtensionType::String) => Ok(ExtensionValue::String(value.to_string())),
+            Some(ExtensionType::Boolean) => convert::<bool>(name, value),
+            Some(ExtensionType::Integer) => convert::<i64>(name, value),
+            None if self.infer => Ok(infer(value)),
+            None => Ok(value),
+        }
+    }
+
+    /// Convert the extensions of `event` to their declared or inferred type.
+    pub fn apply(&self, event: &mut Event) -> Result<()> {
+        if self.is_noop() {
+            return Ok(());
+        }
+        let extensions: Vec<(String, ExtensionValue)> = event
+            .iter_extensions()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect();
+        for (name, value) in extensions {
+            let converted = self.convert(&name, value)?;
+            event.set_extension(&name, converted);
+        }
+        Ok(())
+    }
+
+    /// Whether no extension is converted.
+    fn is_noop(&self) -> bool {
+        self.declared.is_empty() && !self.infer
+    }
+}
+
+fn convert<T>(name: &str, value: ExtensionValue) -> Result<ExtensionValue>
+where
+    T: FromExtensionValue + Into<ExtensionValue>,
+{
+    match T::from_extension_value(&value) {
+        Ok(converted) => Ok(converted.into()),
+        Err(e) => Err(ExtensionTypeError::new::<T>(name, &value, e).into()),
+    }
+}
+
+fn infer(value: ExtensionValue) -> ExtensionValue {
+    let s = match &value {
+        ExtensionValue::String(s) => s.as_str(),
+        _ => return value,
+    };
+    match s {
+        "true" => return ExtensionValue::Boolean(true),
+        "false" => return ExtensionValue::Boolean(false),
+        _ => {}
+    }
+    match s.parse::<i32>() {
+        Ok(i) if i.to_string() == s => ExtensionValue::Integer(i64::from(i)),
+        _ => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::Error;
+    use crate::test::fixtures;
+
+    #[test]
+    fn convert() {
+        let types = ExtensionTypes::new()
+            .declare("count", ExtensionType::Integer)
+            .declare("flag", ExtensionType::Boolean)
+            .declare("code", ExtensionType::String);
+
+        assert_eq!(
+            types.convert("count", "10".into()).unwrap(),
+            ExtensionValue::Integer(10)
+        );
+        assert_eq!(
+            types.convert("flag", "false".into()).unwrap(),
+            ExtensionValue::Boolean(false)
+        );
+        assert_eq!(
+            types.convert("code", ExtensionValue::Integer(7)).unwrap(),
+            ExtensionValue::String("7".to_string())
+        );
+        assert_eq!(
+            types.convert("other", "10".into()).unwrap(),
+            ExtensionValue::String("10".to_string())
+        );
+        assert!(matches!(
+            types.convert("count", "ten".into()),
+            Err(Error::InvalidExtensionType { .. })
+        ));
+    }
+
+    #[test]
+    fn infer() {
+        let types = ExtensionTypes::new().infer(true);
+
+        for (value, expected) in [
+            ("10", ExtensionValue::Integer(10)),
+            ("-10", ExtensionValue::Integer(-10)),
+            ("true", ExtensionValue::Boolean(true)),
+            ("010", ExtensionValue::from("010")),
+            ("+10", ExtensionValue::from("+10")),
+            ("4294967296", ExtensionValue::from("4294967296")),
+            ("True", ExtensionValue::from("True")),
+        ] {
+            assert_eq!(types.convert("ext", value.into()).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn apply() {
+        let mut event = fixtures::v10::minimal_string_extension();
+        ExtensionTypes::new().infer(true).apply(&mut event).unwrap();
+
+        let mut expected = fixtures::v10::minimal();
+        expected.set_extension("someint", 10);
+        assert_eq!(event, expected);
+    }
+}
@@ -0,0 +1,127 @@
+//! Conversions between events and HTTP messages with any [`http_body::Body`], e.g. the bodies
+//! of hyper 1.x, axum or tonic, sharing one path to collect and build the bodies.
+//!
+//! ```
+//! use cloudevents::binding::http::body::{request_to_event, to_request};
+//! use cloudevents::Event;
+//!
+//! async fn forward(event: Event) -> cloudevents::message::Result<Event> {
+//!     // Any body implementing `http_body::Body` can be collected, e.g. a hyper `Incoming`
+//!     let request = to_request(event)?;
+//!     request_to_event(request).await
+//! }
+//! ```
+//!
+//! [`http_body::Body`]: http_body_lib::Body
+
+use super::to_event_bytes;
+use crate::message::{Error, Result};
+use crate::Event;
+use bytes::Bytes;
+use http::{HeaderMap, Request, Response};
+use http_body_lib::Body;
+use http_body_util::{BodyExt, Full};
+use std::convert::TryFrom;
+
+/// Collect `body` and turn it, with the `headers`, into an [`Event`] sharing the collected
+/// buffer with its data.
+pub async fn to_event_from_body<B>(headers: &HeaderMap, body: B) -> Result<Event>
+where
+    B: Body,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    let bytes = body
+        .collect()
+        .await
+        .map_err(|e| Error::Other { source: e.into() })?
+        .to_bytes();
+    to_event_bytes(headers, bytes)
+}
+
+/// Collect the body of `request` and turn it into an [`Event`].
+pub async fn request_to_event<B>(request: Request<B>) -> Result<Event>
+where
+    B: Body,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    let (parts, body) = request.into_parts();
+    to_event_from_body(&parts.headers, body).await
+}
+
+/// Collect the body of `response` and turn it into an [`Event`].
+pub async fn response_to_event<B>(response: Response<B>) -> Result<Event>
+where
+    B: Body,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    let (parts, body) = response.into_parts();
+    to_event_from_body(&parts.headers, body).await
+}
+
+/// Serialize `event` in binary mode into headers and a [`Full`] body, which the HTTP libraries
+/// can box into their own body type.
+pub fn to_body(event: Event) -> Result<(HeaderMap, Full<Bytes>)> {
+    let (parts, body) = Request::<Option<Vec<u8>>>::try_from(event)?.into_parts();
+    Ok((parts.headers, Full::from(body.unwrap_or_default())))
+}
+
+/// Serialize `event` in binary mode into a request, to set the method and the uri of.
+pub fn to_request(event: Event) -> Result<Request<Full<Bytes>>> {
+    let (headers, body) = to_body(event)?;
+    let mut request = Request::new(body);
+    *request.headers_mut() = headers;
+    Ok(request)
+}
+
+/// Serialize `event` in binary mode into a `200 OK` response.
+pub fn to_response(event: Event) -> Result<Response<Full<Bytes>>> {
+    let (headers, body) = to_body(event)?;
+    let mut response = Response::new(body);
+    *response.headers_mut() = headers;
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use http_body_util::StreamBody;
+    use std::convert::Infallible;
+
+    #[tokio::test]
+    async fn test_request_roundtrip() {
+        let expected = fixtures::v10::full_binary_json_data_string_extension();
+
+        let request = to_request(expected.clone()).unwrap();
+
+        assert_eq!(request.headers()["ce-id"], "0001");
+        assert_eq!(request_to_event(request).await.unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_response_roundtrip() {
+        let expected = fixtures::v10::minimal_string_extension();
+
+        let response = to_response(expected.clone()).unwrap();
+
+        assert_eq!(response_to_event(response).await.unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_to_event_from_chunked_body() {
+        let expected = fixtures::v10::full_json_data();
+        let json = serde_json::to_vec(&expected).unwrap();
+        let (head, tail) = json.split_at(json.len() / 2);
+        let body = StreamBody::new(futures::stream::iter(vec![
+            Ok::<_, Infallible>(http_body_lib::Frame::data(Bytes::copy_from_slice(head))),
+            Ok(http_body_lib::Frame::data(Bytes::copy_from_slice(tail))),
+        ]));
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            http::HeaderValue::from_static("application/cloudevents+json"),
+        );
+
+        assert_eq!(to_event_from_body(&headers, body).await.unwrap(), expected);
+    }
+}
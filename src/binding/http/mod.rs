@@ -1,21 +1,38 @@
+#[cfg_attr(docsrs, doc(cfg(feature = "http-body")))]
+#[cfg(feature = "http-body")]
+pub mod body;
 pub mod builder;
 pub mod deserializer;
 mod headers;
 
 use crate::{
-    message::{Error, MessageDeserializer},
+    binding::{
+        attribute_name, is_media_type, percent_decode_header_value, DuplicateHeaders, Limits,
+        CLOUDEVENTS_JSON_HEADER,
+    },
+    event::{DataRef, EventRef},
+    message::{Encoding, Error, MessageDeserializer},
     Event,
 };
 use deserializer::Deserializer;
 pub use headers::Headers;
 mod serializer;
+#[cfg_attr(docsrs, doc(cfg(feature = "http-binding")))]
+#[cfg(feature = "http-binding")]
+pub mod stream;
 
 pub use builder::Builder;
+use bytes::Bytes;
 use core::convert::TryFrom;
 use http::Response;
 
 use http;
+#[cfg(feature = "reqwest")]
+pub(crate) use serializer::header_key;
+#[cfg(any(feature = "reqwest", test))]
+pub(crate) use serializer::header_value;
 pub use serializer::Serializer;
+use std::borrow::Cow;
 use std::convert::TryInto;
 use std::fmt::Debug;
 
@@ -26,11 +43,99 @@ pub fn to_event<'a, T: Headers<'a>>(
     headers: &'a T,
     body: Vec<u8>,
 ) -> std::result::Result<Event, Error> {
-    MessageDeserializer::into_event(Deserializer::new(headers, body))
+    super::instrument::deserialize("http", || {
+        MessageDeserializer::into_event(Deserializer::new(headers, body))
+    })
+}
+
+/// Turn a pile of HTTP headers and a body held in a [`Bytes`] buffer into a CloudEvent,
+/// sharing the buffer with the event data instead of copying it
+pub fn to_event_bytes<'a, T: Headers<'a>>(
+    headers: &'a T,
+    body: Bytes,
+) -> std::result::Result<Event, Error> {
+    to_event_with(headers, body, DuplicateHeaders::default())
+}
+
+/// Like [`to_event_bytes`], applying the `duplicates` policy to the repeated headers carrying
+/// an attribute
+pub fn to_event_with<'a, T: Headers<'a>>(
+    headers: &'a T,
+    body: impl Into<Bytes>,
+    duplicates: DuplicateHeaders,
+) -> std::result::Result<Event, Error> {
+    to_event_with_limits(headers, body, duplicates, Limits::default())
+}
+
+/// Like [`to_event_with`], rejecting the messages exceeding the `limits`
+pub fn to_event_with_limits<'a, T: Headers<'a>>(
+    headers: &'a T,
+    body: impl Into<Bytes>,
+    duplicates: DuplicateHeaders,
+    limits: Limits,
+) -> std::result::Result<Event, Error> {
+    super::instrument::deserialize("http", || {
+        let deserializer = Deserializer::from_bytes(headers, body.into())
+            .duplicate_headers(duplicates)
+            .limits(limits);
+        let structured = deserializer.encoding() == Encoding::STRUCTURED;
+        let event = MessageDeserializer::into_event(deserializer)?;
+        if structured {
+            limits.check(&event)?;
+        }
+        Ok(event)
+    })
+}
+
+/// Turn the headers and the body of an HTTP message into an [`EventRef`] borrowing them,
+/// without copying the attributes nor the data
+pub fn to_event_ref<'a, T: Headers<'a>>(
+    headers: &'a T,
+    body: &'a [u8],
+) -> std::result::Result<EventRef<'a>, Error> {
+    if headers
+        .get(http::header::CONTENT_TYPE)
+        .is_some_and(|v| is_media_type(v, CLOUDEVENTS_JSON_HEADER))
+    {
+        return Ok(EventRef::from_json_slice(body)?);
+    }
+    if headers.get(SPEC_VERSION_HEADER).is_none() {
+        return Err(Error::WrongEncoding {});
+    }
+    let mut attributes = Vec::new();
+    for (hn, hv) in headers.iter() {
+        let value = crate::header_value_to_str!(hv)?;
+        let attribute = match attribute_name("ce-", hn.as_str()) {
+            Some(name) => (name, percent_decode_header_value(value)?),
+            _ if hn == http::header::CONTENT_TYPE => {
+                (Cow::Borrowed("datacontenttype"), Cow::Borrowed(value))
+            }
+            #[cfg(feature = "content-encoding")]
+            _ if hn == http::header::CONTENT_ENCODING => (
+                Cow::Borrowed(crate::content_encoding::CONTENT_ENCODING_EXTENSION),
+                Cow::Borrowed(value),
+            ),
+            _ => continue,
+        };
+        attributes.push(attribute);
+    }
+    let data = Some(body)
+        .filter(|b| !b.is_empty())
+        .map(|b| DataRef::Binary(Cow::Borrowed(b)));
+    EventRef::from_attributes(attributes, data)
 }
 
 pub fn header_prefix(name: &str) -> String {
-    super::header_prefix("ce-", name)
+    header_name(name).into_owned()
+}
+
+/// Like [`header_prefix`], without allocating the header names of the spec attributes.
+pub(crate) fn header_name(name: &str) -> Cow<'static, str> {
+    #[cfg(feature = "content-encoding")]
+    if name == crate::content_encoding::CONTENT_ENCODING_EXTENSION {
+        return Cow::Borrowed("content-encoding");
+    }
+    header_name!("ce-", name)
 }
 
 impl<T> TryFrom<Response<T>> for Event
@@ -50,8 +155,10 @@ where
 
 #[cfg(test)]
 mod tests {
+    use super::to_event_with_limits;
+    use crate::binding::{DuplicateHeaders, Limits};
     use crate::test::fixtures;
-    use crate::Event;
+    use crate::{AttributesWriter, Event};
     use core::convert::TryFrom;
     use http::Response;
 
@@ -72,4 +179,270 @@ mod tests {
 
         assert_eq!(event, Event::try_from(response).unwrap());
     }
+
+    #[test]
+    fn test_mixed_case_headers() {
+        let event = fixtures::v10::minimal_string_extension();
+
+        let response = Response::builder()
+            .header("Ce-Id", fixtures::id())
+            .header("CE-Source", fixtures::source())
+            .header("CE-TYPE", fixtures::ty())
+            .header("Ce-SpecVersion", "1.0")
+            .header("ce-SomeInt", "10")
+            .body(Vec::new())
+            .unwrap();
+
+        assert_eq!(event, Event::try_from(response).unwrap());
+
+        let expected = fixtures::v10::full_json_data();
+        let body = serde_json::to_vec(&expected).unwrap();
+        let mut headers = http::HeaderMap::new();
+        headers.insert(
+            "Content-Type",
+            http::HeaderValue::from_static("Application/CloudEvents+JSON; charset=utf-8"),
+        );
+
+        assert_eq!(super::to_event(&headers, body.clone()).unwrap(), expected);
+        assert_eq!(
+            super::to_event_ref(&headers, &body)
+                .unwrap()
+                .into_event()
+                .unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_invalid_extension() {
+        let response = Response::builder()
+            .header("ce-id", fixtures::id())
+            .header("ce-source", fixtures::source())
+            .header("ce-type", fixtures::ty())
+            .header("ce-specversion", "1.0")
+            .header("ce-some_int", "10")
+            .body(Vec::new())
+            .unwrap();
+
+        assert!(matches!(
+            Event::try_from(response),
+            Err(crate::message::Error::InvalidExtension { .. })
+        ));
+    }
+
+    #[test]
+    fn test_limits_count_repeated_headers() {
+        let mut response = Response::builder()
+            .header("ce-id", fixtures::id())
+            .header("ce-source", fixtures::source())
+            .header("ce-type", fixtures::ty())
+            .header("ce-specversion", "1.0");
+        for i in 0..3 {
+            response = response.header("ce-someint", i.to_string());
+        }
+        let response = response.body(()).unwrap();
+        let headers = response.headers();
+
+        assert!(to_event_with_limits(
+            headers,
+            Vec::new(),
+            DuplicateHeaders::LastWins,
+            Limits::new().max_extensions(3)
+        )
+        .is_ok());
+        assert!(matches!(
+            to_event_with_limits(
+                headers,
+                Vec::new(),
+                DuplicateHeaders::LastWins,
+                Limits::new().max_extensions(2)
+            ),
+            Err(crate::message::Error::TooManyExtensions { max: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_limits_structured_len() {
+        let body = serde_json::to_vec(&fixtures::v10::full_json_data()).unwrap();
+        let response = Response::builder()
+            .header("content-type", "application/cloudevents+json")
+            .body(())
+            .unwrap();
+        let headers = response.headers();
+
+        assert!(to_event_with_limits(
+            headers,
+            body.clone(),
+            DuplicateHeaders::default(),
+            Limits::new().max_structured_len(body.len())
+        )
+        .is_ok());
+        assert!(matches!(
+            to_event_with_limits(
+                headers,
+                body,
+                DuplicateHeaders::default(),
+                Limits::new().max_structured_len(16)
+            ),
+            Err(crate::message::Error::StructuredMessageTooLong { max: 16 })
+        ));
+    }
+
+    #[test]
+    fn test_extension_types() {
+        use crate::binding::{ExtensionType, ExtensionTypes};
+        use crate::message::MessageDeserializer;
+
+        let response = Response::builder()
+            .header("ce-id", fixtures::id())
+            .header("ce-source", fixtures::source())
+            .header("ce-type", fixtures::ty())
+            .header("ce-specversion", "1.0")
+            .header("ce-someint", "10")
+            .header("ce-flag", "yes")
+            .body(())
+            .unwrap();
+        let to_event = |types| {
+            super::Deserializer::new(response.headers(), Vec::new())
+                .extension_types(types)
+                .into_event()
+        };
+
+        let event = to_event(ExtensionTypes::new().infer(true)).unwrap();
+        assert_eq!(event.extension("someint"), Some(&10.into()));
+        assert_eq!(event.extension("flag"), Some(&"yes".into()));
+
+        assert!(matches!(
+            to_event(ExtensionTypes::new().declare("flag", ExtensionType::Boolean)),
+            Err(crate::message::Error::InvalidExtensionType { .. })
+        ));
+    }
+
+    #[test]
+    fn test_to_event_bytes() {
+        let body = bytes::Bytes::from_static(b"{\"hello\": \"world\"}");
+        let headers = Response::builder()
+            .header("ce-id", fixtures::id())
+            .header("ce-source", fixtures::source())
+            .header("ce-type", fixtures::ty())
+            .header("ce-specversion", "1.0")
+            .header("content-type", "application/json")
+            .body(())
+            .unwrap()
+            .headers()
+            .to_owned();
+
+        let event = super::to_event_bytes(&headers, body.clone()).unwrap();
+
+        match event.data() {
+            Some(crate::event::Data::Bytes(b)) => assert_eq!(b.as_ptr(), body.as_ptr()),
+            data => panic!("unexpected data {:?}", data),
+        }
+        assert_eq!(
+            event.data(),
+            Some(&crate::event::Data::Binary(body.to_vec()))
+        );
+    }
+
+    #[test]
+    fn test_header_name() {
+        use std::borrow::Cow;
+
+        assert!(matches!(super::header_name("id"), Cow::Borrowed("ce-id")));
+        assert!(matches!(
+            super::header_name("datacontenttype"),
+            Cow::Borrowed("content-type")
+        ));
+        assert_eq!(super::header_name("someint"), "ce-someint");
+    }
+
+    #[test]
+    fn test_header_value() {
+        use crate::message::MessageAttributeValue;
+
+        assert_eq!(
+            super::header_value(
+                "subject",
+                MessageAttributeValue::String("héllo \"%".to_string())
+            )
+            .unwrap(),
+            "h%C3%A9llo%20%22%25"
+        );
+        assert_eq!(
+            super::header_value("someint", MessageAttributeValue::Integer(10)).unwrap(),
+            "10"
+        );
+        assert!(super::header_value(
+            "datacontenttype",
+            MessageAttributeValue::String("héllo".to_string())
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_percent_encoded_roundtrip() {
+        let mut expected = fixtures::v10::minimal();
+        expected.set_subject(Some("Grüße, \"100%\""));
+        expected.set_extension("note", "café");
+        let request = http::Request::<Option<Vec<u8>>>::try_from(expected.clone()).unwrap();
+
+        assert_eq!(
+            request.headers()["ce-subject"],
+            "Gr%C3%BC%C3%9Fe,%20%22100%25%22"
+        );
+        assert_eq!(
+            super::to_event(request.headers(), Vec::new()).unwrap(),
+            expected
+        );
+
+        let event_ref = super::to_event_ref(request.headers(), &[]).unwrap();
+        assert_eq!(
+            event_ref.attributes.subject.as_deref(),
+            Some("Grüße, \"100%\"")
+        );
+        assert_eq!(event_ref.into_event().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_to_event_ref() {
+        let expected = fixtures::v10::full_binary_json_data_string_extension();
+        let request = http::Request::<Option<Vec<u8>>>::try_from(expected.clone()).unwrap();
+        let body = request.body().clone().unwrap();
+
+        let event_ref = super::to_event_ref(request.headers(), &body).unwrap();
+
+        assert!(matches!(
+            event_ref.attributes.id,
+            std::borrow::Cow::Borrowed(_)
+        ));
+        assert_eq!(event_ref.into_event().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_to_event_ref_structured() {
+        let expected = fixtures::v10::full_json_data();
+        let body = serde_json::to_vec(&expected).unwrap();
+        let mut headers = http::HeaderMap::new();
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            http::HeaderValue::from_static("application/cloudevents+json"),
+        );
+
+        let event_ref = super::to_event_ref(&headers, &body).unwrap();
+
+        assert_eq!(event_ref.into_event().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_relative_source_roundtrip() {
+        let mut expected = fixtures::v10::minimal();
+        expected.set_source("/cluster/node-1");
+        let request = http::Request::<Option<Vec<u8>>>::try_from(expected.clone()).unwrap();
+
+        assert_eq!(request.headers()["ce-source"], "/cluster/node-1");
+        assert_eq!(
+            super::to_event(request.headers(), Vec::new()).unwrap(),
+            expected
+        );
+    }
 }
@@ -2,8 +2,8 @@ use std::{cell::RefCell, rc::Rc};
 
 use crate::binding::http::builder::Builder;
 use crate::binding::{
-    http::{header_prefix, SPEC_VERSION_HEADER},
-    CLOUDEVENTS_JSON_HEADER,
+    http::{header_name, SPEC_VERSION_HEADER},
+    percent_encode_header_value, CLOUDEVENTS_JSON_HEADER,
 };
 use crate::event::SpecVersion;
 use crate::message::BinaryDeserializer;
@@ -13,18 +13,43 @@ use crate::message::{
 use crate::Event;
 use http::Request;
 
+use bytes::Bytes;
 use http;
+use std::borrow::Cow;
 use std::convert::TryFrom;
 use std::fmt::Debug;
 
-macro_rules! str_to_header_value {
-    ($header_value:expr) => {
-        http::header::HeaderValue::from_str(&$header_value.to_string()).map_err(|e| {
-            crate::message::Error::Other {
-                source: Box::new(e),
-            }
-        })
+/// Convert `value` to the [`http::HeaderValue`] of the attribute `name`, moving the string of
+/// [`MessageAttributeValue::String`] instead of copying it.
+///
+/// The values of the `ce-` headers are percent-encoded.
+pub(crate) fn header_value(name: &str, value: MessageAttributeValue) -> Result<http::HeaderValue> {
+    let value = String::from(value);
+    let bytes = if header_name(name).starts_with("ce-") {
+        match percent_encode_header_value(&value) {
+            Cow::Borrowed(_) => Bytes::from(value),
+            Cow::Owned(encoded) => Bytes::from(encoded),
+        }
+    } else {
+        Bytes::from(value)
     };
+    let value = http::HeaderValue::from_maybe_shared(bytes).map_err(|e| Error::Other {
+        source: Box::new(e),
+    })?;
+    // Only visible ASCII can be read back by the deserializer
+    value.to_str().map_err(|e| Error::Other {
+        source: Box::new(e),
+    })?;
+    Ok(value)
+}
+
+pub(crate) fn header_key(name: &str) -> Result<http::HeaderName> {
+    match header_name(name) {
+        Cow::Borrowed(s) => Ok(http::HeaderName::from_static(s)),
+        Cow::Owned(s) => http::HeaderName::try_from(s).map_err(|e| Error::Other {
+            source: Box::new(e),
+        }),
+    }
 }
 
 pub struct Serializer<T> {
@@ -40,23 +65,24 @@ impl<T> Serializer<T> {
 
 impl<T> BinarySerializer<T> for Serializer<T> {
     fn set_spec_version(self, spec_version: SpecVersion) -> Result<Self> {
-        self.builder
-            .borrow_mut()
-            .header(SPEC_VERSION_HEADER, str_to_header_value!(spec_version)?);
+        self.builder.borrow_mut().header(
+            SPEC_VERSION_HEADER,
+            http::HeaderValue::from_static(spec_version.as_str()),
+        );
         Ok(self)
     }
 
     fn set_attribute(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
         self.builder
             .borrow_mut()
-            .header(&header_prefix(name), str_to_header_value!(value)?);
+            .header(&header_name(name), header_value(name, value)?);
         Ok(self)
     }
 
     fn set_extension(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
         self.builder
             .borrow_mut()
-            .header(&header_prefix(name), str_to_header_value!(value)?);
+            .header(&header_name(name), header_value(name, value)?);
         Ok(self)
     }
 
@@ -86,19 +112,20 @@ where
     <T as TryFrom<Vec<u8>>>::Error: Debug,
 {
     fn set_spec_version(mut self, sv: SpecVersion) -> Result<Self> {
-        self = self.header(SPEC_VERSION_HEADER, &sv.to_string());
+        self = self.header(
+            http::HeaderName::from_static(SPEC_VERSION_HEADER),
+            http::HeaderValue::from_static(sv.as_str()),
+        );
         Ok(self)
     }
 
     fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
-        let key = &header_prefix(name);
-        self = self.header(key, &value.to_string());
+        self = self.header(header_key(name)?, header_value(name, value)?);
         Ok(self)
     }
 
     fn set_extension(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
-        let key = &header_prefix(name);
-        self = self.header(key, &value.to_string());
+        self = self.header(header_key(name)?, header_value(name, value)?);
         Ok(self)
     }
 
@@ -124,12 +151,16 @@ where
     type Error = crate::message::Error;
 
     fn try_from(event: Event) -> Result<Self> {
-        BinaryDeserializer::deserialize_binary(event, http::request::Builder::new())
+        crate::binding::instrument::serialize("http", event, |event| {
+            BinaryDeserializer::deserialize_binary(event, http::request::Builder::new())
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::event::{WithSecrets, SENSITIVE_EXTENSION_MASK};
+    use crate::message::BinaryDeserializer;
     use crate::test::fixtures;
     use bytes::Bytes;
     use http::Request;
@@ -158,4 +189,21 @@ mod tests {
             &Bytes::from(fixtures::json_data().to_string())
         );
     }
+
+    #[test]
+    fn test_sensitive_extension_to_http_request() {
+        let mut event = fixtures::v10::minimal_string_extension();
+        event.set_extension("authtoken", "s3cr3t");
+        event.mark_sensitive_extension("authtoken");
+
+        let request: Request<Option<Vec<u8>>> = Request::try_from(event.clone()).unwrap();
+        assert_eq!(request.headers()["ce-authtoken"], SENSITIVE_EXTENSION_MASK);
+
+        let request: Request<Option<Vec<u8>>> = BinaryDeserializer::deserialize_binary(
+            WithSecrets(event),
+            http::request::Builder::new(),
+        )
+        .unwrap();
+        assert_eq!(request.headers()["ce-authtoken"], "s3cr3t");
+    }
 }
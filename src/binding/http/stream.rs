@@ -0,0 +1,233 @@
+//! Events with a streamed body, to forward large payloads without buffering them in memory.
+//!
+//! In binary mode the attributes of a [`StreamingEvent`] are read from and written to the
+//! headers, while its body is passed through as a stream. The structured mode embeds the data
+//! in the JSON event, so the body must be buffered first, as configured by [`Spool`].
+
+use super::{to_event, Headers};
+use crate::binding::{CLOUDEVENTS_JSON_HEADER, CONTENT_TYPE};
+use crate::message::{BinaryDeserializer, Error, MessageDeserializer, Result};
+use crate::Event;
+use bytes::{Bytes, BytesMut};
+use futures::stream::{self, BoxStream, StreamExt};
+use http::request::{Builder, Request};
+use std::convert::TryFrom;
+
+/// Stream of the body chunks of a [`StreamingEvent`].
+pub type BodyStream = BoxStream<'static, std::io::Result<Bytes>>;
+
+/// How a body stream is handled when it must be buffered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Spool {
+    /// Fail instead of buffering the body.
+    Reject,
+    /// Buffer the body in memory, failing if it is larger than `limit` bytes.
+    Buffer { limit: usize },
+}
+
+/// [`Event`] whose data is a [`BodyStream`].
+pub struct StreamingEvent {
+    event: Event,
+    body: Option<BodyStream>,
+}
+
+impl StreamingEvent {
+    /// Create a new [`StreamingEvent`] with the attributes of `event` and the data read from `body`.
+    ///
+    /// The data of `event`, if any, is dropped. Its `datacontenttype` is kept.
+    pub fn new(mut event: Event, body: BodyStream) -> Self {
+        event.data = None;
+        StreamingEvent {
+            event,
+            body: Some(body),
+        }
+    }
+
+    /// Get the attributes of the event.
+    pub fn event(&self) -> &Event {
+        &self.event
+    }
+
+    /// Get the attributes of the event, to modify them.
+    pub fn event_mut(&mut self) -> &mut Event {
+        &mut self.event
+    }
+
+    /// Split into the event, without data, and the body stream.
+    pub fn into_parts(self) -> (Event, Option<BodyStream>) {
+        (self.event, self.body)
+    }
+
+    /// Buffer the body into the data of the event, failing if it is larger than `limit` bytes.
+    pub async fn into_event(self, limit: usize) -> Result<Event> {
+        let (mut event, body) = self.into_parts();
+        if let Some(body) = body {
+            let data = spool(body, Spool::Buffer { limit }).await?;
+            event.set_data_unchecked(data);
+        }
+        Ok(event)
+    }
+
+    /// Build a binary mode request, streaming the body.
+    pub fn into_binary_request(self, builder: Builder) -> Result<Request<BodyStream>> {
+        let (event, body) = self.into_parts();
+        let request: Request<Option<Vec<u8>>> =
+            BinaryDeserializer::deserialize_binary(event, builder)?;
+        Ok(request.map(|_| body.unwrap_or_else(|| stream::empty().boxed())))
+    }
+
+    /// Build a structured mode request, buffering the body as configured by `spool`.
+    pub async fn into_structured_request(
+        self,
+        builder: Builder,
+        spool: Spool,
+    ) -> Result<Request<Vec<u8>>> {
+        let (mut event, body) = self.into_parts();
+        if let Some(body) = body {
+            let data = self::spool(body, spool).await?;
+            event.set_data_unchecked(data);
+        }
+        builder
+            .header(CONTENT_TYPE, CLOUDEVENTS_JSON_HEADER)
+            .body(serde_json::to_vec(&event)?)
+            .map_err(|e| Error::Other {
+                source: Box::new(e),
+            })
+    }
+}
+
+/// Turn HTTP headers and a body stream into a [`StreamingEvent`].
+///
+/// A binary mode body is passed through, while a structured mode body is buffered as configured
+/// by `spool`, to read the event attributes.
+pub async fn to_streaming_event<'a, T: Headers<'a>>(
+    headers: &'a T,
+    body: BodyStream,
+    spool: Spool,
+) -> Result<StreamingEvent> {
+    let deserializer = super::Deserializer::new(headers, Vec::new());
+    if deserializer.encoding() == crate::message::Encoding::BINARY {
+        let event = MessageDeserializer::into_event(deserializer)?;
+        return Ok(StreamingEvent {
+            event,
+            body: Some(body),
+        });
+    }
+
+    let mut event = to_event(headers, Vec::from(self::spool(body, spool).await?))?;
+    let body = match event.data.take() {
+        Some(data) => {
+            let data = Bytes::try_from(data)?;
+            Some(stream::once(async move { Ok(data) }).boxed())
+        }
+        None => None,
+    };
+    Ok(StreamingEvent { event, body })
+}
+
+async fn spool(mut body: BodyStream, spool: Spool) -> Result<Bytes> {
+    let limit = match spool {
+        Spool::Reject => {
+            return Err(Error::Other {
+                source: "the event body must be buffered, which is rejected".into(),
+            })
+        }
+        Spool::Buffer { limit } => limit,
+    };
+
+    let mut buffer = BytesMut::new();
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk?;
+        if buffer.len() + chunk.len() > limit {
+            return Err(Error::Other {
+                source: format!("the event body is larger than {} bytes", limit).into(),
+            });
+        }
+        buffer.extend_from_slice(&chunk);
+    }
+    Ok(buffer.freeze())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+
+    fn body(chunks: &[&'static [u8]]) -> BodyStream {
+        stream::iter(
+            chunks
+                .iter()
+                .map(|c| Ok(Bytes::from_static(c)))
+                .collect::<Vec<_>>(),
+        )
+        .boxed()
+    }
+
+    async fn collect(body: BodyStream) -> Vec<u8> {
+        body.map(|c| c.unwrap().to_vec()).concat().await
+    }
+
+    #[tokio::test]
+    async fn binary_passthrough() {
+        let request = StreamingEvent::new(
+            fixtures::v10::full_binary_json_data_string_extension(),
+            body(&[b"{\"hello\":", b"\"world\"}"]),
+        )
+        .into_binary_request(Request::post("http://localhost"))
+        .unwrap();
+
+        let (parts, body) = request.into_parts();
+        assert_eq!(parts.headers["ce-id"], "0001");
+        assert_eq!(parts.headers["content-type"], "application/json");
+
+        let received = to_streaming_event(&parts.headers, body, Spool::Reject)
+            .await
+            .unwrap();
+        assert_eq!(
+            received.into_event(1024).await.unwrap(),
+            fixtures::v10::full_binary_json_data_string_extension()
+        );
+    }
+
+    #[tokio::test]
+    async fn structured_spool() {
+        let event = StreamingEvent::new(fixtures::v10::minimal(), body(&[b"hello ", b"world"]));
+        assert!(matches!(
+            event
+                .into_structured_request(Request::post("http://localhost"), Spool::Reject)
+                .await,
+            Err(Error::Other { .. })
+        ));
+
+        let request = StreamingEvent::new(fixtures::v10::minimal(), body(&[b"hello ", b"world"]))
+            .into_structured_request(
+                Request::post("http://localhost"),
+                Spool::Buffer { limit: 1024 },
+            )
+            .await
+            .unwrap();
+        let (parts, json) = request.into_parts();
+        assert_eq!(parts.headers["content-type"], CLOUDEVENTS_JSON_HEADER);
+
+        let received = to_streaming_event(
+            &parts.headers,
+            stream::once(async move { Ok(Bytes::from(json)) }).boxed(),
+            Spool::Buffer { limit: 1024 },
+        )
+        .await
+        .unwrap();
+        assert_eq!(received.event(), &fixtures::v10::minimal());
+        let (_, body) = received.into_parts();
+        assert_eq!(collect(body.unwrap()).await, b"hello world");
+    }
+
+    #[tokio::test]
+    async fn limit() {
+        let event = StreamingEvent::new(fixtures::v10::minimal(), body(&[b"hello ", b"world"]));
+
+        assert!(matches!(
+            event.into_event(8).await,
+            Err(Error::Other { .. })
+        ));
+    }
+}
@@ -1,6 +1,9 @@
 use super::{Headers, SPEC_VERSION_HEADER};
 use crate::{
-    binding::CLOUDEVENTS_JSON_HEADER,
+    binding::{
+        attribute_name, is_media_type, percent_decode_header_value, DuplicateHeaders,
+        ExtensionTypes, Limits, CLOUDEVENTS_JSON_HEADER,
+    },
     event::SpecVersion,
     header_value_to_str, message,
     message::{
@@ -8,17 +11,55 @@ use crate::{
         Result, StructuredDeserializer, StructuredSerializer,
     },
 };
+use bytes::Bytes;
 use http_0_2 as http;
 use std::convert::TryFrom;
 
 pub struct Deserializer<'a, T: Headers<'a>> {
     headers: &'a T,
-    body: Vec<u8>,
+    body: Bytes,
+    duplicates: DuplicateHeaders,
+    limits: Limits,
+    extension_types: ExtensionTypes,
 }
 
 impl<'a, T: Headers<'a>> Deserializer<'a, T> {
     pub fn new(headers: &'a T, body: Vec<u8>) -> Deserializer<'a, T> {
-        Deserializer { headers, body }
+        Self::from_bytes(headers, Bytes::from(body))
+    }
+
+    /// Create a new [`Deserializer`], moving the `body` into the event data without copying it.
+    pub fn from_bytes(headers: &'a T, body: Bytes) -> Deserializer<'a, T> {
+        Deserializer {
+            headers,
+            body,
+            duplicates: DuplicateHeaders::default(),
+            limits: Limits::default(),
+            extension_types: ExtensionTypes::default(),
+        }
+    }
+
+    /// Set the policy for the repeated headers carrying an attribute. Defaults to
+    /// [`DuplicateHeaders::LastWins`].
+    pub fn duplicate_headers(mut self, duplicates: DuplicateHeaders) -> Self {
+        self.duplicates = duplicates;
+        self
+    }
+
+    /// Set the [`Limits`] checked against the headers of a binary mode message, before
+    /// deduplicating and reading them, so a repeated header counts once per occurrence, and
+    /// against the length of a structured mode message, before parsing it. Nothing is limited
+    /// by default.
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Set the [`ExtensionTypes`] of the extensions of a binary mode message, which are
+    /// otherwise read as strings.
+    pub fn extension_types(mut self, extension_types: ExtensionTypes) -> Self {
+        self.extension_types = extension_types;
+        self
     }
 }
 
@@ -28,57 +69,104 @@ impl<'a, T: Headers<'a>> BinaryDeserializer for Deserializer<'a, T> {
             return Err(message::Error::WrongEncoding {});
         }
 
-        let spec_version = SpecVersion::try_from(
+        // Check the limits against the raw headers, before deduplicating them
+        let raw_spec_version = self
+            .headers
+            .get(SPEC_VERSION_HEADER)
+            .and_then(|hv| hv.to_str().ok())
+            .and_then(|sv| SpecVersion::try_from(sv).ok());
+        if let Some(spec_version) = raw_spec_version {
+            let attributes = spec_version.attribute_names();
+            self.limits.check_attributes(
+                self.headers
+                    .iter()
+                    .filter(|(hn, _)| is_attribute_header(hn))
+                    .map(|(hn, hv)| {
+                        let name = hn.as_str().strip_prefix("ce-");
+                        let is_extension = name.is_some_and(|name| !attributes.contains(&name));
+                        (name.unwrap_or(hn.as_str()), hv.len(), is_extension)
+                    }),
+            )?;
+        }
+
+        let headers = self.duplicates.dedup(
             self.headers
-                .get(SPEC_VERSION_HEADER)
+                .iter()
+                .filter(|(hn, _)| is_attribute_header(hn)),
+        )?;
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(hn, _)| *hn == name)
+                .map(|(_, hv)| *hv)
+        };
+
+        let spec_version = SpecVersion::try_from(
+            header(SPEC_VERSION_HEADER)
                 .map(|a| header_value_to_str!(a))
                 .unwrap()?,
-        )?;
+        )
+        .map_err(|e| e.in_attribute(SPEC_VERSION_HEADER))?;
 
         let attributes = spec_version.attribute_names();
 
         visitor = visitor.set_spec_version(spec_version)?;
 
-        for (hn, hv) in self.headers.iter().filter(|(hn, _)| {
-            let key = hn.as_str();
-            SPEC_VERSION_HEADER.ne(key) && key.starts_with("ce-")
-        }) {
-            let name = &hn.as_str()["ce-".len()..];
-
-            if attributes.contains(&name) {
-                visitor = visitor.set_attribute(
-                    name,
-                    MessageAttributeValue::String(String::from(header_value_to_str!(hv)?)),
-                )?
+        for (hn, hv) in &headers {
+            let name = match attribute_name("ce-", hn.as_str()) {
+                Some(name) if name != "specversion" => name,
+                _ => continue,
+            };
+
+            let value = MessageAttributeValue::String(
+                percent_decode_header_value(header_value_to_str!(hv)?)?.into_owned(),
+            );
+
+            if attributes.contains(&name.as_ref()) {
+                visitor = visitor.set_attribute(&name, value)?
             } else {
-                visitor = visitor.set_extension(
-                    name,
-                    MessageAttributeValue::String(String::from(header_value_to_str!(hv)?)),
-                )?
+                let value = self.extension_types.convert(&name, value.into())?;
+                visitor = visitor.set_extension(&name, value.into())?
             }
         }
 
-        if let Some(hv) = self.headers.get(http::header::CONTENT_TYPE) {
+        if let Some(hv) = header(http::header::CONTENT_TYPE.as_str()) {
             visitor = visitor.set_attribute(
                 "datacontenttype",
                 MessageAttributeValue::String(String::from(header_value_to_str!(hv)?)),
             )?
         }
 
+        #[cfg(feature = "content-encoding")]
+        if let Some(hv) = header(http::header::CONTENT_ENCODING.as_str()) {
+            visitor = visitor.set_extension(
+                crate::content_encoding::CONTENT_ENCODING_EXTENSION,
+                MessageAttributeValue::String(String::from(header_value_to_str!(hv)?)),
+            )?
+        }
+
         if !self.body.is_empty() {
-            visitor.end_with_data(self.body)
+            visitor.end_with_bytes(self.body)
         } else {
             visitor.end()
         }
     }
 }
 
+/// Whether the header `name` carries an attribute.
+fn is_attribute_header(name: &http::HeaderName) -> bool {
+    attribute_name("ce-", name.as_str()).is_some()
+        || name == http::header::CONTENT_TYPE
+        || (cfg!(feature = "content-encoding") && name == http::header::CONTENT_ENCODING)
+}
+
 impl<'a, T: Headers<'a>> StructuredDeserializer for Deserializer<'a, T> {
     fn deserialize_structured<R: Sized, V: StructuredSerializer<R>>(self, visitor: V) -> Result<R> {
         if self.encoding() != Encoding::STRUCTURED {
             return Err(message::Error::WrongEncoding {});
         }
-        visitor.set_structured_event(self.body)
+        self.limits.check_structured_len(self.body.len())?;
+        visitor.set_structured_event(Vec::from(self.body))
     }
 }
 
@@ -87,9 +175,7 @@ impl<'a, T: Headers<'a>> MessageDeserializer for Deserializer<'a, T> {
         if self
             .headers
             .get(http::header::CONTENT_TYPE)
-            .and_then(|v| v.to_str().ok())
-            .filter(|&v| v.starts_with(CLOUDEVENTS_JSON_HEADER))
-            .is_some()
+            .is_some_and(|v| is_media_type(v, CLOUDEVENTS_JSON_HEADER))
         {
             Encoding::STRUCTURED
         } else if self.headers.get(SPEC_VERSION_HEADER).is_some() {
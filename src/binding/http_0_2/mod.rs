@@ -3,7 +3,8 @@ pub mod deserializer;
 mod headers;
 
 use crate::{
-    message::{Error, MessageDeserializer},
+    binding::{DuplicateHeaders, Limits},
+    message::{Encoding, Error, MessageDeserializer},
     Event,
 };
 use deserializer::Deserializer;
@@ -11,10 +12,12 @@ pub use headers::Headers;
 mod serializer;
 
 pub use builder::Builder;
+use bytes::Bytes;
 use core::convert::TryFrom;
 use http::Response;
 use http_0_2 as http;
 pub use serializer::Serializer;
+use std::borrow::Cow;
 use std::convert::TryInto;
 use std::fmt::Debug;
 
@@ -25,11 +28,61 @@ pub fn to_event<'a, T: Headers<'a>>(
     headers: &'a T,
     body: Vec<u8>,
 ) -> std::result::Result<Event, Error> {
-    MessageDeserializer::into_event(Deserializer::new(headers, body))
+    super::instrument::deserialize("http", || {
+        MessageDeserializer::into_event(Deserializer::new(headers, body))
+    })
+}
+
+/// Turn a pile of HTTP headers and a body held in a [`Bytes`] buffer into a CloudEvent,
+/// sharing the buffer with the event data instead of copying it
+pub fn to_event_bytes<'a, T: Headers<'a>>(
+    headers: &'a T,
+    body: Bytes,
+) -> std::result::Result<Event, Error> {
+    to_event_with(headers, body, DuplicateHeaders::default())
+}
+
+/// Like [`to_event_bytes`], applying the `duplicates` policy to the repeated headers carrying
+/// an attribute
+pub fn to_event_with<'a, T: Headers<'a>>(
+    headers: &'a T,
+    body: impl Into<Bytes>,
+    duplicates: DuplicateHeaders,
+) -> std::result::Result<Event, Error> {
+    to_event_with_limits(headers, body, duplicates, Limits::default())
+}
+
+/// Like [`to_event_with`], rejecting the messages exceeding the `limits`
+pub fn to_event_with_limits<'a, T: Headers<'a>>(
+    headers: &'a T,
+    body: impl Into<Bytes>,
+    duplicates: DuplicateHeaders,
+    limits: Limits,
+) -> std::result::Result<Event, Error> {
+    super::instrument::deserialize("http", || {
+        let deserializer = Deserializer::from_bytes(headers, body.into())
+            .duplicate_headers(duplicates)
+            .limits(limits);
+        let structured = deserializer.encoding() == Encoding::STRUCTURED;
+        let event = MessageDeserializer::into_event(deserializer)?;
+        if structured {
+            limits.check(&event)?;
+        }
+        Ok(event)
+    })
 }
 
 pub fn header_prefix(name: &str) -> String {
-    super::header_prefix("ce-", name)
+    header_name(name).into_owned()
+}
+
+/// Like [`header_prefix`], without allocating the header names of the spec attributes.
+pub(crate) fn header_name(name: &str) -> Cow<'static, str> {
+    #[cfg(feature = "content-encoding")]
+    if name == crate::content_encoding::CONTENT_ENCODING_EXTENSION {
+        return Cow::Borrowed("content-encoding");
+    }
+    header_name!("ce-", name)
 }
 
 impl<T> TryFrom<Response<T>> for Event
@@ -70,4 +123,26 @@ mod tests {
 
         assert_eq!(event, Event::try_from(response).unwrap());
     }
+
+    #[test]
+    fn test_limits_structured_len() {
+        use super::to_event_with_limits;
+        use crate::binding::{DuplicateHeaders, Limits};
+
+        let body = serde_json::to_vec(&fixtures::v10::full_json_data()).unwrap();
+        let response = Response::builder()
+            .header("content-type", "application/cloudevents+json")
+            .body(())
+            .unwrap();
+
+        assert!(matches!(
+            to_event_with_limits(
+                response.headers(),
+                body,
+                DuplicateHeaders::default(),
+                Limits::new().max_structured_len(16)
+            ),
+            Err(crate::message::Error::StructuredMessageTooLong { max: 16 })
+        ));
+    }
 }
@@ -2,8 +2,8 @@ use std::{cell::RefCell, rc::Rc};
 
 use crate::binding::http_0_2::builder::Builder;
 use crate::binding::{
-    http_0_2::{header_prefix, SPEC_VERSION_HEADER},
-    CLOUDEVENTS_JSON_HEADER,
+    http_0_2::{header_name, SPEC_VERSION_HEADER},
+    percent_encode_header_value, CLOUDEVENTS_JSON_HEADER,
 };
 use crate::event::SpecVersion;
 use crate::message::BinaryDeserializer;
@@ -11,19 +11,44 @@ use crate::message::{
     BinarySerializer, Error, MessageAttributeValue, Result, StructuredSerializer,
 };
 use crate::Event;
+use bytes::Bytes;
 use http::Request;
 use http_0_2 as http;
+use std::borrow::Cow;
 use std::convert::TryFrom;
 use std::fmt::Debug;
 
-macro_rules! str_to_header_value {
-    ($header_value:expr) => {
-        http::header::HeaderValue::from_str(&$header_value.to_string()).map_err(|e| {
-            crate::message::Error::Other {
-                source: Box::new(e),
-            }
-        })
+/// Convert `value` to the [`http::HeaderValue`] of the attribute `name`, moving the string of
+/// [`MessageAttributeValue::String`] instead of copying it.
+///
+/// The values of the `ce-` headers are percent-encoded.
+fn header_value(name: &str, value: MessageAttributeValue) -> Result<http::HeaderValue> {
+    let value = String::from(value);
+    let bytes = if header_name(name).starts_with("ce-") {
+        match percent_encode_header_value(&value) {
+            Cow::Borrowed(_) => Bytes::from(value),
+            Cow::Owned(encoded) => Bytes::from(encoded),
+        }
+    } else {
+        Bytes::from(value)
     };
+    let value = http::HeaderValue::from_maybe_shared(bytes).map_err(|e| Error::Other {
+        source: Box::new(e),
+    })?;
+    // Only visible ASCII can be read back by the deserializer
+    value.to_str().map_err(|e| Error::Other {
+        source: Box::new(e),
+    })?;
+    Ok(value)
+}
+
+fn header_key(name: &str) -> Result<http::HeaderName> {
+    match header_name(name) {
+        Cow::Borrowed(s) => Ok(http::HeaderName::from_static(s)),
+        Cow::Owned(s) => http::HeaderName::try_from(s).map_err(|e| Error::Other {
+            source: Box::new(e),
+        }),
+    }
 }
 
 pub struct Serializer<T> {
@@ -39,23 +64,24 @@ impl<T> Serializer<T> {
 
 impl<T> BinarySerializer<T> for Serializer<T> {
     fn set_spec_version(self, spec_version: SpecVersion) -> Result<Self> {
-        self.builder
-            .borrow_mut()
-            .header(SPEC_VERSION_HEADER, str_to_header_value!(spec_version)?);
+        self.builder.borrow_mut().header(
+            SPEC_VERSION_HEADER,
+            http::HeaderValue::from_static(spec_version.as_str()),
+        );
         Ok(self)
     }
 
     fn set_attribute(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
         self.builder
             .borrow_mut()
-            .header(&header_prefix(name), str_to_header_value!(value)?);
+            .header(&header_name(name), header_value(name, value)?);
         Ok(self)
     }
 
     fn set_extension(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
         self.builder
             .borrow_mut()
-            .header(&header_prefix(name), str_to_header_value!(value)?);
+            .header(&header_name(name), header_value(name, value)?);
         Ok(self)
     }
 
@@ -85,19 +111,20 @@ where
     <T as TryFrom<Vec<u8>>>::Error: Debug,
 {
     fn set_spec_version(mut self, sv: SpecVersion) -> Result<Self> {
-        self = self.header(SPEC_VERSION_HEADER, &sv.to_string());
+        self = self.header(
+            http::HeaderName::from_static(SPEC_VERSION_HEADER),
+            http::HeaderValue::from_static(sv.as_str()),
+        );
         Ok(self)
     }
 
     fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
-        let key = &header_prefix(name);
-        self = self.header(key, &value.to_string());
+        self = self.header(header_key(name)?, header_value(name, value)?);
         Ok(self)
     }
 
     fn set_extension(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
-        let key = &header_prefix(name);
-        self = self.header(key, &value.to_string());
+        self = self.header(header_key(name)?, header_value(name, value)?);
         Ok(self)
     }
 
@@ -123,7 +150,9 @@ where
     type Error = crate::message::Error;
 
     fn try_from(event: Event) -> Result<Self> {
-        BinaryDeserializer::deserialize_binary(event, http::request::Builder::new())
+        crate::binding::instrument::serialize("http", event, |event| {
+            BinaryDeserializer::deserialize_binary(event, http::request::Builder::new())
+        })
     }
 }
 
@@ -0,0 +1,208 @@
+//! Facade over the [`http`](super::http) and [`http_0_2`](super::http_0_2) bindings, with the
+//! same API for the 1.x and 0.2 versions of the `http` crate, so the crates supporting both
+//! can convert events without duplicating the code for each version.
+//!
+//! ```
+//! use cloudevents::binding::http_compat::{HeaderMap, MessageBuilder};
+//! use cloudevents::message::Result;
+//! use cloudevents::Event;
+//!
+//! // Compiles against whichever `http` version provides the headers and the builder
+//! fn echo<H: HeaderMap, B: MessageBuilder>(
+//!     headers: &H,
+//!     body: Vec<u8>,
+//!     builder: B,
+//! ) -> Result<B::Message> {
+//!     let event = headers.to_event(body)?;
+//!     builder.event(event)
+//! }
+//! ```
+
+use crate::message::{Error, Result};
+use crate::Event;
+use bytes::Bytes;
+use std::convert::TryFrom;
+
+/// Header map of the `http` crate, either 1.x or 0.2.
+///
+/// This trait is sealed and cannot be implemented for types outside of this crate.
+pub trait HeaderMap: private::Sealed {
+    /// Turn these headers and a `body` into an [`Event`].
+    fn to_event(&self, body: Vec<u8>) -> Result<Event>;
+
+    /// Turn these headers and a `body` held in a [`Bytes`] buffer into an [`Event`], sharing
+    /// the buffer with the event data instead of copying it.
+    fn to_event_bytes(&self, body: Bytes) -> Result<Event>;
+
+    /// Insert the binary mode headers of `event`, replacing the existing values, and return
+    /// its data to be used as body.
+    fn insert_event(&mut self, event: Event) -> Result<Option<Vec<u8>>>;
+}
+
+/// Request or response builder of the `http` crate, either 1.x or 0.2.
+///
+/// This trait is sealed and cannot be implemented for types outside of this crate.
+pub trait MessageBuilder: private::Sealed + Sized {
+    /// The message built, with a [`Vec<u8>`] body.
+    type Message;
+
+    /// Build the message with the binary mode headers of `event` and its data as body.
+    fn event(self, event: Event) -> Result<Self::Message>;
+}
+
+macro_rules! impl_http_compat {
+    ($http:ident, $binding:ident) => {
+        impl HeaderMap for $http::HeaderMap {
+            fn to_event(&self, body: Vec<u8>) -> Result<Event> {
+                super::$binding::to_event(self, body)
+            }
+
+            fn to_event_bytes(&self, body: Bytes) -> Result<Event> {
+                super::$binding::to_event_bytes(self, body)
+            }
+
+            fn insert_event(&mut self, event: Event) -> Result<Option<Vec<u8>>> {
+                let (parts, body) =
+                    $http::Request::<Option<Vec<u8>>>::try_from(event)?.into_parts();
+                self.extend(parts.headers);
+                Ok(body)
+            }
+        }
+
+        impl MessageBuilder for $http::request::Builder {
+            type Message = $http::Request<Vec<u8>>;
+
+            fn event(mut self, event: Event) -> Result<Self::Message> {
+                let mut headers = $http::HeaderMap::new();
+                let body = headers.insert_event(event)?;
+                // Without headers the builder has an error, returned by `body`
+                if let Some(h) = self.headers_mut() {
+                    h.extend(headers);
+                }
+                self.body(body.unwrap_or_default())
+                    .map_err(|e| Error::Other {
+                        source: Box::new(e),
+                    })
+            }
+        }
+
+        impl MessageBuilder for $http::response::Builder {
+            type Message = $http::Response<Vec<u8>>;
+
+            fn event(mut self, event: Event) -> Result<Self::Message> {
+                let mut headers = $http::HeaderMap::new();
+                let body = headers.insert_event(event)?;
+                // Without headers the builder has an error, returned by `body`
+                if let Some(h) = self.headers_mut() {
+                    h.extend(headers);
+                }
+                self.body(body.unwrap_or_default())
+                    .map_err(|e| Error::Other {
+                        source: Box::new(e),
+                    })
+            }
+        }
+    };
+}
+
+#[cfg(any(
+    feature = "http-binding",
+    feature = "reqwest",
+    feature = "axum",
+    feature = "poem"
+))]
+impl_http_compat!(http, http);
+
+#[cfg(any(feature = "http-0-2-binding", feature = "actix", feature = "warp"))]
+impl_http_compat!(http_0_2, http_0_2);
+
+mod private {
+    // Sealing the HeaderMap and MessageBuilder
+    pub trait Sealed {}
+    #[cfg(any(
+        feature = "http-binding",
+        feature = "reqwest",
+        feature = "axum",
+        feature = "poem"
+    ))]
+    mod http_1 {
+        impl super::Sealed for http::HeaderMap {}
+        impl super::Sealed for http::request::Builder {}
+        impl super::Sealed for http::response::Builder {}
+    }
+    #[cfg(any(feature = "http-0-2-binding", feature = "actix", feature = "warp"))]
+    mod http_0_2 {
+        impl super::Sealed for http_0_2::HeaderMap {}
+        impl super::Sealed for http_0_2::request::Builder {}
+        impl super::Sealed for http_0_2::response::Builder {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HeaderMap, MessageBuilder};
+    use crate::message::Result;
+    use crate::test::fixtures;
+    use crate::Event;
+
+    fn echo<H: HeaderMap, B: MessageBuilder>(
+        headers: &H,
+        body: Vec<u8>,
+        builder: B,
+    ) -> Result<B::Message> {
+        builder.event(headers.to_event(body)?)
+    }
+
+    #[cfg(any(
+        feature = "http-binding",
+        feature = "reqwest",
+        feature = "axum",
+        feature = "poem"
+    ))]
+    #[test]
+    fn test_http_1() {
+        let expected = fixtures::v10::full_binary_json_data_string_extension();
+        let mut headers = http::HeaderMap::new();
+        headers.insert("ce-id", http::HeaderValue::from_static("stale"));
+        let body = headers.insert_event(expected.clone()).unwrap().unwrap();
+
+        assert_eq!(headers["ce-id"], "0001");
+        let response = echo(&headers, body, http::Response::builder().status(201)).unwrap();
+        assert_eq!(response.status(), 201);
+        let (parts, body) = response.into_parts();
+        assert_eq!(parts.headers.to_event(body).unwrap(), expected);
+    }
+
+    #[cfg(any(feature = "http-0-2-binding", feature = "actix", feature = "warp"))]
+    #[test]
+    fn test_http_0_2() {
+        let expected = fixtures::v10::full_binary_json_data_string_extension();
+        let mut headers = http_0_2::HeaderMap::new();
+        let body = headers.insert_event(expected.clone()).unwrap().unwrap();
+
+        let request = echo(&headers, body, http_0_2::Request::builder()).unwrap();
+        let (parts, body) = request.into_parts();
+        assert_eq!(parts.headers.to_event_bytes(body.into()).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_invalid_builder() {
+        let event: Event = fixtures::v10::minimal();
+
+        #[cfg(any(
+            feature = "http-binding",
+            feature = "reqwest",
+            feature = "axum",
+            feature = "poem"
+        ))]
+        assert!(http::Request::builder()
+            .uri("not a uri")
+            .event(event.clone())
+            .is_err());
+        #[cfg(any(feature = "http-0-2-binding", feature = "actix", feature = "warp"))]
+        assert!(http_0_2::Request::builder()
+            .uri("not a uri")
+            .event(event)
+            .is_err());
+    }
+}
@@ -0,0 +1,277 @@
+//! Instrumentation of the bindings with [tracing](https://docs.rs/tracing), enabled by the
+//! `tracing` feature, and the [`crate::observer::EventObserver`], enabled by the `observer`
+//! feature.
+//!
+//! The serialize, deserialize, send and receive operations run inside `DEBUG` spans named
+//! `cloudevents.<operation>`, with the `binding`, `event.id`, `event.type` and `event.source`
+//! fields. Failures are logged as `WARN` events, except the failures to deserialize or receive
+//! untrusted input, logged as `DEBUG` events so a peer can't flood the logs. Without the
+//! features, the operations are run as is.
+#![cfg_attr(
+    not(any(feature = "tracing", feature = "observer")),
+    allow(unused_variables)
+)]
+
+use crate::Event;
+use std::fmt::Display;
+
+#[cfg(feature = "tracing")]
+use crate::event::AttributesReader;
+#[cfg(feature = "observer")]
+use crate::observer::{measure::Measure, Operation};
+#[cfg(feature = "tracing")]
+use tracing_lib as tracing;
+
+#[cfg(feature = "tracing")]
+macro_rules! event_span {
+    ($name:literal, $binding:expr) => {
+        tracing::debug_span!(
+            $name,
+            binding = $binding,
+            "event.id" = tracing::field::Empty,
+            "event.type" = tracing::field::Empty,
+            "event.source" = tracing::field::Empty,
+        )
+    };
+}
+
+#[cfg(feature = "tracing")]
+fn record(span: &tracing::Span, event: &Event) {
+    span.record("event.id", event.id());
+    span.record("event.type", event.ty());
+    span.record("event.source", tracing::field::display(event.source()));
+}
+
+#[cfg(feature = "tracing")]
+fn log_result<T, E: Display>(result: &Result<T, E>, operation: &str, untrusted: bool) {
+    match result {
+        Ok(_) => tracing::trace!("{} succeeded", operation),
+        Err(e) if untrusted => tracing::debug!(error = %e, "{} failed", operation),
+        Err(e) => tracing::warn!(error = %e, "{} failed", operation),
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn record_result<E: Display>(span: &tracing::Span, result: &Result<Event, E>) {
+    if let Ok(event) = result {
+        record(span, event);
+    }
+}
+
+/// Serialize `event` with `f`.
+#[cfg(any(
+    feature = "http-common",
+    feature = "rdkafka",
+    feature = "nats",
+    feature = "coap"
+))]
+pub(crate) fn serialize<T, E: Display>(
+    binding: &'static str,
+    event: Event,
+    f: impl FnOnce(Event) -> Result<T, E>,
+) -> Result<T, E> {
+    #[cfg(feature = "tracing")]
+    let span = event_span!("cloudevents.serialize", binding);
+    #[cfg(feature = "tracing")]
+    record(&span, &event);
+    #[cfg(feature = "tracing")]
+    let _enter = span.enter();
+    #[cfg(feature = "observer")]
+    let measure = Measure::start(binding, Operation::Serialize, Some(&event));
+
+    let result = f(event);
+
+    #[cfg(feature = "tracing")]
+    log_result(&result, "serialize", false);
+    #[cfg(feature = "observer")]
+    measure.finish(&result, None);
+    result
+}
+
+/// Deserialize an event with `f`.
+#[cfg(any(
+    feature = "http-common",
+    feature = "rdkafka",
+    feature = "rumqttc",
+    feature = "nats",
+    feature = "coap"
+))]
+pub(crate) fn deserialize<E: Display>(
+    binding: &'static str,
+    f: impl FnOnce() -> Result<Event, E>,
+) -> Result<Event, E> {
+    #[cfg(feature = "tracing")]
+    let span = event_span!("cloudevents.deserialize", binding);
+    #[cfg(feature = "tracing")]
+    let _enter = span.enter();
+    #[cfg(feature = "observer")]
+    let measure = Measure::start(binding, Operation::Deserialize, None);
+
+    let result = f();
+
+    #[cfg(feature = "tracing")]
+    record_result(&span, &result);
+    #[cfg(feature = "tracing")]
+    log_result(&result, "deserialize", true);
+    #[cfg(feature = "observer")]
+    measure.finish(&result, result.as_ref().ok());
+    result
+}
+
+/// Decode an event received by a source with `f`.
+#[cfg(any(
+    feature = "rdkafka",
+    feature = "nats",
+    all(feature = "rumqttc", feature = "blocking")
+))]
+pub(crate) fn receive<E: Display>(
+    binding: &'static str,
+    f: impl FnOnce() -> Result<Event, E>,
+) -> Result<Event, E> {
+    #[cfg(feature = "tracing")]
+    let span = event_span!("cloudevents.receive", binding);
+    #[cfg(feature = "tracing")]
+    let _enter = span.enter();
+    #[cfg(feature = "observer")]
+    let measure = Measure::start(binding, Operation::Receive, None);
+
+    let result = f();
+
+    #[cfg(feature = "tracing")]
+    record_result(&span, &result);
+    #[cfg(feature = "tracing")]
+    log_result(&result, "receive", true);
+    #[cfg(feature = "observer")]
+    measure.finish(&result, result.as_ref().ok());
+    result
+}
+
+/// Send `event` with `f`.
+#[cfg(any(
+    feature = "reqwest",
+    feature = "rdkafka",
+    feature = "rumqttc",
+    feature = "nats"
+))]
+pub(crate) async fn send<T, E, F, Fut>(binding: &'static str, event: Event, f: F) -> Result<T, E>
+where
+    E: Display,
+    F: FnOnce(Event) -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+{
+    #[cfg(feature = "tracing")]
+    use tracing::Instrument;
+
+    #[cfg(feature = "tracing")]
+    let span = event_span!("cloudevents.send", binding);
+    #[cfg(feature = "tracing")]
+    record(&span, &event);
+    #[cfg(feature = "observer")]
+    let measure = Measure::start(binding, Operation::Send, Some(&event));
+
+    let future = f(event);
+    #[cfg(feature = "tracing")]
+    let future = future.instrument(span);
+    let result = future.await;
+
+    #[cfg(feature = "tracing")]
+    log_result(&result, "send", false);
+    #[cfg(feature = "observer")]
+    measure.finish(&result, None);
+    result
+}
+
+#[cfg(all(test, feature = "tracing", any(feature = "rdkafka", feature = "nats")))]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Metadata, Subscriber};
+
+    /// Subscriber collecting the spans as `name binding event.id` and the events as their level.
+    #[derive(Clone, Default)]
+    struct Collector(Arc<Mutex<Vec<String>>>);
+
+    struct Fields<'a>(&'a mut String);
+
+    impl Visit for Fields<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            if field.name() == "binding" || field.name() == "event.id" {
+                self.0.push_str(&format!(" {:?}", value));
+            }
+        }
+
+        fn record_str(&mut self, field: &Field, value: &str) {
+            if field.name() == "binding" || field.name() == "event.id" {
+                self.0.push(' ');
+                self.0.push_str(value);
+            }
+        }
+    }
+
+    impl Subscriber for Collector {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut entries = self.0.lock().unwrap();
+            let mut entry = span.metadata().name().to_string();
+            span.record(&mut Fields(&mut entry));
+            entries.push(entry);
+            Id::from_u64(entries.len() as u64)
+        }
+
+        fn record(&self, span: &Id, values: &Record<'_>) {
+            let mut entries = self.0.lock().unwrap();
+            let entry = &mut entries[span.into_u64() as usize - 1];
+            values.record(&mut Fields(entry));
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &tracing::Event<'_>) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(event.metadata().level().to_string());
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[test]
+    fn spans() {
+        let collector = Collector::default();
+        tracing::subscriber::with_default(collector.clone(), || {
+            serialize("test", fixtures::v10::minimal(), |e| {
+                Ok::<_, crate::message::Error>(e)
+            })
+            .unwrap();
+            deserialize("test", || {
+                Ok::<_, crate::message::Error>(fixtures::v10::minimal())
+            })
+            .unwrap();
+            receive("test", || {
+                Err::<Event, _>(crate::message::Error::WrongEncoding {})
+            })
+            .unwrap_err();
+        });
+
+        assert_eq!(
+            *collector.0.lock().unwrap(),
+            vec![
+                "cloudevents.serialize test 0001",
+                "TRACE",
+                "cloudevents.deserialize test 0001",
+                "TRACE",
+                "cloudevents.receive test",
+                "DEBUG",
+            ]
+        );
+    }
+}
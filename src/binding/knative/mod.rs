@@ -0,0 +1,305 @@
+//! This module provides [`SinkBindingClient`], a client for services bound to a sink by a
+//! [Knative SinkBinding](https://knative.dev/docs/eventing/custom-event-source/sinkbinding/)
+//! (or any other source injecting the sink in the environment).
+//!
+//! The client reads the sink URL from the `K_SINK` environment variable and the
+//! extensions to add to every event from the `K_CE_OVERRIDES` environment variable,
+//! then posts the events in binary mode to the sink, retrying on transient failures.
+//!
+//! ```
+//! use cloudevents::binding::knative::SinkBindingClient;
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = SinkBindingClient::from_env()?;
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("example.test")
+//!     .source("http://localhost/")
+//!     .build()?;
+//!
+//! client.send(event).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+#![deny(rustdoc::broken_intra_doc_links)]
+
+use reqwest_lib as reqwest;
+
+use super::reqwest::RequestBuilderExt;
+use crate::{message, Event};
+use async_trait::async_trait;
+use serde::Deserialize;
+use snafu::{ResultExt, Snafu};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Environment variable containing the sink URL.
+pub static K_SINK: &str = "K_SINK";
+/// Environment variable containing the CloudEvents overrides.
+pub static K_CE_OVERRIDES: &str = "K_CE_OVERRIDES";
+
+/// Default number of retries of a failed delivery.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+/// Default delay before the first retry, doubled at every retry.
+pub const DEFAULT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Represents an error of the [`SinkBindingClient`]
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Missing environment variable {}", name))]
+    MissingEnv { name: &'static str },
+    #[snafu(display("Invalid sink URL: {}", source))]
+    InvalidSink { source: url::ParseError },
+    #[snafu(display("Invalid CloudEvents overrides: {}", source))]
+    InvalidOverrides { source: serde_json::Error },
+    #[snafu(display("Error while serializing the event: {}", source))]
+    #[snafu(context(false))]
+    MessageError { source: message::Error },
+    #[snafu(display("Error while sending the event: {}", source))]
+    HttpError { source: reqwest::Error },
+}
+
+/// Result type alias for return values of the [`SinkBindingClient`]
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// CloudEvents overrides, as defined by the `K_CE_OVERRIDES` environment variable.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct CeOverrides {
+    /// Extensions to set on every event.
+    #[serde(default)]
+    pub extensions: HashMap<String, String>,
+}
+
+/// Client posting events to the sink of a Knative SinkBinding.
+#[derive(Debug, Clone)]
+pub struct SinkBindingClient {
+    client: reqwest::Client,
+    sink: reqwest::Url,
+    overrides: CeOverrides,
+    max_retries: u32,
+    backoff: Duration,
+}
+
+impl SinkBindingClient {
+    /// Create a new [`SinkBindingClient`] from the `K_SINK` and `K_CE_OVERRIDES` environment variables.
+    pub fn from_env() -> Result<Self> {
+        Self::from_vars(
+            std::env::var(K_SINK).ok(),
+            std::env::var(K_CE_OVERRIDES).ok(),
+        )
+    }
+
+    fn from_vars(sink: Option<String>, overrides: Option<String>) -> Result<Self> {
+        let sink = sink
+            .ok_or(Error::MissingEnv { name: "K_SINK" })
+            .and_then(|s| reqwest::Url::parse(&s).context(InvalidSinkSnafu))?;
+        let overrides = match overrides.filter(|o| !o.trim().is_empty()) {
+            Some(o) => serde_json::from_str(&o).context(InvalidOverridesSnafu)?,
+            None => CeOverrides::default(),
+        };
+        Ok(Self::new(reqwest::Client::new(), sink, overrides))
+    }
+
+    /// Create a new [`SinkBindingClient`] posting to `sink` and applying `overrides`.
+    pub fn new(client: reqwest::Client, sink: reqwest::Url, overrides: CeOverrides) -> Self {
+        SinkBindingClient {
+            client,
+            sink,
+            overrides,
+            max_retries: DEFAULT_MAX_RETRIES,
+            backoff: DEFAULT_BACKOFF,
+        }
+    }
+
+    /// Retry a failed delivery up to `max_retries` times, waiting `backoff` before the
+    /// first retry and doubling it at every retry.
+    pub fn with_retries(mut self, max_retries: u32, backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.backoff = backoff;
+        self
+    }
+
+    /// The sink URL.
+    pub fn sink(&self) -> &reqwest::Url {
+        &self.sink
+    }
+
+    /// The CloudEvents overrides applied to every event.
+    pub fn overrides(&self) -> &CeOverrides {
+        &self.overrides
+    }
+
+    /// Set the override extensions on `event`.
+    pub fn apply_overrides(&self, event: &mut Event) {
+        for (name, value) in &self.overrides.extensions {
+            event.set_extension(name, value.as_str());
+        }
+    }
+
+    /// Apply the overrides to `event` and post it to the sink.
+    ///
+    /// Connection errors, timeouts, `429 Too Many Requests` and `5xx` responses are retried,
+    /// any other error is returned immediately.
+    pub async fn send(&self, mut event: Event) -> Result<()> {
+        self.apply_overrides(&mut event);
+
+        let mut backoff = self.backoff;
+        let mut attempt = 0;
+        loop {
+            let result = self
+                .client
+                .post(self.sink.clone())
+                .event(event.clone())?
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status);
+            match result {
+                Ok(_) => return Ok(()),
+                Err(e) if attempt < self.max_retries && is_retryable(&e) => {
+                    attempt += 1;
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                Err(e) => return Err(Error::HttpError { source: e }),
+            }
+        }
+    }
+}
+
+fn is_retryable(error: &reqwest::Error) -> bool {
+    match error.status() {
+        Some(status) => {
+            status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+        }
+        None => error.is_connect() || error.is_timeout(),
+    }
+}
+
+#[async_trait]
+impl crate::transport::EventSink for SinkBindingClient {
+    async fn send(&self, event: Event) -> crate::transport::Result<()> {
+        SinkBindingClient::send(self, event)
+            .await
+            .map_err(|e| match e {
+                Error::MessageError { source } => source.into(),
+                e => crate::transport::Error::transport(e),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+
+    #[test]
+    fn test_from_vars() {
+        let client = SinkBindingClient::from_vars(
+            Some(
+                "http://broker-ingress.knative-eventing.svc.cluster.local/default/default"
+                    .to_string(),
+            ),
+            Some(r#"{"extensions":{"extra":"test","sample":"value"}}"#.to_string()),
+        )
+        .unwrap();
+
+        assert_eq!(
+            client.sink().as_str(),
+            "http://broker-ingress.knative-eventing.svc.cluster.local/default/default"
+        );
+
+        let mut event = fixtures::v10::minimal();
+        client.apply_overrides(&mut event);
+
+        assert_eq!(event.extension("extra").unwrap().to_string(), "test");
+        assert_eq!(event.extension("sample").unwrap().to_string(), "value");
+    }
+
+    #[test]
+    fn test_from_vars_errors() {
+        assert!(matches!(
+            SinkBindingClient::from_vars(None, None),
+            Err(Error::MissingEnv { name: "K_SINK" })
+        ));
+        assert!(matches!(
+            SinkBindingClient::from_vars(Some("not a url".to_string()), None),
+            Err(Error::InvalidSink { .. })
+        ));
+        assert!(matches!(
+            SinkBindingClient::from_vars(
+                Some("http://localhost".to_string()),
+                Some("{".to_string())
+            ),
+            Err(Error::InvalidOverrides { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_send_with_overrides() {
+        let url = reqwest::Url::parse(&mockito::server_url())
+            .unwrap()
+            .join("/knative")
+            .unwrap();
+        let m = mockito::mock("POST", "/knative")
+            .match_header("ce-id", "0001")
+            .match_header("ce-extra", "test")
+            .create();
+
+        let mut overrides = CeOverrides::default();
+        overrides
+            .extensions
+            .insert("extra".to_string(), "test".to_string());
+        let client = SinkBindingClient::new(reqwest::Client::new(), url, overrides);
+
+        client
+            .send(fixtures::v10::minimal_string_extension())
+            .await
+            .unwrap();
+
+        m.assert();
+    }
+
+    #[tokio::test]
+    async fn test_send_retries() {
+        let url = reqwest::Url::parse(&mockito::server_url())
+            .unwrap()
+            .join("/knative_unavailable")
+            .unwrap();
+        let m = mockito::mock("POST", "/knative_unavailable")
+            .with_status(503)
+            .expect(3)
+            .create();
+
+        let client = SinkBindingClient::new(reqwest::Client::new(), url, CeOverrides::default())
+            .with_retries(2, Duration::from_millis(1));
+
+        assert!(matches!(
+            client.send(fixtures::v10::minimal()).await,
+            Err(Error::HttpError { .. })
+        ));
+
+        m.assert();
+    }
+
+    #[tokio::test]
+    async fn test_send_does_not_retry_client_errors() {
+        let url = reqwest::Url::parse(&mockito::server_url())
+            .unwrap()
+            .join("/knative_bad_request")
+            .unwrap();
+        let m = mockito::mock("POST", "/knative_bad_request")
+            .with_status(400)
+            .expect(1)
+            .create();
+
+        let client = SinkBindingClient::new(reqwest::Client::new(), url, CeOverrides::default())
+            .with_retries(2, Duration::from_millis(1));
+
+        assert!(client.send(fixtures::v10::minimal()).await.is_err());
+
+        m.assert();
+    }
+}
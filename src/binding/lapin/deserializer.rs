@@ -0,0 +1,189 @@
+use lapin_lib as lapin;
+
+use crate::binding::amqp::{self, impl_deserializer, HeaderValue};
+use crate::message::{MessageAttributeValue, MessageDeserializer, Result};
+use crate::Event;
+use lapin::message::Delivery;
+use lapin::types::AMQPValue;
+use lapin::BasicProperties;
+
+/// Wrapper for [`Delivery`] that implements [`MessageDeserializer`] trait.
+pub struct DeliveryDeserializer(amqp::Message<AMQPValue>);
+
+impl DeliveryDeserializer {
+    /// Create a new [`DeliveryDeserializer`] from the message properties and body.
+    pub fn new(properties: &BasicProperties, payload: Vec<u8>) -> DeliveryDeserializer {
+        DeliveryDeserializer(amqp::Message {
+            content_type: properties.content_type().as_ref().map(|ct| ct.to_string()),
+            headers: properties
+                .headers()
+                .as_ref()
+                .map(|h| {
+                    h.inner()
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), v.clone()))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            payload,
+        })
+    }
+}
+
+impl_deserializer!(DeliveryDeserializer);
+
+impl HeaderValue for AMQPValue {
+    fn into_attribute_value(self) -> Result<MessageAttributeValue> {
+        Ok(match self {
+            AMQPValue::Boolean(b) => MessageAttributeValue::Boolean(b),
+            AMQPValue::ShortShortInt(i) => MessageAttributeValue::Integer(i.into()),
+            AMQPValue::ShortShortUInt(i) => MessageAttributeValue::Integer(i.into()),
+            AMQPValue::ShortInt(i) => MessageAttributeValue::Integer(i.into()),
+            AMQPValue::ShortUInt(i) => MessageAttributeValue::Integer(i.into()),
+            AMQPValue::LongInt(i) => MessageAttributeValue::Integer(i.into()),
+            AMQPValue::LongUInt(i) => MessageAttributeValue::Integer(i.into()),
+            AMQPValue::LongLongInt(i) => MessageAttributeValue::Integer(i),
+            AMQPValue::ShortString(s) => MessageAttributeValue::String(s.to_string()),
+            AMQPValue::LongString(s) => {
+                MessageAttributeValue::String(String::from_utf8(s.as_bytes().to_vec()).map_err(
+                    |e| crate::message::Error::Other {
+                        source: Box::new(e),
+                    },
+                )?)
+            }
+            AMQPValue::ByteArray(b) => MessageAttributeValue::Binary(b.as_slice().to_vec()),
+            AMQPValue::Timestamp(t) => amqp::timestamp(t)?,
+            v => return Err(amqp::unsupported(v)),
+        })
+    }
+}
+
+/// Method to transform a [`Delivery`] to [`Event`].
+pub fn delivery_to_event(delivery: &Delivery) -> Result<Event> {
+    MessageDeserializer::into_event(DeliveryDeserializer::new(
+        &delivery.properties,
+        delivery.data.clone(),
+    ))
+}
+
+/// Extension Trait for [`Delivery`] which acts as a wrapper for the function [`delivery_to_event()`].
+///
+/// This trait is sealed and cannot be implemented for types outside of this crate.
+pub trait DeliveryExt: private::Sealed {
+    /// Generates [`Event`] from [`Delivery`].
+    fn to_event(&self) -> Result<Event>;
+}
+
+impl DeliveryExt for Delivery {
+    fn to_event(&self) -> Result<Event> {
+        delivery_to_event(self)
+    }
+}
+
+mod private {
+    use lapin_lib as lapin;
+
+    // Sealing the DeliveryExt
+    pub trait Sealed {}
+    impl Sealed for lapin::message::Delivery {}
+}
+
+#[cfg(test)]
+mod tests {
+    use lapin_lib as lapin;
+
+    use super::*;
+    use crate::binding::lapin::MessageRecord;
+    use crate::message::{Error, StructuredDeserializer};
+    use crate::test::fixtures;
+    use crate::{EventBuilder, EventBuilderV10};
+    use lapin::acker::Acker;
+    use lapin::types::ShortString;
+
+    fn delivery(message_record: MessageRecord) -> Delivery {
+        Delivery {
+            delivery_tag: 1,
+            exchange: ShortString::from("test exchange"),
+            routing_key: ShortString::from("test key"),
+            redelivered: false,
+            properties: message_record.properties(),
+            data: message_record.payload,
+            acker: Acker::default(),
+        }
+    }
+
+    #[test]
+    fn test_binary_delivery() {
+        let expected = fixtures::v10::minimal_string_extension();
+
+        let message_record = MessageRecord::from_event(
+            EventBuilderV10::new()
+                .id("0001")
+                .ty("test_event.test_application")
+                .source("http://localhost/")
+                .extension("someint", "10")
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(delivery(message_record).to_event().unwrap(), expected)
+    }
+
+    #[test]
+    fn test_binary_delivery_typed_extensions() {
+        let expected = fixtures::v10::full_binary_json_data_string_extension();
+        let input = EventBuilderV10::from(expected.clone())
+            .extension("intex", 10)
+            .extension("boolex", true)
+            .build()
+            .unwrap();
+
+        let message_record = MessageRecord::from_event(input.clone()).unwrap();
+        let headers = message_record.properties().headers().clone().unwrap();
+
+        assert_eq!(
+            headers.inner().get("cloudEvents:intex"),
+            Some(&AMQPValue::LongLongInt(10))
+        );
+        assert_eq!(
+            headers.inner().get("cloudEvents:boolex"),
+            Some(&AMQPValue::Boolean(true))
+        );
+        assert_eq!(
+            message_record
+                .properties()
+                .content_type()
+                .as_ref()
+                .map(|ct| ct.as_str()),
+            Some("application/json")
+        );
+
+        let actual = delivery(message_record).to_event().unwrap();
+
+        assert_eq!(actual.extension("intex"), input.extension("intex"));
+        assert_eq!(actual.extension("boolex"), input.extension("boolex"));
+        assert_eq!(actual.data(), expected.data());
+    }
+
+    #[test]
+    fn test_structured_delivery() {
+        let expected = fixtures::v10::full_json_data_string_extension();
+
+        let message_record =
+            StructuredDeserializer::deserialize_structured(expected.clone(), MessageRecord::new())
+                .unwrap();
+
+        assert_eq!(delivery(message_record).to_event().unwrap(), expected)
+    }
+
+    #[test]
+    fn test_unknown_encoding() {
+        let message_record = MessageRecord::new();
+
+        assert!(matches!(
+            delivery(message_record).to_event(),
+            Err(Error::WrongEncoding {})
+        ))
+    }
+}
@@ -0,0 +1,66 @@
+//! This library provides AMQP 0.9.1 protocol bindings for CloudEvents
+//! using the [lapin](https://docs.rs/lapin) library, e.g. to talk to RabbitMQ.
+//!
+//! In binary mode, attributes and extensions are written as `cloudEvents:`-prefixed message
+//! headers, following the [AMQP Protocol Binding](https://github.com/cloudevents/spec/blob/v1.0.2/cloudevents/bindings/amqp-protocol-binding.md),
+//! while `datacontenttype` is mapped to the `content-type` message property.
+//!
+//! To publish Cloudevents:
+//!
+//! ```
+//! # use lapin_lib as lapin;
+//! use cloudevents::Event;
+//! use cloudevents::binding::lapin::{BasicPublishExt, MessageRecord};
+//! use lapin::Channel;
+//! use lapin::options::BasicPublishOptions;
+//!
+//! # async fn publish(channel: &Channel, event: Event) -> Result<(), Box<dyn std::error::Error>> {
+//! let message_record = MessageRecord::from_event(event)?;
+//!
+//! channel
+//!     .basic_publish_record(
+//!         "exchange",
+//!         "routing_key",
+//!         BasicPublishOptions::default(),
+//!         &message_record,
+//!     )
+//!     .await?
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! To consume Cloudevents:
+//!
+//! ```
+//! # use lapin_lib as lapin;
+//! use cloudevents::binding::lapin::DeliveryExt;
+//! use futures::StreamExt;
+//! use lapin::Consumer;
+//! use lapin::options::BasicAckOptions;
+//!
+//! # async fn consume(mut consumer: Consumer) -> Result<(), Box<dyn std::error::Error>> {
+//! while let Some(delivery) = consumer.next().await {
+//!     let delivery = delivery?;
+//!     let event = delivery.to_event()?;
+//!     println!("Received Event: {}", event);
+//!     delivery.ack(BasicAckOptions::default()).await?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+#![deny(rustdoc::broken_intra_doc_links)]
+
+mod deserializer;
+mod serializer;
+mod transport;
+
+pub use deserializer::delivery_to_event;
+pub use deserializer::DeliveryDeserializer;
+pub use deserializer::DeliveryExt;
+
+pub use serializer::BasicPublishExt;
+pub use serializer::MessageRecord;
+
+pub use transport::{LapinSink, LapinSource};
@@ -0,0 +1,160 @@
+use lapin_lib as lapin;
+
+use crate::binding::{
+    amqp::{header_prefix, SPEC_VERSION_HEADER},
+    CLOUDEVENTS_JSON_HEADER,
+};
+use crate::event::SpecVersion;
+use crate::message::{
+    BinaryDeserializer, BinarySerializer, MessageAttributeValue, Result, StructuredSerializer,
+};
+use crate::Event;
+use async_trait::async_trait;
+use lapin::options::BasicPublishOptions;
+use lapin::publisher_confirm::PublisherConfirm;
+use lapin::types::{AMQPValue, FieldTable, LongString, ShortString};
+use lapin::{BasicProperties, Channel};
+
+/// This struct contains a serialized CloudEvent message in the AMQP 0.9.1 shape.
+/// Implements [`StructuredSerializer`] & [`BinarySerializer`] traits.
+///
+/// To instantiate a new `MessageRecord` from an [`Event`],
+/// look at [`Self::from_event`] or use [`StructuredDeserializer::deserialize_structured`](crate::message::StructuredDeserializer::deserialize_structured)
+/// or [`BinaryDeserializer::deserialize_binary`].
+pub struct MessageRecord {
+    pub(crate) properties: BasicProperties,
+    pub(crate) headers: FieldTable,
+    pub(crate) payload: Vec<u8>,
+}
+
+impl MessageRecord {
+    /// Create a new empty [`MessageRecord`]
+    pub fn new() -> Self {
+        MessageRecord {
+            properties: BasicProperties::default(),
+            headers: FieldTable::default(),
+            payload: Vec::new(),
+        }
+    }
+
+    /// Create a new [`MessageRecord`], filled with `event` serialized in binary mode.
+    pub fn from_event(event: Event) -> Result<Self> {
+        BinaryDeserializer::deserialize_binary(event, MessageRecord::new())
+    }
+
+    /// Get the [`BasicProperties`] to publish this record with, including the `cloudEvents:*` headers.
+    pub fn properties(&self) -> BasicProperties {
+        if self.headers.inner().is_empty() {
+            self.properties.clone()
+        } else {
+            self.properties.clone().with_headers(self.headers.clone())
+        }
+    }
+
+    /// Get the message body.
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+}
+
+impl Default for MessageRecord {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BinarySerializer<MessageRecord> for MessageRecord {
+    fn set_spec_version(mut self, sv: SpecVersion) -> Result<Self> {
+        self.headers.insert(
+            ShortString::from(SPEC_VERSION_HEADER),
+            AMQPValue::LongString(LongString::from(sv.as_str())),
+        );
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        if name == "datacontenttype" {
+            self.properties = self
+                .properties
+                .with_content_type(ShortString::from(value.to_string()));
+        } else {
+            self.headers
+                .insert(ShortString::from(header_prefix(name)), to_amqp_value(value));
+        }
+        Ok(self)
+    }
+
+    fn set_extension(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.set_attribute(name, value)
+    }
+
+    fn end_with_data(mut self, bytes: Vec<u8>) -> Result<MessageRecord> {
+        self.payload = bytes;
+        Ok(self)
+    }
+
+    fn end(self) -> Result<MessageRecord> {
+        Ok(self)
+    }
+}
+
+impl StructuredSerializer<MessageRecord> for MessageRecord {
+    fn set_structured_event(mut self, bytes: Vec<u8>) -> Result<MessageRecord> {
+        self.properties = self
+            .properties
+            .with_content_type(ShortString::from(CLOUDEVENTS_JSON_HEADER));
+        self.payload = bytes;
+        Ok(self)
+    }
+}
+
+fn to_amqp_value(value: MessageAttributeValue) -> AMQPValue {
+    match value {
+        MessageAttributeValue::Boolean(b) => AMQPValue::Boolean(b),
+        MessageAttributeValue::Integer(i) => AMQPValue::LongLongInt(i),
+        v => AMQPValue::LongString(LongString::from(v.to_string())),
+    }
+}
+
+/// Extension Trait for [`Channel`] to publish a [`MessageRecord`].
+///
+/// This trait is sealed and cannot be implemented for types outside of this crate.
+#[async_trait]
+pub trait BasicPublishExt: private::Sealed {
+    /// Publish `message_record` to `exchange` with `routing_key`.
+    async fn basic_publish_record(
+        &self,
+        exchange: &str,
+        routing_key: &str,
+        options: BasicPublishOptions,
+        message_record: &MessageRecord,
+    ) -> lapin::Result<PublisherConfirm>;
+}
+
+#[async_trait]
+impl BasicPublishExt for Channel {
+    async fn basic_publish_record(
+        &self,
+        exchange: &str,
+        routing_key: &str,
+        options: BasicPublishOptions,
+        message_record: &MessageRecord,
+    ) -> lapin::Result<PublisherConfirm> {
+        self.basic_publish(
+            exchange,
+            routing_key,
+            options,
+            message_record.payload(),
+            message_record.properties(),
+        )
+        .await
+    }
+}
+
+mod private {
+    use lapin_lib as lapin;
+
+    // Sealing the BasicPublishExt
+    pub trait Sealed {}
+    impl Sealed for lapin::Channel {}
+}
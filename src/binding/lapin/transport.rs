@@ -0,0 +1,95 @@
+use lapin_lib as lapin;
+
+use super::{BasicPublishExt, DeliveryExt, MessageRecord};
+use crate::transport::{Error, EventSink, EventSource, Result};
+use crate::Event;
+use async_trait::async_trait;
+use futures::StreamExt;
+use lapin::acker::Acker;
+use lapin::options::{BasicAckOptions, BasicPublishOptions, BasicRejectOptions};
+use lapin::{Channel, Consumer};
+
+/// [`EventSink`] publishing the events in binary mode to an exchange.
+pub struct LapinSink {
+    channel: Channel,
+    exchange: String,
+    routing_key: String,
+}
+
+impl LapinSink {
+    /// Create a new [`LapinSink`], publishing the events to `exchange` with `routing_key`.
+    pub fn new(
+        channel: Channel,
+        exchange: impl Into<String>,
+        routing_key: impl Into<String>,
+    ) -> Self {
+        LapinSink {
+            channel,
+            exchange: exchange.into(),
+            routing_key: routing_key.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for LapinSink {
+    async fn send(&self, event: Event) -> Result<()> {
+        let message_record = MessageRecord::from_event(event)?;
+        self.channel
+            .basic_publish_record(
+                &self.exchange,
+                &self.routing_key,
+                BasicPublishOptions::default(),
+                &message_record,
+            )
+            .await
+            .map_err(Error::transport)?
+            .await
+            .map_err(Error::transport)?;
+        Ok(())
+    }
+}
+
+/// [`EventSource`] receiving the events of a [`Consumer`], acknowledging them with [`Acker`].
+///
+/// The deliveries which can't be decoded are rejected without requeuing them, so the broker
+/// routes them to the dead-letter exchange of the queue, if any, instead of redelivering them.
+pub struct LapinSource {
+    consumer: Consumer,
+}
+
+impl LapinSource {
+    /// Create a new [`LapinSource`] from a `consumer`.
+    pub fn new(consumer: Consumer) -> Self {
+        LapinSource { consumer }
+    }
+}
+
+#[async_trait]
+impl EventSource for LapinSource {
+    type Ack = Acker;
+
+    async fn receive(&mut self) -> Option<Result<(Event, Acker)>> {
+        let delivery = match self.consumer.next().await? {
+            Ok(delivery) => delivery,
+            Err(e) => return Some(Err(Error::transport(e))),
+        };
+        match delivery.to_event() {
+            Ok(event) => Some(Ok((event, delivery.acker))),
+            Err(e) => Some(
+                delivery
+                    .acker
+                    .reject(BasicRejectOptions { requeue: false })
+                    .await
+                    .map_err(Error::transport)
+                    .and(Err(Error::from(e))),
+            ),
+        }
+    }
+
+    async fn ack(&mut self, ack: Acker) -> Result<()> {
+        ack.ack(BasicAckOptions::default())
+            .await
+            .map_err(Error::transport)
+    }
+}
@@ -0,0 +1,167 @@
+use crate::message::{Error, Result};
+use crate::Event;
+
+/// Limits on the attributes of the deserialized messages, to reject the abusive messages
+/// before allocating their attributes.
+///
+/// The HTTP and Kafka deserializers check the headers of the binary mode messages before
+/// reading them, while the events of the structured mode messages, e.g. MQTT messages, are
+/// checked once parsed with [`Limits::check`], after checking their length with
+/// [`Limits::check_structured_len`]. By default, nothing is limited.
+///
+/// ```
+/// use cloudevents::binding::Limits;
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+///
+/// let limits = Limits::new()
+///     .max_extensions(16)
+///     .max_value_len(1024)
+///     .max_attributes_len(8 * 1024)
+///     .max_structured_len(64 * 1024);
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .ty("example.test")
+///     .source("http://localhost/")
+///     .extension("note", "a".repeat(2048))
+///     .build()
+///     .unwrap();
+///
+/// assert!(limits.check(&event).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Limits {
+    max_extensions: Option<usize>,
+    max_value_len: Option<usize>,
+    max_attributes_len: Option<usize>,
+    max_structured_len: Option<usize>,
+}
+
+impl Limits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject the messages with more than `max` extensions.
+    pub fn max_extensions(mut self, max: usize) -> Self {
+        self.max_extensions = Some(max);
+        self
+    }
+
+    /// Reject the messages with an attribute value longer than `max` bytes.
+    pub fn max_value_len(mut self, max: usize) -> Self {
+        self.max_value_len = Some(max);
+        self
+    }
+
+    /// Reject the messages whose attribute names and values are longer than `max` bytes in
+    /// total.
+    pub fn max_attributes_len(mut self, max: usize) -> Self {
+        self.max_attributes_len = Some(max);
+        self
+    }
+
+    /// Reject the structured mode messages longer than `max` bytes, before parsing them.
+    pub fn max_structured_len(mut self, max: usize) -> Self {
+        self.max_structured_len = Some(max);
+        self
+    }
+
+    /// Check the length of a structured mode message, before parsing it.
+    pub fn check_structured_len(&self, len: usize) -> Result<()> {
+        match self.max_structured_len.filter(|max| len > *max) {
+            Some(max) => Err(Error::StructuredMessageTooLong { max }),
+            None => Ok(()),
+        }
+    }
+
+    /// Check the attributes of `event`, e.g. once deserialized from a structured mode message.
+    pub fn check(&self, event: &Event) -> Result<()> {
+        self.check_attributes(
+            event
+                .iter_attributes()
+                .map(|(name, value)| (name, value.to_string().len(), false))
+                .chain(
+                    event
+                        .iter_extensions()
+                        .map(|(name, value)| (name, value.to_string().len(), true)),
+                ),
+        )
+    }
+
+    /// Check the attributes given as their name, the length of their value, and whether they
+    /// are an extension, stopping at the first limit exceeded.
+    pub(crate) fn check_attributes<'a>(
+        &self,
+        attributes: impl IntoIterator<Item = (&'a str, usize, bool)>,
+    ) -> Result<()> {
+        if *self == Limits::default() {
+            return Ok(());
+        }
+        let mut extensions = 0;
+        let mut total = 0;
+        for (name, len, is_extension) in attributes {
+            extensions += usize::from(is_extension);
+            if let Some(max) = self.max_extensions.filter(|max| extensions > *max) {
+                return Err(Error::TooManyExtensions { max });
+            }
+            if let Some(max) = self.max_value_len.filter(|max| len > *max) {
+                return Err(Error::AttributeValueTooLong {
+                    name: name.to_string(),
+                    max,
+                });
+            }
+            total += name.len() + len;
+            if let Some(max) = self.max_attributes_len.filter(|max| total > *max) {
+                return Err(Error::AttributesTooLong { max });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+
+    #[test]
+    fn check() {
+        let event = fixtures::v10::full_json_data();
+        assert!(Limits::new().check(&event).is_ok());
+
+        assert!(matches!(
+            Limits::new().max_extensions(2).check(&event),
+            Err(Error::TooManyExtensions { max: 2 })
+        ));
+        assert!(matches!(
+            Limits::new().max_value_len(4).check(&event),
+            Err(Error::AttributeValueTooLong { max: 4, .. })
+        ));
+        assert!(matches!(
+            Limits::new().max_attributes_len(64).check(&event),
+            Err(Error::AttributesTooLong { max: 64 })
+        ));
+        assert!(Limits::new()
+            .max_extensions(3)
+            .max_value_len(64)
+            .max_attributes_len(1024)
+            .check(&event)
+            .is_ok());
+    }
+
+    #[test]
+    fn check_structured_len() {
+        assert!(Limits::new().check_structured_len(usize::MAX).is_ok());
+        assert!(Limits::new()
+            .max_structured_len(16)
+            .check_structured_len(16)
+            .is_ok());
+        assert!(matches!(
+            Limits::new()
+                .max_structured_len(16)
+                .check_structured_len(17),
+            Err(Error::StructuredMessageTooLong { max: 16 })
+        ));
+    }
+}
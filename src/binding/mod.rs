@@ -1,11 +1,46 @@
 //! Provides protocol binding implementations for [`crate::Event`].
 
+/// Header name of the attribute `$name` with the `$prefix`, as a [`Cow`](std::borrow::Cow)
+/// borrowing a static string for the spec attributes, so serializing them doesn't allocate.
+#[cfg(any(feature = "http-common", feature = "rdkafka"))]
+macro_rules! header_name {
+    ($prefix:literal, $name:expr) => {
+        match $name {
+            "datacontenttype" => std::borrow::Cow::Borrowed($crate::binding::CONTENT_TYPE),
+            "specversion" => std::borrow::Cow::Borrowed(concat!($prefix, "specversion")),
+            "id" => std::borrow::Cow::Borrowed(concat!($prefix, "id")),
+            "type" => std::borrow::Cow::Borrowed(concat!($prefix, "type")),
+            "source" => std::borrow::Cow::Borrowed(concat!($prefix, "source")),
+            "subject" => std::borrow::Cow::Borrowed(concat!($prefix, "subject")),
+            "time" => std::borrow::Cow::Borrowed(concat!($prefix, "time")),
+            "dataschema" => std::borrow::Cow::Borrowed(concat!($prefix, "dataschema")),
+            "schemaurl" => std::borrow::Cow::Borrowed(concat!($prefix, "schemaurl")),
+            "datacontentencoding" => {
+                std::borrow::Cow::Borrowed(concat!($prefix, "datacontentencoding"))
+            }
+            name => std::borrow::Cow::Owned([$prefix, name].concat()),
+        }
+    };
+}
+
 #[cfg_attr(docsrs, doc(cfg(feature = "actix")))]
 #[cfg(feature = "actix")]
 pub mod actix;
+#[cfg_attr(docsrs, doc(cfg(feature = "amqprs")))]
+#[cfg(feature = "amqprs")]
+pub mod amqprs;
 #[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
 #[cfg(feature = "axum")]
 pub mod axum;
+#[cfg_attr(docsrs, doc(cfg(feature = "coap")))]
+#[cfg(feature = "coap")]
+pub mod coap;
+#[cfg_attr(docsrs, doc(cfg(feature = "eventbridge")))]
+#[cfg(feature = "eventbridge")]
+pub mod eventbridge;
+#[cfg_attr(docsrs, doc(cfg(feature = "eventgrid")))]
+#[cfg(feature = "eventgrid")]
+pub mod eventgrid;
 
 #[cfg_attr(
     docsrs,
@@ -31,18 +66,66 @@ pub mod http;
 #[cfg(any(feature = "http-0-2-binding", feature = "actix", feature = "warp",))]
 pub mod http_0_2;
 
+#[cfg_attr(docsrs, doc(cfg(feature = "http-common")))]
+#[cfg(feature = "http-common")]
+pub mod http_compat;
+
+#[cfg(feature = "http-common")]
+mod duplicates;
+#[cfg_attr(docsrs, doc(cfg(feature = "http-common")))]
+#[cfg(feature = "http-common")]
+pub use duplicates::DuplicateHeaders;
+
+mod extension_types;
+pub use extension_types::{ExtensionType, ExtensionTypes};
+#[cfg(any(
+    feature = "http-common",
+    feature = "rdkafka",
+    feature = "rumqttc",
+    feature = "nats",
+    feature = "coap"
+))]
+pub(crate) mod instrument;
+mod limits;
+pub use limits::Limits;
+#[cfg_attr(docsrs, doc(cfg(feature = "knative")))]
+#[cfg(feature = "knative")]
+pub mod knative;
+#[cfg_attr(docsrs, doc(cfg(feature = "lapin")))]
+#[cfg(feature = "lapin")]
+pub mod lapin;
 #[cfg_attr(docsrs, doc(cfg(feature = "nats")))]
 #[cfg(feature = "nats")]
 pub mod nats;
+#[cfg_attr(docsrs, doc(cfg(feature = "nsq")))]
+#[cfg(feature = "nsq")]
+pub mod nsq;
+#[cfg_attr(docsrs, doc(cfg(feature = "pipe")))]
+#[cfg(feature = "pipe")]
+pub mod pipe;
 #[cfg_attr(docsrs, doc(cfg(feature = "poem")))]
 #[cfg(feature = "poem")]
 pub mod poem;
+#[cfg_attr(docsrs, doc(cfg(feature = "pubsub")))]
+#[cfg(feature = "pubsub")]
+pub mod pubsub;
 #[cfg_attr(docsrs, doc(cfg(feature = "rdkafka")))]
 #[cfg(feature = "rdkafka")]
 pub mod rdkafka;
+#[cfg_attr(docsrs, doc(cfg(feature = "redis")))]
+#[cfg(feature = "redis")]
+pub mod redis;
 #[cfg_attr(docsrs, doc(cfg(feature = "reqwest")))]
 #[cfg(feature = "reqwest")]
 pub mod reqwest;
+#[cfg_attr(docsrs, doc(cfg(feature = "rumqttc")))]
+#[cfg(feature = "rumqttc")]
+pub mod rumqttc;
+#[cfg_attr(docsrs, doc(cfg(feature = "tonic")))]
+#[cfg(feature = "tonic")]
+pub mod tonic;
+#[cfg(any(feature = "nats", feature = "rumqttc"))]
+pub(crate) mod topic;
 #[cfg_attr(docsrs, doc(cfg(feature = "warp")))]
 #[cfg(feature = "warp")]
 pub mod warp;
@@ -50,15 +133,72 @@ pub mod warp;
 #[cfg(feature = "rdkafka")]
 pub(crate) mod kafka {
     pub static SPEC_VERSION_HEADER: &str = "ce_specversion";
-    pub fn header_prefix(name: &str) -> String {
-        super::header_prefix("ce_", name)
+    pub fn header_name(name: &str) -> std::borrow::Cow<'static, str> {
+        header_name!("ce_", name)
     }
 }
 
+#[cfg(any(feature = "lapin", feature = "amqprs"))]
+pub(crate) mod amqp;
+
+#[cfg(any(
+    feature = "http-common",
+    feature = "rdkafka",
+    feature = "pubsub",
+    feature = "nats",
+    feature = "lapin",
+    feature = "amqprs",
+))]
 pub(crate) static CLOUDEVENTS_JSON_HEADER: &str = "application/cloudevents+json";
+#[cfg(any(feature = "reqwest", feature = "nats"))]
 pub(crate) static CLOUDEVENTS_BATCH_JSON_HEADER: &str = "application/cloudevents-batch+json";
+#[cfg(any(
+    feature = "http-common",
+    feature = "rdkafka",
+    feature = "pubsub",
+    feature = "nats",
+))]
 pub(crate) static CONTENT_TYPE: &str = "content-type";
 
+/// Whether the `content_type` header value is the `media_type` (possibly followed by
+/// parameters), ignoring the ASCII case as media types are case-insensitive.
+#[cfg(any(
+    feature = "http-common",
+    feature = "rdkafka",
+    feature = "nats",
+    feature = "protobuf",
+))]
+pub(crate) fn is_media_type(content_type: impl AsRef<[u8]>, media_type: &str) -> bool {
+    content_type
+        .as_ref()
+        .get(..media_type.len())
+        .is_some_and(|v| v.eq_ignore_ascii_case(media_type.as_bytes()))
+}
+
+/// Name of the attribute carried by the header `name`, if it starts with `prefix` ignoring
+/// the ASCII case. The name is lowercased, since attribute names are lowercase while some
+/// producers capitalize their headers, e.g. `CE-Type`.
+#[cfg(any(
+    feature = "http-common",
+    feature = "rdkafka",
+    feature = "nats",
+    feature = "tonic",
+))]
+pub(crate) fn attribute_name<'a>(prefix: &str, name: &'a str) -> Option<std::borrow::Cow<'a, str>> {
+    if !name.is_char_boundary(prefix.len()) {
+        return None;
+    }
+    let (head, tail) = name.split_at(prefix.len());
+    if !head.eq_ignore_ascii_case(prefix) {
+        None
+    } else if tail.bytes().any(|b| b.is_ascii_uppercase()) {
+        Some(std::borrow::Cow::Owned(tail.to_ascii_lowercase()))
+    } else {
+        Some(std::borrow::Cow::Borrowed(tail))
+    }
+}
+
+#[cfg(any(feature = "pubsub", feature = "nats"))]
 fn header_prefix(prefix: &str, name: &str) -> String {
     if name == "datacontenttype" {
         CONTENT_TYPE.to_string()
@@ -67,6 +207,30 @@ fn header_prefix(prefix: &str, name: &str) -> String {
     }
 }
 
+/// Characters percent-encoded in the values of the `ce-` HTTP headers, as required by the
+/// HTTP protocol binding: all but the printable ASCII characters, plus space, `"` and `%`.
+#[cfg(any(feature = "http-common", feature = "tonic"))]
+const HTTP_HEADER_VALUE_ENCODE_SET: &percent_encoding::AsciiSet =
+    &percent_encoding::CONTROLS.add(b' ').add(b'"').add(b'%');
+
+/// Percent-encode the value of a `ce-` HTTP header.
+#[cfg(any(feature = "http-common", feature = "tonic"))]
+pub(crate) fn percent_encode_header_value(value: &str) -> std::borrow::Cow<'_, str> {
+    percent_encoding::utf8_percent_encode(value, HTTP_HEADER_VALUE_ENCODE_SET).into()
+}
+
+/// Decode the percent-encoded value of a `ce-` HTTP header.
+#[cfg(any(feature = "http-common", feature = "tonic"))]
+pub(crate) fn percent_decode_header_value(
+    value: &str,
+) -> crate::message::Result<std::borrow::Cow<'_, str>> {
+    percent_encoding::percent_decode_str(value)
+        .decode_utf8()
+        .map_err(|e| crate::message::Error::Other {
+            source: Box::new(e),
+        })
+}
+
 #[macro_export]
 macro_rules! header_value_to_str {
     ($header_value:expr) => {
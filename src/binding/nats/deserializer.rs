@@ -1,7 +1,17 @@
+use super::{SubjectTemplate, SPEC_VERSION_HEADER};
 use crate::{
-    message::{Result, StructuredDeserializer},
+    binding::{
+        attribute_name, instrument, is_media_type, CLOUDEVENTS_BATCH_JSON_HEADER, CONTENT_TYPE,
+    },
+    event::SpecVersion,
+    message::{
+        BinaryDeserializer, BinarySerializer, Encoding, Error, MessageAttributeValue,
+        MessageDeserializer, Result, StructuredDeserializer,
+    },
     Event,
 };
+use std::collections::HashMap;
+use std::convert::TryFrom;
 
 use nats_lib as nats;
 
@@ -10,20 +20,118 @@ impl StructuredDeserializer for nats::Message {
         self,
         serializer: V,
     ) -> crate::message::Result<R> {
+        if self.encoding() != Encoding::STRUCTURED {
+            return Err(Error::WrongEncoding {});
+        }
         serializer.set_structured_event(self.data.to_vec())
     }
 }
 
+impl BinaryDeserializer for nats::Message {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, mut visitor: V) -> Result<R> {
+        if self.encoding() != Encoding::BINARY {
+            return Err(Error::WrongEncoding {});
+        }
+
+        let mut headers: HashMap<String, String> = self
+            .headers
+            .iter()
+            .flat_map(|h| h.iter())
+            .filter_map(|(hn, hv)| {
+                hv.iter()
+                    .next()
+                    .map(|v| (hn.to_ascii_lowercase(), v.to_string()))
+            })
+            .collect();
+
+        let spec_version =
+            SpecVersion::try_from(headers.remove(SPEC_VERSION_HEADER).unwrap().as_str())
+                .map_err(|e| e.in_attribute(SPEC_VERSION_HEADER))?;
+
+        let attributes = spec_version.attribute_names();
+
+        visitor = visitor.set_spec_version(spec_version)?;
+
+        if let Some(ct) = headers.remove(CONTENT_TYPE) {
+            visitor = visitor.set_attribute("datacontenttype", MessageAttributeValue::String(ct))?
+        }
+
+        for (hn, hv) in headers {
+            let name = match attribute_name("ce-", &hn) {
+                Some(name) => name,
+                None => continue,
+            };
+
+            if attributes.contains(&name.as_ref()) {
+                visitor = visitor.set_attribute(&name, MessageAttributeValue::String(hv))?
+            } else {
+                visitor = visitor.set_extension(&name, MessageAttributeValue::String(hv))?
+            }
+        }
+
+        if !self.data.is_empty() {
+            visitor.end_with_data(self.data)
+        } else {
+            visitor.end()
+        }
+    }
+}
+
+impl MessageDeserializer for nats::Message {
+    fn encoding(&self) -> Encoding {
+        match &self.headers {
+            Some(h) if header(h, SPEC_VERSION_HEADER).is_some() => Encoding::BINARY,
+            Some(h) if is_batch(h) => Encoding::UNKNOWN,
+            _ => Encoding::STRUCTURED,
+        }
+    }
+}
+
+fn is_batch(headers: &nats::header::HeaderMap) -> bool {
+    header(headers, CONTENT_TYPE).is_some_and(|ct| is_media_type(ct, CLOUDEVENTS_BATCH_JSON_HEADER))
+}
+
+/// First value of the header `name`, ignoring the ASCII case of the header names.
+fn header<'a>(headers: &'a nats::header::HeaderMap, name: &str) -> Option<&'a String> {
+    headers
+        .iter()
+        .find(|(hn, _)| hn.eq_ignore_ascii_case(name))
+        .and_then(|(_, hv)| hv.iter().next())
+}
+
 /// Trait implemented by [`nats::Message`] to enable convenient deserialization to [`Event`]
 ///
+/// Both structured and binary mode messages are supported, the mode is detected from the
+/// presence of the `ce-specversion` header.
+///
 /// Trait sealed <https://rust-lang.github.io/api-guidelines/future-proofing.html#sealed-traits-protect-against-downstream-implementations-c-sealed>
 pub trait MessageExt: private::Sealed {
     fn to_event(&self) -> Result<Event>;
+
+    /// Deserialize a batch mode message, or a structured/binary mode message as a single element batch.
+    fn to_events(&self) -> Result<Vec<Event>>;
+
+    /// Generates [`Event`], setting the attributes named by `template` to the matching tokens
+    /// of the subject. Fails if the subject doesn't match the template.
+    fn to_event_with_subject(&self, template: &SubjectTemplate) -> Result<Event>;
 }
 
 impl MessageExt for nats::Message {
     fn to_event(&self) -> Result<Event> {
-        StructuredDeserializer::into_event(self.to_owned())
+        instrument::deserialize("nats", || MessageDeserializer::into_event(self.to_owned()))
+    }
+
+    fn to_events(&self) -> Result<Vec<Event>> {
+        match &self.headers {
+            Some(h) if is_batch(h) => Ok(serde_json::from_slice(&self.data)?),
+            _ => Ok(vec![self.to_event()?]),
+        }
+    }
+
+    fn to_event_with_subject(&self, template: &SubjectTemplate) -> Result<Event> {
+        let mut event = self.to_event()?;
+        template.apply(&self.subject, &mut event)?;
+        Ok(event)
     }
 }
 
@@ -37,7 +145,9 @@ mod private {
 
 #[cfg(test)]
 mod tests {
+    use crate::binding::nats::{ContentMode, NatsCloudEvent};
     use crate::test::fixtures;
+    use crate::AttributesWriter;
     use nats_lib as nats;
     use serde_json::json;
 
@@ -74,4 +184,127 @@ mod tests {
 
         assert_eq!(expected, actual)
     }
+
+    #[test]
+    fn test_binary_deserialize_v10() {
+        let expected = fixtures::v10::full_binary_json_data_string_extension();
+
+        let nats_event = NatsCloudEvent::from_event(expected.clone(), ContentMode::Binary).unwrap();
+        let headers = nats_event.headers.clone();
+
+        assert_eq!(headers.get("ce-id"), Some(&fixtures::id()));
+        assert_eq!(
+            headers.get("content-type").map(String::as_str),
+            Some("application/json")
+        );
+
+        let nats_message =
+            nats::Message::new("not_relevant", None, nats_event.payload, Some(headers));
+
+        let actual = nats_message.to_event().unwrap();
+
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn test_binary_deserialize_no_data() {
+        let expected = fixtures::v10::minimal_string_extension();
+
+        let nats_event = NatsCloudEvent::from_event(expected.clone(), ContentMode::Binary).unwrap();
+        let nats_message = nats::Message::new(
+            "not_relevant",
+            None,
+            nats_event.payload,
+            Some(nats_event.headers),
+        );
+
+        let actual = nats_message.to_event().unwrap();
+
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn test_binary_deserialize_mixed_case_headers() {
+        let expected = fixtures::v10::minimal_string_extension();
+
+        let mut headers = nats::header::HeaderMap::new();
+        headers.insert("CE-SpecVersion", "1.0");
+        headers.insert("Ce-Id", fixtures::id());
+        headers.insert("CE-Type", fixtures::ty());
+        headers.insert("ce-Source", fixtures::source());
+        headers.insert("CE-SOMEINT", "10");
+        let nats_message = nats::Message::new("not_relevant", None, [], Some(headers));
+
+        assert_eq!(expected, nats_message.to_event().unwrap())
+    }
+
+    #[test]
+    fn test_structured_with_headers() {
+        let expected = fixtures::v10::full_json_data_string_extension();
+
+        let nats_event =
+            NatsCloudEvent::from_event(expected.clone(), ContentMode::Structured).unwrap();
+
+        assert_eq!(
+            nats_event.headers.get("content-type").map(String::as_str),
+            Some("application/cloudevents+json")
+        );
+
+        let nats_message = nats::Message::new(
+            "not_relevant",
+            None,
+            nats_event.payload,
+            Some(nats_event.headers),
+        );
+
+        assert_eq!(expected, nats_message.to_event().unwrap())
+    }
+
+    #[test]
+    fn test_deserialize_with_subject() {
+        let template = SubjectTemplate::new("orders.{subject}").unwrap();
+        let nats_event =
+            NatsCloudEvent::from_event(fixtures::v10::minimal(), ContentMode::Binary).unwrap();
+        let nats_message = nats::Message::new(
+            "orders.42",
+            None,
+            nats_event.payload,
+            Some(nats_event.headers),
+        );
+
+        let mut expected = fixtures::v10::minimal();
+        expected.set_subject(Some("42"));
+        assert_eq!(
+            expected,
+            nats_message.to_event_with_subject(&template).unwrap()
+        );
+
+        let template = SubjectTemplate::new("invoices.{subject}").unwrap();
+        assert!(matches!(
+            nats_message.to_event_with_subject(&template),
+            Err(Error::TopicMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_batch_deserialize() {
+        let expected = vec![
+            fixtures::v10::full_json_data_string_extension(),
+            fixtures::v03::full_json_data(),
+        ];
+
+        let nats_event = NatsCloudEvent::from_events(expected.clone()).unwrap();
+        let nats_message = nats::Message::new(
+            "not_relevant",
+            None,
+            nats_event.payload,
+            Some(nats_event.headers),
+        );
+
+        assert!(matches!(
+            nats_message.to_event(),
+            Err(Error::WrongEncoding {})
+        ));
+        assert_eq!(expected, nats_message.to_events().unwrap())
+    }
 }
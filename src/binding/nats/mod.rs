@@ -18,7 +18,7 @@
 //! Serialize [Event](https://docs.rs/cloudevents-sdk/latest/cloudevents/event/struct.Event.html) into [NatsCloudEvent] and publish to nats subject
 //! ```
 //!     use nats_lib as nats;
-//!     use cloudevents::binding::nats::NatsCloudEvent;
+//!     use cloudevents::binding::nats::{ContentMode, NatsCloudEvent};
 //!     use cloudevents::{EventBuilder, EventBuilderV10, Event};
 //!     use serde_json::json;
 //!
@@ -33,11 +33,83 @@
 //!           .build()
 //!           .unwrap();
 //!
-//!       nc.publish("whatever.subject.you.like", NatsCloudEvent::from_event(event).unwrap()).unwrap();
+//!       nc.publish("whatever.subject.you.like", NatsCloudEvent::from_event(event, ContentMode::Structured).unwrap()).unwrap();
+//!     }
+//! ```
+//!
+//! Serialize [Event](https://docs.rs/cloudevents-sdk/latest/cloudevents/event/struct.Event.html) in binary mode, mapping the attributes to `ce-` headers, and publish to nats subject
+//! ```
+//!     use nats_lib as nats;
+//!     use cloudevents::binding::nats::{ContentMode, NatsCloudEvent};
+//!     use cloudevents::Event;
+//!
+//!     fn publish(nc: &nats::Connection, event: Event) {
+//!       let nats_event = NatsCloudEvent::from_event(event, ContentMode::Binary).unwrap();
+//!
+//!       nc.publish_with_reply_or_headers(
+//!           "whatever.subject.you.like",
+//!           None,
+//!           Some(&nats_event.headers),
+//!           &nats_event,
+//!       )
+//!       .unwrap();
+//!     }
+//! ```
+//!
+//! Send an [Event](https://docs.rs/cloudevents-sdk/latest/cloudevents/event/struct.Event.html) as a request and wait for the reply, and reply to requests
+//! ```
+//!     use nats_lib as nats;
+//!     use cloudevents::binding::nats::{ConnectionExt, MessageExt, ReplyExt};
+//!     use cloudevents::Event;
+//!     use std::time::Duration;
+//!
+//!     fn request(nc: &nats::Connection, event: Event) {
+//!       let reply = nc
+//!           .request_event("service.subject", event, Some(Duration::from_secs(1)))
+//!           .unwrap();
+//!
+//!       println!("{}", reply);
+//!     }
+//!
+//!     fn serve(nc: &nats::Connection) {
+//!       let sub = nc.subscribe("service.subject").unwrap();
+//!       for request in sub.messages() {
+//!         let event = request.to_event().unwrap();
+//!         request.reply_event(event).unwrap();
+//!       }
+//!     }
+//! ```
+//!
+//! Map the subjects to the event attributes with a [`SubjectTemplate`], so the routing
+//! information carried by the subject is kept in the [Event](https://docs.rs/cloudevents-sdk/latest/cloudevents/event/struct.Event.html)
+//! ```
+//!     use nats_lib as nats;
+//!     use cloudevents::binding::nats::{MessageExt, SubjectTemplate};
+//!
+//!     fn consume(nc: &nats::Connection) {
+//!       let template = SubjectTemplate::new("orders.{type}.{subject>}").unwrap();
+//!       let sub = nc.subscribe(&template.filter()).unwrap();
+//!       for message in sub.messages() {
+//!         // The type and the subject of the event are set from the subject of the message
+//!         let event = message.to_event_with_subject(&template).unwrap();
+//!         println!("{}", event);
+//!       }
 //!     }
 //! ```
 mod deserializer;
+mod request_reply;
 mod serializer;
+mod subject;
+mod transport;
 
 pub use deserializer::MessageExt;
-pub use serializer::NatsCloudEvent;
+pub use request_reply::{ConnectionExt, Error, ReplyExt, Result};
+pub use serializer::{ContentMode, NatsCloudEvent};
+pub use subject::SubjectTemplate;
+pub use transport::{NatsSink, NatsSource};
+
+pub(crate) static SPEC_VERSION_HEADER: &str = "ce-specversion";
+
+pub(crate) fn header_prefix(name: &str) -> String {
+    super::header_prefix("ce-", name)
+}
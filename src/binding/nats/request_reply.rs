@@ -0,0 +1,109 @@
+use super::{ContentMode, MessageExt, NatsCloudEvent};
+use crate::{message, Event};
+use snafu::Snafu;
+use std::io;
+use std::time::Duration;
+
+use nats_lib as nats;
+
+/// Represents an error during a request-reply exchange of [`Event`]s
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("No reply received before the timeout"))]
+    Timeout {},
+    #[snafu(display("IO Error: {}", source))]
+    IOError { source: io::Error },
+    #[snafu(display("Error while serializing/deserializing the event: {}", source))]
+    #[snafu(context(false))]
+    MessageError { source: message::Error },
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Self {
+        match source.kind() {
+            io::ErrorKind::TimedOut => Error::Timeout {},
+            _ => Error::IOError { source },
+        }
+    }
+}
+
+/// Result type alias for return values of the request-reply helpers
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Trait implemented by [`nats::Connection`] to send an [`Event`] as a request and receive the reply as an [`Event`]
+///
+/// Trait sealed <https://rust-lang.github.io/api-guidelines/future-proofing.html#sealed-traits-protect-against-downstream-implementations-c-sealed>
+pub trait ConnectionExt: private::Sealed {
+    /// Publish `event` in structured mode on `subject` and wait for the reply,
+    /// for at most `timeout` if provided.
+    fn request_event(
+        &self,
+        subject: &str,
+        event: Event,
+        timeout: Option<Duration>,
+    ) -> Result<Event>;
+}
+
+impl ConnectionExt for nats::Connection {
+    fn request_event(
+        &self,
+        subject: &str,
+        event: Event,
+        timeout: Option<Duration>,
+    ) -> Result<Event> {
+        let request = NatsCloudEvent::from_event(event, ContentMode::Structured)?;
+        let reply = self.request_with_headers_or_timeout(
+            subject,
+            Some(&request.headers),
+            timeout,
+            &request,
+        )?;
+        Ok(reply.to_event()?)
+    }
+}
+
+/// Trait implemented by [`nats::Message`] to reply to a request with an [`Event`]
+///
+/// Trait sealed <https://rust-lang.github.io/api-guidelines/future-proofing.html#sealed-traits-protect-against-downstream-implementations-c-sealed>
+pub trait ReplyExt: private::Sealed {
+    /// Publish `event` in structured mode on the reply subject of this message.
+    fn reply_event(&self, event: Event) -> Result<()>;
+}
+
+impl ReplyExt for nats::Message {
+    fn reply_event(&self, event: Event) -> Result<()> {
+        Ok(self.respond(NatsCloudEvent::from_event(event, ContentMode::Structured)?)?)
+    }
+}
+
+mod private {
+    use nats_lib as nats;
+
+    // Sealing the ConnectionExt and ReplyExt
+    pub trait Sealed {}
+    impl Sealed for nats::Connection {}
+    impl Sealed for nats::Message {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+
+    #[test]
+    fn test_reply_without_reply_subject() {
+        let nats_message = nats::Message::new("not_relevant", None, "", None);
+
+        assert!(matches!(
+            nats_message.reply_event(fixtures::v10::minimal()),
+            Err(Error::IOError { .. })
+        ))
+    }
+
+    #[test]
+    fn test_timeout_error_mapping() {
+        let error = Error::from(io::Error::new(io::ErrorKind::TimedOut, "timed out"));
+
+        assert!(matches!(error, Error::Timeout {}))
+    }
+}
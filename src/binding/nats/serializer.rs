@@ -1,13 +1,30 @@
+use super::{header_prefix, SPEC_VERSION_HEADER};
 use crate::{
-    message::{Error, Result},
+    binding::{instrument, CLOUDEVENTS_BATCH_JSON_HEADER, CLOUDEVENTS_JSON_HEADER, CONTENT_TYPE},
+    event::SpecVersion,
+    message::{BinaryDeserializer, BinarySerializer, Error, MessageAttributeValue, Result},
     Event,
 };
 
-/// Helper struct containing text data bytes of JSON serialized [Event]
+use nats_lib as nats;
+
+/// Content mode used to serialize an [Event] into a [`NatsCloudEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentMode {
+    /// The payload contains the JSON serialized [Event].
+    Structured,
+    /// The attributes are mapped to `ce-`-prefixed headers and the payload contains the event data.
+    Binary,
+    /// The payload contains a JSON array of serialized [Event]s.
+    Batch,
+}
+
+/// Helper struct containing the payload and headers of a serialized [Event]
 ///
 /// Implements [`AsRef`] so it can be directly passed to [`nats::Connection`](https://docs.rs/nats/0.21.0/nats/struct.Connection.html) methods as payload.
 pub struct NatsCloudEvent {
     pub payload: Vec<u8>,
+    pub headers: nats::header::HeaderMap,
 }
 
 impl AsRef<[u8]> for NatsCloudEvent {
@@ -17,10 +34,59 @@ impl AsRef<[u8]> for NatsCloudEvent {
 }
 
 impl NatsCloudEvent {
-    pub fn from_event(event: Event) -> Result<Self> {
-        match serde_json::to_vec(&event) {
-            Ok(payload) => Ok(Self { payload }),
+    /// Serialize `event` using the given content `mode`.
+    pub fn from_event(event: Event, mode: ContentMode) -> Result<Self> {
+        instrument::serialize("nats", event, |event| match mode {
+            ContentMode::Structured => Self::from_json(&event, CLOUDEVENTS_JSON_HEADER),
+            ContentMode::Binary => BinaryDeserializer::deserialize_binary(
+                event,
+                NatsCloudEvent {
+                    payload: Vec::new(),
+                    headers: nats::header::HeaderMap::new(),
+                },
+            ),
+            ContentMode::Batch => Self::from_events(vec![event]),
+        })
+    }
+
+    /// Serialize `events` in batch mode.
+    pub fn from_events(events: Vec<Event>) -> Result<Self> {
+        Self::from_json(&events, CLOUDEVENTS_BATCH_JSON_HEADER)
+    }
+
+    fn from_json(value: &impl serde::Serialize, content_type: &str) -> Result<Self> {
+        match serde_json::to_vec(value) {
+            Ok(payload) => {
+                let mut headers = nats::header::HeaderMap::new();
+                headers.insert(CONTENT_TYPE, content_type);
+                Ok(Self { payload, headers })
+            }
             Err(e) => Err(Error::SerdeJsonError { source: e }),
         }
     }
 }
+
+impl BinarySerializer<NatsCloudEvent> for NatsCloudEvent {
+    fn set_spec_version(mut self, sv: SpecVersion) -> Result<Self> {
+        self.headers.insert(SPEC_VERSION_HEADER, sv.to_string());
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.headers.insert(header_prefix(name), value.to_string());
+        Ok(self)
+    }
+
+    fn set_extension(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.set_attribute(name, value)
+    }
+
+    fn end_with_data(mut self, bytes: Vec<u8>) -> Result<NatsCloudEvent> {
+        self.payload = bytes;
+        Ok(self)
+    }
+
+    fn end(self) -> Result<NatsCloudEvent> {
+        Ok(self)
+    }
+}
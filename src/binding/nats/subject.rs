@@ -0,0 +1,133 @@
+use crate::binding::topic::{Syntax, Template};
+use crate::message::Result;
+use crate::Event;
+use std::fmt;
+
+const NATS: Syntax = Syntax {
+    separator: '.',
+    wildcard: "*",
+    multi_wildcard: ">",
+    reserved: &['*', '>', ' ', '\t', '\r', '\n', '\0'],
+};
+
+/// Mapping between NATS subjects and the attributes of the events, defined by a template such as
+/// `orders.{type}.{subject}`.
+///
+/// The tokens of the template are either literals or placeholders naming the `id`, the
+/// `source`, the `type`, the `subject` or an extension of the event. A placeholder matches a
+/// single token of a subject, except for a last placeholder ending with `>`, e.g.
+/// `orders.{subject>}`, which matches the remaining tokens.
+///
+/// ```
+/// use cloudevents::binding::nats::SubjectTemplate;
+/// use cloudevents::{AttributesReader, EventBuilder, EventBuilderV10};
+///
+/// let template = SubjectTemplate::new("orders.{type}.{subject>}").unwrap();
+/// assert_eq!(template.filter(), "orders.*.>");
+///
+/// let mut event = EventBuilderV10::new()
+///     .id("0001")
+///     .ty("created")
+///     .source("http://localhost/")
+///     .subject("eu.42")
+///     .build()
+///     .unwrap();
+/// assert_eq!(template.subject(&event).unwrap(), "orders.created.eu.42");
+///
+/// template.apply("orders.shipped.us.7", &mut event).unwrap();
+/// assert_eq!(event.ty(), "shipped");
+/// assert_eq!(event.subject(), Some("us.7"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubjectTemplate(Template);
+
+impl SubjectTemplate {
+    /// Parse `template`, failing if a token is empty, contains a wildcard, or names an
+    /// attribute other than `id`, `source`, `type`, `subject` or a valid extension name.
+    pub fn new(template: &str) -> Result<Self> {
+        Template::parse(template, NATS).map(SubjectTemplate)
+    }
+
+    /// The subject to subscribe to, replacing the placeholders with the `*` and `>` wildcards.
+    pub fn filter(&self) -> String {
+        self.0.filter()
+    }
+
+    /// The subject to publish `event` on, failing if an attribute of the template is missing,
+    /// empty, or contains a wildcard, a whitespace or, unless it matches the remaining tokens,
+    /// a `.`.
+    pub fn subject(&self, event: &Event) -> Result<String> {
+        self.0.topic(event)
+    }
+
+    /// Set the attributes of `event` to the matching tokens of `subject`, failing if the
+    /// subject doesn't match the template.
+    pub fn apply(&self, subject: &str, event: &mut Event) -> Result<()> {
+        self.0.apply(subject, event)
+    }
+}
+
+impl fmt::Display for SubjectTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::Error;
+    use crate::test::fixtures;
+    use crate::{AttributesReader, AttributesWriter};
+
+    #[test]
+    fn parse() {
+        let template = SubjectTemplate::new("events.{region}.{type}").unwrap();
+        assert_eq!(template.filter(), "events.*.*");
+
+        for invalid in [
+            "events..{type}",
+            "events.*.{type}",
+            "events.>",
+            "events.{subject>}.{type}",
+            "events.{time}",
+        ] {
+            assert!(SubjectTemplate::new(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn subject_and_apply() {
+        let template = SubjectTemplate::new("events.{type}.{subject>}").unwrap();
+        let mut event = fixtures::v10::minimal();
+        event.set_type("created");
+
+        assert!(matches!(
+            template.subject(&event),
+            Err(Error::InvalidTopicAttribute { name, .. }) if name == "subject"
+        ));
+
+        template
+            .apply("events.created.orders.42", &mut event)
+            .unwrap();
+        assert_eq!(event.ty(), "created");
+        assert_eq!(event.subject(), Some("orders.42"));
+        assert_eq!(
+            template.subject(&event).unwrap(),
+            "events.created.orders.42"
+        );
+
+        event.set_type("order.created");
+        assert!(template.subject(&event).is_err());
+        event.set_type("created");
+        event.set_subject(Some("orders 42"));
+        assert!(template.subject(&event).is_err());
+
+        for mismatch in ["events.created", "other.created.orders", "events..orders"] {
+            assert!(matches!(
+                template.apply(mismatch, &mut event),
+                Err(Error::TopicMismatch { .. })
+            ));
+        }
+    }
+}
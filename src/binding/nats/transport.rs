@@ -0,0 +1,130 @@
+use super::{ContentMode, MessageExt, NatsCloudEvent, SubjectTemplate};
+use crate::binding::instrument;
+use crate::transport::{Error, EventSink, EventSource, Result};
+use crate::Event;
+use async_trait::async_trait;
+
+use nats_lib as nats;
+
+/// [`EventSink`] publishing the events on a subject, using [`nats::asynk::Connection`].
+///
+/// The subject is either fixed, or derived from the attributes of each event by a
+/// [`SubjectTemplate`].
+pub struct NatsSink {
+    connection: nats::asynk::Connection,
+    subject: Subject,
+    mode: ContentMode,
+}
+
+enum Subject {
+    Fixed(String),
+    Template(SubjectTemplate),
+}
+
+impl NatsSink {
+    /// Create a new [`NatsSink`], publishing the events on `subject` using the given content `mode`.
+    pub fn new(
+        connection: nats::asynk::Connection,
+        subject: impl Into<String>,
+        mode: ContentMode,
+    ) -> Self {
+        NatsSink {
+            connection,
+            subject: Subject::Fixed(subject.into()),
+            mode,
+        }
+    }
+
+    /// Create a new [`NatsSink`], publishing each event on the subject derived from its
+    /// attributes by `template`, using the given content `mode`.
+    ///
+    /// Sending an event whose attributes don't fit the template fails, see
+    /// [`SubjectTemplate::subject`].
+    pub fn with_template(
+        connection: nats::asynk::Connection,
+        template: SubjectTemplate,
+        mode: ContentMode,
+    ) -> Self {
+        NatsSink {
+            connection,
+            subject: Subject::Template(template),
+            mode,
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for NatsSink {
+    async fn send(&self, event: Event) -> Result<()> {
+        instrument::send("nats", event, |event| async move {
+            let subject = match &self.subject {
+                Subject::Fixed(subject) => subject.clone(),
+                Subject::Template(template) => template.subject(&event)?,
+            };
+            let nats_event = NatsCloudEvent::from_event(event, self.mode)?;
+            self.connection
+                .publish_with_reply_or_headers(
+                    &subject,
+                    None,
+                    Some(&nats_event.headers),
+                    &nats_event,
+                )
+                .await
+                .map_err(Error::transport)
+        })
+        .await
+    }
+}
+
+/// [`EventSource`] receiving the events of a [`nats::asynk::Subscription`].
+///
+/// Core NATS has no acknowledgements, so [`EventSource::ack`] does nothing.
+pub struct NatsSource {
+    subscription: nats::asynk::Subscription,
+    template: Option<SubjectTemplate>,
+}
+
+impl NatsSource {
+    /// Create a new [`NatsSource`] from a `subscription`.
+    pub fn new(subscription: nats::asynk::Subscription) -> Self {
+        NatsSource {
+            subscription,
+            template: None,
+        }
+    }
+
+    /// Set the attributes named by `template` to the matching tokens of the subject of each
+    /// received message, e.g. when subscribed to [`SubjectTemplate::filter`]. Receiving a
+    /// message whose subject doesn't match the template fails.
+    pub fn subject_template(mut self, template: SubjectTemplate) -> Self {
+        self.template = Some(template);
+        self
+    }
+}
+
+#[async_trait]
+impl EventSource for NatsSource {
+    type Ack = ();
+
+    async fn receive(&mut self) -> Option<Result<(Event, ())>> {
+        let message = self.subscription.next().await?;
+        let message = nats::Message::new(
+            &message.subject,
+            message.reply.as_deref(),
+            message.data,
+            message.headers,
+        );
+        Some(
+            instrument::receive("nats", || match &self.template {
+                Some(template) => message.to_event_with_subject(template),
+                None => message.to_event(),
+            })
+            .map(|e| (e, ()))
+            .map_err(Error::from),
+        )
+    }
+
+    async fn ack(&mut self, _ack: ()) -> Result<()> {
+        Ok(())
+    }
+}
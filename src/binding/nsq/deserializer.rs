@@ -0,0 +1,50 @@
+use crate::{message::Result, Event};
+use tokio_nsq::NSQMessage;
+
+/// Trait implemented by [`NSQMessage`] to enable convenient deserialization to [`Event`]
+///
+/// Trait sealed <https://rust-lang.github.io/api-guidelines/future-proofing.html#sealed-traits-protect-against-downstream-implementations-c-sealed>
+pub trait MessageExt: private::Sealed {
+    fn to_event(&self) -> Result<Event>;
+}
+
+impl MessageExt for NSQMessage {
+    fn to_event(&self) -> Result<Event> {
+        body_to_event(&self.body)
+    }
+}
+
+pub(crate) fn body_to_event(body: &[u8]) -> Result<Event> {
+    Ok(serde_json::from_slice(body)?)
+}
+
+mod private {
+    // Sealing the MessageExt
+    pub trait Sealed {}
+    impl Sealed for tokio_nsq::NSQMessage {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::nsq::NsqCloudEvent;
+    use crate::test::fixtures;
+
+    #[test]
+    fn test_structured_roundtrip_v10() {
+        let expected = fixtures::v10::full_json_data_string_extension();
+
+        let payload: Vec<u8> = NsqCloudEvent::from_event(expected.clone()).unwrap().into();
+
+        assert_eq!(expected, body_to_event(&payload).unwrap())
+    }
+
+    #[test]
+    fn test_structured_roundtrip_v03() {
+        let expected = fixtures::v03::full_json_data();
+
+        let payload: Vec<u8> = NsqCloudEvent::from_event(expected.clone()).unwrap().into();
+
+        assert_eq!(expected, body_to_event(&payload).unwrap())
+    }
+}
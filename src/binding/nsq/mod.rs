@@ -0,0 +1,38 @@
+//! This module provides bindings between [cloudevents-sdk](https://docs.rs/cloudevents-sdk) and [NSQ](https://nsq.io),
+//! using the [tokio-nsq](https://docs.rs/tokio-nsq) library.
+//!
+//! NSQ messages have no headers, so events are always transported in structured mode.
+//! ## Examples
+//! Deserialize [tokio_nsq::NSQMessage](https://docs.rs/tokio-nsq/0.14.0/tokio_nsq/struct.NSQMessage.html) into [Event](https://docs.rs/cloudevents-sdk/latest/cloudevents/event/struct.Event.html)
+//! ```
+//!     use cloudevents::binding::nsq::MessageExt;
+//!     use tokio_nsq::NSQConsumer;
+//!
+//!     async fn consume(consumer: &mut NSQConsumer) {
+//!       if let Some(message) = consumer.consume_filtered().await {
+//!         let cloud_event = message.to_event().unwrap();
+//!         println!("{}", cloud_event.to_string());
+//!         message.finish().await;
+//!       }
+//!     }
+//! ```
+//!
+//! Publish [Event](https://docs.rs/cloudevents-sdk/latest/cloudevents/event/struct.Event.html)s to a NSQ topic,
+//! one at a time or in a single `MPUB` command
+//! ```
+//!     use cloudevents::binding::nsq::ProducerExt;
+//!     use cloudevents::Event;
+//!     use tokio_nsq::{NSQProducer, NSQTopic};
+//!
+//!     async fn publish(producer: &mut NSQProducer, event: Event, events: Vec<Event>) {
+//!       let topic = NSQTopic::new("topic").unwrap();
+//!
+//!       producer.publish_event(&topic, event).await.unwrap();
+//!       producer.publish_events(&topic, events).await.unwrap();
+//!     }
+//! ```
+mod deserializer;
+mod serializer;
+
+pub use deserializer::MessageExt;
+pub use serializer::{NsqCloudEvent, ProducerExt};
@@ -0,0 +1,73 @@
+use crate::{
+    message::{Error, Result},
+    Event,
+};
+use async_trait::async_trait;
+use std::sync::Arc;
+use tokio_nsq::{NSQProducer, NSQTopic};
+
+/// Helper struct containing text data bytes of JSON serialized [Event]
+///
+/// Implements [`AsRef`] and [`Into<Vec<u8>>`](From) so it can be directly used as [`NSQProducer`] message value.
+pub struct NsqCloudEvent {
+    pub payload: Vec<u8>,
+}
+
+impl AsRef<[u8]> for NsqCloudEvent {
+    fn as_ref(&self) -> &[u8] {
+        self.payload.as_ref()
+    }
+}
+
+impl From<NsqCloudEvent> for Vec<u8> {
+    fn from(event: NsqCloudEvent) -> Self {
+        event.payload
+    }
+}
+
+impl NsqCloudEvent {
+    pub fn from_event(event: Event) -> Result<Self> {
+        match serde_json::to_vec(&event) {
+            Ok(payload) => Ok(Self { payload }),
+            Err(e) => Err(Error::SerdeJsonError { source: e }),
+        }
+    }
+}
+
+/// Trait implemented by [`NSQProducer`] to publish [`Event`]s in structured mode
+///
+/// Trait sealed <https://rust-lang.github.io/api-guidelines/future-proofing.html#sealed-traits-protect-against-downstream-implementations-c-sealed>
+#[async_trait]
+pub trait ProducerExt: private::Sealed {
+    /// Queue a `PUB` of `event` to `topic`.
+    async fn publish_event(&mut self, topic: &Arc<NSQTopic>, event: Event) -> Result<()>;
+
+    /// Queue a single `MPUB` of `events` to `topic`, each event being a separate NSQ message.
+    async fn publish_events(&mut self, topic: &Arc<NSQTopic>, events: Vec<Event>) -> Result<()>;
+}
+
+#[async_trait]
+impl ProducerExt for NSQProducer {
+    async fn publish_event(&mut self, topic: &Arc<NSQTopic>, event: Event) -> Result<()> {
+        let value = NsqCloudEvent::from_event(event)?.into();
+        self.publish(topic, value)
+            .await
+            .map_err(|e| Error::Other { source: e.into() })
+    }
+
+    async fn publish_events(&mut self, topic: &Arc<NSQTopic>, events: Vec<Event>) -> Result<()> {
+        let values = events
+            .into_iter()
+            .map(|e| NsqCloudEvent::from_event(e).map(Vec::from))
+            .collect::<Result<Vec<_>>>()?;
+        self.publish_multiple(topic, values)
+            .await
+            .map_err(|e| Error::Other { source: e.into() })
+    }
+}
+
+mod private {
+    // Sealing the ProducerExt
+    pub trait Sealed {}
+    impl Sealed for tokio_nsq::NSQProducer {}
+}
@@ -0,0 +1,203 @@
+//! This module provides a binding over the standard streams of the process, reading the events
+//! from stdin and writing them to stdout in the [JSON lines](https://jsonlines.org/) format,
+//! one structured event per line, so CloudEvents processors can be composed with Unix pipes,
+//! e.g. as sidecars or CLI filters.
+//!
+//! ```no_run
+//! use cloudevents::binding::pipe;
+//! use cloudevents::AttributesReader;
+//!
+//! // cat events.jsonl | my-filter > orders.jsonl
+//! # async fn example() -> cloudevents::transport::Result<()> {
+//! pipe::filter_map(|event| {
+//!     if event.ty().starts_with("com.example.order.") {
+//!         Some(event)
+//!     } else {
+//!         None
+//!     }
+//! })
+//! .await
+//! # }
+//! ```
+//!
+//! [`PipeSource`] and [`PipeSink`] implement the [`EventSource`] and [`EventSink`] traits, to
+//! forward the events between the standard streams and any other transport.
+
+use crate::jsonl::{replay_reader, EventLogWriter};
+use crate::transport::{Error, EventSink, EventSource, Result};
+use crate::Event;
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use tokio::io::{AsyncBufRead, AsyncWrite, BufReader, Stdout};
+use tokio::sync::Mutex;
+
+/// [`EventSource`] reading one structured event per line, from stdin by default.
+///
+/// The blank lines are skipped. There is nothing to acknowledge, so [`EventSource::ack`] is a
+/// no-op.
+pub struct PipeSource {
+    events: BoxStream<'static, crate::jsonl::Result<Event>>,
+}
+
+impl PipeSource {
+    /// Create a new [`PipeSource`] reading the lines of `reader`.
+    pub fn new<R: AsyncBufRead + Unpin + Send + 'static>(reader: R) -> Self {
+        PipeSource {
+            events: replay_reader(reader).boxed(),
+        }
+    }
+
+    /// Create a new [`PipeSource`] reading the stdin of the process.
+    pub fn stdin() -> Self {
+        Self::new(BufReader::new(tokio::io::stdin()))
+    }
+}
+
+#[async_trait]
+impl EventSource for PipeSource {
+    type Ack = ();
+
+    async fn receive(&mut self) -> Option<Result<(Event, ())>> {
+        let result = self.events.next().await?;
+        Some(result.map(|event| (event, ())).map_err(Error::transport))
+    }
+
+    async fn ack(&mut self, _ack: ()) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// [`EventSink`] writing one structured event per line, to stdout by default.
+///
+/// The output is flushed after each event, so the downstream processes receive the events as
+/// soon as they are sent.
+pub struct PipeSink<W = Stdout> {
+    writer: Mutex<EventLogWriter<W>>,
+}
+
+impl<W: AsyncWrite + Unpin> PipeSink<W> {
+    /// Create a new [`PipeSink`] writing the lines to `writer`.
+    pub fn new(writer: W) -> Self {
+        PipeSink {
+            writer: Mutex::new(EventLogWriter::new(writer)),
+        }
+    }
+
+    /// Return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().into_inner()
+    }
+}
+
+impl PipeSink<Stdout> {
+    /// Create a new [`PipeSink`] writing to the stdout of the process.
+    pub fn stdout() -> Self {
+        Self::new(tokio::io::stdout())
+    }
+}
+
+#[async_trait]
+impl<W: AsyncWrite + Unpin + Send> EventSink for PipeSink<W> {
+    async fn send(&self, event: Event) -> Result<()> {
+        let mut writer = self.writer.lock().await;
+        writer.append(&event).await.map_err(Error::transport)?;
+        writer.flush().await.map_err(Error::transport)
+    }
+
+    /// Write all the events, flushing the output once.
+    async fn send_all(&self, events: Vec<Event>) -> Result<()> {
+        let mut writer = self.writer.lock().await;
+        for event in &events {
+            writer.append(event).await.map_err(Error::transport)?;
+        }
+        writer.flush().await.map_err(Error::transport)
+    }
+}
+
+/// Read the events from stdin, and write the events returned by `f` to stdout, until the end
+/// of the input. Stops at the first invalid line.
+pub async fn filter_map<F>(f: F) -> Result<()>
+where
+    F: FnMut(Event) -> Option<Event> + Send,
+{
+    run(&mut PipeSource::stdin(), &PipeSink::stdout(), f).await
+}
+
+async fn run<F, W>(source: &mut PipeSource, sink: &PipeSink<W>, mut f: F) -> Result<()>
+where
+    F: FnMut(Event) -> Option<Event> + Send,
+    W: AsyncWrite + Unpin + Send,
+{
+    while let Some(received) = source.receive().await {
+        let (event, ack) = received?;
+        if let Some(event) = f(event) {
+            sink.send(event).await?;
+        }
+        source.ack(ack).await?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use crate::AttributesReader;
+
+    fn lines(events: &[Event]) -> String {
+        events
+            .iter()
+            .map(|e| serde_json::to_string(e).unwrap() + "\n")
+            .collect()
+    }
+
+    fn events(output: Vec<u8>) -> Vec<Event> {
+        output
+            .split(|b| *b == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| serde_json::from_slice(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn source_and_sink() {
+        let expected = vec![fixtures::v10::full_json_data(), fixtures::v03::minimal()];
+        let mut source = PipeSource::new(std::io::Cursor::new(lines(&expected).into_bytes()));
+        let sink = PipeSink::new(Vec::new());
+
+        while let Some(received) = source.receive().await {
+            let (event, ack) = received.unwrap();
+            sink.send(event).await.unwrap();
+            source.ack(ack).await.unwrap();
+        }
+
+        assert_eq!(events(sink.into_inner()), expected);
+    }
+
+    #[tokio::test]
+    async fn filter() {
+        let input = vec![fixtures::v10::minimal(), fixtures::v10::full_json_data()];
+        let mut source = PipeSource::new(std::io::Cursor::new(lines(&input).into_bytes()));
+        let sink = PipeSink::new(Vec::new());
+
+        run(&mut source, &sink, |event| {
+            event.subject().is_some().then_some(event)
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(events(sink.into_inner()), input[1..]);
+    }
+
+    #[tokio::test]
+    async fn invalid_line() {
+        let mut source = PipeSource::new(&b"{\"id\": 1}\n"[..]);
+        let sink = PipeSink::new(Vec::new());
+
+        assert!(matches!(
+            run(&mut source, &sink, Some).await,
+            Err(Error::TransportError { .. })
+        ));
+    }
+}
@@ -56,7 +56,10 @@ mod tests {
         let (req, mut body) = req.split();
         let resp = Event::from_request(&req, &mut body).await.err().unwrap();
         assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
-        assert_eq!(resp.to_string(), "Invalid specversion BAD SPECIFICATION");
+        assert_eq!(
+            resp.to_string(),
+            "Invalid specversion `BAD SPECIFICATION` in `ce-specversion`, supported versions: 0.3, 1.0"
+        );
     }
 
     #[tokio::test]
@@ -71,9 +74,9 @@ mod tests {
             .header("ce-source", "http://localhost/")
             .header("ce-subject", "cloudevents-sdk")
             .header("content-type", "application/json")
-            .header("ce-string_ex", "val")
-            .header("ce-int_ex", "10")
-            .header("ce-bool_ex", "true")
+            .header("ce-stringex", "val")
+            .header("ce-intex", "10")
+            .header("ce-boolex", "true")
             .header("ce-time", fixtures::time().to_rfc3339())
             .body(fixtures::json_data_binary());
         let (req, mut body) = req.split();
@@ -19,6 +19,7 @@ impl IntoResponse for Event {
         match self.data {
             Some(data) => match data {
                 Data::Binary(v) => builder.body(Bytes::copy_from_slice(v.as_slice())),
+                Data::Bytes(b) => builder.body(b.clone()),
                 Data::String(s) => builder.body(s.clone()),
                 Data::Json(j) => match serde_json::to_string(&j) {
                     Ok(s) => builder.body(s),
@@ -104,7 +105,7 @@ mod tests {
             "application/json"
         );
         assert_eq!(
-            resp.headers().get("ce-int_ex").unwrap().to_str().unwrap(),
+            resp.headers().get("ce-intex").unwrap().to_str().unwrap(),
             "10"
         );
 
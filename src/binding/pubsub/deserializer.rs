@@ -0,0 +1,198 @@
+use super::SPEC_VERSION_ATTRIBUTE;
+use crate::binding::{CLOUDEVENTS_JSON_HEADER, CONTENT_TYPE};
+use crate::event::SpecVersion;
+use crate::message::{
+    BinaryDeserializer, BinarySerializer, Encoding, MessageAttributeValue, MessageDeserializer,
+    Result, StructuredDeserializer, StructuredSerializer,
+};
+use crate::{message, Event};
+use google_cloud_googleapis::pubsub::v1::PubsubMessage;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// Wrapper for [`PubsubMessage`] that implements [`MessageDeserializer`] trait.
+pub struct PubsubMessageDeserializer {
+    pub(crate) attributes: HashMap<String, String>,
+    pub(crate) payload: Vec<u8>,
+}
+
+impl PubsubMessageDeserializer {
+    /// Create a new [`PubsubMessageDeserializer`] from the message attributes and data.
+    pub fn new(attributes: HashMap<String, String>, payload: Vec<u8>) -> PubsubMessageDeserializer {
+        PubsubMessageDeserializer {
+            attributes,
+            payload,
+        }
+    }
+}
+
+impl BinaryDeserializer for PubsubMessageDeserializer {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(mut self, mut visitor: V) -> Result<R> {
+        if self.encoding() != Encoding::BINARY {
+            return Err(message::Error::WrongEncoding {});
+        }
+
+        let spec_version = SpecVersion::try_from(
+            self.attributes
+                .remove(SPEC_VERSION_ATTRIBUTE)
+                .unwrap()
+                .as_str(),
+        )
+        .map_err(|e| e.in_attribute(SPEC_VERSION_ATTRIBUTE))?;
+
+        let attributes = spec_version.attribute_names();
+
+        visitor = visitor.set_spec_version(spec_version)?;
+
+        if let Some(ct) = self.attributes.remove(CONTENT_TYPE) {
+            visitor = visitor.set_attribute("datacontenttype", MessageAttributeValue::String(ct))?
+        }
+
+        for (an, av) in self
+            .attributes
+            .into_iter()
+            .filter(|(an, _)| an.starts_with("ce-"))
+        {
+            let name = &an["ce-".len()..];
+
+            if attributes.contains(&name) {
+                visitor = visitor.set_attribute(name, MessageAttributeValue::String(av))?
+            } else {
+                visitor = visitor.set_extension(name, MessageAttributeValue::String(av))?
+            }
+        }
+
+        if !self.payload.is_empty() {
+            visitor.end_with_data(self.payload)
+        } else {
+            visitor.end()
+        }
+    }
+}
+
+impl StructuredDeserializer for PubsubMessageDeserializer {
+    fn deserialize_structured<R: Sized, V: StructuredSerializer<R>>(self, visitor: V) -> Result<R> {
+        if self.encoding() != Encoding::STRUCTURED {
+            return Err(message::Error::WrongEncoding {});
+        }
+        visitor.set_structured_event(self.payload)
+    }
+}
+
+impl MessageDeserializer for PubsubMessageDeserializer {
+    fn encoding(&self) -> Encoding {
+        match (
+            self.attributes
+                .get(CONTENT_TYPE)
+                .map(|s| s.starts_with(CLOUDEVENTS_JSON_HEADER))
+                .unwrap_or(false),
+            self.attributes.get(SPEC_VERSION_ATTRIBUTE),
+        ) {
+            (true, _) => Encoding::STRUCTURED,
+            (_, Some(_)) => Encoding::BINARY,
+            _ => Encoding::UNKNOWN,
+        }
+    }
+}
+
+/// Method to transform a [`PubsubMessage`] to [`Event`].
+pub fn message_to_event(message: &PubsubMessage) -> Result<Event> {
+    MessageDeserializer::into_event(PubsubMessageDeserializer::new(
+        message.attributes.clone(),
+        message.data.clone(),
+    ))
+}
+
+/// Extension Trait for [`PubsubMessage`] which acts as a wrapper for the function [`message_to_event()`].
+///
+/// This trait is sealed and cannot be implemented for types outside of this crate.
+pub trait PubsubMessageExt: private::Sealed {
+    /// Generates [`Event`] from [`PubsubMessage`].
+    fn to_event(&self) -> Result<Event>;
+}
+
+impl PubsubMessageExt for PubsubMessage {
+    fn to_event(&self) -> Result<Event> {
+        message_to_event(self)
+    }
+}
+
+mod private {
+    // Sealing the PubsubMessageExt
+    pub trait Sealed {}
+    impl Sealed for google_cloud_googleapis::pubsub::v1::PubsubMessage {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::pubsub::MessageRecord;
+    use crate::test::fixtures;
+    use crate::{EventBuilder, EventBuilderV10};
+
+    #[test]
+    fn test_binary_message() {
+        let expected = fixtures::v10::minimal_string_extension();
+
+        let message_record = MessageRecord::from_event(
+            EventBuilderV10::new()
+                .id("0001")
+                .ty("test_event.test_application")
+                .source("http://localhost/")
+                .extension("someint", "10")
+                .build()
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            message_record.attributes().get("ce-id").map(String::as_str),
+            Some("0001")
+        );
+        assert_eq!(
+            PubsubMessage::from(message_record).to_event().unwrap(),
+            expected
+        )
+    }
+
+    #[test]
+    fn test_binary_message_with_data() {
+        let expected = fixtures::v10::full_binary_json_data_string_extension();
+
+        let message_record = MessageRecord::from_event(expected.clone()).unwrap();
+
+        assert_eq!(
+            message_record
+                .attributes()
+                .get("content-type")
+                .map(String::as_str),
+            Some("application/json")
+        );
+        assert_eq!(
+            PubsubMessage::from(message_record).to_event().unwrap(),
+            expected
+        )
+    }
+
+    #[test]
+    fn test_structured_message() {
+        let expected = fixtures::v10::full_json_data_string_extension();
+
+        let message_record =
+            StructuredDeserializer::deserialize_structured(expected.clone(), MessageRecord::new())
+                .unwrap();
+
+        assert_eq!(
+            PubsubMessage::from(message_record).to_event().unwrap(),
+            expected
+        )
+    }
+
+    #[test]
+    fn test_unknown_encoding() {
+        assert!(matches!(
+            PubsubMessage::default().to_event(),
+            Err(message::Error::WrongEncoding {})
+        ))
+    }
+}
@@ -0,0 +1,70 @@
+//! This library provides Google Cloud Pub/Sub protocol bindings for CloudEvents
+//! using the [google-cloud-pubsub](https://docs.rs/google-cloud-pubsub) library.
+//!
+//! In binary mode, attributes and extensions are written as `ce-`-prefixed message
+//! attributes, following the [Pub/Sub Protocol Binding](https://github.com/google/knative-gcp/blob/main/docs/spec/pubsub-protocol-binding.md),
+//! while `datacontenttype` is mapped to the `content-type` message attribute.
+//!
+//! To publish Cloudevents:
+//!
+//! ```
+//! use cloudevents::Event;
+//! use cloudevents::binding::pubsub::{MessageRecord, PublisherExt};
+//! use google_cloud_pubsub::publisher::Publisher;
+//!
+//! # async fn publish(publisher: &Publisher, event: Event) -> Result<(), Box<dyn std::error::Error>> {
+//! let message_record = MessageRecord::from_event(event)?;
+//!
+//! let message_id = publisher.publish_record(message_record).await.get().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! To consume Cloudevents:
+//!
+//! ```
+//! use cloudevents::binding::pubsub::PubsubMessageExt;
+//! use google_cloud_pubsub::subscriber::ReceivedMessage;
+//!
+//! # async fn consume(message: ReceivedMessage) -> Result<(), Box<dyn std::error::Error>> {
+//! let event = message.message.to_event()?;
+//! println!("Received Event: {}", event);
+//! message.ack().await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! To consume Cloudevents from a push subscription, parse the HTTP request body
+//! with [`push_body_to_event`]:
+//!
+//! ```
+//! use cloudevents::binding::pubsub::push_body_to_event;
+//!
+//! # fn handle(body: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+//! let event = push_body_to_event(body)?;
+//! println!("Received Event: {}", event);
+//! # Ok(())
+//! # }
+//! ```
+
+#![deny(rustdoc::broken_intra_doc_links)]
+
+mod deserializer;
+mod push;
+mod serializer;
+
+pub use deserializer::message_to_event;
+pub use deserializer::PubsubMessageDeserializer;
+pub use deserializer::PubsubMessageExt;
+
+pub use push::push_body_to_event;
+pub use push::{PushMessage, PushRequest};
+
+pub use serializer::MessageRecord;
+pub use serializer::PublisherExt;
+
+pub(crate) static SPEC_VERSION_ATTRIBUTE: &str = "ce-specversion";
+
+pub(crate) fn attribute_prefix(name: &str) -> String {
+    super::header_prefix("ce-", name)
+}
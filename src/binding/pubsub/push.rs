@@ -0,0 +1,105 @@
+use super::PubsubMessageDeserializer;
+use crate::message::{MessageDeserializer, Result};
+use crate::Event;
+use base64::prelude::*;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Body of the HTTP request sent by a Pub/Sub push subscription.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PushRequest {
+    pub message: PushMessage,
+    #[serde(default)]
+    pub subscription: String,
+}
+
+/// Message wrapped in a [`PushRequest`], with its `data` encoded in base64.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PushMessage {
+    #[serde(default)]
+    pub attributes: HashMap<String, String>,
+    #[serde(default)]
+    pub data: Option<String>,
+    #[serde(default)]
+    pub message_id: String,
+    #[serde(default)]
+    pub publish_time: Option<DateTime<Utc>>,
+}
+
+impl PushRequest {
+    /// Generates [`Event`] from the wrapped [`PushMessage`].
+    pub fn to_event(self) -> Result<Event> {
+        let payload = match self.message.data {
+            Some(data) => BASE64_STANDARD.decode(data)?,
+            None => Vec::new(),
+        };
+        MessageDeserializer::into_event(PubsubMessageDeserializer::new(
+            self.message.attributes,
+            payload,
+        ))
+    }
+}
+
+/// Method to transform the body of a push subscription HTTP request to [`Event`].
+pub fn push_body_to_event(body: &[u8]) -> Result<Event> {
+    serde_json::from_slice::<PushRequest>(body)?.to_event()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use serde_json::json;
+
+    #[test]
+    fn test_push_binary() {
+        let expected = fixtures::v10::full_binary_json_data_string_extension();
+        let body = json!({
+            "message": {
+                "attributes": {
+                    "ce-specversion": "1.0",
+                    "ce-id": fixtures::id(),
+                    "ce-type": fixtures::ty(),
+                    "ce-source": fixtures::source(),
+                    "ce-subject": fixtures::subject(),
+                    "ce-time": fixtures::time().to_rfc3339(),
+                    "ce-stringex": "val",
+                    "ce-intex": "10",
+                    "ce-boolex": "true",
+                    "content-type": "application/json"
+                },
+                "data": BASE64_STANDARD.encode(fixtures::json_data_binary()),
+                "messageId": "2070443601311540",
+                "publishTime": "2021-02-26T19:13:55.749Z"
+            },
+            "subscription": "projects/myproject/subscriptions/mysubscription"
+        });
+
+        assert_eq!(
+            push_body_to_event(body.to_string().as_bytes()).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_push_structured() {
+        let expected = fixtures::v10::full_json_data_string_extension();
+        let body = json!({
+            "message": {
+                "attributes": {
+                    "content-type": "application/cloudevents+json"
+                },
+                "data": BASE64_STANDARD.encode(serde_json::to_vec(&expected).unwrap()),
+                "messageId": "2070443601311540"
+            },
+            "subscription": "projects/myproject/subscriptions/mysubscription"
+        });
+
+        assert_eq!(
+            push_body_to_event(body.to_string().as_bytes()).unwrap(),
+            expected
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use super::{attribute_prefix, SPEC_VERSION_ATTRIBUTE};
+use crate::binding::{CLOUDEVENTS_JSON_HEADER, CONTENT_TYPE};
+use crate::event::SpecVersion;
+use crate::message::{
+    BinaryDeserializer, BinarySerializer, MessageAttributeValue, Result, StructuredSerializer,
+};
+use crate::Event;
+use async_trait::async_trait;
+use google_cloud_googleapis::pubsub::v1::PubsubMessage;
+use google_cloud_pubsub::publisher::{Awaiter, Publisher};
+use std::collections::HashMap;
+
+/// This struct contains a serialized CloudEvent message in the Pub/Sub shape.
+/// Implements [`StructuredSerializer`] & [`BinarySerializer`] traits.
+///
+/// To instantiate a new `MessageRecord` from an [`Event`],
+/// look at [`Self::from_event`] or use [`StructuredDeserializer::deserialize_structured`](crate::message::StructuredDeserializer::deserialize_structured)
+/// or [`BinaryDeserializer::deserialize_binary`].
+pub struct MessageRecord {
+    pub(crate) attributes: HashMap<String, String>,
+    pub(crate) payload: Vec<u8>,
+}
+
+impl MessageRecord {
+    /// Create a new empty [`MessageRecord`]
+    pub fn new() -> Self {
+        MessageRecord {
+            attributes: HashMap::new(),
+            payload: Vec::new(),
+        }
+    }
+
+    /// Create a new [`MessageRecord`], filled with `event` serialized in binary mode.
+    pub fn from_event(event: Event) -> Result<Self> {
+        BinaryDeserializer::deserialize_binary(event, MessageRecord::new())
+    }
+
+    /// Get the message attributes, including the `ce-*` attributes.
+    pub fn attributes(&self) -> &HashMap<String, String> {
+        &self.attributes
+    }
+
+    /// Get the message data.
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+}
+
+impl Default for MessageRecord {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<MessageRecord> for PubsubMessage {
+    fn from(message_record: MessageRecord) -> Self {
+        PubsubMessage {
+            data: message_record.payload,
+            attributes: message_record.attributes,
+            ..Default::default()
+        }
+    }
+}
+
+impl BinarySerializer<MessageRecord> for MessageRecord {
+    fn set_spec_version(mut self, sv: SpecVersion) -> Result<Self> {
+        self.attributes
+            .insert(SPEC_VERSION_ATTRIBUTE.to_string(), sv.to_string());
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.attributes
+            .insert(attribute_prefix(name), value.to_string());
+        Ok(self)
+    }
+
+    fn set_extension(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.set_attribute(name, value)
+    }
+
+    fn end_with_data(mut self, bytes: Vec<u8>) -> Result<MessageRecord> {
+        self.payload = bytes;
+        Ok(self)
+    }
+
+    fn end(self) -> Result<MessageRecord> {
+        Ok(self)
+    }
+}
+
+impl StructuredSerializer<MessageRecord> for MessageRecord {
+    fn set_structured_event(mut self, bytes: Vec<u8>) -> Result<MessageRecord> {
+        self.attributes.insert(
+            CONTENT_TYPE.to_string(),
+            CLOUDEVENTS_JSON_HEADER.to_string(),
+        );
+        self.payload = bytes;
+        Ok(self)
+    }
+}
+
+/// Extension Trait for [`Publisher`] to publish a [`MessageRecord`].
+///
+/// This trait is sealed and cannot be implemented for types outside of this crate.
+#[async_trait]
+pub trait PublisherExt: private::Sealed {
+    /// Publish `message_record`, returning the [`Awaiter`] of the publish result.
+    async fn publish_record(&self, message_record: MessageRecord) -> Awaiter;
+}
+
+#[async_trait]
+impl PublisherExt for Publisher {
+    async fn publish_record(&self, message_record: MessageRecord) -> Awaiter {
+        self.publish(message_record.into()).await
+    }
+}
+
+mod private {
+    // Sealing the PublisherExt
+    pub trait Sealed {}
+    impl Sealed for google_cloud_pubsub::publisher::Publisher {}
+}
@@ -0,0 +1,159 @@
+//! Blocking counterparts of the Kafka transport, over the [`BaseProducer`] and the
+//! [`BaseConsumer`], which also implements [`PollSource`].
+
+use rdkafka_lib as rdkafka;
+
+use super::{BaseRecordExt, KafkaAck, MessageExt, MessageRecord, RecordMetadata};
+use crate::binding::{instrument, Limits};
+use crate::transport::blocking::{EventSink, EventSource, PollSource};
+use crate::transport::{Error, Result};
+use crate::Event;
+use rdkafka::consumer::{BaseConsumer, CommitMode, Consumer, ConsumerContext};
+use rdkafka::error::{KafkaError, RDKafkaErrorCode};
+use rdkafka::message::Message;
+use rdkafka::producer::{BaseProducer, BaseRecord, Producer};
+use rdkafka::util::Timeout;
+use rdkafka::{Offset, TopicPartitionList};
+use std::time::Duration;
+
+/// Interval at which a full producer queue is polled for room.
+const QUEUE_FULL_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Blocking [`EventSink`] producing the events in binary mode to a topic with a [`BaseProducer`].
+///
+/// Each send blocks until the producer queue has been flushed. Delivery failures are reported
+/// to the [`ProducerContext`](rdkafka::producer::ProducerContext) of the producer.
+pub struct KafkaSink {
+    producer: BaseProducer,
+    topic: String,
+}
+
+impl KafkaSink {
+    /// Create a new [`KafkaSink`], producing the events to `topic`.
+    pub fn new(producer: BaseProducer, topic: impl Into<String>) -> Self {
+        KafkaSink {
+            producer,
+            topic: topic.into(),
+        }
+    }
+
+    fn produce(&self, message_record: &MessageRecord) -> Result<()> {
+        let mut record =
+            BaseRecord::<(), Vec<u8>>::to(&self.topic).message_record(message_record)?;
+        loop {
+            match self.producer.send(record) {
+                Ok(()) => return Ok(()),
+                Err((KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull), r)) => {
+                    self.producer.poll(QUEUE_FULL_POLL_INTERVAL);
+                    record = r;
+                }
+                Err((e, _)) => return Err(Error::transport(e)),
+            }
+        }
+    }
+}
+
+impl EventSink for KafkaSink {
+    fn send(&self, event: Event) -> Result<()> {
+        let message_record = instrument::serialize("kafka", event, MessageRecord::from_event)?;
+        self.produce(&message_record)?;
+        self.producer
+            .flush(Timeout::Never)
+            .map_err(Error::transport)
+    }
+
+    /// Produce all the events, then flush the producer queue once.
+    fn send_all(&self, events: Vec<Event>) -> Result<()> {
+        for event in events {
+            self.produce(&MessageRecord::from_event(event)?)?;
+        }
+        self.producer
+            .flush(Timeout::Never)
+            .map_err(Error::transport)
+    }
+}
+
+/// Blocking [`EventSource`] receiving the events of a [`BaseConsumer`], acknowledging them by
+/// committing their offset.
+///
+/// The offsets of the records which can't be decoded are committed before returning the error,
+/// so they aren't consumed again after a restart.
+pub struct KafkaSource {
+    consumer: BaseConsumer,
+    record_metadata: bool,
+    limits: Limits,
+}
+
+impl KafkaSource {
+    /// Create a new [`KafkaSource`] from a subscribed `consumer`.
+    pub fn new(consumer: BaseConsumer) -> Self {
+        KafkaSource {
+            consumer,
+            record_metadata: false,
+            limits: Limits::default(),
+        }
+    }
+
+    /// Carry the topic, partition, offset and timestamp of the consumed records in the
+    /// extensions of the events, see [`RecordMetadata`](super::RecordMetadata). Disabled by default.
+    pub fn record_metadata(mut self, enabled: bool) -> Self {
+        self.record_metadata = enabled;
+        self
+    }
+
+    /// Reject the consumed records exceeding `limits`, failing with
+    /// [`message::Error`](crate::message::Error) errors. No limits are set by default.
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+}
+
+impl EventSource for KafkaSource {
+    type Ack = KafkaAck;
+
+    fn receive(&mut self) -> Option<Result<(Event, KafkaAck)>> {
+        let message = match self.consumer.poll(Timeout::Never)? {
+            Ok(message) => message,
+            Err(e) => return Some(Err(Error::transport(e))),
+        };
+        let ack = KafkaAck {
+            topic: message.topic().to_string(),
+            partition: message.partition(),
+            offset: message.offset(),
+        };
+        let received = instrument::receive("kafka", || {
+            message.to_event_with_limits(self.limits).map(|mut event| {
+                if self.record_metadata {
+                    RecordMetadata::from_message(&message).set_extensions(&mut event);
+                }
+                event
+            })
+        });
+        match received {
+            Ok(event) => Some(Ok((event, ack))),
+            Err(e) => Some(self.ack(ack).and(Err(Error::from(e)))),
+        }
+    }
+
+    fn ack(&mut self, ack: KafkaAck) -> Result<()> {
+        let mut offsets = TopicPartitionList::new();
+        offsets
+            .add_partition_offset(&ack.topic, ack.partition, Offset::Offset(ack.offset + 1))
+            .map_err(Error::transport)?;
+        self.consumer
+            .commit(&offsets, CommitMode::Sync)
+            .map_err(Error::transport)
+    }
+}
+
+/// Receive the events of the subscribed topics, waiting for at most `timeout`. The offsets are
+/// committed according to the consumer configuration, e.g. `enable.auto.commit`.
+impl<C: ConsumerContext> PollSource for BaseConsumer<C> {
+    fn poll_event(&mut self, timeout: Duration) -> Option<Result<Event>> {
+        Some(match self.poll(timeout)? {
+            Ok(message) => instrument::receive("kafka", || message.to_event()).map_err(Error::from),
+            Err(e) => Err(Error::transport(e)),
+        })
+    }
+}
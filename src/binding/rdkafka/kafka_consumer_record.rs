@@ -1,30 +1,53 @@
 use rdkafka_lib as rdkafka;
 
-use crate::binding::{kafka::SPEC_VERSION_HEADER, CLOUDEVENTS_JSON_HEADER, CONTENT_TYPE};
-use crate::event::SpecVersion;
+use super::RecordMetadata;
+use crate::binding::{
+    attribute_name, instrument, is_media_type, kafka::SPEC_VERSION_HEADER, ExtensionTypes, Limits,
+    CLOUDEVENTS_JSON_HEADER, CONTENT_TYPE,
+};
+use crate::event::{DataRef, EventRef, SpecVersion};
 use crate::message::{
     BinaryDeserializer, BinarySerializer, Encoding, MessageAttributeValue, MessageDeserializer,
     Result, StructuredDeserializer, StructuredSerializer,
 };
 use crate::{message, Event};
 use rdkafka::message::{BorrowedMessage, Headers, Message, OwnedMessage};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::str;
 
+/// How [`ConsumerRecordDeserializer`] handles the header values which aren't valid UTF-8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InvalidUtf8 {
+    /// Fail with [`message::Error::InvalidHeaderValue`].
+    #[default]
+    Error,
+    /// Replace the invalid sequences with `U+FFFD REPLACEMENT CHARACTER`.
+    Lossy,
+    /// Keep the value of the extensions as binary, which is represented as a base64 string.
+    /// The spec attributes still fail with [`message::Error::InvalidHeaderValue`].
+    Binary,
+}
+
 /// Wrapper for [`Message`] that implements [`MessageDeserializer`] trait.
 pub struct ConsumerRecordDeserializer {
     pub(crate) headers: HashMap<String, Vec<u8>>,
     pub(crate) payload: Option<Vec<u8>>,
+    invalid_utf8: InvalidUtf8,
+    limits: Limits,
+    extension_types: ExtensionTypes,
 }
 
 impl ConsumerRecordDeserializer {
     fn get_kafka_headers(message: &impl Message) -> Result<HashMap<String, Vec<u8>>> {
         match message.headers() {
             None => Err(crate::message::Error::WrongEncoding {}),
+            // Headers with a null value are skipped, and the names are lowercased so they
+            // match regardless of how the producer capitalized them
             Some(headers) => Ok(headers
                 .iter()
-                .map(|h| (h.key.to_string(), Vec::from(h.value.unwrap())))
+                .filter_map(|h| Some((h.key.to_ascii_lowercase(), Vec::from(h.value?))))
                 .collect()),
         }
     }
@@ -33,8 +56,69 @@ impl ConsumerRecordDeserializer {
         Ok(ConsumerRecordDeserializer {
             headers: Self::get_kafka_headers(message)?,
             payload: message.payload().map(Vec::from),
+            invalid_utf8: InvalidUtf8::default(),
+            limits: Limits::default(),
+            extension_types: ExtensionTypes::default(),
         })
     }
+
+    /// Set how the header values which aren't valid UTF-8 are handled, by default failing
+    /// with [`message::Error::InvalidHeaderValue`].
+    ///
+    /// ```
+    /// # use rdkafka_lib as rdkafka;
+    /// use cloudevents::binding::rdkafka::{ConsumerRecordDeserializer, InvalidUtf8};
+    /// use cloudevents::message::MessageDeserializer;
+    /// use rdkafka::message::OwnedMessage;
+    ///
+    /// fn to_event(message: &OwnedMessage) -> cloudevents::message::Result<cloudevents::Event> {
+    ///     ConsumerRecordDeserializer::new(message)?
+    ///         .invalid_utf8(InvalidUtf8::Lossy)
+    ///         .into_event()
+    /// }
+    /// ```
+    pub fn invalid_utf8(mut self, invalid_utf8: InvalidUtf8) -> Self {
+        self.invalid_utf8 = invalid_utf8;
+        self
+    }
+
+    /// Set the [`Limits`] on the headers of the binary mode records, which are rejected before
+    /// their attributes are read, and on the length of the structured mode records, which are
+    /// rejected before being parsed. No limits are set by default.
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Set the [`ExtensionTypes`] of the extensions of the binary mode records, which are
+    /// otherwise read as strings.
+    pub fn extension_types(mut self, extension_types: ExtensionTypes) -> Self {
+        self.extension_types = extension_types;
+        self
+    }
+
+    fn header_value(
+        &self,
+        name: &str,
+        value: Vec<u8>,
+        is_extension: bool,
+    ) -> Result<MessageAttributeValue> {
+        match String::from_utf8(value) {
+            Ok(s) => Ok(MessageAttributeValue::String(s)),
+            Err(e) => match self.invalid_utf8 {
+                InvalidUtf8::Lossy => Ok(MessageAttributeValue::String(
+                    String::from_utf8_lossy(e.as_bytes()).into_owned(),
+                )),
+                InvalidUtf8::Binary if is_extension => {
+                    Ok(MessageAttributeValue::Binary(e.into_bytes()))
+                }
+                _ => Err(message::Error::InvalidHeaderValue {
+                    name: name.to_string(),
+                    source: Box::new(e.utf8_error()),
+                }),
+            },
+        }
+    }
 }
 
 impl BinaryDeserializer for ConsumerRecordDeserializer {
@@ -45,57 +129,52 @@ impl BinaryDeserializer for ConsumerRecordDeserializer {
 
         let spec_version = SpecVersion::try_from(
             str::from_utf8(&self.headers.remove(SPEC_VERSION_HEADER).unwrap()).map_err(|e| {
-                crate::message::Error::Other {
+                message::Error::InvalidHeaderValue {
+                    name: SPEC_VERSION_HEADER.to_string(),
                     source: Box::new(e),
                 }
             })?,
-        )?;
+        )
+        .map_err(|e| e.in_attribute(SPEC_VERSION_HEADER))?;
 
         let attributes = spec_version.attribute_names();
 
+        self.limits
+            .check_attributes(self.headers.iter().filter_map(
+                |(hn, hv)| match hn.strip_prefix("ce_") {
+                    Some(name) => Some((name, hv.len(), !attributes.contains(&name))),
+                    None if hn == CONTENT_TYPE => Some(("datacontenttype", hv.len(), false)),
+                    None => None,
+                },
+            ))?;
+
         visitor = visitor.set_spec_version(spec_version)?;
 
         if let Some(hv) = self.headers.remove(CONTENT_TYPE) {
             visitor = visitor.set_attribute(
                 "datacontenttype",
-                MessageAttributeValue::String(String::from_utf8(hv).map_err(|e| {
-                    crate::message::Error::Other {
-                        source: Box::new(e),
-                    }
-                })?),
+                self.header_value(CONTENT_TYPE, hv, false)?,
             )?
         }
 
-        for (hn, hv) in self
-            .headers
+        let headers = std::mem::take(&mut self.headers);
+        for (hn, hv) in headers
             .into_iter()
             .filter(|(hn, _)| SPEC_VERSION_HEADER != *hn && hn.starts_with("ce_"))
         {
             let name = &hn["ce_".len()..];
 
             if attributes.contains(&name) {
-                visitor = visitor.set_attribute(
-                    name,
-                    MessageAttributeValue::String(String::from_utf8(hv).map_err(|e| {
-                        crate::message::Error::Other {
-                            source: Box::new(e),
-                        }
-                    })?),
-                )?
+                visitor = visitor.set_attribute(name, self.header_value(&hn, hv, false)?)?
             } else {
-                visitor = visitor.set_extension(
-                    name,
-                    MessageAttributeValue::String(String::from_utf8(hv).map_err(|e| {
-                        crate::message::Error::Other {
-                            source: Box::new(e),
-                        }
-                    })?),
-                )?
+                let value = self.header_value(&hn, hv, true)?;
+                let value = self.extension_types.convert(name, value.into())?;
+                visitor = visitor.set_extension(name, value.into())?
             }
         }
 
-        if self.payload.is_some() {
-            visitor.end_with_data(self.payload.unwrap())
+        if let Some(payload) = self.payload {
+            visitor.end_with_data(payload)
         } else {
             visitor.end()
         }
@@ -107,7 +186,9 @@ impl StructuredDeserializer for ConsumerRecordDeserializer {
         if self.encoding() != Encoding::STRUCTURED {
             return Err(message::Error::WrongEncoding {});
         }
-        visitor.set_structured_event(self.payload.unwrap())
+        let payload = self.payload.unwrap_or_default();
+        self.limits.check_structured_len(payload.len())?;
+        visitor.set_structured_event(payload)
     }
 }
 
@@ -115,10 +196,8 @@ impl MessageDeserializer for ConsumerRecordDeserializer {
     fn encoding(&self) -> Encoding {
         match (
             self.headers
-                .get("content-type")
-                .and_then(|s| String::from_utf8(s.to_vec()).ok())
-                .map(|s| s.starts_with(CLOUDEVENTS_JSON_HEADER))
-                .unwrap_or(false),
+                .get(CONTENT_TYPE)
+                .is_some_and(|v| is_media_type(v, CLOUDEVENTS_JSON_HEADER)),
             self.headers.get(SPEC_VERSION_HEADER),
         ) {
             (true, _) => Encoding::STRUCTURED,
@@ -130,7 +209,71 @@ impl MessageDeserializer for ConsumerRecordDeserializer {
 
 /// Method to transform a [`Message`] to [`Event`].
 pub fn record_to_event(msg: &impl Message) -> Result<Event> {
-    MessageDeserializer::into_event(ConsumerRecordDeserializer::new(msg)?)
+    instrument::deserialize("kafka", || {
+        MessageDeserializer::into_event(ConsumerRecordDeserializer::new(msg)?)
+    })
+}
+
+/// Method to transform a [`Message`] to an [`EventRef`] borrowing its headers and payload,
+/// without copying the attributes nor the data.
+pub fn record_to_event_ref<M: Message>(msg: &M) -> Result<EventRef<'_>> {
+    let headers = msg.headers().ok_or(message::Error::WrongEncoding {})?;
+    let is_structured = headers.iter().any(|h| {
+        h.key.eq_ignore_ascii_case(CONTENT_TYPE)
+            && h.value
+                .is_some_and(|v| is_media_type(v, CLOUDEVENTS_JSON_HEADER))
+    });
+    if is_structured {
+        return Ok(EventRef::from_json_slice(
+            msg.payload().unwrap_or_default(),
+        )?);
+    }
+    if !headers
+        .iter()
+        .any(|h| h.key.eq_ignore_ascii_case(SPEC_VERSION_HEADER))
+    {
+        return Err(message::Error::WrongEncoding {});
+    }
+    let mut attributes = Vec::new();
+    for header in headers.iter() {
+        let name = match attribute_name("ce_", header.key) {
+            Some(name) => name,
+            None if header.key.eq_ignore_ascii_case(CONTENT_TYPE) => {
+                Cow::Borrowed("datacontenttype")
+            }
+            None => continue,
+        };
+        let value = str::from_utf8(header.value.unwrap_or_default()).map_err(|e| {
+            message::Error::InvalidHeaderValue {
+                name: header.key.to_string(),
+                source: Box::new(e),
+            }
+        })?;
+        attributes.push((name, value));
+    }
+    let data = msg.payload().map(|p| DataRef::Binary(Cow::Borrowed(p)));
+    EventRef::from_attributes(attributes, data)
+}
+
+/// Method to transform a [`Message`] to [`Event`], rejecting the messages exceeding `limits`.
+pub fn record_to_event_with_limits(msg: &impl Message, limits: Limits) -> Result<Event> {
+    instrument::deserialize("kafka", || {
+        let deserializer = ConsumerRecordDeserializer::new(msg)?.limits(limits);
+        let is_structured = deserializer.encoding() == Encoding::STRUCTURED;
+        let event = MessageDeserializer::into_event(deserializer)?;
+        if is_structured {
+            limits.check(&event)?;
+        }
+        Ok(event)
+    })
+}
+
+/// Method to transform a [`Message`] to [`Event`], carrying the [`RecordMetadata`] of the
+/// message in extensions.
+pub fn record_to_event_with_metadata(msg: &impl Message) -> Result<Event> {
+    let mut event = record_to_event(msg)?;
+    RecordMetadata::from_message(msg).set_extensions(&mut event);
+    Ok(event)
 }
 
 /// Extension Trait for [`Message`] which acts as a wrapper for the function [`record_to_event()`].
@@ -139,18 +282,53 @@ pub fn record_to_event(msg: &impl Message) -> Result<Event> {
 pub trait MessageExt: private::Sealed {
     /// Generates [`Event`] from [`BorrowedMessage`].
     fn to_event(&self) -> Result<Event>;
+
+    /// Generates [`Event`] carrying the topic, partition, offset and timestamp of this message
+    /// in extensions, see [`record_to_event_with_metadata()`].
+    fn to_event_with_metadata(&self) -> Result<Event>;
+
+    /// Generates [`Event`], failing if this message exceeds `limits`, see
+    /// [`record_to_event_with_limits()`].
+    fn to_event_with_limits(&self, limits: Limits) -> Result<Event>;
+
+    /// Generates an [`EventRef`] borrowing this message, see [`record_to_event_ref()`].
+    fn to_event_ref(&self) -> Result<EventRef<'_>>;
 }
 
 impl MessageExt for BorrowedMessage<'_> {
     fn to_event(&self) -> Result<Event> {
         record_to_event(self)
     }
+
+    fn to_event_with_metadata(&self) -> Result<Event> {
+        record_to_event_with_metadata(self)
+    }
+
+    fn to_event_with_limits(&self, limits: Limits) -> Result<Event> {
+        record_to_event_with_limits(self, limits)
+    }
+
+    fn to_event_ref(&self) -> Result<EventRef<'_>> {
+        record_to_event_ref(self)
+    }
 }
 
 impl MessageExt for OwnedMessage {
     fn to_event(&self) -> Result<Event> {
         record_to_event(self)
     }
+
+    fn to_event_with_metadata(&self) -> Result<Event> {
+        record_to_event_with_metadata(self)
+    }
+
+    fn to_event_with_limits(&self, limits: Limits) -> Result<Event> {
+        record_to_event_with_limits(self, limits)
+    }
+
+    fn to_event_ref(&self) -> Result<EventRef<'_>> {
+        record_to_event_ref(self)
+    }
 }
 
 mod private {
@@ -168,6 +346,8 @@ mod tests {
 
     use super::*;
     use crate::binding::rdkafka::kafka_producer_record::MessageRecord;
+    use crate::event::{WithSecrets, SENSITIVE_EXTENSION_MASK};
+    use crate::message::BinaryDeserializer;
 
     use crate::test::fixtures;
     use crate::{EventBuilder, EventBuilderV10};
@@ -202,7 +382,11 @@ mod tests {
             Some(message_record.headers),
         );
 
-        assert_eq!(owned_message.to_event().unwrap(), expected)
+        assert_eq!(owned_message.to_event().unwrap(), expected);
+        assert_eq!(
+            owned_message.to_event_ref().unwrap().into_event().unwrap(),
+            expected
+        )
     }
 
     #[test]
@@ -231,4 +415,180 @@ mod tests {
 
         assert_eq!(owned_message.to_event().unwrap(), expected)
     }
+
+    #[test]
+    fn test_sensitive_extension_record() {
+        let mut event = fixtures::v10::minimal_string_extension();
+        event.set_extension("authtoken", "s3cr3t");
+        event.mark_sensitive_extension("authtoken");
+        let header = |record: &MessageRecord| {
+            record
+                .headers
+                .iter()
+                .find(|header| header.key == "ce_authtoken")
+                .and_then(|header| header.value)
+                .map(<[u8]>::to_vec)
+        };
+
+        let masked = MessageRecord::from_event(event.clone()).unwrap();
+        assert_eq!(header(&masked), Some(SENSITIVE_EXTENSION_MASK.into()));
+
+        let record =
+            BinaryDeserializer::deserialize_binary(WithSecrets(event), MessageRecord::new())
+                .unwrap();
+        assert_eq!(header(&record), Some(b"s3cr3t".to_vec()));
+    }
+
+    #[test]
+    fn test_invalid_utf8_header() {
+        let message_record =
+            MessageRecord::from_event(fixtures::v10::minimal_string_extension()).unwrap();
+        let headers = message_record.headers.insert(rdkafka::message::Header {
+            key: "ce_rogue",
+            value: Some(&b"caf\xe9"[..]),
+        });
+        let owned_message = OwnedMessage::new(
+            message_record.payload,
+            None,
+            String::from("test topic"),
+            rdkafka::message::Timestamp::NotAvailable,
+            10,
+            10,
+            Some(headers),
+        );
+        let to_event = |invalid_utf8| {
+            MessageDeserializer::into_event(
+                ConsumerRecordDeserializer::new(&owned_message)
+                    .unwrap()
+                    .invalid_utf8(invalid_utf8),
+            )
+        };
+
+        assert!(matches!(
+            to_event(InvalidUtf8::Error),
+            Err(message::Error::InvalidHeaderValue { name, .. }) if name == "ce_rogue"
+        ));
+        assert_eq!(
+            to_event(InvalidUtf8::Lossy).unwrap().extension("rogue"),
+            Some(&"caf\u{fffd}".into())
+        );
+        assert_eq!(
+            to_event(InvalidUtf8::Binary).unwrap().extension("rogue"),
+            Some(&"Y2Fm6Q==".into())
+        );
+    }
+
+    #[test]
+    fn test_mixed_case_headers() {
+        let expected = fixtures::v10::minimal_string_extension();
+
+        let headers = [
+            ("CE_SpecVersion", "1.0".to_string()),
+            ("Ce_Id", fixtures::id()),
+            ("CE_TYPE", fixtures::ty()),
+            ("ce_Source", fixtures::source()),
+            ("CE_SomeInt", "10".to_string()),
+        ];
+        let headers = headers.iter().fold(
+            rdkafka::message::OwnedHeaders::new(),
+            |headers, (key, value)| {
+                headers.insert(rdkafka::message::Header {
+                    key,
+                    value: Some(value.as_str()),
+                })
+            },
+        );
+        let owned_message = OwnedMessage::new(
+            None,
+            None,
+            String::from("test topic"),
+            rdkafka::message::Timestamp::NotAvailable,
+            10,
+            10,
+            Some(headers),
+        );
+
+        assert_eq!(owned_message.to_event().unwrap(), expected);
+        assert_eq!(
+            owned_message.to_event_ref().unwrap().into_event().unwrap(),
+            expected
+        )
+    }
+
+    #[test]
+    fn test_limits() {
+        let message = |event| {
+            let record = MessageRecord::from_event(event).unwrap();
+            OwnedMessage::new(
+                record.payload,
+                None,
+                String::from("test topic"),
+                rdkafka::message::Timestamp::NotAvailable,
+                10,
+                10,
+                Some(record.headers),
+            )
+        };
+        let binary = message(fixtures::v10::full_binary_json_data_string_extension());
+        let limits = Limits::new().max_extensions(3).max_value_len(64);
+
+        assert!(binary.to_event_with_limits(limits).is_ok());
+        assert!(matches!(
+            binary.to_event_with_limits(limits.max_extensions(1)),
+            Err(message::Error::TooManyExtensions { max: 1 })
+        ));
+        assert!(matches!(
+            binary.to_event_with_limits(limits.max_attributes_len(32)),
+            Err(message::Error::AttributesTooLong { max: 32 })
+        ));
+
+        let headers = rdkafka::message::OwnedHeaders::new().insert(rdkafka::message::Header {
+            key: "content-type",
+            value: Some("application/cloudevents+json"),
+        });
+        let structured = OwnedMessage::new(
+            Some(serde_json::to_vec(&fixtures::v10::full_json_data()).unwrap()),
+            None,
+            String::from("test topic"),
+            rdkafka::message::Timestamp::NotAvailable,
+            10,
+            10,
+            Some(headers),
+        );
+        assert!(structured.to_event_with_limits(limits).is_ok());
+        assert!(matches!(
+            structured.to_event_with_limits(limits.max_value_len(4)),
+            Err(message::Error::AttributeValueTooLong { max: 4, .. })
+        ));
+        assert!(matches!(
+            structured.to_event_with_limits(limits.max_structured_len(16)),
+            Err(message::Error::StructuredMessageTooLong { max: 16 })
+        ));
+    }
+
+    #[test]
+    fn test_extension_types() {
+        let record = MessageRecord::from_event(fixtures::v10::minimal_string_extension()).unwrap();
+        let owned_message = OwnedMessage::new(
+            record.payload,
+            None,
+            String::from("test topic"),
+            rdkafka::message::Timestamp::NotAvailable,
+            10,
+            10,
+            Some(record.headers),
+        );
+
+        let event = MessageDeserializer::into_event(
+            ConsumerRecordDeserializer::new(&owned_message)
+                .unwrap()
+                .extension_types(ExtensionTypes::new().infer(true)),
+        )
+        .unwrap();
+        assert_eq!(event.extension("someint"), Some(&10.into()));
+        assert_eq!(
+            owned_message.to_event().unwrap(),
+            fixtures::v10::minimal_string_extension()
+        );
+    }
 }
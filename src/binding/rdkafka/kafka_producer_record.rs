@@ -1,7 +1,8 @@
 use rdkafka_lib as rdkafka;
 
 use crate::binding::{
-    kafka::{header_prefix, SPEC_VERSION_HEADER},
+    instrument,
+    kafka::{header_name, SPEC_VERSION_HEADER},
     CLOUDEVENTS_JSON_HEADER, CONTENT_TYPE,
 };
 use crate::event::SpecVersion;
@@ -11,6 +12,7 @@ use crate::message::{
 use crate::Event;
 use rdkafka::message::{Header, OwnedHeaders, ToBytes};
 use rdkafka::producer::{BaseRecord, FutureRecord};
+use std::borrow::Cow;
 
 /// This struct contains a serialized CloudEvent message in the Kafka shape.
 /// Implements [`StructuredSerializer`] & [`BinarySerializer`] traits.
@@ -34,7 +36,9 @@ impl MessageRecord {
 
     /// Create a new [`MessageRecord`], filled with `event` serialized in binary mode.
     pub fn from_event(event: Event) -> Result<Self> {
-        BinaryDeserializer::deserialize_binary(event, MessageRecord::new())
+        instrument::serialize("kafka", event, |event| {
+            BinaryDeserializer::deserialize_binary(event, MessageRecord::new())
+        })
     }
 }
 
@@ -46,20 +50,24 @@ impl Default for MessageRecord {
 
 impl BinarySerializer<MessageRecord> for MessageRecord {
     fn set_spec_version(mut self, sv: SpecVersion) -> Result<Self> {
-        let v = sv.to_string();
         let header = Header {
             key: SPEC_VERSION_HEADER,
-            value: Some(&v),
+            value: Some(sv.as_str()),
         };
         self.headers = self.headers.insert(header);
         Ok(self)
     }
 
     fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
-        let v = value.to_string();
+        // The headers are copied by librdkafka, so borrow the string values
+        let key = header_name(name);
+        let v = match &value {
+            MessageAttributeValue::String(s) => Cow::Borrowed(s.as_str()),
+            v => Cow::Owned(v.to_string()),
+        };
         let header = Header {
-            key: &header_prefix(name),
-            value: Some(&v),
+            key: &key,
+            value: Some(v.as_ref()),
         };
         self.headers = self.headers.insert(header);
         Ok(self)
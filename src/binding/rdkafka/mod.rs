@@ -48,16 +48,35 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! [`MessageExt::to_event_with_metadata`] and [`KafkaSource::record_metadata`] attach the topic,
+//! partition, offset and timestamp of the consumed records to the events, see [`RecordMetadata`].
 
 #![deny(rustdoc::broken_intra_doc_links)]
 
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+#[cfg(feature = "blocking")]
+pub mod blocking;
 mod kafka_consumer_record;
 mod kafka_producer_record;
+mod record_metadata;
+mod transport;
 
 pub use kafka_consumer_record::record_to_event;
+pub use kafka_consumer_record::record_to_event_ref;
+pub use kafka_consumer_record::record_to_event_with_limits;
+pub use kafka_consumer_record::record_to_event_with_metadata;
 pub use kafka_consumer_record::ConsumerRecordDeserializer;
+pub use kafka_consumer_record::InvalidUtf8;
 pub use kafka_consumer_record::MessageExt;
 
 pub use kafka_producer_record::BaseRecordExt;
 pub use kafka_producer_record::FutureRecordExt;
 pub use kafka_producer_record::MessageRecord;
+
+pub use record_metadata::{
+    RecordMetadata, KAFKA_OFFSET_EXTENSION, KAFKA_PARTITION_EXTENSION, KAFKA_TIMESTAMP_EXTENSION,
+    KAFKA_TOPIC_EXTENSION,
+};
+
+pub use transport::{KafkaAck, KafkaSink, KafkaSource};
@@ -0,0 +1,151 @@
+use rdkafka_lib as rdkafka;
+
+use crate::Event;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rdkafka::message::Message;
+use std::convert::TryFrom;
+
+/// Extension holding the topic of the consumed record.
+pub const KAFKA_TOPIC_EXTENSION: &str = "kafkatopic";
+/// Extension holding the partition of the consumed record.
+pub const KAFKA_PARTITION_EXTENSION: &str = "kafkapartition";
+/// Extension holding the offset of the consumed record.
+pub const KAFKA_OFFSET_EXTENSION: &str = "kafkaoffset";
+/// Extension holding the timestamp of the consumed record, when available.
+pub const KAFKA_TIMESTAMP_EXTENSION: &str = "kafkatimestamp";
+
+/// Position and timestamp of a consumed Kafka record, carried by an [`Event`] in the
+/// [`kafkatopic`](KAFKA_TOPIC_EXTENSION), [`kafkapartition`](KAFKA_PARTITION_EXTENSION),
+/// [`kafkaoffset`](KAFKA_OFFSET_EXTENSION) and [`kafkatimestamp`](KAFKA_TIMESTAMP_EXTENSION)
+/// extensions, so downstream processors can track the consumed offsets without the rdkafka
+/// message.
+///
+/// ```
+/// # use rdkafka_lib as rdkafka;
+/// use cloudevents::binding::rdkafka::{MessageExt, RecordMetadata};
+/// use rdkafka::message::BorrowedMessage;
+///
+/// fn consume(message: &BorrowedMessage<'_>) -> cloudevents::message::Result<()> {
+///     let event = message.to_event_with_metadata()?;
+///     let metadata = RecordMetadata::from_event(&event).unwrap();
+///     println!("{}/{}@{}", metadata.topic, metadata.partition, metadata.offset);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordMetadata {
+    pub topic: String,
+    pub partition: i32,
+    pub offset: i64,
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+impl RecordMetadata {
+    /// Read the metadata of `message`.
+    pub fn from_message(message: &impl Message) -> Self {
+        RecordMetadata {
+            topic: message.topic().to_string(),
+            partition: message.partition(),
+            offset: message.offset(),
+            timestamp: message
+                .timestamp()
+                .to_millis()
+                .and_then(DateTime::from_timestamp_millis),
+        }
+    }
+
+    /// Read the metadata from the extensions of `event`, returning `None` if the topic, the
+    /// partition or the offset is missing or invalid.
+    pub fn from_event(event: &Event) -> Option<Self> {
+        Some(RecordMetadata {
+            topic: event.extension_as(KAFKA_TOPIC_EXTENSION).ok()??,
+            partition: i32::try_from(
+                event
+                    .extension_as::<i64>(KAFKA_PARTITION_EXTENSION)
+                    .ok()??,
+            )
+            .ok()?,
+            offset: event.extension_as(KAFKA_OFFSET_EXTENSION).ok()??,
+            timestamp: event.extension_as(KAFKA_TIMESTAMP_EXTENSION).ok().flatten(),
+        })
+    }
+
+    /// Set the extensions of `event`, replacing the metadata it may already carry.
+    pub fn set_extensions(&self, event: &mut Event) {
+        event.set_extension(KAFKA_TOPIC_EXTENSION, self.topic.as_str());
+        event.set_extension(KAFKA_PARTITION_EXTENSION, i64::from(self.partition));
+        event.set_extension(KAFKA_OFFSET_EXTENSION, self.offset);
+        match self.timestamp {
+            Some(timestamp) => event.set_extension(
+                KAFKA_TIMESTAMP_EXTENSION,
+                timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+            ),
+            None => {
+                event.remove_extension(KAFKA_TIMESTAMP_EXTENSION);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::rdkafka::{MessageExt, MessageRecord};
+    use crate::test::fixtures;
+    use rdkafka::message::{OwnedMessage, Timestamp};
+
+    fn message(timestamp: Timestamp) -> OwnedMessage {
+        let record = MessageRecord::from_event(fixtures::v10::minimal()).unwrap();
+        OwnedMessage::new(
+            record.payload,
+            None,
+            "orders".to_string(),
+            timestamp,
+            3,
+            42,
+            Some(record.headers),
+        )
+    }
+
+    #[test]
+    fn to_event_with_metadata() {
+        let message = message(Timestamp::CreateTime(1_584_615_600_123));
+
+        let event = message.to_event_with_metadata().unwrap();
+
+        assert_eq!(
+            event.extension(KAFKA_TOPIC_EXTENSION).unwrap().to_string(),
+            "orders"
+        );
+        assert_eq!(
+            event
+                .extension(KAFKA_TIMESTAMP_EXTENSION)
+                .unwrap()
+                .to_string(),
+            "2020-03-19T11:00:00.123Z"
+        );
+        assert_eq!(
+            RecordMetadata::from_event(&event),
+            Some(RecordMetadata {
+                topic: "orders".to_string(),
+                partition: 3,
+                offset: 42,
+                timestamp: DateTime::from_timestamp_millis(1_584_615_600_123),
+            })
+        );
+        assert_eq!(message.to_event().unwrap(), fixtures::v10::minimal());
+    }
+
+    #[test]
+    fn without_timestamp() {
+        let mut event = fixtures::v10::minimal();
+        event.set_extension(KAFKA_TIMESTAMP_EXTENSION, "2020-03-19T11:00:00Z");
+
+        let metadata = RecordMetadata::from_message(&message(Timestamp::NotAvailable));
+        metadata.set_extensions(&mut event);
+
+        assert!(event.extension(KAFKA_TIMESTAMP_EXTENSION).is_none());
+        assert_eq!(RecordMetadata::from_event(&event), Some(metadata));
+        assert_eq!(RecordMetadata::from_event(&fixtures::v10::minimal()), None);
+    }
+}
@@ -0,0 +1,193 @@
+use rdkafka_lib as rdkafka;
+
+use super::{FutureRecordExt, MessageExt, MessageRecord, RecordMetadata};
+use crate::binding::{instrument, Limits};
+use crate::transport::{Error, EventSink, EventSource, Result};
+use crate::Event;
+use async_trait::async_trait;
+use rdkafka::consumer::{CommitMode, Consumer, StreamConsumer};
+use rdkafka::message::Message;
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::util::Timeout;
+use rdkafka::{Offset, TopicPartitionList};
+
+/// [`EventSink`] producing the events in binary mode to a topic.
+pub struct KafkaSink {
+    producer: FutureProducer,
+    topic: String,
+}
+
+impl KafkaSink {
+    /// Create a new [`KafkaSink`], producing the events to `topic`.
+    pub fn new(producer: FutureProducer, topic: impl Into<String>) -> Self {
+        KafkaSink {
+            producer,
+            topic: topic.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for KafkaSink {
+    async fn send(&self, event: Event) -> Result<()> {
+        instrument::send("kafka", event, |event| async move {
+            let message_record = MessageRecord::from_event(event)?;
+            self.producer
+                .send(
+                    FutureRecord::<(), Vec<u8>>::to(&self.topic).message_record(&message_record),
+                    Timeout::Never,
+                )
+                .await
+                .map(|_| ())
+                .map_err(|(e, _)| Error::transport(e))
+        })
+        .await
+    }
+}
+
+/// Position of a received message, used to commit its offset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KafkaAck {
+    pub topic: String,
+    pub partition: i32,
+    pub offset: i64,
+}
+
+/// [`EventSource`] receiving the events of a [`StreamConsumer`], acknowledging them by committing their offset.
+///
+/// The offsets of the records which can't be decoded are committed before returning the error,
+/// so they aren't consumed again after a restart.
+pub struct KafkaSource {
+    consumer: StreamConsumer,
+    record_metadata: bool,
+    limits: Limits,
+}
+
+impl KafkaSource {
+    /// Create a new [`KafkaSource`] from a subscribed `consumer`.
+    pub fn new(consumer: StreamConsumer) -> Self {
+        KafkaSource {
+            consumer,
+            record_metadata: false,
+            limits: Limits::default(),
+        }
+    }
+
+    /// Carry the topic, partition, offset and timestamp of the consumed records in the
+    /// extensions of the events, see [`RecordMetadata`](super::RecordMetadata). Disabled by default.
+    pub fn record_metadata(mut self, enabled: bool) -> Self {
+        self.record_metadata = enabled;
+        self
+    }
+
+    /// Reject the consumed records exceeding `limits`, failing with
+    /// [`message::Error`](crate::message::Error) errors. No limits are set by default.
+    pub fn limits(mut self, limits: Limits) -> Self {
+        self.limits = limits;
+        self
+    }
+}
+
+#[async_trait]
+impl EventSource for KafkaSource {
+    type Ack = KafkaAck;
+
+    async fn receive(&mut self) -> Option<Result<(Event, KafkaAck)>> {
+        let message = match self.consumer.recv().await {
+            Ok(message) => message,
+            Err(e) => return Some(Err(Error::transport(e))),
+        };
+        let ack = KafkaAck {
+            topic: message.topic().to_string(),
+            partition: message.partition(),
+            offset: message.offset(),
+        };
+        let received = instrument::receive("kafka", || {
+            message.to_event_with_limits(self.limits).map(|mut event| {
+                if self.record_metadata {
+                    RecordMetadata::from_message(&message).set_extensions(&mut event);
+                }
+                event
+            })
+        });
+        match received {
+            Ok(event) => Some(Ok((event, ack))),
+            Err(e) => Some(self.ack(ack).await.and(Err(Error::from(e)))),
+        }
+    }
+
+    async fn ack(&mut self, ack: KafkaAck) -> Result<()> {
+        let mut offsets = TopicPartitionList::new();
+        offsets
+            .add_partition_offset(&ack.topic, ack.partition, Offset::Offset(ack.offset + 1))
+            .map_err(Error::transport)?;
+        self.consumer
+            .commit(&offsets, CommitMode::Async)
+            .map_err(Error::transport)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use rdkafka::config::ClientConfig;
+    use rdkafka::mocking::MockCluster;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn undecodable_records_are_committed() {
+        let cluster = MockCluster::new(1).unwrap();
+        cluster.create_topic("events", 1, 1).unwrap();
+        let producer: FutureProducer = ClientConfig::new()
+            .set("bootstrap.servers", cluster.bootstrap_servers())
+            .create()
+            .unwrap();
+        producer
+            .send(
+                FutureRecord::<(), str>::to("events").payload("not an event"),
+                Timeout::Never,
+            )
+            .await
+            .unwrap();
+        KafkaSink::new(producer, "events")
+            .send(fixtures::v10::minimal())
+            .await
+            .unwrap();
+
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", cluster.bootstrap_servers())
+            .set("group.id", "test")
+            .set("auto.offset.reset", "earliest")
+            .set("enable.auto.commit", "false")
+            .create()
+            .unwrap();
+        consumer.subscribe(&["events"]).unwrap();
+        let mut source = KafkaSource::new(consumer);
+
+        assert!(matches!(
+            source.receive().await,
+            Some(Err(Error::MessageError { .. }))
+        ));
+        // The offset is committed asynchronously
+        let mut committed = Offset::Invalid;
+        for _ in 0..50 {
+            committed = source
+                .consumer
+                .committed(Timeout::After(Duration::from_secs(1)))
+                .unwrap()
+                .find_partition("events", 0)
+                .unwrap()
+                .offset();
+            if committed == Offset::Offset(1) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert_eq!(committed, Offset::Offset(1));
+
+        let (event, ack) = source.receive().await.unwrap().unwrap();
+        assert_eq!(event, fixtures::v10::minimal());
+        assert_eq!(ack.offset, 1);
+    }
+}
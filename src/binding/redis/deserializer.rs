@@ -0,0 +1,86 @@
+use super::CHANNEL_EXTENSION;
+use crate::{
+    message::{Result, StructuredDeserializer},
+    Event,
+};
+
+use redis_lib as redis;
+
+impl StructuredDeserializer for redis::Msg {
+    fn deserialize_structured<R: Sized, V: crate::message::StructuredSerializer<R>>(
+        self,
+        serializer: V,
+    ) -> crate::message::Result<R> {
+        serializer.set_structured_event(self.get_payload_bytes().to_vec())
+    }
+}
+
+/// Trait implemented by [`redis::Msg`] to enable convenient deserialization to [`Event`]
+///
+/// Trait sealed <https://rust-lang.github.io/api-guidelines/future-proofing.html#sealed-traits-protect-against-downstream-implementations-c-sealed>
+pub trait MsgExt: private::Sealed {
+    fn to_event(&self) -> Result<Event>;
+}
+
+impl MsgExt for redis::Msg {
+    fn to_event(&self) -> Result<Event> {
+        let mut event = StructuredDeserializer::into_event(self.to_owned())?;
+        event.set_extension(CHANNEL_EXTENSION, self.get_channel_name());
+        Ok(event)
+    }
+}
+
+mod private {
+    use redis_lib as redis;
+
+    // Sealing the MsgExt
+    pub trait Sealed {}
+    impl Sealed for redis::Msg {}
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binding::redis::RedisCloudEvent;
+    use crate::test::fixtures;
+    use crate::{EventBuilder, EventBuilderV10};
+    use redis_lib as redis;
+
+    use super::*;
+
+    fn redis_message(channel: &str, payload: Vec<u8>) -> redis::Msg {
+        redis::Msg::from_owned_value(redis::Value::Array(vec![
+            redis::Value::BulkString(b"message".to_vec()),
+            redis::Value::BulkString(channel.as_bytes().to_vec()),
+            redis::Value::BulkString(payload),
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_structured_deserialize_v10() {
+        let input = fixtures::v10::full_json_data_string_extension();
+        let expected = EventBuilderV10::from(input.clone())
+            .extension("channel", "test")
+            .build()
+            .unwrap();
+
+        let payload = RedisCloudEvent::from_event(input).unwrap().payload;
+        let actual = redis_message("test", payload).to_event().unwrap();
+
+        assert_eq!(expected, actual)
+    }
+
+    #[test]
+    fn test_structured_deserialize_v03() {
+        let input = fixtures::v03::full_json_data();
+        let expected = crate::EventBuilderV03::from(input.clone())
+            .extension("channel", "test")
+            .build()
+            .unwrap();
+
+        let payload = RedisCloudEvent::from_event(input).unwrap().payload;
+        let actual = redis_message("test", payload).to_event().unwrap();
+
+        assert_eq!(expected, actual)
+    }
+}
@@ -0,0 +1,48 @@
+//! This module provides bindings between [cloudevents-sdk](https://docs.rs/cloudevents-sdk) and [redis](https://docs.rs/redis) pub/sub channels.
+//!
+//! Events are always published in structured mode. When received, the name of the channel
+//! is added to the event as the `channel` extension.
+//! ## Examples
+//! Deserialize [redis::Msg](https://docs.rs/redis/0.27.6/redis/struct.Msg.html) into [Event](https://docs.rs/cloudevents-sdk/latest/cloudevents/event/struct.Event.html)
+//! ```
+//!     use redis_lib as redis;
+//!     use cloudevents::binding::redis::MsgExt;
+//!
+//!     fn consume(con: &mut redis::Connection) {
+//!       let mut pubsub = con.as_pubsub();
+//!       pubsub.subscribe("test").unwrap();
+//!       let redis_message = pubsub.get_message().unwrap();
+//!       let cloud_event = redis_message.to_event().unwrap();
+//!
+//!       println!("{}", cloud_event.to_string());
+//!     }
+//! ```
+//!
+//! Serialize [Event](https://docs.rs/cloudevents-sdk/latest/cloudevents/event/struct.Event.html) into [RedisCloudEvent] and publish to a redis channel
+//! ```
+//!     use redis_lib as redis;
+//!     use cloudevents::binding::redis::RedisCloudEvent;
+//!     use cloudevents::{EventBuilder, EventBuilderV10, Event};
+//!     use redis::Commands;
+//!     use serde_json::json;
+//!
+//!     fn publish(con: &mut redis::Connection) {
+//!       let event = EventBuilderV10::new()
+//!           .id("123".to_string())
+//!           .ty("example.test")
+//!           .source("http://localhost/")
+//!           .data("application/json", json!({"hello": "world"}))
+//!           .build()
+//!           .unwrap();
+//!
+//!       let _: i64 = con.publish("test", RedisCloudEvent::from_event(event).unwrap()).unwrap();
+//!     }
+//! ```
+mod deserializer;
+mod serializer;
+
+pub use deserializer::MsgExt;
+pub use serializer::RedisCloudEvent;
+
+/// Name of the extension the channel of a received message is mapped to.
+pub static CHANNEL_EXTENSION: &str = "channel";
@@ -0,0 +1,37 @@
+use crate::{
+    message::{Error, Result},
+    Event,
+};
+
+use redis_lib as redis;
+
+/// Helper struct containing text data bytes of JSON serialized [Event]
+///
+/// Implements [`redis::ToRedisArgs`] so it can be directly passed to [`redis::Commands::publish`](https://docs.rs/redis/0.27.6/redis/trait.Commands.html#method.publish) as message.
+pub struct RedisCloudEvent {
+    pub payload: Vec<u8>,
+}
+
+impl AsRef<[u8]> for RedisCloudEvent {
+    fn as_ref(&self) -> &[u8] {
+        self.payload.as_ref()
+    }
+}
+
+impl redis::ToRedisArgs for RedisCloudEvent {
+    fn write_redis_args<W>(&self, out: &mut W)
+    where
+        W: ?Sized + redis::RedisWrite,
+    {
+        out.write_arg(&self.payload)
+    }
+}
+
+impl RedisCloudEvent {
+    pub fn from_event(event: Event) -> Result<Self> {
+        match serde_json::to_vec(&event) {
+            Ok(payload) => Ok(Self { payload }),
+            Err(e) => Err(Error::SerdeJsonError { source: e }),
+        }
+    }
+}
@@ -0,0 +1,303 @@
+//! Blocking counterparts of the reqwest binding, for the [`reqwest::blocking`] client.
+//!
+//! ```
+//! # use reqwest_lib as reqwest;
+//! use cloudevents::binding::reqwest::blocking::{RequestBuilderExt, ResponseExt};
+//! use cloudevents::{EventBuilderV10, EventBuilder};
+//! use serde_json::json;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = reqwest::blocking::Client::new();
+//!
+//! let event_to_send = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("example.test")
+//!     .source("http://localhost/")
+//!     .data("application/json", json!({"hello": "world"}))
+//!     .build()?;
+//!
+//! let received_event = client.post("http://localhost")
+//!   .event(event_to_send)?
+//!   .send()?
+//!   .into_event()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use reqwest_lib as reqwest;
+
+use crate::binding::{
+    self,
+    http::{header_key, header_value, SPEC_VERSION_HEADER},
+    instrument, CLOUDEVENTS_BATCH_JSON_HEADER, CLOUDEVENTS_JSON_HEADER,
+};
+use crate::event::SpecVersion;
+use crate::message::{
+    BinaryDeserializer, BinarySerializer, Error, MessageAttributeValue, Result,
+    StructuredSerializer,
+};
+use crate::transport::{self, blocking::EventSink};
+use crate::Event;
+use http::header;
+use reqwest::blocking::{Client, RequestBuilder, Response};
+
+/// Wrapper for the blocking [`RequestBuilder`] that implements [`StructuredSerializer`] & [`BinarySerializer`] traits.
+pub struct RequestSerializer {
+    req: RequestBuilder,
+}
+
+impl RequestSerializer {
+    pub fn new(req: RequestBuilder) -> RequestSerializer {
+        RequestSerializer { req }
+    }
+}
+
+impl BinarySerializer<RequestBuilder> for RequestSerializer {
+    fn set_spec_version(mut self, spec_ver: SpecVersion) -> Result<Self> {
+        self.req = self.req.header(
+            reqwest::header::HeaderName::from_static(SPEC_VERSION_HEADER),
+            reqwest::header::HeaderValue::from_static(spec_ver.as_str()),
+        );
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.req = self
+            .req
+            .header(header_key(name)?, header_value(name, value)?);
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.req = self
+            .req
+            .header(header_key(name)?, header_value(name, value)?);
+        Ok(self)
+    }
+
+    fn end_with_data(self, bytes: Vec<u8>) -> Result<RequestBuilder> {
+        Ok(self.req.body(bytes))
+    }
+
+    fn end(self) -> Result<RequestBuilder> {
+        Ok(self.req)
+    }
+}
+
+impl StructuredSerializer<RequestBuilder> for RequestSerializer {
+    fn set_structured_event(self, bytes: Vec<u8>) -> Result<RequestBuilder> {
+        Ok(self
+            .req
+            .header(reqwest::header::CONTENT_TYPE, CLOUDEVENTS_JSON_HEADER)
+            .body(bytes))
+    }
+}
+
+/// Method to fill a blocking [`RequestBuilder`] with an [`Event`].
+pub fn event_to_request(event: Event, request_builder: RequestBuilder) -> Result<RequestBuilder> {
+    instrument::serialize("http", event, |event| {
+        BinaryDeserializer::deserialize_binary(event, RequestSerializer::new(request_builder))
+    })
+}
+
+/// Method to fill a blocking [`RequestBuilder`] with a batched [`Vec<Event>`].
+pub fn events_to_request(
+    events: Vec<Event>,
+    request_builder: RequestBuilder,
+) -> Result<RequestBuilder> {
+    let bytes = serde_json::to_vec(&events)?;
+    Ok(request_builder
+        .header(reqwest::header::CONTENT_TYPE, CLOUDEVENTS_BATCH_JSON_HEADER)
+        .body(bytes))
+}
+
+/// Method to transform an incoming blocking [`Response`] to [`Event`].
+pub fn response_to_event(res: Response) -> Result<Event> {
+    let h = res.headers().to_owned();
+    let b = res.bytes().map_err(|e| Error::Other {
+        source: Box::new(e),
+    })?;
+    binding::http::to_event(&h, b.to_vec())
+}
+
+/// Method to transform an incoming blocking [`Response`] to a batched [`Vec<Event>`]
+pub fn response_to_events(res: Response) -> Result<Vec<Event>> {
+    if !res
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .is_some_and(|v| binding::is_media_type(v, CLOUDEVENTS_BATCH_JSON_HEADER))
+    {
+        return Err(Error::WrongEncoding {});
+    }
+
+    let bytes = res.bytes().map_err(|e| Error::Other {
+        source: Box::new(e),
+    })?;
+
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+/// Extension Trait for the blocking [`RequestBuilder`] which acts as a wrapper for the function [`event_to_request()`].
+///
+/// This trait is sealed and cannot be implemented for types outside of this crate.
+pub trait RequestBuilderExt: private::Sealed {
+    /// Write in this [`RequestBuilder`] the provided [`Event`].
+    fn event(self, event: Event) -> Result<RequestBuilder>;
+    /// Write in this [`RequestBuilder`] the provided batched [`Vec<Event>`].
+    fn events(self, events: Vec<Event>) -> Result<RequestBuilder>;
+}
+
+impl RequestBuilderExt for RequestBuilder {
+    fn event(self, event: Event) -> Result<RequestBuilder> {
+        event_to_request(event, self)
+    }
+
+    fn events(self, events: Vec<Event>) -> Result<RequestBuilder> {
+        events_to_request(events, self)
+    }
+}
+
+/// Extension Trait for the blocking [`Response`] which acts as a wrapper for the function [`response_to_event()`].
+///
+/// This trait is sealed and cannot be implemented for types outside of this crate.
+pub trait ResponseExt: private::Sealed {
+    /// Convert this [`Response`] to [`Event`].
+    fn into_event(self) -> Result<Event>;
+    /// Convert this [`Response`] to a batched [`Vec<Event>`].
+    fn into_events(self) -> Result<Vec<Event>>;
+}
+
+impl ResponseExt for Response {
+    fn into_event(self) -> Result<Event> {
+        response_to_event(self)
+    }
+
+    fn into_events(self) -> Result<Vec<Event>> {
+        response_to_events(self)
+    }
+}
+
+/// Blocking [`EventSink`] sending each event with a `POST` request in binary mode, and the
+/// events passed to [`EventSink::send_all`] with a single request in batch mode.
+pub struct ReqwestSink {
+    client: Client,
+    url: reqwest::Url,
+}
+
+impl ReqwestSink {
+    /// Create a new [`ReqwestSink`], sending the events to `url`.
+    pub fn new(client: Client, url: reqwest::Url) -> Self {
+        ReqwestSink { client, url }
+    }
+}
+
+impl EventSink for ReqwestSink {
+    fn send(&self, event: Event) -> transport::Result<()> {
+        self.client
+            .post(self.url.clone())
+            .event(event)?
+            .send()
+            .and_then(Response::error_for_status)
+            .map_err(transport::Error::transport)?;
+        Ok(())
+    }
+
+    /// Send all the events with a single `POST` request in batch mode.
+    fn send_all(&self, events: Vec<Event>) -> transport::Result<()> {
+        self.client
+            .post(self.url.clone())
+            .events(events)?
+            .send()
+            .and_then(Response::error_for_status)
+            .map_err(transport::Error::transport)?;
+        Ok(())
+    }
+}
+
+// Sealing the RequestBuilderExt and the ResponseExt
+mod private {
+    use reqwest_lib as reqwest;
+
+    pub trait Sealed {}
+    impl Sealed for reqwest::blocking::RequestBuilder {}
+    impl Sealed for reqwest::blocking::Response {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+
+    fn url(path: &str) -> reqwest::Url {
+        reqwest::Url::parse(&mockito::server_url())
+            .unwrap()
+            .join(path)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_request_and_response() {
+        let _m = mockito::mock("POST", "/blocking_echo")
+            .match_header("ce-specversion", "1.0")
+            .match_header("ce-id", "0001")
+            .with_status(200)
+            .with_header("ce-specversion", "1.0")
+            .with_header("ce-id", "0001")
+            .with_header("ce-type", "test_event.test_application")
+            .with_header("ce-source", "http://localhost/")
+            .with_header("ce-someint", "10")
+            .create();
+
+        let event = Client::new()
+            .post(url("/blocking_echo"))
+            .event(fixtures::v10::minimal_string_extension())
+            .unwrap()
+            .send()
+            .unwrap()
+            .into_event()
+            .unwrap();
+
+        assert_eq!(event, fixtures::v10::minimal_string_extension());
+    }
+
+    #[test]
+    fn test_sink() {
+        let events = vec![
+            fixtures::v10::minimal_string_extension(),
+            fixtures::v10::full_json_data(),
+        ];
+        let single = mockito::mock("POST", "/blocking_sink")
+            .match_header("ce-id", "0001")
+            .expect(1)
+            .create();
+        let batch = mockito::mock("POST", "/blocking_sink")
+            .match_header("content-type", CLOUDEVENTS_BATCH_JSON_HEADER)
+            .match_body(mockito::Matcher::Exact(
+                serde_json::to_string(&events).unwrap(),
+            ))
+            .expect(1)
+            .create();
+
+        let sink = ReqwestSink::new(Client::new(), url("/blocking_sink"));
+        sink.send(fixtures::v10::minimal_string_extension())
+            .unwrap();
+        sink.send_all(events).unwrap();
+
+        single.assert();
+        batch.assert();
+    }
+
+    #[test]
+    fn test_sink_error_status() {
+        let _m = mockito::mock("POST", "/blocking_sink_error")
+            .with_status(500)
+            .create();
+
+        let sink = ReqwestSink::new(Client::new(), url("/blocking_sink_error"));
+
+        assert!(matches!(
+            sink.send(fixtures::v10::minimal()),
+            Err(transport::Error::TransportError { .. })
+        ));
+    }
+}
@@ -1,8 +1,8 @@
 use reqwest_lib as reqwest;
 
 use crate::binding::{
-    http::{header_prefix, SPEC_VERSION_HEADER},
-    CLOUDEVENTS_BATCH_JSON_HEADER, CLOUDEVENTS_JSON_HEADER,
+    http::{header_key, header_value, SPEC_VERSION_HEADER},
+    instrument, CLOUDEVENTS_BATCH_JSON_HEADER, CLOUDEVENTS_JSON_HEADER,
 };
 use crate::event::SpecVersion;
 use crate::message::{
@@ -33,19 +33,24 @@ impl RequestSerializer {
 
 impl BinarySerializer<RequestBuilder> for RequestSerializer {
     fn set_spec_version(mut self, spec_ver: SpecVersion) -> Result<Self> {
-        self.req = self.req.header(SPEC_VERSION_HEADER, spec_ver.to_string());
+        self.req = self.req.header(
+            reqwest::header::HeaderName::from_static(SPEC_VERSION_HEADER),
+            reqwest::header::HeaderValue::from_static(spec_ver.as_str()),
+        );
         Ok(self)
     }
 
     fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
-        let key = &header_prefix(name);
-        self.req = self.req.header(key, value.to_string());
+        self.req = self
+            .req
+            .header(header_key(name)?, header_value(name, value)?);
         Ok(self)
     }
 
     fn set_extension(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
-        let key = &header_prefix(name);
-        self.req = self.req.header(key, value.to_string());
+        self.req = self
+            .req
+            .header(header_key(name)?, header_value(name, value)?);
         Ok(self)
     }
 
@@ -69,7 +74,9 @@ impl StructuredSerializer<RequestBuilder> for RequestSerializer {
 
 /// Method to fill a [`RequestBuilder`] with an [`Event`].
 pub fn event_to_request(event: Event, request_builder: RequestBuilder) -> Result<RequestBuilder> {
-    BinaryDeserializer::deserialize_binary(event, RequestSerializer::new(request_builder))
+    instrument::serialize("http", event, |event| {
+        BinaryDeserializer::deserialize_binary(event, RequestSerializer::new(request_builder))
+    })
 }
 
 /// Method to fill a [`RequestBuilder`] with a batched [`Vec<Event>`].
@@ -156,9 +163,9 @@ mod tests {
             .with_header("ce-source", "http://localhost/")
             .with_header("ce-subject", "cloudevents-sdk")
             .with_header("content-type", "application/json")
-            .with_header("ce-string_ex", "val")
-            .with_header("ce-int_ex", "10")
-            .with_header("ce-bool_ex", "true")
+            .with_header("ce-stringex", "val")
+            .with_header("ce-intex", "10")
+            .with_header("ce-boolex", "true")
             .with_header("ce-time", &fixtures::time().to_rfc3339())
             .match_body(Matcher::Exact(fixtures::json_data().to_string()))
             .create();
@@ -19,12 +19,10 @@ pub async fn response_to_event(res: Response) -> Result<Event> {
 
 /// Method to transform an incoming [`Response`] to a batched [`Vec<Event>`]
 pub async fn response_to_events(res: Response) -> Result<Vec<Event>> {
-    if res
+    if !res
         .headers()
         .get(header::CONTENT_TYPE)
-        .and_then(|v| v.to_str().ok())
-        .filter(|&v| v.starts_with(binding::CLOUDEVENTS_BATCH_JSON_HEADER))
-        .is_none()
+        .is_some_and(|v| binding::is_media_type(v, binding::CLOUDEVENTS_BATCH_JSON_HEADER))
     {
         return Err(Error::WrongEncoding {});
     }
@@ -112,9 +110,9 @@ mod tests {
             .with_header("ce-source", "http://localhost/")
             .with_header("ce-subject", "cloudevents-sdk")
             .with_header("content-type", "application/json")
-            .with_header("ce-string_ex", "val")
-            .with_header("ce-int_ex", "10")
-            .with_header("ce-bool_ex", "true")
+            .with_header("ce-stringex", "val")
+            .with_header("ce-intex", "10")
+            .with_header("ce-boolex", "true")
             .with_header("ce-time", &fixtures::time().to_rfc3339())
             .with_body(fixtures::json_data().to_string())
             .create();
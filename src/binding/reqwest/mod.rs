@@ -30,11 +30,16 @@
 
 #![deny(rustdoc::broken_intra_doc_links)]
 
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+#[cfg(feature = "blocking")]
+pub mod blocking;
 mod client_request;
 mod client_response;
+mod transport;
 
 pub use client_request::event_to_request;
 pub use client_request::RequestBuilderExt;
 pub use client_request::RequestSerializer;
 pub use client_response::response_to_event;
 pub use client_response::ResponseExt;
+pub use transport::ReqwestSink;
@@ -0,0 +1,120 @@
+use reqwest_lib as reqwest;
+
+use super::RequestBuilderExt;
+use crate::binding::instrument;
+use crate::transport::{Error, EventSink, Result};
+use crate::Event;
+use async_trait::async_trait;
+
+/// [`EventSink`] sending each event with a `POST` request in binary mode, and the events
+/// passed to [`EventSink::send_all`] with a single request in batch mode.
+pub struct ReqwestSink {
+    client: reqwest::Client,
+    url: reqwest::Url,
+}
+
+impl ReqwestSink {
+    /// Create a new [`ReqwestSink`], sending the events to `url`.
+    pub fn new(client: reqwest::Client, url: reqwest::Url) -> Self {
+        ReqwestSink { client, url }
+    }
+}
+
+#[async_trait]
+impl EventSink for ReqwestSink {
+    async fn send(&self, event: Event) -> Result<()> {
+        instrument::send("http", event, |event| async move {
+            self.client
+                .post(self.url.clone())
+                .event(event)?
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .map_err(Error::transport)?;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Send all the events with a single `POST` request in batch mode.
+    async fn send_all(&self, events: Vec<Event>) -> Result<()> {
+        self.client
+            .post(self.url.clone())
+            .events(events)?
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(Error::transport)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+
+    #[tokio::test]
+    async fn test_send() {
+        let url = reqwest::Url::parse(&mockito::server_url())
+            .unwrap()
+            .join("/sink")
+            .unwrap();
+        let m = mockito::mock("POST", "/sink")
+            .match_header("ce-specversion", "1.0")
+            .match_header("ce-id", "0001")
+            .expect(2)
+            .create();
+
+        let sink = ReqwestSink::new(reqwest::Client::new(), url);
+        for _ in 0..2 {
+            sink.send(fixtures::v10::minimal_string_extension())
+                .await
+                .unwrap();
+        }
+
+        m.assert();
+    }
+
+    #[tokio::test]
+    async fn test_send_all() {
+        let url = reqwest::Url::parse(&mockito::server_url())
+            .unwrap()
+            .join("/sink_batch")
+            .unwrap();
+        let events = vec![
+            fixtures::v10::minimal_string_extension(),
+            fixtures::v10::full_json_data(),
+        ];
+        let m = mockito::mock("POST", "/sink_batch")
+            .match_header("content-type", "application/cloudevents-batch+json")
+            .match_body(mockito::Matcher::Exact(
+                serde_json::to_string(&events).unwrap(),
+            ))
+            .expect(1)
+            .create();
+
+        let sink = ReqwestSink::new(reqwest::Client::new(), url);
+        sink.send_all(events).await.unwrap();
+
+        m.assert();
+    }
+
+    #[tokio::test]
+    async fn test_send_error_status() {
+        let url = reqwest::Url::parse(&mockito::server_url())
+            .unwrap()
+            .join("/sink_error")
+            .unwrap();
+        let _m = mockito::mock("POST", "/sink_error")
+            .with_status(500)
+            .create();
+
+        let sink = ReqwestSink::new(reqwest::Client::new(), url);
+
+        assert!(matches!(
+            sink.send(fixtures::v10::minimal()).await,
+            Err(Error::TransportError { .. })
+        ));
+    }
+}
@@ -0,0 +1,177 @@
+use super::TopicTemplate;
+use crate::binding::{instrument, Limits};
+use crate::message::{Result, StructuredDeserializer, StructuredSerializer};
+use crate::Event;
+
+use rumqttc_lib as rumqttc;
+
+impl StructuredDeserializer for rumqttc::Publish {
+    fn deserialize_structured<R: Sized, V: StructuredSerializer<R>>(
+        self,
+        serializer: V,
+    ) -> Result<R> {
+        serializer.set_structured_event(self.payload.to_vec())
+    }
+}
+
+/// Trait implemented by [`rumqttc::Publish`] to enable convenient deserialization to [`Event`]
+///
+/// Trait sealed <https://rust-lang.github.io/api-guidelines/future-proofing.html#sealed-traits-protect-against-downstream-implementations-c-sealed>
+pub trait PublishExt: private::Sealed {
+    fn to_event(&self) -> Result<Event>;
+
+    /// Generates [`Event`], setting the attributes named by `template` to the matching levels
+    /// of the topic. Fails if the topic doesn't match the template.
+    fn to_event_with_topic(&self, template: &TopicTemplate) -> Result<Event>;
+
+    /// Generates [`Event`], failing if the payload or the attributes exceed `limits`. The
+    /// length of the payload is checked before parsing it.
+    fn to_event_with_limits(&self, limits: Limits) -> Result<Event>;
+}
+
+impl PublishExt for rumqttc::Publish {
+    fn to_event(&self) -> Result<Event> {
+        instrument::deserialize("mqtt", || {
+            StructuredDeserializer::into_event(self.to_owned())
+        })
+    }
+
+    fn to_event_with_topic(&self, template: &TopicTemplate) -> Result<Event> {
+        let mut event = self.to_event()?;
+        template.apply(&self.topic, &mut event)?;
+        Ok(event)
+    }
+
+    fn to_event_with_limits(&self, limits: Limits) -> Result<Event> {
+        limits.check_structured_len(self.payload.len())?;
+        let event = self.to_event()?;
+        limits.check(&event)?;
+        Ok(event)
+    }
+}
+
+#[cfg(feature = "blocking")]
+mod poll {
+    use super::PublishExt;
+    use crate::binding::instrument;
+    use crate::transport::blocking::PollSource;
+    use crate::transport::{Error, Result};
+    use crate::Event;
+    use rumqttc_lib as rumqttc;
+    use std::time::{Duration, Instant};
+
+    /// Receive the events published to the subscribed topics, driving the connection until an
+    /// event is received or the `timeout` expires. The other notifications are skipped.
+    ///
+    /// Returns `None` once the client is dropped, as the connection is closed.
+    impl PollSource for rumqttc::Connection {
+        fn poll_event(&mut self, timeout: Duration) -> Option<Result<Event>> {
+            let deadline = Instant::now() + timeout;
+            loop {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                match self.recv_timeout(remaining) {
+                    Ok(Ok(rumqttc::Event::Incoming(rumqttc::Packet::Publish(publish)))) => {
+                        return Some(
+                            instrument::receive("mqtt", || publish.to_event()).map_err(Error::from),
+                        )
+                    }
+                    Ok(Ok(_)) => continue,
+                    Ok(Err(e)) => return Some(Err(Error::transport(e))),
+                    Err(_) => return None,
+                }
+            }
+        }
+    }
+}
+
+mod private {
+    use rumqttc_lib as rumqttc;
+
+    // Sealing the PublishExt
+    pub trait Sealed {}
+    impl Sealed for rumqttc::Publish {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::rumqttc::MqttCloudEvent;
+    use crate::test::fixtures;
+    use crate::AttributesWriter;
+    use rumqttc::QoS;
+
+    #[test]
+    fn test_structured_deserialize_v10() {
+        let expected = fixtures::v10::full_json_data_string_extension();
+
+        let payload = MqttCloudEvent::from_event(expected.clone()).unwrap();
+        let publish = rumqttc::Publish::new("test", QoS::AtLeastOnce, payload);
+
+        assert_eq!(publish.to_event().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_structured_deserialize_v03() {
+        let expected = fixtures::v03::full_json_data();
+
+        let payload = MqttCloudEvent::from_event(expected.clone()).unwrap();
+        let publish = rumqttc::Publish::new("test", QoS::AtMostOnce, payload);
+
+        assert_eq!(publish.to_event().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_deserialize_with_topic() {
+        let template = TopicTemplate::new("devices/{subject}/events").unwrap();
+        let payload = MqttCloudEvent::from_event(fixtures::v10::minimal()).unwrap();
+
+        let publish = rumqttc::Publish::new("devices/sensor-42/events", QoS::AtMostOnce, payload);
+        let mut expected = fixtures::v10::minimal();
+        expected.set_subject(Some("sensor-42"));
+        assert_eq!(publish.to_event_with_topic(&template).unwrap(), expected);
+
+        let publish = rumqttc::Publish::new("devices/sensor-42", QoS::AtMostOnce, publish.payload);
+        assert!(publish.to_event_with_topic(&template).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_with_limits() {
+        let payload = MqttCloudEvent::from_event(fixtures::v10::full_json_data()).unwrap();
+        let publish = rumqttc::Publish::new("test", QoS::AtMostOnce, payload);
+
+        assert_eq!(
+            publish.to_event_with_limits(Limits::new()).unwrap(),
+            fixtures::v10::full_json_data()
+        );
+        assert!(publish
+            .to_event_with_limits(Limits::new().max_extensions(2))
+            .is_err());
+        assert!(matches!(
+            publish.to_event_with_limits(Limits::new().max_structured_len(16)),
+            Err(crate::message::Error::StructuredMessageTooLong { max: 16 })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_payload() {
+        let publish = rumqttc::Publish::new("test", QoS::AtMostOnce, b"not an event".to_vec());
+
+        assert!(publish.to_event().is_err());
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn test_poll_connection_error() {
+        use crate::transport::blocking::PollSource;
+        use crate::transport::Error;
+        use std::time::Duration;
+
+        let options = rumqttc::MqttOptions::new("test", "127.0.0.1", 1);
+        let (_client, mut connection) = rumqttc::Client::new(options, 10);
+
+        assert!(matches!(
+            connection.poll_event(Duration::from_secs(5)),
+            Some(Err(Error::TransportError { .. }))
+        ));
+    }
+}
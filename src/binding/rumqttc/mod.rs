@@ -0,0 +1,62 @@
+//! This module provides bindings between [cloudevents-sdk](https://docs.rs/cloudevents-sdk) and [rumqttc](https://docs.rs/rumqttc),
+//! implementing the MQTT 3.1.1 protocol binding, which only supports the structured content mode.
+//! ## Examples
+//! Deserialize [rumqttc::Publish](https://docs.rs/rumqttc/0.24.0/rumqttc/struct.Publish.html) into [Event](https://docs.rs/cloudevents-sdk/latest/cloudevents/event/struct.Event.html)
+//! ```
+//!     use rumqttc_lib as rumqttc;
+//!     use cloudevents::binding::rumqttc::PublishExt;
+//!
+//!     fn consume(connection: &mut rumqttc::Connection) {
+//!       for notification in connection.iter() {
+//!         if let Ok(rumqttc::Event::Incoming(rumqttc::Packet::Publish(publish))) = notification {
+//!           let cloud_event = publish.to_event().unwrap();
+//!
+//!           println!("{}", cloud_event);
+//!         }
+//!       }
+//!     }
+//! ```
+//!
+//! Serialize [Event](https://docs.rs/cloudevents-sdk/latest/cloudevents/event/struct.Event.html) into [MqttCloudEvent] and publish it to a topic
+//! ```
+//!     use rumqttc_lib as rumqttc;
+//!     use cloudevents::binding::rumqttc::MqttCloudEvent;
+//!     use cloudevents::{EventBuilder, EventBuilderV10};
+//!     use serde_json::json;
+//!
+//!     fn publish(client: &rumqttc::Client) {
+//!       let event = EventBuilderV10::new()
+//!           .id("123".to_string())
+//!           .ty("example.test")
+//!           .source("http://localhost/")
+//!           .data("application/json", json!({"hello": "world"}))
+//!           .build()
+//!           .unwrap();
+//!
+//!       let payload = MqttCloudEvent::from_event(event).unwrap();
+//!       client
+//!           .publish("test", rumqttc::QoS::AtLeastOnce, false, payload)
+//!           .unwrap();
+//!     }
+//! ```
+//!
+//! [`MqttSink`] implements the [`EventSink`](crate::transport::EventSink) trait, publishing the
+//! events with a [`rumqttc::AsyncClient`](https://docs.rs/rumqttc/0.24.0/rumqttc/struct.AsyncClient.html).
+//!
+//! A [`TopicTemplate`] such as `devices/{subject}/events/{type}` maps the topics to the
+//! attributes of the events: [`PublishExt::to_event_with_topic`] sets the attributes from the
+//! topic of a consumed message, and [`MqttSink::with_template`] publishes each event to the
+//! topic derived from its attributes.
+//!
+//! With the `blocking` feature, [`rumqttc::Connection`](https://docs.rs/rumqttc/0.24.0/rumqttc/struct.Connection.html)
+//! implements [`PollSource`](crate::transport::blocking::PollSource), to receive the events
+//! from an application event loop.
+mod deserializer;
+mod serializer;
+mod topic;
+mod transport;
+
+pub use deserializer::PublishExt;
+pub use serializer::MqttCloudEvent;
+pub use topic::TopicTemplate;
+pub use transport::MqttSink;
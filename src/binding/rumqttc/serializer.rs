@@ -0,0 +1,34 @@
+use crate::{
+    message::{Error, Result},
+    Event,
+};
+
+/// Helper struct containing the JSON serialized [Event], to be published as the payload of an
+/// MQTT message in structured mode.
+///
+/// Implements `Into<Vec<u8>>`, so it can be directly passed to
+/// [`rumqttc::Client::publish`](https://docs.rs/rumqttc/0.24.0/rumqttc/struct.Client.html#method.publish) as payload.
+pub struct MqttCloudEvent {
+    pub payload: Vec<u8>,
+}
+
+impl AsRef<[u8]> for MqttCloudEvent {
+    fn as_ref(&self) -> &[u8] {
+        self.payload.as_ref()
+    }
+}
+
+impl From<MqttCloudEvent> for Vec<u8> {
+    fn from(event: MqttCloudEvent) -> Self {
+        event.payload
+    }
+}
+
+impl MqttCloudEvent {
+    pub fn from_event(event: Event) -> Result<Self> {
+        match serde_json::to_vec(&event) {
+            Ok(payload) => Ok(Self { payload }),
+            Err(e) => Err(Error::SerdeJsonError { source: e }),
+        }
+    }
+}
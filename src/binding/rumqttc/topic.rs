@@ -0,0 +1,177 @@
+use crate::binding::topic::{Syntax, Template};
+use crate::message::Result;
+use crate::Event;
+use std::fmt;
+
+const MQTT: Syntax = Syntax {
+    separator: '/',
+    wildcard: "+",
+    multi_wildcard: "#",
+    reserved: &['+', '#', '\0'],
+};
+
+/// Mapping between MQTT topics and the attributes of the events, defined by a template such as
+/// `devices/{subject}/events/{type}`.
+///
+/// The levels of the template are either literals or placeholders naming the `id`, the
+/// `source`, the `type`, the `subject` or an extension of the event. A placeholder matches a
+/// single level of a topic, except for a last placeholder ending with `#`, e.g.
+/// `devices/{subject#}`, which matches the remaining levels.
+///
+/// ```
+/// use cloudevents::binding::rumqttc::TopicTemplate;
+/// use cloudevents::{AttributesReader, EventBuilder, EventBuilderV10};
+///
+/// let template = TopicTemplate::new("devices/{subject}/events/{type}").unwrap();
+/// assert_eq!(template.filter(), "devices/+/events/+");
+///
+/// let mut event = EventBuilderV10::new()
+///     .id("0001")
+///     .ty("temperature")
+///     .source("http://localhost/")
+///     .subject("sensor-42")
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     template.topic(&event).unwrap(),
+///     "devices/sensor-42/events/temperature"
+/// );
+///
+/// template
+///     .apply("devices/sensor-7/events/humidity", &mut event)
+///     .unwrap();
+/// assert_eq!(event.subject(), Some("sensor-7"));
+/// assert_eq!(event.ty(), "humidity");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopicTemplate(Template);
+
+impl TopicTemplate {
+    /// Parse `template`, failing if a level is empty, contains a wildcard, or names an
+    /// attribute other than `id`, `source`, `type`, `subject` or a valid extension name.
+    pub fn new(template: &str) -> Result<Self> {
+        Template::parse(template, MQTT).map(TopicTemplate)
+    }
+
+    /// The topic filter to subscribe to, replacing the placeholders with the `+` and `#`
+    /// wildcards.
+    pub fn filter(&self) -> String {
+        self.0.filter()
+    }
+
+    /// The topic to publish `event` to, failing if an attribute of the template is missing,
+    /// empty, or contains a wildcard or, unless it matches the remaining levels, a `/`.
+    pub fn topic(&self, event: &Event) -> Result<String> {
+        self.0.topic(event)
+    }
+
+    /// Set the attributes of `event` to the matching levels of `topic`, failing if the topic
+    /// doesn't match the template.
+    pub fn apply(&self, topic: &str, event: &mut Event) -> Result<()> {
+        self.0.apply(topic, event)
+    }
+}
+
+impl fmt::Display for TopicTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::message::Error;
+    use crate::test::fixtures;
+    use crate::{AttributesReader, AttributesWriter};
+
+    #[test]
+    fn parse() {
+        let template = TopicTemplate::new("tenants/{tenant}/{type}").unwrap();
+        assert_eq!(template.filter(), "tenants/+/+");
+        assert_eq!(template.to_string(), "tenants/{tenant}/{type}");
+
+        for invalid in [
+            "devices//{type}",
+            "devices/+/{type}",
+            "devices/#",
+            "devices/{subject}-{type}",
+            "devices/{time}",
+            "devices/{Region}",
+            "{type}/{type}",
+            "devices/{subject#}/{type}",
+        ] {
+            assert!(TopicTemplate::new(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn topic() {
+        let template = TopicTemplate::new("devices/{subject}/{region}/{type}").unwrap();
+        let mut event = fixtures::v10::minimal();
+        event.set_subject(Some("sensor-42"));
+
+        assert!(matches!(
+            template.topic(&event),
+            Err(Error::InvalidTopicAttribute { name, .. }) if name == "region"
+        ));
+
+        event.set_extension("region", "eu");
+        assert_eq!(
+            template.topic(&event).unwrap(),
+            format!("devices/sensor-42/eu/{}", fixtures::ty())
+        );
+
+        event.set_subject(Some("sensors/42"));
+        assert!(template.topic(&event).is_err());
+    }
+
+    #[test]
+    fn remaining_levels() {
+        let template = TopicTemplate::new("tenants/{tenant}/{subject#}").unwrap();
+        assert_eq!(template.filter(), "tenants/+/#");
+
+        let mut event = fixtures::v10::minimal();
+        template
+            .apply("tenants/acme/sensors/42", &mut event)
+            .unwrap();
+        assert_eq!(event.subject(), Some("sensors/42"));
+        assert_eq!(template.topic(&event).unwrap(), "tenants/acme/sensors/42");
+
+        assert!(template.apply("tenants/acme", &mut event).is_err());
+        assert!(template
+            .apply("tenants/acme/sensors//42", &mut event)
+            .is_err());
+        event.set_subject(Some("sensors/"));
+        assert!(template.topic(&event).is_err());
+    }
+
+    #[test]
+    fn apply() {
+        let template = TopicTemplate::new("devices/{subject}/{region}/{type}").unwrap();
+        let mut event = fixtures::v10::minimal();
+
+        template
+            .apply("devices/sensor-42/eu/temperature", &mut event)
+            .unwrap();
+        assert_eq!(event.subject(), Some("sensor-42"));
+        assert_eq!(event.ty(), "temperature");
+        assert_eq!(event.extension("region").unwrap().to_string(), "eu");
+        assert_eq!(
+            template.topic(&event).unwrap(),
+            "devices/sensor-42/eu/temperature"
+        );
+
+        for mismatch in [
+            "devices/sensor-42/eu",
+            "devices/sensor-42/eu/temperature/celsius",
+            "sensors/sensor-42/eu/temperature",
+            "devices//eu/temperature",
+        ] {
+            assert!(matches!(
+                template.apply(mismatch, &mut event),
+                Err(Error::TopicMismatch { .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use super::{MqttCloudEvent, TopicTemplate};
+use crate::binding::instrument;
+use crate::transport::{Error, EventSink, Result};
+use crate::Event;
+use async_trait::async_trait;
+
+use rumqttc_lib as rumqttc;
+
+/// [`EventSink`] publishing the events in structured mode to a topic, using
+/// [`rumqttc::AsyncClient`].
+///
+/// The topic is either fixed, or derived from the attributes of each event by a
+/// [`TopicTemplate`].
+///
+/// The publish requests are queued to the event loop of the client, so a send only fails once
+/// the event loop has been dropped. Wrap the sink in a
+/// [`ReplayingSink`](crate::transport::ReplayingSink) to replay the events lost with the
+/// connection.
+pub struct MqttSink {
+    client: rumqttc::AsyncClient,
+    topic: Topic,
+    qos: rumqttc::QoS,
+}
+
+enum Topic {
+    Fixed(String),
+    Template(TopicTemplate),
+}
+
+impl MqttSink {
+    /// Create a new [`MqttSink`], publishing the events to `topic` with the given `qos`.
+    pub fn new(client: rumqttc::AsyncClient, topic: impl Into<String>, qos: rumqttc::QoS) -> Self {
+        MqttSink {
+            client,
+            topic: Topic::Fixed(topic.into()),
+            qos,
+        }
+    }
+
+    /// Create a new [`MqttSink`], publishing each event to the topic derived from its
+    /// attributes by `template`, with the given `qos`.
+    ///
+    /// Sending an event whose attributes don't fit the template fails, see
+    /// [`TopicTemplate::topic`].
+    pub fn with_template(
+        client: rumqttc::AsyncClient,
+        template: TopicTemplate,
+        qos: rumqttc::QoS,
+    ) -> Self {
+        MqttSink {
+            client,
+            topic: Topic::Template(template),
+            qos,
+        }
+    }
+}
+
+#[async_trait]
+impl EventSink for MqttSink {
+    async fn send(&self, event: Event) -> Result<()> {
+        instrument::send("mqtt", event, |event| async move {
+            let topic = match &self.topic {
+                Topic::Fixed(topic) => topic.clone(),
+                Topic::Template(template) => template.topic(&event)?,
+            };
+            let payload = MqttCloudEvent::from_event(event)?;
+            self.client
+                .publish(topic, self.qos, false, payload)
+                .await
+                .map_err(Error::transport)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+
+    #[tokio::test]
+    async fn test_sink() {
+        let options = rumqttc::MqttOptions::new("test", "127.0.0.1", 1);
+        let (client, eventloop) = rumqttc::AsyncClient::new(options, 10);
+        let sink = MqttSink::new(client, "events", rumqttc::QoS::AtLeastOnce);
+
+        // Queued to the event loop
+        sink.send(fixtures::v10::full_json_data()).await.unwrap();
+
+        drop(eventloop);
+        assert!(matches!(
+            sink.send(fixtures::v10::minimal()).await,
+            Err(Error::TransportError { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sink_with_template() {
+        let options = rumqttc::MqttOptions::new("test", "127.0.0.1", 1);
+        let (client, _eventloop) = rumqttc::AsyncClient::new(options, 10);
+        let template = TopicTemplate::new("devices/{subject}/events").unwrap();
+        let sink = MqttSink::with_template(client, template, rumqttc::QoS::AtLeastOnce);
+
+        sink.send(fixtures::v10::full_json_data()).await.unwrap();
+        // The event has no subject
+        assert!(matches!(
+            sink.send(fixtures::v10::minimal()).await,
+            Err(Error::MessageError { .. })
+        ));
+    }
+}
@@ -0,0 +1,209 @@
+use super::serializer::MetadataSerializer;
+use super::{HEADER_PREFIX, SPEC_VERSION_HEADER};
+use crate::binding::{attribute_name, percent_decode_header_value};
+use crate::event::{SpecVersion, PROTOBUF_CONTENT_TYPE};
+use crate::message::{BinaryDeserializer, BinarySerializer, Error, MessageAttributeValue, Result};
+use crate::{AttributesReader, AttributesWriter, Data, Event};
+
+use prost::Message;
+use std::convert::TryFrom;
+use tonic::metadata::{KeyAndValueRef, MetadataMap};
+use tonic_lib as tonic;
+
+/// Deserializer of the event attributes carried by the `ce-` entries of a [`MetadataMap`].
+pub(crate) struct MetadataDeserializer<'a> {
+    metadata: &'a MetadataMap,
+}
+
+impl<'a> MetadataDeserializer<'a> {
+    pub(crate) fn new(metadata: &'a MetadataMap) -> Self {
+        MetadataDeserializer { metadata }
+    }
+
+    /// Whether the metadata carries an event, i.e. has a `ce-specversion` entry.
+    pub(crate) fn has_event(&self) -> bool {
+        self.metadata.contains_key(SPEC_VERSION_HEADER)
+    }
+}
+
+fn header_value_to_str<'a>(
+    name: &str,
+    value: &'a tonic::metadata::AsciiMetadataValue,
+) -> Result<&'a str> {
+    value.to_str().map_err(|e| Error::InvalidHeaderValue {
+        name: name.to_string(),
+        source: Box::new(e),
+    })
+}
+
+impl BinaryDeserializer for MetadataDeserializer<'_> {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, mut visitor: V) -> Result<R> {
+        let spec_version = match self.metadata.get(SPEC_VERSION_HEADER) {
+            Some(value) => SpecVersion::try_from(header_value_to_str(SPEC_VERSION_HEADER, value)?)
+                .map_err(|e| e.in_attribute("specversion"))?,
+            None => return Err(Error::WrongEncoding {}),
+        };
+
+        let attribute_names = spec_version.attribute_names();
+
+        visitor = visitor.set_spec_version(spec_version)?;
+
+        for entry in self.metadata.iter() {
+            let (key, value) = match entry {
+                KeyAndValueRef::Ascii(key, value) => (key, value),
+                KeyAndValueRef::Binary(..) => continue,
+            };
+            let name = match attribute_name(HEADER_PREFIX, key.as_str()) {
+                Some(name) if name != "specversion" => name,
+                _ => continue,
+            };
+            let value = percent_decode_header_value(header_value_to_str(key.as_str(), value)?)?;
+            let value = MessageAttributeValue::String(value.into_owned());
+
+            if attribute_names.contains(&name.as_ref()) {
+                visitor = visitor.set_attribute(&name, value)?
+            } else {
+                visitor = visitor.set_extension(&name, value)?
+            }
+        }
+
+        visitor.end()
+    }
+}
+
+/// The event attributes read from the metadata of a request by the
+/// [`EventInterceptor`](super::EventInterceptor), stored in the request extensions.
+#[derive(Clone, Debug)]
+pub(crate) struct EventMetadata(pub(crate) Event);
+
+/// Trait implemented by [`tonic::Request`] to carry the attributes of an [`Event`] in its
+/// metadata, with its message as the event data.
+///
+/// Trait sealed <https://rust-lang.github.io/api-guidelines/future-proofing.html#sealed-traits-protect-against-downstream-implementations-c-sealed>
+pub trait RequestExt: private::Sealed {
+    /// Set the `ce-` metadata of the request to the attributes and the extensions of `event`.
+    /// The data of `event` is discarded, the message of the request being the event data.
+    fn set_event_metadata(&mut self, event: Event) -> Result<()>;
+
+    /// The event attributes of the request, without data, if its metadata carries an event.
+    fn event_metadata(&self) -> Result<Option<Event>>;
+
+    /// Convert the request to an [`Event`], with the protobuf encoding of its message as
+    /// data. The `datacontenttype` defaults to
+    /// [`PROTOBUF_CONTENT_TYPE`](crate::event::PROTOBUF_CONTENT_TYPE).
+    ///
+    /// Returns [`Error::WrongEncoding`] if the metadata doesn't carry an event.
+    fn to_event(&self) -> Result<Event>;
+}
+
+impl<M: Message> RequestExt for tonic::Request<M> {
+    fn set_event_metadata(&mut self, event: Event) -> Result<()> {
+        event.deserialize_binary(MetadataSerializer::new(self.metadata_mut()))
+    }
+
+    fn event_metadata(&self) -> Result<Option<Event>> {
+        if let Some(EventMetadata(event)) = self.extensions().get::<EventMetadata>() {
+            return Ok(Some(event.clone()));
+        }
+        let deserializer = MetadataDeserializer::new(self.metadata());
+        if !deserializer.has_event() {
+            return Ok(None);
+        }
+        BinaryDeserializer::into_event(deserializer).map(Some)
+    }
+
+    fn to_event(&self) -> Result<Event> {
+        let mut event = self.event_metadata()?.ok_or(Error::WrongEncoding {})?;
+        if event.datacontenttype().is_none() {
+            event.set_datacontenttype(Some(PROTOBUF_CONTENT_TYPE));
+        }
+        event.set_data_unchecked(Data::Binary(self.get_ref().encode_to_vec()));
+        Ok(event)
+    }
+}
+
+mod private {
+    use tonic_lib as tonic;
+
+    // Sealing the RequestExt
+    pub trait Sealed {}
+    impl<M: prost::Message> Sealed for tonic::Request<M> {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct Greeting {
+        #[prost(string, tag = "1")]
+        text: String,
+    }
+
+    fn greeting() -> Greeting {
+        Greeting {
+            text: "hello".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_metadata_roundtrip() {
+        let mut expected = fixtures::v10::minimal_string_extension();
+        expected.set_subject(Some(fixtures::subject()));
+        expected.set_time(Some(fixtures::time()));
+
+        let mut request = tonic::Request::new(greeting());
+        request.set_event_metadata(expected.clone()).unwrap();
+
+        assert_eq!(request.metadata().get("ce-specversion").unwrap(), "1.0");
+        assert_eq!(request.metadata().get("ce-id").unwrap(), "0001");
+        assert_eq!(request.event_metadata().unwrap(), Some(expected));
+    }
+
+    #[test]
+    fn test_to_event() {
+        let mut request = tonic::Request::new(greeting());
+        request
+            .set_event_metadata(fixtures::v10::full_json_data_string_extension())
+            .unwrap();
+
+        let event = request.to_event().unwrap();
+
+        assert_eq!(event.datacontenttype(), Some("application/json"));
+        assert_eq!(
+            event.data(),
+            Some(&Data::Binary(greeting().encode_to_vec()))
+        );
+
+        let event = tonic::Request::new(greeting());
+        assert!(matches!(event.to_event(), Err(Error::WrongEncoding {})));
+    }
+
+    #[test]
+    fn test_to_event_default_content_type() {
+        let mut request = tonic::Request::new(greeting());
+        request
+            .set_event_metadata(fixtures::v10::minimal())
+            .unwrap();
+
+        let event = request.to_event().unwrap();
+
+        assert_eq!(event.datacontenttype(), Some(PROTOBUF_CONTENT_TYPE));
+    }
+
+    #[test]
+    fn test_percent_encoded_values() {
+        let mut expected = fixtures::v10::minimal();
+        expected.set_subject(Some("caf\u{e9} latte"));
+
+        let mut request = tonic::Request::new(greeting());
+        request.set_event_metadata(expected.clone()).unwrap();
+
+        assert_eq!(
+            request.metadata().get("ce-subject").unwrap(),
+            "caf%C3%A9%20latte"
+        );
+        assert_eq!(request.event_metadata().unwrap(), Some(expected));
+    }
+}
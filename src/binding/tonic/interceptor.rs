@@ -0,0 +1,94 @@
+use super::deserializer::{EventMetadata, MetadataDeserializer};
+use super::SPEC_VERSION_HEADER;
+use crate::message::BinaryDeserializer;
+
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+use tonic_lib as tonic;
+
+/// Server [`Interceptor`] reading the event attributes carried by the `ce-` metadata of the
+/// requests, to make them available to the handlers with
+/// [`RequestExt`](super::RequestExt).
+///
+/// Requests with invalid event metadata are rejected with [`tonic::Code::InvalidArgument`],
+/// as well as the requests without event metadata if it's [`required`](Self::required).
+#[derive(Clone, Debug, Default)]
+pub struct EventInterceptor {
+    required: bool,
+}
+
+impl EventInterceptor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reject the requests whose metadata doesn't carry an event. Defaults to `false`.
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+}
+
+impl Interceptor for EventInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let deserializer = MetadataDeserializer::new(request.metadata());
+        if !deserializer.has_event() {
+            return if self.required {
+                Err(Status::invalid_argument(format!(
+                    "missing {} metadata",
+                    SPEC_VERSION_HEADER
+                )))
+            } else {
+                Ok(request)
+            };
+        }
+        let event = BinaryDeserializer::into_event(deserializer)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?;
+        request.extensions_mut().insert(EventMetadata(event));
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binding::tonic::RequestExt;
+    use crate::test::fixtures;
+    use tonic::Code;
+
+    #[test]
+    fn test_intercept() {
+        let expected = fixtures::v10::minimal_string_extension();
+        let mut request = Request::new(());
+        request.set_event_metadata(expected.clone()).unwrap();
+
+        let request = EventInterceptor::new().call(request).unwrap();
+
+        assert!(request.extensions().get::<EventMetadata>().is_some());
+        assert_eq!(request.event_metadata().unwrap(), Some(expected));
+    }
+
+    #[test]
+    fn test_intercept_without_event() {
+        let request = EventInterceptor::new().call(Request::new(())).unwrap();
+        assert_eq!(request.event_metadata().unwrap(), None);
+
+        let status = EventInterceptor::new()
+            .required(true)
+            .call(Request::new(()))
+            .unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+
+    #[test]
+    fn test_intercept_invalid_event() {
+        let mut request = Request::new(());
+        request
+            .metadata_mut()
+            .insert("ce-specversion", "2.0".parse().unwrap());
+
+        let status = EventInterceptor::new().call(request).unwrap_err();
+
+        assert_eq!(status.code(), Code::InvalidArgument);
+    }
+}
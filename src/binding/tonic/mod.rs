@@ -0,0 +1,63 @@
+//! This module provides bindings between [cloudevents-sdk](https://docs.rs/cloudevents-sdk) and [tonic](https://docs.rs/tonic),
+//! to send events in binary content mode over any gRPC method: the attributes are carried by
+//! the `ce-` metadata of the request, while its message is the event data.
+//!
+//! To send events as the `io.cloudevents.v1.CloudEvent` message instead, see
+//! [`crate::protobuf`].
+//! ## Examples
+//! Set the attributes of an [Event](https://docs.rs/cloudevents-sdk/latest/cloudevents/event/struct.Event.html) as the metadata of a request
+//! ```
+//!     use tonic_lib as tonic;
+//!     use cloudevents::binding::tonic::RequestExt;
+//!     use cloudevents::{EventBuilder, EventBuilderV10};
+//!
+//!     fn request<M: prost::Message>(message: M) -> tonic::Request<M> {
+//!       let event = EventBuilderV10::new()
+//!           .id("123".to_string())
+//!           .ty("example.test")
+//!           .source("http://localhost/")
+//!           .build()
+//!           .unwrap();
+//!
+//!       let mut request = tonic::Request::new(message);
+//!       request.set_event_metadata(event).unwrap();
+//!       request
+//!     }
+//! ```
+//!
+//! Receive the [Event](https://docs.rs/cloudevents-sdk/latest/cloudevents/event/struct.Event.html) of a request, with its message as data,
+//! after the [`EventInterceptor`] validated its metadata, e.g. with
+//! `MyServiceServer::with_interceptor(service, EventInterceptor::new().required(true))`
+//! ```
+//!     use tonic_lib as tonic;
+//!     use cloudevents::binding::tonic::RequestExt;
+//!
+//!     fn handle<M: prost::Message>(request: tonic::Request<M>) -> Result<(), tonic::Status> {
+//!       let event = request
+//!           .to_event()
+//!           .map_err(|e| tonic::Status::invalid_argument(e.to_string()))?;
+//!
+//!       println!("{}", event);
+//!       Ok(())
+//!     }
+//! ```
+mod deserializer;
+mod interceptor;
+mod serializer;
+
+pub use deserializer::RequestExt;
+pub use interceptor::EventInterceptor;
+pub use serializer::MetadataSerializer;
+
+/// Prefix of the metadata keys carrying the attributes.
+pub static HEADER_PREFIX: &str = "ce-";
+/// Metadata key of the `specversion` attribute.
+pub static SPEC_VERSION_HEADER: &str = "ce-specversion";
+
+/// Metadata key of the attribute `name`.
+///
+/// Unlike the HTTP binding, `datacontenttype` isn't mapped to `content-type`, which gRPC
+/// reserves for its own media type.
+fn header_name(name: &str) -> String {
+    [HEADER_PREFIX, name].concat()
+}
@@ -0,0 +1,62 @@
+use super::header_name;
+use crate::binding::percent_encode_header_value;
+use crate::event::SpecVersion;
+use crate::message::{BinarySerializer, Error, MessageAttributeValue, Result};
+use std::convert::TryFrom;
+
+use tonic::metadata::{AsciiMetadataKey, AsciiMetadataValue, MetadataMap};
+use tonic_lib as tonic;
+
+/// Serializer writing the attributes and the extensions of an event to the `ce-` entries of
+/// a [`MetadataMap`]. The data of the event is discarded, since the message of the request
+/// carries it.
+pub struct MetadataSerializer<'a> {
+    metadata: &'a mut MetadataMap,
+}
+
+impl<'a> MetadataSerializer<'a> {
+    pub fn new(metadata: &'a mut MetadataMap) -> Self {
+        MetadataSerializer { metadata }
+    }
+
+    fn insert(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        let key = AsciiMetadataKey::from_bytes(header_name(name).as_bytes()).map_err(|e| {
+            Error::Other {
+                source: Box::new(e),
+            }
+        })?;
+        let value = String::from(value);
+        let value = AsciiMetadataValue::try_from(percent_encode_header_value(&value).as_ref())
+            .map_err(|e| Error::InvalidHeaderValue {
+                name: key.to_string(),
+                source: Box::new(e),
+            })?;
+        self.metadata.insert(key, value);
+        Ok(self)
+    }
+}
+
+impl BinarySerializer<()> for MetadataSerializer<'_> {
+    fn set_spec_version(self, spec_version: SpecVersion) -> Result<Self> {
+        self.insert(
+            "specversion",
+            MessageAttributeValue::String(spec_version.to_string()),
+        )
+    }
+
+    fn set_attribute(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.insert(name, value)
+    }
+
+    fn set_extension(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.insert(name, value)
+    }
+
+    fn end_with_data(self, _bytes: Vec<u8>) -> Result<()> {
+        Ok(())
+    }
+
+    fn end(self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,225 @@
+//! Mapping between the topics of a broker and the attributes of the events, through templates
+//! such as `devices/{subject}/events/{type}`.
+
+use crate::event::{validate_extension, AttributesWriter, ExtensionValue};
+use crate::message::{Error, Result};
+use crate::Event;
+use std::fmt;
+
+/// Syntax of the topics of a broker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Syntax {
+    /// Separator of the levels of a topic.
+    pub separator: char,
+    /// Wildcard matching a single level in subscriptions.
+    pub wildcard: &'static str,
+    /// Wildcard matching the remaining levels in subscriptions.
+    pub multi_wildcard: &'static str,
+    /// Characters which cannot appear in a level of a published topic.
+    pub reserved: &'static [char],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Level {
+    Literal(String),
+    /// A single level holding the value of an attribute.
+    Attribute(String),
+    /// The remaining levels, holding the value of an attribute.
+    Rest(String),
+}
+
+/// A template of topics, made of literal levels and of `{attribute}` levels matching the value
+/// of an attribute or an extension of the event.
+///
+/// The last level can be a placeholder followed by the multi-level wildcard of the broker, e.g.
+/// `orders.{subject>}` with NATS, matching the remaining levels of the topic.
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct Template {
+    template: String,
+    syntax: Syntax,
+    levels: Vec<Level>,
+}
+
+impl fmt::Debug for Template {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Template").field(&self.template).finish()
+    }
+}
+
+impl fmt::Display for Template {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.template)
+    }
+}
+
+impl Template {
+    /// Parse `template`, whose levels are either literals or `{attribute}` placeholders. The
+    /// placeholders name the `id`, the `source`, the `type`, the `subject` or an extension.
+    pub fn parse(template: &str, syntax: Syntax) -> Result<Self> {
+        let invalid = |reason: String| Error::InvalidTopicTemplate {
+            template: template.to_string(),
+            reason,
+        };
+        let mut levels: Vec<Level> = Vec::new();
+        for level in template.split(syntax.separator) {
+            if matches!(levels.last(), Some(Level::Rest(_))) {
+                return Err(invalid(format!(
+                    "the {} placeholder must be the last level",
+                    syntax.multi_wildcard
+                )));
+            }
+            let placeholder = level.strip_prefix('{').and_then(|l| l.strip_suffix('}'));
+            let level = match placeholder {
+                Some(placeholder) => {
+                    let (name, rest) = match placeholder.strip_suffix(syntax.multi_wildcard) {
+                        Some(name) => (name, true),
+                        None => (placeholder, false),
+                    };
+                    match name {
+                        "id" | "source" | "type" | "subject" => {}
+                        "specversion"
+                        | "datacontenttype"
+                        | "dataschema"
+                        | "schemaurl"
+                        | "datacontentencoding"
+                        | "time" => {
+                            return Err(invalid(format!(
+                                "the attribute {} cannot be mapped to a topic",
+                                name
+                            )))
+                        }
+                        _ => validate_extension(name, &ExtensionValue::Boolean(false))?,
+                    }
+                    if levels
+                        .iter()
+                        .any(|l| matches!(l, Level::Attribute(n) | Level::Rest(n) if n == name))
+                    {
+                        return Err(invalid(format!("the attribute {} is repeated", name)));
+                    }
+                    if rest {
+                        Level::Rest(name.to_string())
+                    } else {
+                        Level::Attribute(name.to_string())
+                    }
+                }
+                None if level.is_empty() => {
+                    return Err(invalid("empty levels are not allowed".to_string()))
+                }
+                None if level
+                    .contains(|c| c == '{' || c == '}' || syntax.reserved.contains(&c)) =>
+                {
+                    return Err(invalid(format!("invalid level {}", level)))
+                }
+                None => Level::Literal(level.to_string()),
+            };
+            levels.push(level);
+        }
+        Ok(Template {
+            template: template.to_string(),
+            syntax,
+            levels,
+        })
+    }
+
+    /// The subscription filter matching the topics of the template.
+    pub fn filter(&self) -> String {
+        self.levels
+            .iter()
+            .map(|level| match level {
+                Level::Literal(literal) => literal.as_str(),
+                Level::Attribute(_) => self.syntax.wildcard,
+                Level::Rest(_) => self.syntax.multi_wildcard,
+            })
+            .collect::<Vec<_>>()
+            .join(&self.syntax.separator.to_string())
+    }
+
+    /// The topic of `event`, failing if an attribute of the template is missing, empty, or
+    /// contains a reserved character or, unless it fills the remaining levels, the separator.
+    pub fn topic(&self, event: &Event) -> Result<String> {
+        let mut values = Vec::with_capacity(self.levels.len());
+        for level in &self.levels {
+            let (name, rest) = match level {
+                Level::Literal(literal) => {
+                    values.push(literal.clone());
+                    continue;
+                }
+                Level::Attribute(name) => (name, false),
+                Level::Rest(name) => (name, true),
+            };
+            let invalid = |reason: &str| Error::InvalidTopicAttribute {
+                name: name.clone(),
+                reason: reason.to_string(),
+            };
+            let value = event
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, value)| value.to_string())
+                .ok_or_else(|| invalid("missing attribute"))?;
+            if value.contains(|c| self.syntax.reserved.contains(&c)) {
+                return Err(invalid("the value contains a reserved character"));
+            }
+            if !rest && value.contains(self.syntax.separator) {
+                return Err(invalid("the value contains the separator"));
+            }
+            if value.split(self.syntax.separator).any(str::is_empty) {
+                return Err(invalid("the value would produce an empty level"));
+            }
+            values.push(value);
+        }
+        Ok(values.join(&self.syntax.separator.to_string()))
+    }
+
+    /// Set the attributes of `event` to the matching levels of `topic`, failing if the topic
+    /// doesn't match the template.
+    pub fn apply(&self, topic: &str, event: &mut Event) -> Result<()> {
+        let mismatch = || Error::TopicMismatch {
+            topic: topic.to_string(),
+            template: self.template.clone(),
+        };
+        let mut remaining = topic;
+        let mut values = Vec::with_capacity(self.levels.len());
+        for (i, level) in self.levels.iter().enumerate() {
+            let last = i + 1 == self.levels.len();
+            let value = match level {
+                Level::Rest(_) => std::mem::take(&mut remaining),
+                _ => match remaining.split_once(self.syntax.separator) {
+                    Some((value, tail)) if !last => {
+                        remaining = tail;
+                        value
+                    }
+                    None if last => std::mem::take(&mut remaining),
+                    _ => return Err(mismatch()),
+                },
+            };
+            match level {
+                Level::Literal(literal) if literal != value => return Err(mismatch()),
+                Level::Literal(_) => {}
+                Level::Attribute(name) | Level::Rest(name) => {
+                    if value.split(self.syntax.separator).any(str::is_empty) {
+                        return Err(mismatch());
+                    }
+                    values.push((name.as_str(), value));
+                }
+            }
+        }
+        for (name, value) in values {
+            match name {
+                "id" => {
+                    event.set_id(value);
+                }
+                "source" => {
+                    event.set_source(value);
+                }
+                "type" => {
+                    event.set_type(value);
+                }
+                "subject" => {
+                    event.set_subject(Some(value));
+                }
+                name => event.try_set_extension(name, value)?,
+            }
+        }
+        Ok(())
+    }
+}
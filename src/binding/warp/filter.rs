@@ -1,6 +1,7 @@
 use warp_lib as warp;
 
 use crate::binding::http_0_2 as http;
+use crate::binding::{DuplicateHeaders, Limits};
 
 use crate::Event;
 use warp::http::HeaderMap;
@@ -33,13 +34,67 @@ impl warp::reject::Reject for EventFilterError {}
 /// ```
 ///
 pub fn to_event() -> impl Filter<Extract = (Event,), Error = Rejection> + Copy {
+    to_event_with(DuplicateHeaders::default())
+}
+
+///
+/// # Extracts [`crate::Event`] from incoming request, applying the `duplicates` policy to the repeated headers carrying an attribute
+///
+/// ```
+/// # use warp_lib as warp;
+/// use cloudevents::binding::warp::filter::to_event_with;
+/// use cloudevents::binding::DuplicateHeaders;
+/// use warp::Filter;
+///
+/// let routes = warp::any()
+///    .and(to_event_with(DuplicateHeaders::Reject))
+///    .map(|event| {
+///         // do something with the event
+///     }
+/// );
+/// ```
+///
+pub fn to_event_with(
+    duplicates: DuplicateHeaders,
+) -> impl Filter<Extract = (Event,), Error = Rejection> + Copy {
+    to_event_with_limits(duplicates, Limits::default())
+}
+
+///
+/// # Extracts [`crate::Event`] from incoming request, rejecting the requests exceeding the `limits`
+///
+/// ```
+/// # use warp_lib as warp;
+/// use cloudevents::binding::warp::filter::to_event_with_limits;
+/// use cloudevents::binding::{DuplicateHeaders, Limits};
+/// use warp::Filter;
+///
+/// let limits = Limits::new().max_extensions(16).max_value_len(1024);
+/// let routes = warp::any()
+///    .and(warp::body::content_length_limit(64 * 1024))
+///    .and(to_event_with_limits(DuplicateHeaders::Reject, limits))
+///    .map(|event| {
+///         // do something with the event
+///     }
+/// );
+/// ```
+///
+pub fn to_event_with_limits(
+    duplicates: DuplicateHeaders,
+    limits: Limits,
+) -> impl Filter<Extract = (Event,), Error = Rejection> + Copy {
     warp::header::headers_cloned()
         .and(warp::body::bytes())
-        .and_then(create_event)
+        .and_then(move |headers, body| create_event(headers, body, duplicates, limits))
 }
 
-async fn create_event(headers: HeaderMap, body: bytes::Bytes) -> Result<Event, Rejection> {
-    http::to_event(&headers, body.to_vec())
+async fn create_event(
+    headers: HeaderMap,
+    body: bytes::Bytes,
+    duplicates: DuplicateHeaders,
+    limits: Limits,
+) -> Result<Event, Rejection> {
+    http::to_event_with_limits(&headers, body, duplicates, limits)
         .map_err(|error| warp::reject::custom(EventFilterError { error }))
 }
 
@@ -69,6 +124,74 @@ mod tests {
         assert_eq!(expected, result);
     }
 
+    #[tokio::test]
+    async fn test_duplicate_headers() {
+        use super::{create_event, EventFilterError};
+        use crate::binding::DuplicateHeaders;
+        use crate::AttributesReader;
+        use warp::http::{HeaderMap, HeaderValue};
+
+        // The test requests can't repeat a header, so call the filter function directly
+        let mut headers = HeaderMap::new();
+        headers.insert("ce-specversion", HeaderValue::from_static("1.0"));
+        headers.insert("ce-id", HeaderValue::from_static("0001"));
+        headers.insert("ce-type", HeaderValue::from_static("example.test"));
+        headers.insert("ce-source", HeaderValue::from_static("http://localhost/"));
+        headers.append("ce-id", HeaderValue::from_static("0002"));
+
+        let event = create_event(
+            headers.clone(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(event.id(), "0002");
+
+        let rejection = create_event(
+            headers,
+            Default::default(),
+            DuplicateHeaders::Reject,
+            Default::default(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            rejection.find::<EventFilterError>(),
+            Some(EventFilterError {
+                error: crate::message::Error::DuplicateHeader { .. }
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_limits() {
+        use super::{to_event_with_limits, EventFilterError};
+        use crate::binding::{DuplicateHeaders, Limits};
+
+        let result = test::request()
+            .method("POST")
+            .header("ce-specversion", "1.0")
+            .header("ce-id", "0001")
+            .header("ce-type", "test_event.test_application")
+            .header("ce-source", "http://localhost/")
+            .header("ce-someint", "10")
+            .filter(&to_event_with_limits(
+                DuplicateHeaders::default(),
+                Limits::new().max_attributes_len(64),
+            ))
+            .await;
+
+        let rejection = result.unwrap_err();
+        assert!(matches!(
+            rejection.find::<EventFilterError>(),
+            Some(EventFilterError {
+                error: crate::message::Error::AttributesTooLong { max: 64 }
+            })
+        ));
+    }
+
     #[tokio::test]
     async fn test_bad_request() {
         let result = test::request()
@@ -88,7 +211,7 @@ mod tests {
         let reason = rejection.find::<super::EventFilterError>().unwrap();
         assert_eq!(
             reason.error.to_string(),
-            "Invalid specversion BAD SPECIFICATION"
+            "Invalid specversion `BAD SPECIFICATION` in `ce-specversion`, supported versions: 0.3, 1.0"
         )
     }
 
@@ -104,9 +227,9 @@ mod tests {
             .header("ce-source", "http://localhost/")
             .header("ce-subject", "cloudevents-sdk")
             .header("content-type", "application/json")
-            .header("ce-string_ex", "val")
-            .header("ce-int_ex", "10")
-            .header("ce-bool_ex", "true")
+            .header("ce-stringex", "val")
+            .header("ce-intex", "10")
+            .header("ce-boolex", "true")
             .header("ce-time", &fixtures::time().to_rfc3339())
             .json(&fixtures::json_data())
             .filter(&to_event())
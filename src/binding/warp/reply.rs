@@ -102,7 +102,7 @@ mod tests {
             "application/json"
         );
         assert_eq!(
-            resp.headers().get("ce-int_ex").unwrap().to_str().unwrap(),
+            resp.headers().get("ce-intex").unwrap().to_str().unwrap(),
             "10"
         );
 
@@ -0,0 +1,231 @@
+//! This module provides [`EventBus`], an async in-memory broker delivering [`Event`]s
+//! between the components of a single process.
+//!
+//! Subscribers receive the events published on a topic, the events of a given type,
+//! or all the events, through a [`Subscription`]:
+//!
+//! ```
+//! use cloudevents::bus::EventBus;
+//! use cloudevents::{AttributesReader, EventBuilder, EventBuilderV10};
+//!
+//! # async fn example() {
+//! let bus = EventBus::new();
+//! let mut orders = bus.subscribe_topic("orders");
+//! let mut created = bus.subscribe_type("com.example.order.created");
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("com.example.order.created")
+//!     .source("http://localhost/")
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(bus.publish("orders", event).await, 2);
+//! assert_eq!(orders.recv().await.unwrap().id(), "0001");
+//! assert_eq!(created.recv().await.unwrap().id(), "0001");
+//! # }
+//! ```
+//!
+//! Every subscription has a bounded buffer: when it is full, [`EventBus::publish`] waits
+//! for the subscriber to catch up. Dropped subscriptions are removed on the next publish.
+
+use crate::event::AttributesReader;
+use crate::Event;
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+/// Default size of the buffer of each [`Subscription`]
+pub const DEFAULT_CAPACITY: usize = 128;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Filter {
+    All,
+    Topic(String),
+    Type(String),
+}
+
+impl Filter {
+    fn matches(&self, topic: &str, event: &Event) -> bool {
+        match self {
+            Filter::All => true,
+            Filter::Topic(t) => t == topic,
+            Filter::Type(ty) => ty == event.ty(),
+        }
+    }
+}
+
+struct Subscriber {
+    filter: Filter,
+    sender: mpsc::Sender<Event>,
+}
+
+/// In-memory broker delivering [`Event`]s to [`Subscription`]s.
+///
+/// The bus can be cheaply cloned, all the clones share the same subscriptions.
+#[derive(Clone)]
+pub struct EventBus {
+    subscribers: Arc<Mutex<Vec<Subscriber>>>,
+    capacity: usize,
+}
+
+impl EventBus {
+    /// Create a new [`EventBus`] with subscriptions buffering up to [`DEFAULT_CAPACITY`] events.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// Create a new [`EventBus`] with subscriptions buffering up to `capacity` events.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn with_capacity(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be greater than 0");
+        EventBus {
+            subscribers: Arc::new(Mutex::new(Vec::new())),
+            capacity,
+        }
+    }
+
+    /// Subscribe to all the events published on `topic`.
+    pub fn subscribe_topic(&self, topic: impl Into<String>) -> Subscription {
+        self.subscribe(Filter::Topic(topic.into()))
+    }
+
+    /// Subscribe to all the events with type `ty`, regardless of the topic.
+    pub fn subscribe_type(&self, ty: impl Into<String>) -> Subscription {
+        self.subscribe(Filter::Type(ty.into()))
+    }
+
+    /// Subscribe to all the events published on the bus.
+    pub fn subscribe_all(&self) -> Subscription {
+        self.subscribe(Filter::All)
+    }
+
+    fn subscribe(&self, filter: Filter) -> Subscription {
+        let (sender, receiver) = mpsc::channel(self.capacity);
+        self.subscribers
+            .lock()
+            .unwrap()
+            .push(Subscriber { filter, sender });
+        Subscription { receiver }
+    }
+
+    /// Publish `event` on `topic`, returning the number of subscriptions it was delivered to.
+    pub async fn publish(&self, topic: &str, event: Event) -> usize {
+        let senders: Vec<mpsc::Sender<Event>> = {
+            let mut subscribers = self.subscribers.lock().unwrap();
+            subscribers.retain(|s| !s.sender.is_closed());
+            subscribers
+                .iter()
+                .filter(|s| s.filter.matches(topic, &event))
+                .map(|s| s.sender.clone())
+                .collect()
+        };
+
+        let mut delivered = 0;
+        for sender in senders {
+            if sender.send(event.clone()).await.is_ok() {
+                delivered += 1;
+            }
+        }
+        delivered
+    }
+
+    /// Returns the number of active subscriptions.
+    pub fn subscriber_count(&self) -> usize {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        subscribers.retain(|s| !s.sender.is_closed());
+        subscribers.len()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Receiving side of a subscription to an [`EventBus`].
+///
+/// Dropping the subscription unsubscribes it from the bus.
+pub struct Subscription {
+    receiver: mpsc::Receiver<Event>,
+}
+
+impl Subscription {
+    /// Receive the next event, waiting for it to be published.
+    ///
+    /// Returns `None` when all the clones of the [`EventBus`] have been dropped
+    /// and the buffered events have been received.
+    pub async fn recv(&mut self) -> Option<Event> {
+        self.receiver.recv().await
+    }
+
+    /// Receive the next event if one is buffered, without waiting.
+    pub fn try_recv(&mut self) -> Option<Event> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use crate::{EventBuilder, EventBuilderV10};
+
+    #[tokio::test]
+    async fn publish_to_topic() {
+        let bus = EventBus::new();
+        let mut sub = bus.subscribe_topic("a");
+        let mut other = bus.subscribe_topic("b");
+
+        assert_eq!(bus.publish("a", fixtures::v10::minimal()).await, 1);
+
+        assert_eq!(sub.recv().await.unwrap(), fixtures::v10::minimal());
+        assert!(other.try_recv().is_none());
+    }
+
+    #[tokio::test]
+    async fn publish_to_type() {
+        let bus = EventBus::new();
+        let mut sub = bus.subscribe_type(fixtures::ty());
+        let mut other = bus.subscribe_type("other.type");
+        let mut all = bus.subscribe_all();
+
+        assert_eq!(bus.publish("a", fixtures::v10::minimal()).await, 2);
+
+        assert_eq!(sub.recv().await.unwrap(), fixtures::v10::minimal());
+        assert_eq!(all.recv().await.unwrap(), fixtures::v10::minimal());
+        assert!(other.try_recv().is_none());
+    }
+
+    #[tokio::test]
+    async fn dropped_subscriptions_are_removed() {
+        let bus = EventBus::new();
+        let sub = bus.subscribe_all();
+        let _other = bus.subscribe_all();
+        assert_eq!(bus.subscriber_count(), 2);
+
+        drop(sub);
+
+        assert_eq!(bus.subscriber_count(), 1);
+        assert_eq!(bus.publish("a", fixtures::v10::minimal()).await, 1);
+    }
+
+    #[tokio::test]
+    async fn subscription_ends_when_bus_is_dropped() {
+        let bus = EventBus::with_capacity(1);
+        let mut sub = bus.subscribe_all();
+        let event = EventBuilderV10::from(fixtures::v10::minimal())
+            .id("0002")
+            .build()
+            .unwrap();
+
+        bus.publish("a", event.clone()).await;
+        drop(bus);
+
+        assert_eq!(sub.recv().await, Some(event));
+        assert_eq!(sub.recv().await, None);
+    }
+}
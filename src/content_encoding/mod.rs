@@ -0,0 +1,249 @@
+//! This module compresses the `data` of an [`Event`] with gzip or deflate, recording the encoding
+//! in the `contentencoding` extension, to shrink large payloads on the wire.
+//!
+//! ```
+//! use cloudevents::content_encoding::{compress, decompress, ContentEncoding};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//! use serde_json::json;
+//!
+//! let mut event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("example.test")
+//!     .source("http://localhost/")
+//!     .data("application/json", json!({"hello": "world"}))
+//!     .build()
+//!     .unwrap();
+//!
+//! compress(&mut event, ContentEncoding::Gzip).unwrap();
+//! assert_eq!(
+//!     event.data_as::<serde_json::Value>().unwrap(),
+//!     Some(json!({"hello": "world"}))
+//! );
+//!
+//! decompress(&mut event).unwrap();
+//! assert!(event.extension("contentencoding").is_none());
+//! ```
+//!
+//! [`Event::data_as`] transparently decodes the compressed data. In binary mode, the HTTP
+//! bindings map the `contentencoding` extension to the `Content-Encoding` header.
+
+use crate::event::{Data, ExtensionValue};
+use crate::Event;
+use flate2::read::{DeflateDecoder, GzDecoder};
+use flate2::write::{DeflateEncoder, GzEncoder};
+use flate2::Compression;
+use snafu::Snafu;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+/// Name of the extension recording the encoding of the data.
+pub const CONTENT_ENCODING_EXTENSION: &str = "contentencoding";
+
+/// Represents an error while compressing or decompressing the data of an event
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Unsupported content encoding {}", name))]
+    UnsupportedEncoding { name: String },
+    #[snafu(display("The data is already encoded with {}", encoding))]
+    AlreadyEncoded { encoding: String },
+    #[snafu(display("IO Error: {}", source))]
+    #[snafu(context(false))]
+    IOError { source: std::io::Error },
+    #[snafu(display("Error while serializing the data: {}", source))]
+    #[snafu(context(false))]
+    SerializationError { source: serde_json::Error },
+}
+
+/// Result type alias for return values of the content encoding functions
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Supported encodings of the data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncoding {
+    Gzip,
+    Deflate,
+}
+
+impl ContentEncoding {
+    /// Name of the encoding, as used by the `contentencoding` extension and the HTTP
+    /// `Content-Encoding` header.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentEncoding::Gzip => "gzip",
+            ContentEncoding::Deflate => "deflate",
+        }
+    }
+
+    /// Compress `data`.
+    pub fn encode(&self, data: &[u8]) -> Result<Vec<u8>> {
+        Ok(match self {
+            ContentEncoding::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()?
+            }
+            ContentEncoding::Deflate => {
+                let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+                encoder.write_all(data)?;
+                encoder.finish()?
+            }
+        })
+    }
+
+    /// Decompress `data`.
+    pub fn decode(&self, data: &[u8]) -> Result<Vec<u8>> {
+        let mut decoded = Vec::new();
+        match self {
+            ContentEncoding::Gzip => GzDecoder::new(data).read_to_end(&mut decoded)?,
+            ContentEncoding::Deflate => DeflateDecoder::new(data).read_to_end(&mut decoded)?,
+        };
+        Ok(decoded)
+    }
+
+    /// Get the encoding of the data of `event` from its `contentencoding` extension.
+    pub fn of(event: &Event) -> Result<Option<Self>> {
+        match event.extension(CONTENT_ENCODING_EXTENSION) {
+            None => Ok(None),
+            Some(ExtensionValue::String(s)) => s.parse().map(Some),
+            Some(v) => Err(Error::UnsupportedEncoding {
+                name: v.to_string(),
+            }),
+        }
+    }
+}
+
+impl FromStr for ContentEncoding {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim() {
+            s if s.eq_ignore_ascii_case("gzip") => Ok(ContentEncoding::Gzip),
+            s if s.eq_ignore_ascii_case("deflate") => Ok(ContentEncoding::Deflate),
+            _ => Err(Error::UnsupportedEncoding {
+                name: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for ContentEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Compress the data of `event` with `encoding`, setting the `contentencoding` extension.
+///
+/// Events without data are left unchanged.
+pub fn compress(event: &mut Event, encoding: ContentEncoding) -> Result<()> {
+    if let Some(ExtensionValue::String(s)) = event.extension(CONTENT_ENCODING_EXTENSION) {
+        return Err(Error::AlreadyEncoded {
+            encoding: s.clone(),
+        });
+    }
+    let data = match event.data.take() {
+        Some(data) => data,
+        None => return Ok(()),
+    };
+
+    let encoded = Vec::try_from(data.clone())
+        .map_err(Error::from)
+        .and_then(|bytes| encoding.encode(&bytes));
+    match encoded {
+        Ok(encoded) => {
+            event.data = Some(Data::Binary(encoded));
+            event.set_extension(CONTENT_ENCODING_EXTENSION, encoding.as_str());
+            Ok(())
+        }
+        Err(e) => {
+            event.data = Some(data);
+            Err(e)
+        }
+    }
+}
+
+/// Decompress the data of `event` as recorded by its `contentencoding` extension, removing the
+/// extension.
+///
+/// Events without the extension are left unchanged.
+pub fn decompress(event: &mut Event) -> Result<()> {
+    let encoding = match ContentEncoding::of(event)? {
+        Some(encoding) => encoding,
+        None => return Ok(()),
+    };
+    if let Some(bytes) = event.data().and_then(Data::as_bytes) {
+        event.data = Some(Data::Binary(encoding.decode(bytes)?));
+    }
+    event.remove_extension(CONTENT_ENCODING_EXTENSION);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+
+    #[test]
+    fn round_trip() {
+        for encoding in [ContentEncoding::Gzip, ContentEncoding::Deflate] {
+            let mut event = fixtures::v10::full_json_data();
+            compress(&mut event, encoding).unwrap();
+
+            assert_eq!(
+                event.extension(CONTENT_ENCODING_EXTENSION),
+                Some(&ExtensionValue::from(encoding.as_str()))
+            );
+            assert!(matches!(event.data(), Some(Data::Binary(_))));
+            assert_eq!(
+                event.data_as::<serde_json::Value>().unwrap(),
+                Some(fixtures::json_data())
+            );
+            assert!(matches!(
+                compress(&mut event, encoding),
+                Err(Error::AlreadyEncoded { .. })
+            ));
+
+            decompress(&mut event).unwrap();
+            assert_eq!(event.extension(CONTENT_ENCODING_EXTENSION), None);
+            assert_eq!(
+                event.data(),
+                Some(&Data::Binary(fixtures::json_data_binary()))
+            );
+        }
+    }
+
+    #[test]
+    fn unsupported_encoding() {
+        let mut event = fixtures::v10::full_binary_json_data_string_extension();
+        event.set_extension(CONTENT_ENCODING_EXTENSION, "br");
+
+        assert!(matches!(
+            decompress(&mut event),
+            Err(Error::UnsupportedEncoding { .. })
+        ));
+        assert!(event.data_as::<serde_json::Value>().is_err());
+    }
+
+    #[cfg(feature = "http-binding")]
+    #[test]
+    fn http_binary_mode() {
+        use std::convert::TryFrom;
+
+        let mut event = fixtures::v10::full_binary_json_data_string_extension();
+        compress(&mut event, ContentEncoding::Gzip).unwrap();
+
+        let request = http::Request::<Option<Vec<u8>>>::try_from(event.clone()).unwrap();
+        assert_eq!(request.headers()["content-encoding"], "gzip");
+        assert!(request.headers().get("ce-contentencoding").is_none());
+
+        let (parts, body) = request.into_parts();
+        let received = crate::binding::http::to_event(&parts.headers, body.unwrap()).unwrap();
+        assert_eq!(received, event);
+        assert_eq!(
+            received.data_as::<serde_json::Value>().unwrap(),
+            Some(fixtures::json_data())
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! This module provides consumer side deduplication of [`Event`]s, which at-least-once
+//! transports can deliver more than once.
+//!
+//! Events are identified by their `source` and `id` attributes, as defined by the
+//! specification. The keys already seen are kept by a [`DedupStore`]: [`InMemoryStore`]
+//! is a bounded LRU cache whose entries expire after a TTL.
+//!
+//! ```
+//! use cloudevents::dedup::{deduplicate, DedupStore, InMemoryStore};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//! use futures::stream::{self, StreamExt};
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! # async fn example() {
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("example.test")
+//!     .source("http://localhost/")
+//!     .build()
+//!     .unwrap();
+//!
+//! let store = Arc::new(InMemoryStore::new(10_000, Duration::from_secs(3600)));
+//! assert!(!store.is_duplicate(&event).await);
+//! assert!(store.is_duplicate(&event).await);
+//!
+//! let events = stream::iter(vec![event.clone(), event.clone()]);
+//! assert_eq!(deduplicate(events, store).count().await, 0);
+//! # }
+//! ```
+
+use crate::event::AttributesReader;
+use crate::Event;
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Key identifying an [`Event`]: its `source` and `id` attributes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DedupKey {
+    pub source: String,
+    pub id: String,
+}
+
+impl From<&Event> for DedupKey {
+    fn from(event: &Event) -> Self {
+        DedupKey {
+            source: event.source().to_string(),
+            id: event.id().to_string(),
+        }
+    }
+}
+
+/// Store of the [`DedupKey`]s already seen.
+///
+/// Implementations backed by an external system should return `true` from
+/// [`insert`](Self::insert) when they cannot reach it, so events are processed
+/// at least once rather than dropped.
+#[async_trait]
+pub trait DedupStore: Send + Sync {
+    /// Record `key`, returning `true` if it was not already present.
+    async fn insert(&self, key: DedupKey) -> bool;
+
+    /// Record `event`, returning `true` if an event with the same `source` and `id`
+    /// was already recorded.
+    async fn is_duplicate(&self, event: &Event) -> bool {
+        !self.insert(DedupKey::from(event)).await
+    }
+}
+
+#[async_trait]
+impl<D: DedupStore + ?Sized> DedupStore for Arc<D> {
+    async fn insert(&self, key: DedupKey) -> bool {
+        self.as_ref().insert(key).await
+    }
+}
+
+/// In-memory [`DedupStore`] keeping at most `capacity` keys for at most `ttl`.
+///
+/// When full, the least recently seen key is evicted.
+#[derive(Debug)]
+pub struct InMemoryStore {
+    capacity: usize,
+    ttl: Duration,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    entries: HashMap<DedupKey, (Instant, u64)>,
+    recency: BTreeMap<u64, DedupKey>,
+    counter: u64,
+}
+
+impl InMemoryStore {
+    /// Create a new [`InMemoryStore`].
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        InMemoryStore {
+            capacity,
+            ttl,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Number of keys in the store, including the expired keys not evicted yet.
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().entries.len()
+    }
+
+    /// Returns `true` if the store contains no key.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn insert_at(&self, key: DedupKey, now: Instant) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        state.counter += 1;
+        let counter = state.counter;
+
+        match state.entries.get_mut(&key) {
+            Some((seen, recency)) => {
+                let expired = now.duration_since(*seen) >= self.ttl;
+                if expired {
+                    *seen = now;
+                }
+                state.recency.remove(recency);
+                *recency = counter;
+                state.recency.insert(counter, key);
+                expired
+            }
+            None => {
+                if state.entries.len() >= self.capacity {
+                    if let Some((_, oldest)) = state.recency.pop_first() {
+                        state.entries.remove(&oldest);
+                    }
+                }
+                if self.capacity > 0 {
+                    state.entries.insert(key.clone(), (now, counter));
+                    state.recency.insert(counter, key);
+                }
+                true
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl DedupStore for InMemoryStore {
+    async fn insert(&self, key: DedupKey) -> bool {
+        self.insert_at(key, Instant::now())
+    }
+}
+
+/// Drop the events of `events` which are duplicates according to `store`.
+pub fn deduplicate<S, D>(events: S, store: D) -> impl Stream<Item = Event>
+where
+    S: Stream<Item = Event>,
+    D: DedupStore + Clone + 'static,
+{
+    events.filter(move |event| {
+        let store = store.clone();
+        let key = DedupKey::from(event);
+        async move { store.insert(key).await }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use crate::AttributesWriter;
+    use futures::stream;
+
+    fn key(id: &str) -> DedupKey {
+        DedupKey {
+            source: fixtures::source(),
+            id: id.to_string(),
+        }
+    }
+
+    #[test]
+    fn lru_eviction() {
+        let store = InMemoryStore::new(2, Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert!(store.insert_at(key("1"), now));
+        assert!(store.insert_at(key("2"), now));
+        assert!(!store.insert_at(key("1"), now));
+        // "2" is the least recently seen key
+        assert!(store.insert_at(key("3"), now));
+        assert_eq!(store.len(), 2);
+        assert!(!store.insert_at(key("1"), now));
+        assert!(store.insert_at(key("2"), now));
+    }
+
+    #[test]
+    fn ttl_expiration() {
+        let store = InMemoryStore::new(10, Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert!(store.insert_at(key("1"), now));
+        assert!(!store.insert_at(key("1"), now + Duration::from_secs(59)));
+        assert!(store.insert_at(key("1"), now + Duration::from_secs(60)));
+        assert!(!store.insert_at(key("1"), now + Duration::from_secs(61)));
+    }
+
+    #[tokio::test]
+    async fn deduplicate_stream() {
+        let store = Arc::new(InMemoryStore::new(10, Duration::from_secs(60)));
+        let mut other_source = fixtures::v10::minimal();
+        other_source.set_source("http://example.com/");
+        let events = vec![
+            fixtures::v10::minimal(),
+            fixtures::v10::full_no_data(),
+            other_source.clone(),
+            fixtures::v10::minimal(),
+        ];
+
+        let deduplicated: Vec<Event> = deduplicate(stream::iter(events), store.clone())
+            .collect()
+            .await;
+
+        assert_eq!(deduplicated, vec![fixtures::v10::minimal(), other_source]);
+        assert!(store.is_duplicate(&fixtures::v03::minimal()).await);
+    }
+}
@@ -0,0 +1,235 @@
+//! This module provides the data model of the
+//! [CloudEvents Discovery API](https://github.com/cloudevents/spec/blob/main/discovery/spec.md),
+//! to publish the catalog of events produced by a service and consume the catalogs of others.
+//!
+//! ```
+//! use cloudevents::discovery::{EventType, Service};
+//! use url::Url;
+//!
+//! let mut service = Service::new(
+//!     "3db60532-2ed3-4f0c-b2e9-4c5a2b4c0e3b",
+//!     Url::parse("https://example.com/services/orders").unwrap(),
+//!     "orders",
+//!     Url::parse("https://example.com/subscriptions").unwrap(),
+//! );
+//! service.events.push(EventType::new("com.example.order.created"));
+//!
+//! let json = serde_json::to_string(&service).unwrap();
+//! assert_eq!(serde_json::from_str::<Service>(&json).unwrap(), service);
+//! assert!(service.event_type("com.example.order.created").is_some());
+//! ```
+
+use crate::event::SpecVersion;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use url::Url;
+
+/// A service exposing its events catalog through the Discovery API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Service {
+    /// Identifier of the service, unique within the discovery endpoint.
+    pub id: String,
+    /// Absolute URL of this service resource.
+    pub url: Url,
+    /// Version of the service resource, incremented on every change.
+    #[serde(default)]
+    pub epoch: u64,
+    /// Human readable name of the service.
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// URL of the documentation of the service.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docsurl: Option<Url>,
+    /// CloudEvents specification versions the service can produce.
+    #[serde(default = "default_specversions")]
+    pub specversions: Vec<String>,
+    /// URL of the Subscriptions API endpoint of the service.
+    pub subscriptionurl: Url,
+    /// Source specific configuration supported by the subscriptions, with its description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subscriptionconfig: Option<HashMap<String, String>>,
+    /// Authorization scope needed to create subscriptions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authscope: Option<String>,
+    /// Delivery protocols supported by the subscriptions, e.g. `HTTP` or `KAFKA`.
+    #[serde(default)]
+    pub protocols: Vec<String>,
+    /// Catalog of the events produced by the service.
+    #[serde(default)]
+    pub events: Vec<EventType>,
+}
+
+fn default_specversions() -> Vec<String> {
+    vec![SpecVersion::V10.to_string()]
+}
+
+impl Service {
+    /// Create a new [`Service`] with an empty events catalog.
+    pub fn new(
+        id: impl Into<String>,
+        url: Url,
+        name: impl Into<String>,
+        subscriptionurl: Url,
+    ) -> Self {
+        Service {
+            id: id.into(),
+            url,
+            epoch: 0,
+            name: name.into(),
+            description: None,
+            docsurl: None,
+            specversions: default_specversions(),
+            subscriptionurl,
+            subscriptionconfig: None,
+            authscope: None,
+            protocols: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Get the definition of the event type `ty`, if part of the catalog.
+    pub fn event_type(&self, ty: &str) -> Option<&EventType> {
+        self.events.iter().find(|e| e.ty == ty)
+    }
+}
+
+/// Definition of an event type produced by a [`Service`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventType {
+    /// Value of the `type` attribute of the events.
+    #[serde(rename = "type")]
+    pub ty: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Inline schema of the event data.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dataschemacontent: Option<String>,
+    /// Type of the schema of the event data, e.g. `JsonSchema`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dataschematype: Option<String>,
+    /// Value of the `dataschema` attribute of the events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dataschema: Option<Url>,
+    /// Value of the `datacontenttype` attribute of the events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datacontenttype: Option<String>,
+    /// [URI template](https://tools.ietf.org/html/rfc6570) of the `source` attribute of the events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sourcetemplate: Option<String>,
+    /// Extension attributes set on the events.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extensions: Vec<ExtensionDefinition>,
+}
+
+impl EventType {
+    /// Create a new [`EventType`] for the events of type `ty`.
+    pub fn new(ty: impl Into<String>) -> Self {
+        EventType {
+            ty: ty.into(),
+            description: None,
+            dataschemacontent: None,
+            dataschematype: None,
+            dataschema: None,
+            datacontenttype: None,
+            sourcetemplate: None,
+            extensions: Vec::new(),
+        }
+    }
+}
+
+/// Definition of an extension attribute set on the events of an [`EventType`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtensionDefinition {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: AttributeType,
+    /// URL of the specification of the extension.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub specurl: Option<Url>,
+}
+
+/// Types of the [CloudEvents type system](https://github.com/cloudevents/spec/blob/v1.0/spec.md#type-system).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AttributeType {
+    Boolean,
+    Integer,
+    String,
+    Binary,
+    #[serde(rename = "URI")]
+    Uri,
+    #[serde(rename = "URI-reference")]
+    UriReference,
+    Timestamp,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn service_json() -> Value {
+        json!({
+            "id": "3db60532-2ed3-4f0c-b2e9-4c5a2b4c0e3b",
+            "url": "https://example.com/services/orders",
+            "epoch": 3,
+            "name": "orders",
+            "description": "Order management",
+            "docsurl": "https://example.com/docs/orders",
+            "specversions": ["1.0"],
+            "subscriptionurl": "https://example.com/subscriptions",
+            "subscriptionconfig": {"region": "Region of the orders"},
+            "authscope": "orders:read",
+            "protocols": ["HTTP", "KAFKA"],
+            "events": [
+                {
+                    "type": "com.example.order.created",
+                    "description": "An order was created",
+                    "dataschematype": "JsonSchema",
+                    "dataschema": "https://example.com/schemas/order.json",
+                    "datacontenttype": "application/json",
+                    "sourcetemplate": "/orders/{region}",
+                    "extensions": [
+                        {"name": "partitionkey", "type": "String", "specurl": "https://github.com/cloudevents/spec/blob/v1.0/extensions/partitioning.md"},
+                        {"name": "sequence", "type": "URI-reference"}
+                    ]
+                },
+                {"type": "com.example.order.deleted"}
+            ]
+        })
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        let service: Service = serde_json::from_value(service_json()).unwrap();
+
+        assert_eq!(service.epoch, 3);
+        assert_eq!(service.specversions, vec!["1.0"]);
+        let created = service.event_type("com.example.order.created").unwrap();
+        assert_eq!(created.extensions[1].ty, AttributeType::UriReference);
+        assert!(service.event_type("com.example.order.updated").is_none());
+
+        assert_eq!(serde_json::to_value(service).unwrap(), service_json());
+    }
+
+    #[test]
+    fn defaults() {
+        let service: Service = serde_json::from_value(json!({
+            "id": "orders",
+            "url": "https://example.com/services/orders",
+            "name": "orders",
+            "subscriptionurl": "https://example.com/subscriptions"
+        }))
+        .unwrap();
+
+        assert_eq!(
+            service,
+            Service::new(
+                "orders",
+                Url::parse("https://example.com/services/orders").unwrap(),
+                "orders",
+                Url::parse("https://example.com/subscriptions").unwrap()
+            )
+        );
+    }
+}
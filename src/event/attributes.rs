@@ -1,7 +1,8 @@
 use super::{
     AttributesIntoIteratorV03, AttributesIntoIteratorV10, AttributesV03, AttributesV10,
-    ExtensionValue, SpecVersion, UriReference,
+    ExtensionValue, SpecVersion, Time, UriReference,
 };
+use base64::prelude::*;
 use chrono::{DateTime, Utc};
 use serde::Serializer;
 use std::fmt;
@@ -37,7 +38,7 @@ impl fmt::Display for AttributeValue<'_> {
             AttributeValue::Boolean(b) => f.serialize_bool(**b),
             AttributeValue::Integer(i) => f.serialize_i64(**i),
             AttributeValue::String(s) => f.write_str(s),
-            AttributeValue::Binary(b) => f.write_str(&base64::encode(b)),
+            AttributeValue::Binary(b) => f.write_str(&BASE64_STANDARD.encode(b)),
             AttributeValue::URI(s) => f.write_str(s.as_str()),
             AttributeValue::URIRef(s) => f.write_str(s.as_str()),
             AttributeValue::Time(s) => f.write_str(&s.to_rfc3339()),
@@ -246,7 +247,23 @@ impl Attributes {
         }
     }
 
-    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, AttributeValue)> {
+    /// The `time` attribute, along with the text it was received as.
+    pub(crate) fn time_with_text(&self) -> Option<&Time> {
+        match self {
+            Attributes::V03(a) => a.time.as_ref(),
+            Attributes::V10(a) => a.time.as_ref(),
+        }
+    }
+
+    /// Replace the `time` attribute, keeping its offset.
+    pub(crate) fn replace_time(&mut self, time: Option<Time>) -> Option<Time> {
+        match self {
+            Attributes::V03(a) => std::mem::replace(&mut a.time, time),
+            Attributes::V10(a) => std::mem::replace(&mut a.time, time),
+        }
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, AttributeValue<'_>)> {
         match self {
             Attributes::V03(a) => AttributesIter::IterV03(a.into_iter()),
             Attributes::V10(a) => AttributesIter::IterV10(a.into_iter()),
@@ -0,0 +1,540 @@
+use super::{
+    AttributesReader, Data, Event, EventBinarySerializer, ExtensionValue, SpecVersion, Time,
+};
+use crate::event::data::is_json_content_type;
+use crate::message::{BinaryDeserializer, BinarySerializer, Error, MessageAttributeValue, Result};
+use base64::prelude::*;
+use serde::de::{self, MapAccess, Visitor};
+use serde::{Deserialize, Deserializer};
+use serde_json::value::RawValue;
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// Attributes of an [`EventRef`], borrowed from the buffer they were read from when possible.
+///
+/// The values are kept as received: `time` is parsed and `source`/`dataschema` are validated
+/// only when converting to an [`Event`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributesRef<'a> {
+    pub specversion: SpecVersion,
+    pub id: Cow<'a, str>,
+    pub ty: Cow<'a, str>,
+    pub source: Cow<'a, str>,
+    pub datacontenttype: Option<Cow<'a, str>>,
+    /// `dataschema`, or `schemaurl` in v0.3.
+    pub dataschema: Option<Cow<'a, str>>,
+    pub subject: Option<Cow<'a, str>>,
+    pub time: Option<Cow<'a, str>>,
+}
+
+/// Borrowed counterpart of [`ExtensionValue`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtensionValueRef<'a> {
+    String(Cow<'a, str>),
+    Boolean(bool),
+    Integer(i64),
+}
+
+/// Borrowed counterpart of [`Data`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataRef<'a> {
+    Binary(Cow<'a, [u8]>),
+    String(Cow<'a, str>),
+    /// JSON text, parsed only when converting to an [`Event`].
+    Json(Cow<'a, str>),
+}
+
+/// Borrowed view of an [`Event`], which deserializers can produce without copying the
+/// attributes and the data out of the incoming message.
+///
+/// Convert it to an owned [`Event`] with [`EventRef::into_event`] when needed, or forward it
+/// to another binding with [`BinaryDeserializer`]:
+///
+/// ```
+/// use cloudevents::event::{EventRef, SpecVersion};
+/// use cloudevents::AttributesReader;
+///
+/// let headers = [
+///     ("specversion", "1.0"),
+///     ("id", "0001"),
+///     ("type", "example.test"),
+///     ("source", "http://localhost/"),
+///     ("someint", "10"),
+/// ];
+/// let event_ref = EventRef::from_attributes(headers, None).unwrap();
+/// assert_eq!(event_ref.attributes.id, "0001");
+///
+/// let event = event_ref.into_event().unwrap();
+/// assert_eq!(event.specversion(), SpecVersion::V10);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventRef<'a> {
+    pub attributes: AttributesRef<'a>,
+    pub data: Option<DataRef<'a>>,
+    pub extensions: Vec<(Cow<'a, str>, ExtensionValueRef<'a>)>,
+}
+
+impl<'a> EventRef<'a> {
+    /// Create a new [`EventRef`] from `(name, value)` pairs, as read from the headers of a
+    /// binary mode message, and its `data`.
+    ///
+    /// The `specversion` pair is required, and the names of the attributes unknown to it are
+    /// treated as extensions.
+    pub fn from_attributes<N, V>(
+        attributes: impl IntoIterator<Item = (N, V)>,
+        data: Option<DataRef<'a>>,
+    ) -> Result<Self>
+    where
+        N: Into<Cow<'a, str>>,
+        V: Into<Cow<'a, str>>,
+    {
+        let mut specversion = None;
+        let mut id = None;
+        let mut ty = None;
+        let mut source = None;
+        let mut datacontenttype = None;
+        let mut dataschema = None;
+        let mut subject = None;
+        let mut time = None;
+        let mut extensions = Vec::new();
+
+        for (name, value) in attributes {
+            let name = name.into();
+            let value = value.into();
+            match name.as_ref() {
+                "specversion" => {
+                    specversion = Some(
+                        SpecVersion::try_from(value.as_ref())
+                            .map_err(|e| e.in_attribute("specversion"))?,
+                    )
+                }
+                "id" => id = Some(value),
+                "type" => ty = Some(value),
+                "source" => source = Some(value),
+                "datacontenttype" => datacontenttype = Some(value),
+                "dataschema" | "schemaurl" => dataschema = Some((name, value)),
+                "subject" => subject = Some(value),
+                "time" => time = Some(value),
+                _ => extensions.push((name, ExtensionValueRef::String(value))),
+            }
+        }
+
+        let missing = |attribute_name| Error::EventBuilderError {
+            source: crate::event::EventBuilderError::MissingRequiredAttribute { attribute_name },
+        };
+        let specversion = specversion.ok_or_else(|| missing("specversion"))?;
+        // dataschema and schemaurl are extensions in the spec version which doesn't define them
+        let dataschema = match dataschema {
+            Some((name, value)) if specversion.attribute_names().contains(&name.as_ref()) => {
+                Some(value)
+            }
+            Some((name, value)) => {
+                extensions.push((name, ExtensionValueRef::String(value)));
+                None
+            }
+            None => None,
+        };
+
+        Ok(EventRef {
+            attributes: AttributesRef {
+                specversion,
+                id: id.ok_or_else(|| missing("id"))?,
+                ty: ty.ok_or_else(|| missing("type"))?,
+                source: source.ok_or_else(|| missing("source"))?,
+                datacontenttype,
+                dataschema,
+                subject,
+                time,
+            },
+            data,
+            extensions,
+        })
+    }
+
+    /// Deserialize a structured mode JSON event, borrowing the attributes, the extensions and
+    /// the `data` from `slice` when they don't contain escape sequences.
+    pub fn from_json_slice(slice: &'a [u8]) -> serde_json::Result<Self> {
+        serde_json::from_slice(slice)
+    }
+
+    /// Like [`EventRef::from_json_slice`], but reading from a `str`.
+    pub fn from_json_str(s: &'a str) -> serde_json::Result<Self> {
+        serde_json::from_str(s)
+    }
+
+    /// Get the extension named `extension_name`.
+    pub fn extension(&self, extension_name: &str) -> Option<&ExtensionValueRef<'a>> {
+        self.extensions
+            .iter()
+            .find(|(k, _)| k == extension_name)
+            .map(|(_, v)| v)
+    }
+
+    /// Copy the borrowed values, to keep this [`EventRef`] after the buffer it was read from.
+    pub fn into_owned(self) -> EventRef<'static> {
+        let owned = |c: Cow<'a, str>| Cow::Owned(c.into_owned());
+        let a = self.attributes;
+        EventRef {
+            attributes: AttributesRef {
+                specversion: a.specversion,
+                id: owned(a.id),
+                ty: owned(a.ty),
+                source: owned(a.source),
+                datacontenttype: a.datacontenttype.map(owned),
+                dataschema: a.dataschema.map(owned),
+                subject: a.subject.map(owned),
+                time: a.time.map(owned),
+            },
+            data: self.data.map(|d| match d {
+                DataRef::Binary(b) => DataRef::Binary(Cow::Owned(b.into_owned())),
+                DataRef::String(s) => DataRef::String(owned(s)),
+                DataRef::Json(j) => DataRef::Json(owned(j)),
+            }),
+            extensions: self
+                .extensions
+                .into_iter()
+                .map(|(k, v)| {
+                    let v = match v {
+                        ExtensionValueRef::String(s) => ExtensionValueRef::String(owned(s)),
+                        ExtensionValueRef::Boolean(b) => ExtensionValueRef::Boolean(b),
+                        ExtensionValueRef::Integer(i) => ExtensionValueRef::Integer(i),
+                    };
+                    (owned(k), v)
+                })
+                .collect(),
+        }
+    }
+
+    /// Convert to an owned [`Event`], validating the attributes.
+    pub fn into_event(self) -> Result<Event> {
+        Event::try_from(self)
+    }
+
+    fn deserialize_attributes<R: Sized, V: BinarySerializer<R>>(
+        attributes: AttributesRef<'a>,
+        extensions: Vec<(Cow<'a, str>, ExtensionValueRef<'a>)>,
+        mut visitor: V,
+    ) -> Result<V> {
+        let string = |c: Cow<'a, str>| MessageAttributeValue::String(c.into_owned());
+        let dataschema_name = match attributes.specversion {
+            SpecVersion::V03 => "schemaurl",
+            SpecVersion::V10 => "dataschema",
+        };
+        visitor = visitor.set_spec_version(attributes.specversion)?;
+        visitor = visitor.set_attribute("id", string(attributes.id))?;
+        visitor = visitor.set_attribute("type", string(attributes.ty))?;
+        visitor = visitor.set_attribute("source", string(attributes.source))?;
+        if let Some(datacontenttype) = attributes.datacontenttype {
+            visitor = visitor.set_attribute("datacontenttype", string(datacontenttype))?;
+        }
+        if let Some(dataschema) = attributes.dataschema {
+            visitor = visitor.set_attribute(dataschema_name, string(dataschema))?;
+        }
+        if let Some(subject) = attributes.subject {
+            visitor = visitor.set_attribute("subject", string(subject))?;
+        }
+        if let Some(time) = attributes.time {
+            visitor = visitor.set_attribute("time", string(time))?;
+        }
+        for (name, value) in extensions {
+            let value = match value {
+                ExtensionValueRef::String(s) => string(s),
+                ExtensionValueRef::Boolean(b) => MessageAttributeValue::Boolean(b),
+                ExtensionValueRef::Integer(i) => MessageAttributeValue::Integer(i),
+            };
+            visitor = visitor.set_extension(&name, value)?;
+        }
+        Ok(visitor)
+    }
+}
+
+impl BinaryDeserializer for EventRef<'_> {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, visitor: V) -> Result<R> {
+        let visitor = Self::deserialize_attributes(self.attributes, self.extensions, visitor)?;
+        match self.data {
+            Some(DataRef::Binary(b)) => visitor.end_with_data(b.into_owned()),
+            Some(DataRef::String(s)) | Some(DataRef::Json(s)) => {
+                visitor.end_with_data(s.into_owned().into_bytes())
+            }
+            None => visitor.end(),
+        }
+    }
+
+    fn into_event(self) -> Result<Event> {
+        Event::try_from(self)
+    }
+}
+
+impl TryFrom<EventRef<'_>> for Event {
+    type Error = Error;
+
+    fn try_from(event_ref: EventRef<'_>) -> Result<Self> {
+        let data = match event_ref.data {
+            Some(DataRef::Binary(b)) => Some(Data::Binary(b.into_owned())),
+            Some(DataRef::String(s)) => Some(Data::String(s.into_owned())),
+            Some(DataRef::Json(j)) => Some(Data::Json(serde_json::from_str(&j)?)),
+            None => None,
+        };
+        let mut event = EventRef::deserialize_attributes(
+            event_ref.attributes,
+            event_ref.extensions,
+            EventBinarySerializer::new(),
+        )?
+        .end()?;
+        event.data = data;
+        Ok(event)
+    }
+}
+
+impl<'a> From<&'a Event> for EventRef<'a> {
+    fn from(event: &'a Event) -> Self {
+        EventRef {
+            attributes: AttributesRef {
+                specversion: event.specversion(),
+                id: Cow::Borrowed(event.id()),
+                ty: Cow::Borrowed(event.ty()),
+                source: Cow::Borrowed(event.source().as_str()),
+                datacontenttype: event.datacontenttype().map(Cow::Borrowed),
+                dataschema: event.dataschema().map(|u| Cow::Borrowed(u.as_str())),
+                subject: event.subject().map(Cow::Borrowed),
+                time: event.attributes.time_with_text().map(Time::to_text),
+            },
+            data: event.data().map(|d| match d {
+                Data::Binary(v) => DataRef::Binary(Cow::Borrowed(v)),
+                Data::Bytes(b) => DataRef::Binary(Cow::Borrowed(b)),
+                Data::String(s) => DataRef::String(Cow::Borrowed(s)),
+                Data::Json(j) => DataRef::Json(Cow::Owned(j.to_string())),
+            }),
+            extensions: event
+                .iter_extensions()
+                .map(|(k, v)| {
+                    let v = match v {
+                        ExtensionValue::String(s) => ExtensionValueRef::String(Cow::Borrowed(s)),
+                        ExtensionValue::Boolean(b) => ExtensionValueRef::Boolean(*b),
+                        ExtensionValue::Integer(i) => ExtensionValueRef::Integer(*i),
+                    };
+                    (Cow::Borrowed(k), v)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// String borrowed from the input unless it contains escape sequences.
+#[derive(Deserialize)]
+struct BorrowedStr<'a>(#[serde(borrow)] Cow<'a, str>);
+
+fn parse_raw<'a, T: Deserialize<'a>, E: de::Error>(raw: &'a RawValue) -> std::result::Result<T, E> {
+    serde_json::from_str(raw.get()).map_err(E::custom)
+}
+
+/// Deserializes the JSON event format. The `data` is kept as JSON text, so this works only
+/// with the `serde_json` deserializer.
+impl<'de> Deserialize<'de> for EventRef<'de> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        deserializer.deserialize_map(EventRefVisitor)
+    }
+}
+
+struct EventRefVisitor;
+
+impl<'de> Visitor<'de> for EventRefVisitor {
+    type Value = EventRef<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a CloudEvent in the JSON event format")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        let mut attributes = Vec::new();
+        let mut typed_extensions = Vec::new();
+        let mut data = None;
+        let mut data_base64 = None;
+        let mut datacontentencoding = None;
+
+        while let Some(BorrowedStr(key)) = map.next_key()? {
+            let raw: &'de RawValue = map.next_value()?;
+            if raw.get() == "null" && key != "data" {
+                continue;
+            }
+            match key.as_ref() {
+                "data" => data = Some(raw),
+                "data_base64" => data_base64 = Some(parse_raw::<BorrowedStr, _>(raw)?.0),
+                "datacontentencoding" => {
+                    datacontentencoding = Some(parse_raw::<BorrowedStr, _>(raw)?.0)
+                }
+                _ => match parse_raw::<serde_json::Value, A::Error>(raw) {
+                    Ok(serde_json::Value::Bool(b)) => {
+                        typed_extensions.push((key, ExtensionValueRef::Boolean(b)))
+                    }
+                    Ok(serde_json::Value::Number(n)) if n.is_i64() => typed_extensions
+                        .push((key, ExtensionValueRef::Integer(n.as_i64().unwrap()))),
+                    _ => attributes.push((key, parse_raw::<BorrowedStr, _>(raw)?.0)),
+                },
+            }
+        }
+
+        let mut event = EventRef::from_attributes(attributes, None).map_err(de::Error::custom)?;
+        event.extensions.extend(typed_extensions);
+
+        let is_json = is_json_content_type(
+            event
+                .attributes
+                .datacontenttype
+                .as_deref()
+                .unwrap_or("application/json"),
+        );
+        let base64 = match (data, data_base64, datacontentencoding) {
+            (Some(_), Some(_), _) => {
+                return Err(de::Error::custom(
+                    "Cannot have both data and data_base64 field",
+                ))
+            }
+            (None, Some(d), None) if event.attributes.specversion == SpecVersion::V10 => Some(d),
+            (Some(d), None, Some(dce)) if event.attributes.specversion == SpecVersion::V03 => {
+                if !dce.eq_ignore_ascii_case("base64") {
+                    return Err(de::Error::custom(format_args!(
+                        "unsupported datacontentencoding `{}`",
+                        dce
+                    )));
+                }
+                Some(parse_raw::<BorrowedStr, _>(d)?.0)
+            }
+            (None, None, None) => None,
+            (Some(d), None, None) => {
+                event.data = Some(if is_json {
+                    DataRef::Json(Cow::Borrowed(d.get()))
+                } else {
+                    DataRef::String(parse_raw::<BorrowedStr, _>(d)?.0)
+                });
+                None
+            }
+            _ => {
+                return Err(de::Error::custom(format_args!(
+                    "unexpected data encoding for spec version {}",
+                    event.attributes.specversion
+                )))
+            }
+        };
+        if let Some(base64) = base64 {
+            let bytes = BASE64_STANDARD
+                .decode(base64.as_bytes())
+                .map_err(de::Error::custom)?;
+            event.data = Some(match String::from_utf8(bytes) {
+                Ok(s) if is_json && serde_json::from_str::<de::IgnoredAny>(&s).is_ok() => {
+                    DataRef::Json(Cow::Owned(s))
+                }
+                Ok(s) => DataRef::Binary(Cow::Owned(s.into_bytes())),
+                Err(e) => DataRef::Binary(Cow::Owned(e.into_bytes())),
+            });
+        }
+
+        Ok(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+
+    #[test]
+    fn round_trip() {
+        for event in [
+            fixtures::v10::minimal(),
+            fixtures::v10::full_json_data(),
+            fixtures::v10::full_xml_string_data(),
+            fixtures::v10::full_xml_binary_data(),
+            fixtures::v03::full_json_data(),
+        ] {
+            let event_ref = EventRef::from(&event);
+            assert_eq!(event_ref.clone().into_owned(), event_ref);
+            assert_eq!(event_ref.into_event().unwrap(), event);
+        }
+    }
+
+    #[test]
+    fn from_attributes() {
+        let body = b"hello".to_vec();
+        let headers = vec![
+            ("specversion", "0.3"),
+            ("id", "0001"),
+            ("type", "example.test"),
+            ("source", "http://localhost/"),
+            ("dataschema", "http://localhost/schema"),
+            ("time", "2020-03-16T11:50:00Z"),
+        ];
+
+        let event_ref =
+            EventRef::from_attributes(headers, Some(DataRef::Binary(Cow::Borrowed(&body))))
+                .unwrap();
+
+        assert!(matches!(event_ref.attributes.id, Cow::Borrowed("0001")));
+        assert_eq!(event_ref.attributes.dataschema, None);
+        assert_eq!(
+            event_ref.extension("dataschema"),
+            Some(&ExtensionValueRef::String(Cow::Borrowed(
+                "http://localhost/schema"
+            )))
+        );
+
+        let event = event_ref.into_event().unwrap();
+        assert_eq!(event.specversion(), SpecVersion::V03);
+        assert_eq!(event.time(), Some(&fixtures::time()));
+        assert_eq!(event.data(), Some(&Data::Binary(body)));
+    }
+
+    #[test]
+    fn missing_attributes() {
+        for attributes in [
+            vec![("id", "0001")],
+            vec![("specversion", "1.0"), ("id", "0001")],
+        ] {
+            assert!(matches!(
+                EventRef::from_attributes(attributes, None),
+                Err(Error::EventBuilderError { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn deserialize_json() {
+        for json in [
+            fixtures::v10::minimal_json(),
+            fixtures::v10::full_no_data_json(),
+            fixtures::v10::full_json_data_json(),
+            fixtures::v10::full_json_base64_data_json(),
+            fixtures::v10::full_xml_string_data_json(),
+            fixtures::v10::full_xml_base64_data_json(),
+            fixtures::v03::minimal_json(),
+            fixtures::v03::full_json_data_json(),
+            fixtures::v03::full_json_base64_data_json(),
+            fixtures::v03::full_xml_string_data_json(),
+            fixtures::v03::full_xml_base64_data_json(),
+        ] {
+            let bytes = serde_json::to_vec(&json).unwrap();
+            let expected: Event = serde_json::from_value(json).unwrap();
+
+            let event_ref = EventRef::from_json_slice(&bytes).unwrap();
+
+            assert!(matches!(event_ref.attributes.id, Cow::Borrowed(_)));
+            assert_eq!(event_ref.into_event().unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn deserialize_json_borrows_data() {
+        let json = r#"{"specversion":"1.0","id":"0001","type":"example.test","source":"http://localhost/","data":{"hello":"world"}}"#;
+
+        let event_ref = EventRef::from_json_str(json).unwrap();
+
+        assert_eq!(
+            event_ref.data,
+            Some(DataRef::Json(Cow::Borrowed(r#"{"hello":"world"}"#)))
+        );
+    }
+}
@@ -1,5 +1,11 @@
-use super::Event;
+#[cfg(feature = "schema")]
+use super::Data;
+use super::{Event, ExtensionError, UriReferenceError};
+#[cfg(feature = "schema")]
+use crate::schema::SchemaRegistry;
 use snafu::Snafu;
+#[cfg(feature = "schema")]
+use url::Url;
 
 /// Trait to implement a builder for [`Event`]:
 /// ```
@@ -53,15 +59,143 @@ pub enum Error {
         source: url::ParseError,
     },
     #[snafu(display(
-        "Invalid value setting attribute '{}' with uriref type",
+        "Invalid value setting attribute '{}' with uriref type: {}",
         attribute_name,
+        source
+    ))]
+    InvalidUriRefError {
+        attribute_name: &'static str,
+        source: UriReferenceError,
+    },
+    #[snafu(display("Required attribute {} is empty", attribute_name))]
+    EmptyRequiredAttribute { attribute_name: &'static str },
+    #[snafu(display("{}", source))]
+    #[snafu(context(false))]
+    InvalidExtension { source: ExtensionError },
+    #[cfg(feature = "schema")]
+    #[snafu(display("No schema registered for the event type {}", ty))]
+    UnregisteredSchema { ty: String },
+    #[cfg(feature = "schema")]
+    #[snafu(display(
+        "The data doesn't conform to the schema {} of the event type {}: {}",
+        dataschema,
+        ty,
+        reason
+    ))]
+    DataSchemaViolation {
+        ty: String,
+        dataschema: String,
+        reason: String,
+    },
+    #[snafu(display(
+        "{}",
+        errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ")
     ))]
-    InvalidUriRefError { attribute_name: &'static str },
+    Multiple { errors: Vec<Error> },
+}
+
+impl Error {
+    /// The errors reported by [`EventBuilder::build`], which are many for
+    /// [`Error::Multiple`].
+    pub fn errors(&self) -> &[Error] {
+        match self {
+            Error::Multiple { errors } => errors,
+            error => std::slice::from_ref(error),
+        }
+    }
+
+    /// Turn the `errors` collected by a builder into a single [`Error`].
+    pub(crate) fn from_errors(mut errors: Vec<Error>) -> Self {
+        match errors.len() {
+            1 => errors.remove(0),
+            _ => Error::Multiple { errors },
+        }
+    }
+
+    fn attribute_name(&self) -> Option<&'static str> {
+        match self {
+            Error::MissingRequiredAttribute { attribute_name }
+            | Error::ParseTimeError { attribute_name, .. }
+            | Error::ParseUrlError { attribute_name, .. }
+            | Error::InvalidUriRefError { attribute_name, .. }
+            | Error::EmptyRequiredAttribute { attribute_name } => Some(attribute_name),
+            #[cfg(feature = "schema")]
+            Error::UnregisteredSchema { .. } | Error::DataSchemaViolation { .. } => None,
+            Error::InvalidExtension { .. } | Error::Multiple { .. } => None,
+        }
+    }
+}
+
+/// Check that the required attribute `attribute_name` is set and not empty, adding the
+/// problem to `errors` unless the builder already reported one for this attribute.
+pub(crate) fn check_required_attribute<T: AsRef<str>>(
+    errors: &mut Vec<Error>,
+    attribute_name: &'static str,
+    value: Option<T>,
+) -> Option<T> {
+    if errors
+        .iter()
+        .any(|e| e.attribute_name() == Some(attribute_name))
+    {
+        return value;
+    }
+    match &value {
+        None => errors.push(Error::MissingRequiredAttribute { attribute_name }),
+        Some(v) if v.as_ref().is_empty() => {
+            errors.push(Error::EmptyRequiredAttribute { attribute_name })
+        }
+        Some(_) => {}
+    }
+    value
+}
+
+/// Validate `data` against the schema registered in `registry` for the event type `ty`,
+/// returning the URI of the schema unless the problem is added to `errors`.
+#[cfg(feature = "schema")]
+pub(crate) fn check_registered_schema(
+    errors: &mut Vec<Error>,
+    registry: &SchemaRegistry,
+    ty: &str,
+    data: Option<&Data>,
+) -> Option<Url> {
+    let schema = match registry.get(ty) {
+        Some(schema) => schema,
+        None => {
+            errors.push(Error::UnregisteredSchema { ty: ty.to_string() });
+            return None;
+        }
+    };
+    if let Some(Err(e)) = data.map(|data| schema.validator.validate(data)) {
+        errors.push(Error::DataSchemaViolation {
+            ty: ty.to_string(),
+            dataschema: schema.dataschema.to_string(),
+            reason: e.to_string(),
+        });
+        return None;
+    }
+    Some(schema.dataschema.clone())
+}
+
+/// Check that the `id`, `type` and `source` attributes are not empty, as required by the spec.
+pub(crate) fn check_required_attributes(id: &str, ty: &str, source: &str) -> Result<(), Error> {
+    match [("id", id), ("type", ty), ("source", source)]
+        .iter()
+        .find(|(_, value)| value.is_empty())
+    {
+        Some((attribute_name, _)) => Err(Error::EmptyRequiredAttribute { attribute_name }),
+        None => Ok(()),
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::event::DeserializeMode;
     use crate::test::fixtures;
+    use crate::AttributesReader;
     use crate::Event;
     use crate::EventBuilder;
     use crate::EventBuilderV03;
@@ -220,6 +354,89 @@ mod tests {
         assert_eq!(deserialize_json, out_event)
     }
 
+    #[rstest(attribute, case::id("id"), case::ty("type"), case::source("source"))]
+    fn deserialize_json_with_empty_required_attribute(attribute: &str) {
+        let mut in_json = fixtures::v10::minimal_json();
+        in_json[attribute] = "".into();
+
+        let err = serde_json::from_value::<Event>(in_json.clone()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Required attribute {} is empty", attribute)
+        );
+
+        let event = crate::event::DeserializeOptions::new()
+            .allow_empty_required_attributes(true)
+            .from_value(in_json)
+            .unwrap();
+        assert_eq!(serde_json::to_value(event).unwrap()[attribute], "");
+    }
+
+    #[test]
+    fn deserialize_json_with_unknown_spec_version() {
+        let mut in_json = fixtures::v10::minimal_json();
+        in_json["specversion"] = "2.0".into();
+
+        let err = serde_json::from_value::<Event>(in_json.clone()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid specversion `2.0` in `specversion`, supported versions: 0.3, 1.0"
+        );
+
+        let options = crate::event::DeserializeOptions::new().assume_v10_spec_version(true);
+        assert_eq!(
+            options.from_value(in_json).unwrap(),
+            fixtures::v10::minimal()
+        );
+
+        let mut in_json = fixtures::v10::minimal_json();
+        in_json.as_object_mut().unwrap().remove("specversion");
+        assert_eq!(
+            options.from_value(in_json).unwrap(),
+            fixtures::v10::minimal()
+        );
+    }
+
+    #[rstest(
+        member,
+        value,
+        error,
+        case::null("subject", Value::Null, "null value for `subject`"),
+        case::invalid_name("Some_Ext", json!("value"), "invalid attribute name `Some_Ext`")
+    )]
+    fn deserialize_json_strict(member: &str, value: Value, error: &str) {
+        let mut in_json = fixtures::v10::minimal_json();
+        in_json[member] = value;
+        let options = crate::event::DeserializeOptions::new().mode(DeserializeMode::Strict);
+
+        assert_eq!(options.from_value(in_json).unwrap_err().to_string(), error);
+        assert_eq!(
+            options.from_value(fixtures::v10::minimal_json()).unwrap(),
+            fixtures::v10::minimal()
+        );
+    }
+
+    #[test]
+    fn deserialize_json_lenient() {
+        let mut in_json = fixtures::v10::full_json_data_json();
+        in_json["id"] = json!(1);
+        in_json["subject"] = Value::Null;
+        in_json["ratio"] = json!(0.5);
+        in_json["enabled"] = json!(true);
+
+        assert!(serde_json::from_value::<Event>(in_json.clone()).is_err());
+
+        let event = crate::event::DeserializeOptions::new()
+            .mode(DeserializeMode::Lenient)
+            .from_value(in_json)
+            .unwrap();
+        assert_eq!(event.id(), "1");
+        assert_eq!(event.subject(), None);
+        assert_eq!(event.extension("ratio"), Some(&"0.5".into()));
+        assert_eq!(event.extension("enabled"), Some(&"true".into()));
+        assert_eq!(event.data(), fixtures::v10::full_json_data().data());
+    }
+
     #[test]
     fn deserialize_with_null_attribute() {
         let in_json = json!({
@@ -1,20 +1,101 @@
+use base64::prelude::*;
+use bytes::Bytes;
 use serde_json::Value;
+use snafu::{ResultExt, Snafu};
 use std::convert::TryFrom;
 use std::fmt;
 use std::fmt::Formatter;
-use std::str;
 
 /// Event [data attribute](https://github.com/cloudevents/spec/blob/master/spec.md#event-data) representation
-#[derive(PartialEq, Eq, Debug, Clone)]
+///
+/// [`Data::Binary`] and [`Data::Bytes`] are equal when they hold the same bytes.
+#[derive(Debug, Clone)]
 pub enum Data {
     /// Event has a binary payload
     Binary(Vec<u8>),
+    /// Event has a binary payload in a reference counted buffer, shared when the event is cloned
+    Bytes(Bytes),
     /// Event has a non-json string payload
     String(String),
     /// Event has a json payload
     Json(serde_json::Value),
 }
 
+impl Data {
+    /// Get the binary payload, if the data is [`Data::Binary`] or [`Data::Bytes`].
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Data::Binary(v) => Some(v),
+            Data::Bytes(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    /// Convert the payload to bytes, serializing [`Data::Json`] to JSON.
+    ///
+    /// The `data_base64` of the structured mode events is already decoded to [`Data::Binary`]
+    /// by the event format, while a [`Data::String`] is converted as is, since it can't be told
+    /// apart from a string that happens to be valid base64: use [`Data::decode_base64()`] for
+    /// the strings known to be base64 encoded.
+    pub fn into_bytes(self) -> Vec<u8> {
+        Vec::try_from(self).expect("serializing a JSON value doesn't fail")
+    }
+
+    /// Convert the payload to a string, serializing [`Data::Json`] to JSON.
+    /// Fails if a binary payload isn't valid UTF-8.
+    pub fn into_string(self) -> Result<String, DataError> {
+        String::try_from(self).context(InvalidUtf8Snafu)
+    }
+
+    /// Convert the payload to a JSON value, parsing the string and binary payloads.
+    /// Fails if they aren't valid JSON.
+    pub fn into_json(self) -> Result<Value, DataError> {
+        Value::try_from(self).context(InvalidJsonSnafu)
+    }
+
+    /// Decode a base64 encoded payload, held by a [`Data::String`] or a JSON string, while the
+    /// binary payloads are returned as is. Fails if the payload isn't a valid base64 string.
+    pub fn decode_base64(self) -> Result<Vec<u8>, DataError> {
+        let encoded = match self {
+            Data::Binary(v) => return Ok(v),
+            Data::Bytes(b) => return Ok(Vec::from(b)),
+            Data::String(s) => s,
+            Data::Json(Value::String(s)) => s,
+            Data::Json(_) => return NotBase64Snafu.fail(),
+        };
+        BASE64_STANDARD.decode(encoded).context(InvalidBase64Snafu)
+    }
+}
+
+/// Error converting a [`Data`] payload with [`Data::into_string()`], [`Data::into_json()`] or
+/// [`Data::decode_base64()`].
+#[derive(Debug, Snafu)]
+pub enum DataError {
+    #[snafu(display("The data is not valid UTF-8: {}", source))]
+    InvalidUtf8 { source: std::string::FromUtf8Error },
+    #[snafu(display("The data is not valid JSON: {}", source))]
+    InvalidJson { source: serde_json::Error },
+    #[snafu(display("The data is not valid base64: {}", source))]
+    InvalidBase64 { source: base64::DecodeError },
+    #[snafu(display("The data is JSON which is not a base64 string"))]
+    NotBase64,
+}
+
+impl PartialEq for Data {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Data::String(a), Data::String(b)) => a == b,
+            (Data::Json(a), Data::Json(b)) => a == b,
+            _ => match (self.as_bytes(), other.as_bytes()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Eq for Data {}
+
 pub(crate) fn is_json_content_type(ct: &str) -> bool {
     ct.starts_with("application/json") || ct.starts_with("text/json") || ct.ends_with("+json")
 }
@@ -31,6 +112,12 @@ impl From<Vec<u8>> for Data {
     }
 }
 
+impl From<Bytes> for Data {
+    fn from(value: Bytes) -> Self {
+        Data::Bytes(value)
+    }
+}
+
 impl From<String> for Data {
     fn from(value: String) -> Self {
         Data::String(value)
@@ -49,6 +136,7 @@ impl TryFrom<Data> for serde_json::Value {
     fn try_from(value: Data) -> Result<Self, Self::Error> {
         match value {
             Data::Binary(v) => Ok(serde_json::from_slice(&v)?),
+            Data::Bytes(b) => Ok(serde_json::from_slice(&b)?),
             Data::Json(v) => Ok(v),
             Data::String(s) => Ok(serde_json::from_str(&s)?),
         }
@@ -61,18 +149,33 @@ impl TryFrom<Data> for Vec<u8> {
     fn try_from(value: Data) -> Result<Self, Self::Error> {
         match value {
             Data::Binary(v) => Ok(v),
+            Data::Bytes(b) => Ok(Vec::from(b)),
             Data::Json(v) => Ok(serde_json::to_vec(&v)?),
             Data::String(s) => Ok(s.into_bytes()),
         }
     }
 }
 
+impl TryFrom<Data> for Bytes {
+    type Error = serde_json::Error;
+
+    fn try_from(value: Data) -> Result<Self, Self::Error> {
+        match value {
+            Data::Bytes(b) => Ok(b),
+            Data::Binary(v) => Ok(Bytes::from(v)),
+            Data::Json(v) => Ok(Bytes::from(serde_json::to_vec(&v)?)),
+            Data::String(s) => Ok(Bytes::from(s)),
+        }
+    }
+}
+
 impl TryFrom<Data> for String {
     type Error = std::string::FromUtf8Error;
 
     fn try_from(value: Data) -> Result<Self, Self::Error> {
         match value {
             Data::Binary(v) => Ok(String::from_utf8(v)?),
+            Data::Bytes(b) => Ok(String::from_utf8(Vec::from(b))?),
             Data::Json(v) => Ok(v.to_string()),
             Data::String(s) => Ok(s),
         }
@@ -82,9 +185,88 @@ impl TryFrom<Data> for String {
 impl fmt::Display for Data {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            Data::Binary(vec) => write!(f, "Binary data: {:?}", str::from_utf8(vec).unwrap()),
+            Data::Binary(vec) => write!(f, "Binary data: {:?}", String::from_utf8_lossy(vec)),
+            Data::Bytes(b) => write!(f, "Binary data: {:?}", String::from_utf8_lossy(b)),
             Data::String(s) => write!(f, "String data: {}", s),
             Data::Json(j) => write!(f, "Json data: {}", j),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn display_invalid_utf8() {
+        assert_eq!(
+            Data::Binary(b"abc\xff".to_vec()).to_string(),
+            "Binary data: \"abc\u{fffd}\""
+        );
+        assert_eq!(
+            Data::Bytes(Bytes::from_static(b"\xfe")).to_string(),
+            "Binary data: \"\u{fffd}\""
+        );
+    }
+
+    #[test]
+    fn into_conversions() {
+        let json = json!({"hello": "world"});
+
+        assert_eq!(
+            Data::Json(json.clone()).into_bytes(),
+            br#"{"hello":"world"}"#
+        );
+        assert_eq!(Data::Bytes(Bytes::from_static(b"abc")).into_bytes(), b"abc");
+        assert_eq!(Data::from("abc").into_bytes(), b"abc");
+
+        assert_eq!(Data::Binary(b"abc".to_vec()).into_string().unwrap(), "abc");
+        assert_eq!(
+            Data::Json(json.clone()).into_string().unwrap(),
+            r#"{"hello":"world"}"#
+        );
+        assert!(matches!(
+            Data::Binary(vec![0xff]).into_string(),
+            Err(DataError::InvalidUtf8 { .. })
+        ));
+
+        assert_eq!(
+            Data::from(r#"{"hello":"world"}"#).into_json().unwrap(),
+            json
+        );
+        assert_eq!(
+            Data::Bytes(Bytes::from_static(br#"{"hello":"world"}"#))
+                .into_json()
+                .unwrap(),
+            json
+        );
+        assert!(matches!(
+            Data::from("abc").into_json(),
+            Err(DataError::InvalidJson { .. })
+        ));
+    }
+
+    #[test]
+    fn decode_base64() {
+        assert_eq!(Data::from("aGVsbG8=").decode_base64().unwrap(), b"hello");
+        assert_eq!(
+            Data::Json(json!("aGVsbG8=")).decode_base64().unwrap(),
+            b"hello"
+        );
+        assert_eq!(
+            Data::Binary(b"hello".to_vec()).decode_base64().unwrap(),
+            b"hello"
+        );
+        assert_eq!(Data::from("aGVsbG8=").into_bytes(), b"aGVsbG8=");
+
+        assert!(matches!(
+            Data::from("hello!").decode_base64(),
+            Err(DataError::InvalidBase64 { .. })
+        ));
+        assert!(matches!(
+            Data::Json(json!({"hello": "world"})).decode_base64(),
+            Err(DataError::NotBase64)
+        ));
+    }
+}
@@ -0,0 +1,72 @@
+use snafu::Snafu;
+
+/// Enum of the events an application handles, each variant matching an event type.
+///
+/// With the `derive` feature, `#[derive(EventDispatch)]` implements this trait along with
+/// `TryFrom<Event>`, which matches the `type` of the event against the variants and
+/// deserializes the data from JSON into the field of the matched variant, so adding an event
+/// type makes the compiler point at all the `match` to update:
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use cloudevents::event::{EventDispatch, EventDispatchError};
+/// use cloudevents::{Event, EventBuilder, EventBuilderV10};
+/// use serde::Deserialize;
+/// use std::convert::TryFrom;
+///
+/// #[derive(Deserialize)]
+/// struct OrderCreated {
+///     id: u64,
+/// }
+///
+/// #[derive(EventDispatch)]
+/// enum OrderEvent {
+///     #[cloudevents(ty = "com.example.order.created")]
+///     Created(OrderCreated),
+///     #[cloudevents(ty = "com.example.order.purged")]
+///     Purged,
+/// }
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .ty("com.example.order.created")
+///     .source("http://localhost/")
+///     .data("application/json", serde_json::json!({"id": 42}))
+///     .build()
+///     .unwrap();
+///
+/// let order_event = OrderEvent::try_from(event).unwrap();
+/// assert_eq!(order_event.event_type(), "com.example.order.created");
+/// match order_event {
+///     OrderEvent::Created(order) => assert_eq!(order.id, 42),
+///     OrderEvent::Purged => unreachable!(),
+/// }
+///
+/// let mut event = Event::default();
+/// cloudevents::AttributesWriter::set_type(&mut event, "com.example.order.shipped");
+/// assert!(matches!(
+///     OrderEvent::try_from(event),
+///     Err(EventDispatchError::UnknownType { .. })
+/// ));
+/// # }
+/// ```
+pub trait EventDispatch: Sized {
+    /// The event types matched by the variants.
+    const EVENT_TYPES: &'static [&'static str];
+
+    /// The event type matched by this variant.
+    fn event_type(&self) -> &'static str;
+}
+
+/// Error returned when converting an [`Event`](super::Event) to an [`EventDispatch`] enum.
+#[derive(Debug, Snafu)]
+pub enum EventDispatchError {
+    #[snafu(display("No variant matches the event type {}", ty))]
+    UnknownType { ty: String },
+    #[snafu(display("Invalid data for the event type {}: {}", ty, source))]
+    InvalidData {
+        ty: String,
+        source: serde_json::Error,
+    },
+}
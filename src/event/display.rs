@@ -0,0 +1,153 @@
+use super::{AttributesReader, Data, Event};
+use std::fmt;
+
+/// Displays an [`Event`] on one line, with its main attributes and the size of the data,
+/// e.g. `id=0001 type=example.test source=http://localhost/ data=string(5 bytes)`.
+///
+/// Extensions and the content of the data are omitted, making it suitable for production logs.
+/// Returned by [`Event::display_compact()`].
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayCompact<'a>(&'a Event);
+
+impl fmt::Display for DisplayCompact<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let event = self.0;
+        write!(
+            f,
+            "id={} type={} source={}",
+            event.id(),
+            event.ty(),
+            event.source()
+        )?;
+        if let Some(subject) = event.subject() {
+            write!(f, " subject={}", subject)?;
+        }
+        if let Some(time) = event.time() {
+            write!(f, " time={}", time.to_rfc3339())?;
+        }
+        match event.data() {
+            Some(Data::Binary(v)) => write!(f, " data=binary({} bytes)", v.len()),
+            Some(Data::Bytes(b)) => write!(f, " data=binary({} bytes)", b.len()),
+            Some(Data::String(s)) => write!(f, " data=string({} bytes)", s.len()),
+            Some(Data::Json(_)) => write!(f, " data=json"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Displays an [`Event`] on multiple lines, with all its attributes, extensions and data.
+/// Binary data is displayed as text, the invalid UTF-8 sequences being replaced with `�`.
+///
+/// This is the format of the [`Display`](fmt::Display) implementation of [`Event`].
+/// Returned by [`Event::display_pretty()`].
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayPretty<'a>(&'a Event);
+
+impl fmt::Display for DisplayPretty<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "CloudEvent:")?;
+        self.0
+            .iter_attributes()
+            .try_for_each(|(name, val)| writeln!(f, "  {}: '{}'", name, val))?;
+        self.0.iter_extensions().try_for_each(|(name, val)| {
+            writeln!(f, "  {}: '{}'", name, self.0.extensions.masked(name, val))
+        })?;
+        match self.0.data() {
+            Some(data) => write!(f, "  {}", data)?,
+            None => write!(f, "  No data")?,
+        }
+        writeln!(f)
+    }
+}
+
+/// Displays an [`Event`] in the JSON event format, with the values of the sensitive extensions
+/// replaced by [`SENSITIVE_EXTENSION_MASK`](super::SENSITIVE_EXTENSION_MASK).
+///
+/// Returned by [`Event::display_json()`].
+#[derive(Debug, Clone, Copy)]
+pub struct DisplayJson<'a>(&'a Event);
+
+impl fmt::Display for DisplayJson<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(&self.0.masked()).map_err(|_| fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
+impl Event {
+    /// Display the event on one line, see [`DisplayCompact`].
+    pub fn display_compact(&self) -> DisplayCompact<'_> {
+        DisplayCompact(self)
+    }
+
+    /// Display the event on multiple lines, see [`DisplayPretty`].
+    pub fn display_pretty(&self) -> DisplayPretty<'_> {
+        DisplayPretty(self)
+    }
+
+    /// Display the event as JSON, see [`DisplayJson`].
+    pub fn display_json(&self) -> DisplayJson<'_> {
+        DisplayJson(self)
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display_pretty().fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::event::SENSITIVE_EXTENSION_MASK;
+    use crate::test::fixtures;
+    use crate::Event;
+
+    #[test]
+    fn display_formats() {
+        let event = fixtures::v10::full_json_data();
+
+        assert_eq!(
+            event.display_compact().to_string(),
+            format!(
+                "id={} type={} source={} subject={} time={} data=json",
+                fixtures::id(),
+                fixtures::ty(),
+                fixtures::source(),
+                fixtures::subject(),
+                fixtures::time().to_rfc3339()
+            )
+        );
+        assert_eq!(event.display_pretty().to_string(), event.to_string());
+        assert!(event
+            .to_string()
+            .starts_with("CloudEvent:\n  specversion: '1.0'\n"));
+
+        let json = event.display_json().to_string();
+        assert!(!json.contains('\n'));
+        assert_eq!(serde_json::from_str::<Event>(&json).unwrap(), event);
+    }
+
+    #[test]
+    fn display_pretty_invalid_utf8() {
+        let mut event = fixtures::v10::minimal();
+        event.set_data("application/octet-stream", vec![b'a', 0xff, 0xfe]);
+
+        assert!(event
+            .display_pretty()
+            .to_string()
+            .ends_with("  Binary data: \"a\u{fffd}\u{fffd}\"\n"));
+    }
+
+    #[test]
+    fn display_json_masks_sensitive_extensions() {
+        let mut event = fixtures::v10::minimal();
+        event.set_extension("authtoken", "s3cr3t");
+        event.mark_sensitive_extension("authtoken");
+
+        let json = event.display_json().to_string();
+        assert!(!json.contains("s3cr3t"));
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json["authtoken"], SENSITIVE_EXTENSION_MASK);
+    }
+}
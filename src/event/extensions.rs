@@ -1,6 +1,13 @@
+use super::SENSITIVE_EXTENSION_MASK;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize, Serializer};
-use std::convert::From;
+use smallvec::SmallVec;
+use snafu::Snafu;
+use std::borrow::Cow;
+use std::convert::{From, TryFrom};
 use std::fmt;
+use std::iter::FromIterator;
+use url::Url;
 
 #[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -70,3 +77,513 @@ impl fmt::Display for ExtensionValue {
         }
     }
 }
+
+/// Error returned by [`Event::try_set_extension()`](super::Event::try_set_extension) for an
+/// extension the spec doesn't allow.
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+pub enum ExtensionError {
+    #[snafu(display(
+        "Invalid extension name `{}`, expected 1 to {} lowercase ASCII letters or digits",
+        name,
+        MAX_NAME_LENGTH
+    ))]
+    InvalidName { name: String },
+    #[snafu(display(
+        "Value {} of the extension `{}` is out of the 32-bit signed integer range",
+        value,
+        name
+    ))]
+    IntegerOutOfRange { name: String, value: i64 },
+    #[snafu(display(
+        "Value of the extension `{}` contains the disallowed character {:?}",
+        name,
+        character
+    ))]
+    DisallowedCharacter { name: String, character: char },
+}
+
+/// Maximum length of an extension name. The spec says names SHOULD NOT exceed it.
+const MAX_NAME_LENGTH: usize = 20;
+
+/// Check that `name` and `value` follow the spec: the name is made of lowercase ASCII letters
+/// and digits, integers fit in 32 bits and strings don't contain control characters or
+/// Unicode noncharacters.
+pub(crate) fn validate_extension(name: &str, value: &ExtensionValue) -> Result<(), ExtensionError> {
+    if name.is_empty()
+        || name.len() > MAX_NAME_LENGTH
+        || !name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+    {
+        return Err(ExtensionError::InvalidName {
+            name: name.to_owned(),
+        });
+    }
+    match value {
+        ExtensionValue::Integer(i) if i32::try_from(*i).is_err() => {
+            Err(ExtensionError::IntegerOutOfRange {
+                name: name.to_owned(),
+                value: *i,
+            })
+        }
+        ExtensionValue::String(s) => match s.chars().find(|c| is_disallowed_char(*c)) {
+            Some(character) => Err(ExtensionError::DisallowedCharacter {
+                name: name.to_owned(),
+                character,
+            }),
+            None => Ok(()),
+        },
+        _ => Ok(()),
+    }
+}
+
+fn is_disallowed_char(c: char) -> bool {
+    let c = c as u32;
+    c <= 0x1F
+        || (0x7F..=0x9F).contains(&c)
+        || (0xFDD0..=0xFDEF).contains(&c)
+        || c & 0xFFFE == 0xFFFE
+}
+
+/// Types an [`ExtensionValue`] can be read as, see [`Event::extension_as()`](super::Event::extension_as).
+///
+/// Extensions received in binary mode are always strings, so besides the matching variant the
+/// implementations also parse the string representation of their type.
+pub trait FromExtensionValue: Sized {
+    /// Name of the type, reported in [`ExtensionTypeError`].
+    const TYPE_NAME: &'static str;
+
+    /// Convert `value` to `Self`.
+    fn from_extension_value(
+        value: &ExtensionValue,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+impl FromExtensionValue for String {
+    const TYPE_NAME: &'static str = "string";
+
+    fn from_extension_value(
+        value: &ExtensionValue,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(value.to_string())
+    }
+}
+
+impl FromExtensionValue for i64 {
+    const TYPE_NAME: &'static str = "integer";
+
+    fn from_extension_value(
+        value: &ExtensionValue,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        match value {
+            ExtensionValue::Integer(i) => Ok(*i),
+            ExtensionValue::String(s) => Ok(s.parse()?),
+            ExtensionValue::Boolean(_) => Err("the value is a boolean".into()),
+        }
+    }
+}
+
+impl FromExtensionValue for bool {
+    const TYPE_NAME: &'static str = "boolean";
+
+    fn from_extension_value(
+        value: &ExtensionValue,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        match value {
+            ExtensionValue::Boolean(b) => Ok(*b),
+            ExtensionValue::String(s) => Ok(s.parse()?),
+            ExtensionValue::Integer(_) => Err("the value is an integer".into()),
+        }
+    }
+}
+
+impl FromExtensionValue for DateTime<Utc> {
+    const TYPE_NAME: &'static str = "timestamp";
+
+    fn from_extension_value(
+        value: &ExtensionValue,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        match value {
+            ExtensionValue::String(s) => {
+                Ok(DateTime::parse_from_rfc3339(s).map(|t| t.with_timezone(&Utc))?)
+            }
+            ExtensionValue::Boolean(_) => Err("the value is a boolean".into()),
+            ExtensionValue::Integer(_) => Err("the value is an integer".into()),
+        }
+    }
+}
+
+impl FromExtensionValue for Url {
+    const TYPE_NAME: &'static str = "URI";
+
+    fn from_extension_value(
+        value: &ExtensionValue,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        match value {
+            ExtensionValue::String(s) => Ok(Url::parse(s)?),
+            ExtensionValue::Boolean(_) => Err("the value is a boolean".into()),
+            ExtensionValue::Integer(_) => Err("the value is an integer".into()),
+        }
+    }
+}
+
+/// Error returned by [`Event::extension_as()`](super::Event::extension_as) when an extension
+/// can't be converted to the requested type.
+#[derive(Debug)]
+pub struct ExtensionTypeError {
+    name: String,
+    value: ExtensionValue,
+    type_name: &'static str,
+    source: Box<dyn std::error::Error + Send + Sync>,
+}
+
+impl ExtensionTypeError {
+    pub(crate) fn new<T: FromExtensionValue>(
+        name: &str,
+        value: &ExtensionValue,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    ) -> Self {
+        ExtensionTypeError {
+            name: name.to_owned(),
+            value: value.clone(),
+            type_name: T::TYPE_NAME,
+            source,
+        }
+    }
+
+    /// Name of the extension.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Value of the extension.
+    pub fn value(&self) -> &ExtensionValue {
+        &self.value
+    }
+
+    /// Name of the requested type.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+impl fmt::Display for ExtensionTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Cannot read extension `{}` with value `{}` as {}: {}",
+            self.name, self.value, self.type_name, self.source
+        )
+    }
+}
+
+impl std::error::Error for ExtensionTypeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+/// Number of extensions stored inline by [`Extensions`] before spilling to the heap.
+const INLINE_EXTENSIONS: usize = 4;
+
+/// Storage of the extensions of an [`Event`](super::Event).
+///
+/// Events usually carry a handful of extensions, so they are kept inline in insertion order:
+/// lookups scan a few entries instead of hashing the name, and an event with up to four
+/// extensions doesn't allocate for them. Equality doesn't depend on the order of the extensions,
+/// nor on which of them are sensitive.
+#[derive(Clone, Default)]
+pub struct Extensions {
+    entries: SmallVec<[(String, ExtensionValue); INLINE_EXTENSIONS]>,
+    /// Names of the extensions whose values are masked by the serializers, see
+    /// [`Event::mark_sensitive_extension()`](super::Event::mark_sensitive_extension).
+    sensitive: Vec<String>,
+}
+
+impl Extensions {
+    /// Get the extension named `name`.
+    pub fn get(&self, name: &str) -> Option<&ExtensionValue> {
+        self.entries.iter().find(|(k, _)| k == name).map(|(_, v)| v)
+    }
+
+    /// Set the extension named `name`, returning its previous value.
+    pub fn insert(&mut self, name: String, value: ExtensionValue) -> Option<ExtensionValue> {
+        match self.entries.iter_mut().find(|(k, _)| *k == name) {
+            Some((_, v)) => Some(std::mem::replace(v, value)),
+            None => {
+                self.entries.push((name, value));
+                None
+            }
+        }
+    }
+
+    /// Append the extension named `name` without looking for a previous value, leaving the
+    /// duplicates to [`Extensions::dedup()`], so adding `n` extensions doesn't take `n²`
+    /// comparisons.
+    pub(crate) fn push(&mut self, name: String, value: ExtensionValue) {
+        self.entries.push((name, value));
+    }
+
+    /// Keep a single extension per name, with the value pushed last at the position of the
+    /// first one, as if they were all set with [`Extensions::insert()`].
+    pub(crate) fn dedup(&mut self) {
+        if self.entries.len() < 2 {
+            return;
+        }
+        // A stable sort keeps the extensions with the same name in insertion order
+        let mut order: Vec<usize> = (0..self.entries.len()).collect();
+        order.sort_by(|&a, &b| self.entries[a].0.cmp(&self.entries[b].0));
+
+        let mut duplicate = vec![false; self.entries.len()];
+        let mut start = 0;
+        while start < order.len() {
+            let mut end = start + 1;
+            while end < order.len() && self.entries[order[end]].0 == self.entries[order[start]].0 {
+                duplicate[order[end]] = true;
+                end += 1;
+            }
+            self.entries.swap(order[start], order[end - 1]);
+            start = end;
+        }
+
+        let mut index = 0;
+        self.entries.retain(|_| {
+            index += 1;
+            !duplicate[index - 1]
+        });
+    }
+
+    /// Remove the extension named `name`, returning its value.
+    pub fn remove(&mut self, name: &str) -> Option<ExtensionValue> {
+        let index = self.entries.iter().position(|(k, _)| k == name)?;
+        Some(self.entries.remove(index).1)
+    }
+
+    /// Number of extensions.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if there are no extensions.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Mark the extension named `name` as sensitive.
+    pub(crate) fn mark_sensitive(&mut self, name: String) {
+        if !self.is_sensitive(&name) {
+            self.sensitive.push(name);
+        }
+    }
+
+    /// Returns `true` if the extension named `name` is marked as sensitive.
+    pub(crate) fn is_sensitive(&self, name: &str) -> bool {
+        self.sensitive.iter().any(|sensitive| sensitive == name)
+    }
+
+    /// The value of the extension `name` to serialize: [`SENSITIVE_EXTENSION_MASK`] if it is
+    /// sensitive.
+    pub(crate) fn masked<'a>(
+        &self,
+        name: &str,
+        value: &'a ExtensionValue,
+    ) -> Cow<'a, ExtensionValue> {
+        if self.is_sensitive(name) {
+            Cow::Owned(ExtensionValue::from(SENSITIVE_EXTENSION_MASK))
+        } else {
+            Cow::Borrowed(value)
+        }
+    }
+
+    /// Iterate over the extensions to serialize, with the values of the sensitive ones replaced
+    /// by [`SENSITIVE_EXTENSION_MASK`] unless `include_secrets` is set.
+    pub(crate) fn iter_masked(
+        &self,
+        include_secrets: bool,
+    ) -> impl Iterator<Item = (&String, Cow<'_, ExtensionValue>)> + Clone {
+        self.iter().map(move |(k, v)| match include_secrets {
+            true => (k, Cow::Borrowed(v)),
+            false => (k, self.masked(k, v)),
+        })
+    }
+
+    /// Consume the extensions to serialize them, see [`Extensions::iter_masked()`].
+    pub(crate) fn into_masked(
+        self,
+        include_secrets: bool,
+    ) -> impl Iterator<Item = (String, ExtensionValue)> {
+        let sensitive = if include_secrets {
+            Vec::new()
+        } else {
+            self.sensitive
+        };
+        self.entries.into_iter().map(move |(k, v)| {
+            if sensitive.contains(&k) {
+                (k, ExtensionValue::from(SENSITIVE_EXTENSION_MASK))
+            } else {
+                (k, v)
+            }
+        })
+    }
+
+    /// Iterate mutably over the extensions, in insertion order.
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (&String, &mut ExtensionValue)> {
+        self.entries.iter_mut().map(|(k, v)| (&*k, v))
+    }
+
+    /// Iterate over the extensions, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &ExtensionValue)> + Clone {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+}
+
+impl PartialEq for Extensions {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|(k, v)| other.get(k) == Some(v))
+    }
+}
+
+impl Eq for Extensions {}
+
+impl fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter_masked(false)).finish()
+    }
+}
+
+impl FromIterator<(String, ExtensionValue)> for Extensions {
+    fn from_iter<I: IntoIterator<Item = (String, ExtensionValue)>>(iter: I) -> Self {
+        let mut extensions = Extensions {
+            entries: iter.into_iter().collect(),
+            sensitive: Vec::new(),
+        };
+        extensions.dedup();
+        extensions
+    }
+}
+
+impl IntoIterator for Extensions {
+    type Item = (String, ExtensionValue);
+    type IntoIter = smallvec::IntoIter<[(String, ExtensionValue); INLINE_EXTENSIONS]>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Extensions {
+    type Item = (&'a String, &'a ExtensionValue);
+    type IntoIter = std::iter::Map<
+        std::slice::Iter<'a, (String, ExtensionValue)>,
+        fn(&'a (String, ExtensionValue)) -> (&'a String, &'a ExtensionValue),
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_replaces_in_place() {
+        let mut extensions = Extensions::default();
+        assert_eq!(extensions.insert("a".to_string(), 1i64.into()), None);
+        assert_eq!(extensions.insert("b".to_string(), 2i64.into()), None);
+        assert_eq!(
+            extensions.insert("a".to_string(), 3i64.into()),
+            Some(ExtensionValue::Integer(1))
+        );
+
+        assert_eq!(extensions.len(), 2);
+        assert_eq!(
+            extensions
+                .iter()
+                .map(|(k, _)| k.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+        assert_eq!(extensions.remove("a"), Some(ExtensionValue::Integer(3)));
+        assert_eq!(extensions.remove("a"), None);
+        assert_eq!(extensions.get("b"), Some(&ExtensionValue::Integer(2)));
+    }
+
+    #[test]
+    fn dedup_keeps_last_value() {
+        let extensions: Extensions = vec![
+            ("a".to_string(), 1i64.into()),
+            ("b".to_string(), 2i64.into()),
+            ("a".to_string(), 3i64.into()),
+            ("c".to_string(), 4i64.into()),
+            ("b".to_string(), 5i64.into()),
+            ("a".to_string(), 6i64.into()),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            extensions
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("a", ExtensionValue::Integer(6)),
+                ("b", ExtensionValue::Integer(5)),
+                ("c", ExtensionValue::Integer(4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn validate() {
+        assert!(validate_extension("traceparent", &"00-abc".into()).is_ok());
+        assert!(validate_extension("comexampleextension1", &1i64.into()).is_ok());
+        assert!(validate_extension("a_b", &true.into()).is_err());
+        assert!(validate_extension("Trace", &true.into()).is_err());
+        assert!(validate_extension("", &true.into()).is_err());
+        assert!(validate_extension("comexampleextension12", &true.into()).is_err());
+        assert_eq!(
+            validate_extension("big", &i64::from(i32::MAX).into()),
+            Ok(())
+        );
+        assert_eq!(
+            validate_extension("big", &(i64::from(i32::MAX) + 1).into()),
+            Err(ExtensionError::IntegerOutOfRange {
+                name: "big".to_string(),
+                value: 2147483648
+            })
+        );
+        assert_eq!(
+            validate_extension("str", &"a\nb".into()),
+            Err(ExtensionError::DisallowedCharacter {
+                name: "str".to_string(),
+                character: '\n'
+            })
+        );
+        assert!(validate_extension("str", &"\u{FFFF}".into()).is_err());
+        assert!(validate_extension("str", &"héllo".into()).is_ok());
+    }
+
+    #[test]
+    fn eq_ignores_order() {
+        let ab: Extensions = vec![
+            ("a".to_string(), 1i64.into()),
+            ("b".to_string(), true.into()),
+        ]
+        .into_iter()
+        .collect();
+        let ba: Extensions = vec![
+            ("b".to_string(), true.into()),
+            ("a".to_string(), 1i64.into()),
+        ]
+        .into_iter()
+        .collect();
+        let a: Extensions = vec![("a".to_string(), 1i64.into())].into_iter().collect();
+
+        assert_eq!(ab, ba);
+        assert_ne!(ab, a);
+        assert_ne!(a, ab);
+    }
+}
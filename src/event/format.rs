@@ -1,13 +1,14 @@
 use super::{
     Attributes, Data, Event, EventFormatDeserializerV03, EventFormatDeserializerV10,
-    EventFormatSerializerV03, EventFormatSerializerV10,
+    EventFormatSerializerV03, EventFormatSerializerV10, WithSecrets,
 };
-use crate::event::{AttributesReader, ExtensionValue};
+use crate::event::builder::check_required_attributes;
+use crate::event::{AttributesReader, ExtensionValue, Extensions, SpecVersion};
 use base64::prelude::*;
-use serde::de::{Error, IntoDeserializer};
+use serde::de::{DeserializeSeed, Error, IntoDeserializer};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::{Map, Value};
-use std::collections::HashMap;
+use std::convert::TryFrom;
 
 macro_rules! parse_field {
     ($value:expr, $target_type:ty, $error:ty) => {
@@ -70,6 +71,19 @@ pub fn parse_data_base64_json<E: serde::de::Error>(v: Value) -> Result<Value, E>
     serde_json::from_slice(&data).map_err(E::custom)
 }
 
+/// Serializes bytes as a base64 string, writing the encoding directly to the serializer
+/// instead of allocating an intermediate [`String`].
+pub(crate) struct Base64Data<'a>(pub(crate) &'a [u8]);
+
+impl Serialize for Base64Data<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&base64::display::Base64Display::new(
+            self.0,
+            &BASE64_STANDARD,
+        ))
+    }
+}
+
 pub(crate) trait EventFormatDeserializer {
     fn deserialize_attributes<E: serde::de::Error>(
         map: &mut Map<String, Value>,
@@ -80,8 +94,15 @@ pub(crate) trait EventFormatDeserializer {
         map: &mut Map<String, Value>,
     ) -> Result<Option<Data>, E>;
 
-    fn deserialize_event<E: serde::de::Error>(mut map: Map<String, Value>) -> Result<Event, E> {
+    fn deserialize_event<E: serde::de::Error>(
+        mut map: Map<String, Value>,
+        options: &DeserializeOptions,
+    ) -> Result<Event, E> {
         let attributes = Self::deserialize_attributes(&mut map)?;
+        if !options.allow_empty_required_attributes {
+            check_required_attributes(attributes.id(), attributes.ty(), attributes.source())
+                .map_err(E::custom)?;
+        }
         let data = Self::deserialize_data(
             attributes.datacontenttype().unwrap_or("application/json"),
             &mut map,
@@ -95,7 +116,7 @@ pub(crate) trait EventFormatDeserializer {
                     ExtensionValue::deserialize(v.into_deserializer()).map_err(E::custom)?,
                 ))
             })
-            .collect::<Result<HashMap<String, ExtensionValue>, E>>()?;
+            .collect::<Result<Extensions, E>>()?;
 
         Ok(Event {
             attributes,
@@ -109,44 +130,221 @@ pub(crate) trait EventFormatSerializer<S: Serializer, A: Sized> {
     fn serialize(
         attributes: &A,
         data: &Option<Data>,
-        extensions: &HashMap<String, ExtensionValue>,
+        extensions: &Extensions,
+        include_secrets: bool,
         serializer: S,
     ) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>;
 }
 
-impl<'de> Deserialize<'de> for Event {
-    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+/// Options to deserialize an [`Event`] from the JSON format, while the [`Deserialize`]
+/// implementation of [`Event`] always uses the defaults.
+///
+/// ```
+/// use cloudevents::event::DeserializeOptions;
+/// use cloudevents::Event;
+///
+/// let json = r#"{"specversion":"1.0","id":"","type":"example.test","source":"/"}"#;
+/// assert!(serde_json::from_str::<Event>(json).is_err());
+///
+/// let event = DeserializeOptions::new()
+///     .allow_empty_required_attributes(true)
+///     .from_str(json)
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DeserializeOptions {
+    allow_empty_required_attributes: bool,
+    assume_v10_spec_version: bool,
+    mode: DeserializeMode,
+}
+
+/// How strictly [`DeserializeOptions`] checks the top-level members of a JSON event.
+///
+/// ```
+/// use cloudevents::event::{DeserializeMode, DeserializeOptions, ExtensionValue};
+/// use cloudevents::{AttributesReader, Event};
+///
+/// let json = r#"{"specversion":"1.0","id":42,"type":"example.test","source":"/","retries":3}"#;
+/// assert!(serde_json::from_str::<Event>(json).is_err());
+///
+/// let event = DeserializeOptions::new()
+///     .mode(DeserializeMode::Lenient)
+///     .from_str(json)
+///     .unwrap();
+/// assert_eq!(event.id(), "42");
+/// assert_eq!(event.extension("retries"), Some(&ExtensionValue::from("3")));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeserializeMode {
+    /// Ignore the members with a `null` value, while the values of the wrong type fail.
+    #[default]
+    Standard,
+    /// Fail on the members with a `null` value and on the members whose name isn't a valid
+    /// attribute name, i.e. made of lowercase ASCII letters and digits.
+    Strict,
+    /// Ignore the members with a `null` value, and coerce the numbers and booleans to strings,
+    /// both for the string attributes and the extensions.
+    Lenient,
+}
+
+impl DeserializeMode {
+    fn apply<E: serde::de::Error>(self, map: &mut Map<String, Value>) -> Result<(), E> {
+        match self {
+            DeserializeMode::Standard => {}
+            DeserializeMode::Strict => {
+                for (name, value) in map.iter() {
+                    if value.is_null() {
+                        return Err(E::custom(format_args!("null value for `{}`", name)));
+                    }
+                    if !is_data_member(name) && !is_valid_attribute_name(name) {
+                        return Err(E::custom(format_args!("invalid attribute name `{}`", name)));
+                    }
+                }
+            }
+            DeserializeMode::Lenient => {
+                for (name, value) in map.iter_mut() {
+                    if !is_data_member(name) && (value.is_number() || value.is_boolean()) {
+                        *value = Value::String(value.to_string());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_data_member(name: &str) -> bool {
+    name == "data" || name == "data_base64"
+}
+
+fn is_valid_attribute_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+}
+
+impl DeserializeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accept events with an empty `id`, `type` or `source`, which the spec forbids.
+    pub fn allow_empty_required_attributes(mut self, allow: bool) -> Self {
+        self.allow_empty_required_attributes = allow;
+        self
+    }
+
+    /// Deserialize the events with a missing or unsupported `specversion` as 1.0 events,
+    /// instead of failing.
+    pub fn assume_v10_spec_version(mut self, assume: bool) -> Self {
+        self.assume_v10_spec_version = assume;
+        self
+    }
+
+    /// Set how strictly the top-level members are checked, see [`DeserializeMode`].
+    pub fn mode(mut self, mode: DeserializeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Deserialize an [`Event`] from JSON bytes.
+    pub fn from_slice(&self, v: &[u8]) -> serde_json::Result<Event> {
+        self.deserialize(&mut serde_json::Deserializer::from_slice(v))
+    }
+
+    /// Deserialize an [`Event`] from a JSON string.
+    pub fn from_str(&self, s: &str) -> serde_json::Result<Event> {
+        self.deserialize(&mut serde_json::Deserializer::from_str(s))
+    }
+
+    /// Deserialize an [`Event`] from a JSON [`Value`].
+    pub fn from_value(&self, v: Value) -> serde_json::Result<Event> {
+        self.deserialize(v)
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for &DeserializeOptions {
+    type Value = Event;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Event, <D as Deserializer<'de>>::Error>
     where
         D: Deserializer<'de>,
     {
         let root_value = Value::deserialize(deserializer)?;
         let mut map: Map<String, Value> =
             Map::deserialize(root_value.into_deserializer()).map_err(D::Error::custom)?;
+        self.mode.apply(&mut map)?;
 
-        match extract_field!(map, "specversion", String, <D as Deserializer<'de>>::Error)?.as_str()
-        {
-            "0.3" => EventFormatDeserializerV03::deserialize_event(map),
-            "1.0" => EventFormatDeserializerV10::deserialize_event(map),
-            s => Err(D::Error::unknown_variant(
-                s,
-                &super::spec_version::SPEC_VERSIONS,
-            )),
+        let spec_version =
+            extract_optional_field!(map, "specversion", String, <D as Deserializer<'de>>::Error)?;
+        match spec_version.as_deref().map(SpecVersion::try_from) {
+            Some(Ok(SpecVersion::V03)) => EventFormatDeserializerV03::deserialize_event(map, self),
+            Some(Ok(SpecVersion::V10)) => EventFormatDeserializerV10::deserialize_event(map, self),
+            _ if self.assume_v10_spec_version => {
+                EventFormatDeserializerV10::deserialize_event(map, self)
+            }
+            Some(Err(e)) => Err(D::Error::custom(e.in_attribute("specversion"))),
+            None => Err(D::Error::missing_field("specversion")),
         }
     }
 }
 
+impl<'de> Deserialize<'de> for Event {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        DeserializeOptions::default().deserialize(deserializer)
+    }
+}
+
+fn serialize_event<S: Serializer>(
+    event: &Event,
+    include_secrets: bool,
+    serializer: S,
+) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error> {
+    match &event.attributes {
+        Attributes::V03(a) => EventFormatSerializerV03::serialize(
+            a,
+            &event.data,
+            &event.extensions,
+            include_secrets,
+            serializer,
+        ),
+        Attributes::V10(a) => EventFormatSerializerV10::serialize(
+            a,
+            &event.data,
+            &event.extensions,
+            include_secrets,
+            serializer,
+        ),
+    }
+}
+
 impl Serialize for Event {
     fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
     where
         S: Serializer,
     {
-        match &self.attributes {
-            Attributes::V03(a) => {
-                EventFormatSerializerV03::serialize(a, &self.data, &self.extensions, serializer)
-            }
-            Attributes::V10(a) => {
-                EventFormatSerializerV10::serialize(a, &self.data, &self.extensions, serializer)
-            }
-        }
+        serialize_event(self, false, serializer)
+    }
+}
+
+impl Serialize for WithSecrets<&Event> {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        serialize_event(self.0, true, serializer)
+    }
+}
+
+impl Serialize for WithSecrets<Event> {
+    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        serialize_event(&self.0, true, serializer)
     }
 }
@@ -1,12 +1,13 @@
 use super::Data;
 use super::Event;
-use super::{Attributes, AttributesReader};
+use super::{validate_extension, Attributes, AttributesReader, ExtensionValue, WithSecrets};
 use crate::event::SpecVersion;
 use crate::message::{
     BinaryDeserializer, BinarySerializer, MessageAttributeValue, Result, StructuredDeserializer,
     StructuredSerializer,
 };
 use crate::{EventBuilder, EventBuilderV03, EventBuilderV10};
+use bytes::Bytes;
 
 impl StructuredDeserializer for Event {
     fn deserialize_structured<R, V: StructuredSerializer<R>>(self, visitor: V) -> Result<R> {
@@ -16,21 +17,43 @@ impl StructuredDeserializer for Event {
 }
 
 impl BinaryDeserializer for Event {
-    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, mut visitor: V) -> Result<R> {
-        visitor = visitor.set_spec_version(self.specversion())?;
-        visitor = self.attributes.deserialize_attributes(visitor)?;
-        for (k, v) in self.extensions.into_iter() {
-            visitor = visitor.set_extension(&k, v.into())?;
-        }
-        match self.data {
-            Some(Data::String(s)) => visitor.end_with_data(s.into_bytes()),
-            Some(Data::Binary(v)) => visitor.end_with_data(v),
-            Some(Data::Json(j)) => {
-                let vec: Vec<u8> = serde_json::to_vec(&j)?;
-                visitor.end_with_data(vec)
-            }
-            None => visitor.end(),
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, visitor: V) -> Result<R> {
+        deserialize_binary(self, false, visitor)
+    }
+}
+
+impl StructuredDeserializer for WithSecrets<Event> {
+    fn deserialize_structured<R, V: StructuredSerializer<R>>(self, visitor: V) -> Result<R> {
+        let vec: Vec<u8> = serde_json::to_vec(&self)?;
+        visitor.set_structured_event(vec)
+    }
+}
+
+impl BinaryDeserializer for WithSecrets<Event> {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, visitor: V) -> Result<R> {
+        deserialize_binary(self.0, true, visitor)
+    }
+}
+
+fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(
+    event: Event,
+    include_secrets: bool,
+    mut visitor: V,
+) -> Result<R> {
+    visitor = visitor.set_spec_version(event.specversion())?;
+    visitor = event.attributes.deserialize_attributes(visitor)?;
+    for (k, v) in event.extensions.into_masked(include_secrets) {
+        visitor = visitor.set_extension(&k, v.into())?;
+    }
+    match event.data {
+        Some(Data::String(s)) => visitor.end_with_data(s.into_bytes()),
+        Some(Data::Binary(v)) => visitor.end_with_data(v),
+        Some(Data::Bytes(b)) => visitor.end_with_bytes(b),
+        Some(Data::Json(j)) => {
+            let vec: Vec<u8> = serde_json::to_vec(&j)?;
+            visitor.end_with_data(vec)
         }
+        None => visitor.end(),
     }
 }
 
@@ -55,9 +78,16 @@ pub(crate) trait AttributesSerializer {
 pub(crate) struct EventStructuredSerializer {}
 
 impl StructuredSerializer<Event> for EventStructuredSerializer {
+    #[cfg(not(feature = "simd-json"))]
     fn set_structured_event(self, bytes: Vec<u8>) -> Result<Event> {
         Ok(serde_json::from_slice(&bytes)?)
     }
+
+    // simd-json parses in place, reusing the buffer of the structured message
+    #[cfg(feature = "simd-json")]
+    fn set_structured_event(self, mut bytes: Vec<u8>) -> Result<Event> {
+        Ok(simd_json_lib::serde::from_slice(&mut bytes)?)
+    }
 }
 
 #[derive(Debug)]
@@ -72,6 +102,15 @@ impl EventBinarySerializer {
     }
 }
 
+impl EventBinarySerializer {
+    fn end_with(self, data: Data) -> Result<Event> {
+        Ok(match self {
+            EventBinarySerializer::V03(eb) => eb.data_without_content_type(data).build(),
+            EventBinarySerializer::V10(eb) => eb.data_without_content_type(data).build(),
+        }?)
+    }
+}
+
 impl BinarySerializer<Event> for EventBinarySerializer {
     fn set_spec_version(self, spec_version: SpecVersion) -> Result<Self> {
         Ok(match spec_version {
@@ -89,6 +128,8 @@ impl BinarySerializer<Event> for EventBinarySerializer {
     }
 
     fn set_extension(self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        let value = ExtensionValue::from(value);
+        validate_extension(name, &value)?;
         Ok(match self {
             EventBinarySerializer::V03(eb) => EventBinarySerializer::V03(eb.extension(name, value)),
             EventBinarySerializer::V10(eb) => EventBinarySerializer::V10(eb.extension(name, value)),
@@ -96,14 +137,11 @@ impl BinarySerializer<Event> for EventBinarySerializer {
     }
 
     fn end_with_data(self, bytes: Vec<u8>) -> Result<Event> {
-        Ok(match self {
-            EventBinarySerializer::V03(eb) => {
-                eb.data_without_content_type(Data::Binary(bytes)).build()
-            }
-            EventBinarySerializer::V10(eb) => {
-                eb.data_without_content_type(Data::Binary(bytes)).build()
-            }
-        }?)
+        self.end_with(Data::Binary(bytes))
+    }
+
+    fn end_with_bytes(self, bytes: Bytes) -> Result<Event> {
+        self.end_with(Data::Bytes(bytes))
     }
 
     fn end(self) -> Result<Event> {
@@ -149,6 +187,34 @@ mod tests {
             EventBinarySerializer::new()
                 .set_spec_version(SpecVersion::V10)
                 .unwrap()
+                .set_attribute("type", MessageAttributeValue::String(fixtures::ty()))
+                .unwrap()
+                .set_attribute("source", MessageAttributeValue::String(fixtures::source()))
+                .unwrap()
+                .end()
+                .unwrap_err()
+                .to_string()
+        )
+    }
+
+    #[test]
+    fn binary_deserializer_empty_id() {
+        assert_eq!(
+            Error::EventBuilderError {
+                source: crate::event::EventBuilderError::EmptyRequiredAttribute {
+                    attribute_name: "id"
+                },
+            }
+            .to_string(),
+            EventBinarySerializer::new()
+                .set_spec_version(SpecVersion::V10)
+                .unwrap()
+                .set_attribute("id", MessageAttributeValue::String(String::new()))
+                .unwrap()
+                .set_attribute("type", MessageAttributeValue::String(fixtures::ty()))
+                .unwrap()
+                .set_attribute("source", MessageAttributeValue::String(fixtures::source()))
+                .unwrap()
                 .end()
                 .unwrap_err()
                 .to_string()
@@ -245,4 +311,67 @@ mod tests {
         let event = rmp_serde::from_slice::<Event>(buff.as_slice()).unwrap();
         assert_eq!(event, fixtures::v10::full_json_data(),);
     }
+
+    #[test]
+    fn message_v10_roundtrip_keeps_time_text() -> Result<()> {
+        let mut json = fixtures::v10::minimal_json();
+        json["time"] = "2020-03-16T12:50:00.120+01:00".into();
+
+        let event: Event = serde_json::from_value(json.clone())?;
+        assert_eq!(
+            event.time(),
+            Some(&(fixtures::time() + chrono::Duration::milliseconds(120)))
+        );
+        assert_eq!(serde_json::to_value(&event)?, json);
+
+        let event = BinaryDeserializer::into_event(event)?;
+        assert_eq!(serde_json::to_value(&event)?, json);
+        Ok(())
+    }
+
+    #[test]
+    fn message_v10_time_keeps_nanoseconds() -> Result<()> {
+        let time = fixtures::time() + chrono::Duration::nanoseconds(1);
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .time(time)
+            .build()
+            .unwrap();
+
+        let event = BinaryDeserializer::into_event(event)?;
+
+        assert_eq!(event.time(), Some(&time));
+        assert_eq!(
+            serde_json::to_value(&event)?["time"],
+            "2020-03-16T11:50:00.000000001Z"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn message_v10_time_keeps_offset() -> Result<()> {
+        let time = chrono::DateTime::parse_from_rfc3339("2020-03-16T12:50:00.5+01:00").unwrap();
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("http://localhost/")
+            .time(time)
+            .build()
+            .unwrap();
+
+        let event = BinaryDeserializer::into_event(event)?;
+
+        assert_eq!(
+            event.time(),
+            Some(&(fixtures::time() + chrono::Duration::milliseconds(500)))
+        );
+        assert_eq!(event.time_as(), Some(time));
+        assert_eq!(
+            serde_json::to_value(&event)?["time"],
+            "2020-03-16T12:50:00.500+01:00"
+        );
+        Ok(())
+    }
 }
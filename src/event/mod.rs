@@ -1,26 +1,51 @@
 //! Provides [`Event`] data structure, [`EventBuilder`] and other facilities to work with [`Event`].
 
 mod attributes;
+mod borrowed;
 mod builder;
 mod data;
+mod dispatch;
+mod display;
 mod extensions;
 #[macro_use]
 mod format;
 mod message;
+#[cfg(feature = "protobuf")]
+mod proto;
+mod sensitive;
 mod spec_version;
+mod typed;
 mod types;
+mod uri_reference;
 
 pub use attributes::Attributes;
 pub use attributes::{AttributeValue, AttributesReader, AttributesWriter};
+pub use borrowed::{AttributesRef, DataRef, EventRef, ExtensionValueRef};
 pub use builder::Error as EventBuilderError;
 pub use builder::EventBuilder;
-pub use data::Data;
-pub use extensions::ExtensionValue;
+#[cfg_attr(docsrs, doc(cfg(feature = "derive")))]
+#[cfg(feature = "derive")]
+pub use cloudevents_sdk_derive::EventDispatch;
+pub use data::{Data, DataError};
+pub use dispatch::{EventDispatch, EventDispatchError};
+pub use display::{DisplayCompact, DisplayJson, DisplayPretty};
+pub(crate) use extensions::validate_extension;
+pub use extensions::{
+    ExtensionError, ExtensionTypeError, ExtensionValue, Extensions, FromExtensionValue,
+};
+pub use format::{DeserializeMode, DeserializeOptions};
 pub(crate) use message::EventBinarySerializer;
 pub(crate) use message::EventStructuredSerializer;
+#[cfg_attr(docsrs, doc(cfg(feature = "protobuf")))]
+#[cfg(feature = "protobuf")]
+pub use proto::PROTOBUF_CONTENT_TYPE;
+pub use sensitive::{WithSecrets, SENSITIVE_EXTENSION_MASK};
 pub use spec_version::SpecVersion;
 pub use spec_version::UnknownSpecVersion;
-pub use types::{TryIntoTime, TryIntoUrl, UriReference};
+pub use typed::CloudEvent;
+pub(crate) use types::Time;
+pub use types::{TimeType, TryIntoTime, TryIntoUrl, UriReference};
+pub use uri_reference::{validate_uri_reference, UriReferenceError};
 
 mod v03;
 
@@ -40,8 +65,6 @@ pub(crate) use v10::EventFormatSerializer as EventFormatSerializerV10;
 
 use chrono::{DateTime, Utc};
 use delegate_attr::delegate;
-use std::collections::HashMap;
-use std::fmt;
 use url::Url;
 
 /// Data structure that represents a [CloudEvent](https://github.com/cloudevents/spec/blob/master/spec.md).
@@ -79,7 +102,7 @@ use url::Url;
 pub struct Event {
     pub(crate) attributes: Attributes,
     pub(crate) data: Option<Data>,
-    pub(crate) extensions: HashMap<String, ExtensionValue>,
+    pub(crate) extensions: Extensions,
 }
 
 #[delegate(self.attributes)]
@@ -114,35 +137,22 @@ impl Default for Event {
         Event {
             attributes: Attributes::V10(AttributesV10::default()),
             data: None,
-            extensions: HashMap::default(),
+            extensions: Extensions::default(),
         }
     }
 }
 
-impl fmt::Display for Event {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "CloudEvent:")?;
-        self.iter()
-            .try_for_each(|(name, val)| writeln!(f, "  {}: '{}'", name, val))?;
-        match self.data() {
-            Some(data) => write!(f, "  {}", data)?,
-            None => write!(f, "  No data")?,
-        }
-        writeln!(f)
-    }
-}
-
 impl Event {
     /// Returns an [`Iterator`] for all the available [CloudEvents Context attributes](https://github.com/cloudevents/spec/blob/master/spec.md#context-attributes) and extensions.
     /// Same as chaining [`Event::iter_attributes()`] and [`Event::iter_extensions()`]
-    pub fn iter(&self) -> impl Iterator<Item = (&str, AttributeValue)> {
+    pub fn iter(&self) -> impl Iterator<Item = (&str, AttributeValue<'_>)> {
         self.iter_attributes()
             .chain(self.extensions.iter().map(|(k, v)| (k.as_str(), v.into())))
     }
 
     /// Returns an [`Iterator`] for all the available [CloudEvents Context attributes](https://github.com/cloudevents/spec/blob/master/spec.md#context-attributes), excluding extensions.
     /// This iterator does not contain the `data` field.
-    pub fn iter_attributes(&self) -> impl Iterator<Item = (&str, AttributeValue)> {
+    pub fn iter_attributes(&self) -> impl Iterator<Item = (&str, AttributeValue<'_>)> {
         self.attributes.iter()
     }
 
@@ -156,6 +166,48 @@ impl Event {
         self.data.as_ref()
     }
 
+    /// Deserialize `data` from JSON into `T`, returning `None` if the event has no data.
+    ///
+    /// With the `content-encoding` feature, binary data compressed as recorded by the
+    /// `contentencoding` extension is decompressed first.
+    ///
+    /// ```
+    /// use cloudevents::Event;
+    /// use serde_json::json;
+    ///
+    /// let mut e = Event::default();
+    /// e.set_data("application/json", json!({"hello": "world"}));
+    ///
+    /// let data: Option<serde_json::Value> = e.data_as().unwrap();
+    /// assert_eq!(data, Some(json!({"hello": "world"})));
+    /// ```
+    pub fn data_as<T: serde::de::DeserializeOwned>(&self) -> Result<Option<T>, serde_json::Error> {
+        let data = match &self.data {
+            Some(data) => data,
+            None => return Ok(None),
+        };
+        Ok(Some(match data {
+            Data::Json(v) => T::deserialize(v)?,
+            Data::String(s) => serde_json::from_str(s)?,
+            #[cfg(feature = "content-encoding")]
+            Data::Binary(_) | Data::Bytes(_) => {
+                use crate::content_encoding::{ContentEncoding, Error};
+                let bytes = data.as_bytes().unwrap_or_default();
+                match ContentEncoding::of(self).and_then(|e| e.map(|e| e.decode(bytes)).transpose())
+                {
+                    Ok(Some(decoded)) => serde_json::from_slice(&decoded)?,
+                    Ok(None) => serde_json::from_slice(bytes)?,
+                    Err(Error::IOError { source }) => return Err(serde_json::Error::io(source)),
+                    Err(e) => return Err(serde::de::Error::custom(e)),
+                }
+            }
+            #[cfg(not(feature = "content-encoding"))]
+            Data::Binary(v) => serde_json::from_slice(v)?,
+            #[cfg(not(feature = "content-encoding"))]
+            Data::Bytes(b) => serde_json::from_slice(b)?,
+        }))
+    }
+
     /// Take (`datacontenttype`, `dataschema`, `data`) from this event, leaving these fields empty
     ///
     /// ```
@@ -194,7 +246,7 @@ impl Event {
     ) -> (Option<String>, Option<Data>) {
         (
             self.attributes.set_datacontenttype(Some(datacontenttype)),
-            std::mem::replace(&mut self.data, Some(data.into())),
+            self.data.replace(data.into()),
         )
     }
 
@@ -210,7 +262,66 @@ impl Event {
     /// let old_data = e.set_data_unchecked(json!({}));
     /// ```
     pub fn set_data_unchecked(&mut self, data: impl Into<Data>) -> Option<Data> {
-        std::mem::replace(&mut self.data, Some(data.into()))
+        self.data.replace(data.into())
+    }
+
+    /// Get the `time` attribute as `T`, e.g. a `time::OffsetDateTime` with the `time` feature.
+    ///
+    /// Returns `None` if the event has no `time`, or if it's out of the range of `T`. The types
+    /// carrying an offset, such as [`DateTime<FixedOffset>`](chrono::FixedOffset), are
+    /// expressed in the offset the `time` was received or set with.
+    ///
+    /// ```
+    /// use chrono::{DateTime, FixedOffset, Utc};
+    /// use cloudevents::{AttributesReader, Event, EventBuilder, EventBuilderV10};
+    ///
+    /// let e = EventBuilderV10::new()
+    ///     .id("0001")
+    ///     .ty("example.test")
+    ///     .source("http://localhost/")
+    ///     .time("2020-03-16T12:50:00+02:00")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let time: Option<DateTime<Utc>> = e.time_as();
+    /// assert_eq!(time.as_ref(), e.time());
+    /// let time: DateTime<FixedOffset> = e.time_as().unwrap();
+    /// assert_eq!(time.to_rfc3339(), "2020-03-16T12:50:00+02:00");
+    /// ```
+    pub fn time_as<T: TimeType>(&self) -> Option<T> {
+        self.attributes
+            .time_with_text()
+            .and_then(|t| T::from_fixed_offset(&t.with_offset()))
+    }
+
+    /// Set the `time` attribute from `T`, returning the previous value. The offset of the types
+    /// carrying one is kept when serializing the event.
+    pub fn set_time_as<T: TimeType>(&mut self, time: Option<T>) -> Option<T> {
+        self.attributes
+            .replace_time(time.map(|t| t.into_fixed_offset().into()))
+            .and_then(|t| T::from_fixed_offset(&t.with_offset()))
+    }
+
+    /// Take this event apart into its attributes, `data` and extensions, without copying them.
+    ///
+    /// ```
+    /// use cloudevents::{AttributesWriter, Event};
+    ///
+    /// let (mut attributes, data, extensions) = Event::default().into_parts();
+    /// attributes.set_type("example.transformed");
+    /// let event = Event::from_parts(attributes, data, extensions);
+    /// ```
+    pub fn into_parts(self) -> (Attributes, Option<Data>, Extensions) {
+        (self.attributes, self.data, self.extensions)
+    }
+
+    /// Assemble an event from the parts returned by [`Event::into_parts()`].
+    pub fn from_parts(attributes: Attributes, data: Option<Data>, extensions: Extensions) -> Self {
+        Event {
+            attributes,
+            data,
+            extensions,
+        }
     }
 
     /// Get the [extension](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes) named `extension_name`
@@ -218,6 +329,39 @@ impl Event {
         self.extensions.get(extension_name)
     }
 
+    /// Get the [extension](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes) named `extension_name`
+    /// converted to `T`, among [`String`], [`i64`], [`bool`], [`DateTime<Utc>`](chrono::DateTime)
+    /// and [`Url`](url::Url). String values are parsed, so this works with extensions read
+    /// from binary mode messages too.
+    ///
+    /// ```
+    /// use cloudevents::{Event, EventBuilder, EventBuilderV10};
+    ///
+    /// let event: Event = EventBuilderV10::new()
+    ///     .id("0001")
+    ///     .ty("example.test")
+    ///     .source("http://localhost/")
+    ///     .extension("sequence", "42")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(event.extension_as::<i64>("sequence").unwrap(), Some(42));
+    /// assert!(event.extension_as::<bool>("sequence").is_err());
+    /// assert_eq!(event.extension_as::<i64>("missing").unwrap(), None);
+    /// ```
+    pub fn extension_as<T: FromExtensionValue>(
+        &self,
+        extension_name: &str,
+    ) -> Result<Option<T>, ExtensionTypeError> {
+        self.extensions
+            .get(extension_name)
+            .map(|v| {
+                T::from_extension_value(v)
+                    .map_err(|e| ExtensionTypeError::new::<T>(extension_name, v, e))
+            })
+            .transpose()
+    }
+
     /// Set the [extension](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes) named `extension_name` with `extension_value`
     pub fn set_extension<'name, 'event: 'name>(
         &'event mut self,
@@ -228,6 +372,21 @@ impl Event {
             .insert(extension_name.to_owned(), extension_value.into());
     }
 
+    /// Set the [extension](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes) named `extension_name` with `extension_value`,
+    /// failing if the name isn't made of lowercase ASCII letters and digits, or the value can't
+    /// be encoded as the spec requires, e.g. an integer outside of the 32-bit range.
+    pub fn try_set_extension(
+        &mut self,
+        extension_name: &str,
+        extension_value: impl Into<ExtensionValue>,
+    ) -> Result<(), ExtensionError> {
+        let extension_value = extension_value.into();
+        validate_extension(extension_name, &extension_value)?;
+        self.extensions
+            .insert(extension_name.to_owned(), extension_value);
+        Ok(())
+    }
+
     /// Remove the [extension](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes) named `extension_name`
     pub fn remove_extension<'name, 'event: 'name>(
         &'event mut self,
@@ -240,6 +399,7 @@ impl Event {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
 
     #[test]
     fn take_data() {
@@ -271,6 +431,61 @@ mod tests {
         assert_eq!(e.id(), "002")
     }
 
+    #[test]
+    fn extension_as() {
+        let mut e = Event::default();
+        e.set_extension("int", 42i64);
+        e.set_extension("intstr", "42");
+        e.set_extension("bool", "true");
+        e.set_extension("time", "2020-03-19T12:00:00+01:00");
+        e.set_extension("url", "http://localhost/");
+
+        assert_eq!(e.extension_as::<i64>("int").unwrap(), Some(42));
+        assert_eq!(e.extension_as::<i64>("intstr").unwrap(), Some(42));
+        assert_eq!(e.extension_as::<bool>("bool").unwrap(), Some(true));
+        assert_eq!(
+            e.extension_as::<chrono::DateTime<chrono::Utc>>("time")
+                .unwrap()
+                .map(|t| t.to_rfc3339()),
+            Some("2020-03-19T11:00:00+00:00".to_string())
+        );
+        assert_eq!(
+            e.extension_as::<url::Url>("url").unwrap().map(String::from),
+            Some("http://localhost/".to_string())
+        );
+        assert_eq!(
+            e.extension_as::<String>("int").unwrap(),
+            Some("42".to_string())
+        );
+        assert_eq!(e.extension_as::<i64>("missing").unwrap(), None);
+
+        let err = e.extension_as::<i64>("bool").unwrap_err();
+        assert_eq!(err.name(), "bool");
+        assert_eq!(err.type_name(), "integer");
+        assert!(err
+            .to_string()
+            .starts_with("Cannot read extension `bool` with value `true` as integer: "));
+        assert!(e.extension_as::<url::Url>("int").is_err());
+    }
+
+    #[test]
+    fn try_set_extension() {
+        let mut e = Event::default();
+        assert!(e.try_set_extension("seq", 1i64).is_ok());
+        assert_eq!(e.extension("seq"), Some(&ExtensionValue::Integer(1)));
+
+        assert!(matches!(
+            e.try_set_extension("Seq", 2i64),
+            Err(ExtensionError::InvalidName { .. })
+        ));
+        assert!(matches!(
+            e.try_set_extension("seq", i64::MAX),
+            Err(ExtensionError::IntegerOutOfRange { .. })
+        ));
+        assert_eq!(e.extension("seq"), Some(&ExtensionValue::Integer(1)));
+        assert_eq!(e.extension("Seq"), None);
+    }
+
     #[test]
     fn iter() {
         let mut e = Event::default();
@@ -290,4 +505,41 @@ mod tests {
         );
         assert_eq!(v.remove("aaa"), Some(AttributeValue::String("bbb")))
     }
+
+    #[test]
+    fn into_parts() {
+        let mut e = Event::default();
+        e.set_extension("aaa", "bbb");
+        e.set_data("text/plain", "hello");
+
+        let (attributes, data, extensions) = e.clone().into_parts();
+
+        assert_eq!(data, Some(Data::String("hello".to_string())));
+        assert_eq!(extensions.get("aaa"), Some(&ExtensionValue::from("bbb")));
+        assert_eq!(Event::from_parts(attributes, data, extensions), e);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_as_offset_date_time() {
+        let time = time_lib::OffsetDateTime::from_unix_timestamp(1584359400)
+            .and_then(|t| t.replace_nanosecond(1))
+            .unwrap()
+            .to_offset(time_lib::UtcOffset::from_hms(1, 0, 0).unwrap());
+        let mut e = Event::default();
+
+        e.set_time_as(Some(time));
+
+        assert_eq!(
+            e.time(),
+            Some(&(crate::test::fixtures::time() + chrono::Duration::nanoseconds(1)))
+        );
+        let read: time_lib::OffsetDateTime = e.time_as().unwrap();
+        assert_eq!(read, time);
+        assert_eq!(read.offset(), time.offset());
+        assert_eq!(
+            serde_json::to_value(&e).unwrap()["time"],
+            "2020-03-16T12:50:00.000000001+01:00"
+        );
+    }
 }
@@ -0,0 +1,124 @@
+use super::{Data, Event, EventBuilderV03, EventBuilderV10};
+use prost::{DecodeError, Message, Name};
+use url::Url;
+
+/// `datacontenttype` of the events with a protobuf message as data.
+pub const PROTOBUF_CONTENT_TYPE: &str = "application/protobuf";
+
+/// Encode `msg`, returning its bytes and the schema URL of its type.
+///
+/// The schema is the type URL of the message if it's an absolute URL, otherwise the
+/// `https://type.googleapis.com/` URL of its full name, as in `google.protobuf.Any`.
+fn encode<M: Name>(msg: &M) -> (Vec<u8>, Url) {
+    let schema = Url::parse(&M::type_url()).unwrap_or_else(|_| {
+        Url::parse(&format!("https://type.googleapis.com/{}", M::full_name()))
+            .expect("message full name should be a valid URL path")
+    });
+    (msg.encode_to_vec(), schema)
+}
+
+impl EventBuilderV10 {
+    /// Set the data to the protobuf encoding of `msg`, with the [`PROTOBUF_CONTENT_TYPE`]
+    /// `datacontenttype` and a `dataschema` identifying the message type.
+    pub fn data_proto<M: Name>(self, msg: &M) -> Self {
+        let (data, schema) = encode(msg);
+        self.data_with_schema(PROTOBUF_CONTENT_TYPE, schema, data)
+    }
+}
+
+impl EventBuilderV03 {
+    /// Set the data to the protobuf encoding of `msg`, with the [`PROTOBUF_CONTENT_TYPE`]
+    /// `datacontenttype` and a `schemaurl` identifying the message type.
+    pub fn data_proto<M: Name>(self, msg: &M) -> Self {
+        let (data, schema) = encode(msg);
+        self.data_with_schema(PROTOBUF_CONTENT_TYPE, schema, data)
+    }
+}
+
+impl Event {
+    /// Decode the data as the protobuf message `T`, returning `None` if this event has no data.
+    ///
+    /// JSON data can't be decoded, as it isn't a protobuf message.
+    pub fn data_as_proto<T: Message + Default>(&self) -> Result<Option<T>, DecodeError> {
+        match &self.data {
+            None => Ok(None),
+            Some(Data::Json(_)) => Err(DecodeError::new("event data is JSON")),
+            Some(Data::String(s)) => T::decode(s.as_bytes()).map(Some),
+            Some(Data::Binary(v)) => T::decode(v.as_slice()).map(Some),
+            Some(Data::Bytes(b)) => T::decode(b.clone()).map(Some),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use crate::{AttributesReader, EventBuilder};
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct OrderCreated {
+        #[prost(string, tag = "1")]
+        order_id: String,
+        #[prost(uint32, tag = "2")]
+        quantity: u32,
+    }
+
+    impl Name for OrderCreated {
+        const NAME: &'static str = "OrderCreated";
+        const PACKAGE: &'static str = "example.orders.v1";
+    }
+
+    fn order() -> OrderCreated {
+        OrderCreated {
+            order_id: "42".to_string(),
+            quantity: 3,
+        }
+    }
+
+    #[test]
+    fn data_proto_v10() {
+        let event = EventBuilderV10::from(fixtures::v10::minimal())
+            .data_proto(&order())
+            .build()
+            .unwrap();
+
+        assert_eq!(event.datacontenttype(), Some(PROTOBUF_CONTENT_TYPE));
+        assert_eq!(
+            event.dataschema().map(Url::as_str),
+            Some("https://type.googleapis.com/example.orders.v1.OrderCreated")
+        );
+        assert_eq!(event.data(), Some(&Data::Binary(order().encode_to_vec())));
+        assert_eq!(
+            event.data_as_proto::<OrderCreated>().unwrap(),
+            Some(order())
+        );
+    }
+
+    #[test]
+    fn data_proto_v03() {
+        let event = EventBuilderV03::from(fixtures::v03::minimal())
+            .data_proto(&order())
+            .build()
+            .unwrap();
+
+        assert!(event.dataschema().is_some());
+        assert_eq!(
+            event.data_as_proto::<OrderCreated>().unwrap(),
+            Some(order())
+        );
+    }
+
+    #[test]
+    fn data_as_proto_without_proto_data() {
+        assert_eq!(
+            fixtures::v10::minimal()
+                .data_as_proto::<OrderCreated>()
+                .unwrap(),
+            None
+        );
+        assert!(fixtures::v10::full_json_data()
+            .data_as_proto::<OrderCreated>()
+            .is_err());
+    }
+}
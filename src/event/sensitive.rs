@@ -0,0 +1,123 @@
+use super::{Event, ExtensionValue};
+
+/// Value replacing the sensitive extensions.
+pub const SENSITIVE_EXTENSION_MASK: &str = "***";
+
+/// Wrapper serializing the actual values of the sensitive extensions of an [`Event`], which are
+/// masked by default, see [`Event::mark_sensitive_extension()`].
+///
+/// `WithSecrets<&Event>` implements [`Serialize`](serde::Serialize) for the JSON format, while
+/// `WithSecrets<Event>` can also be passed to the protocol bindings, as a
+/// [`BinaryDeserializer`](crate::message::BinaryDeserializer) or a
+/// [`StructuredDeserializer`](crate::message::StructuredDeserializer).
+///
+/// ```
+/// use cloudevents::event::WithSecrets;
+/// use cloudevents::{EventBuilder, EventBuilderV10};
+///
+/// let mut event = EventBuilderV10::new()
+///     .id("0001")
+///     .ty("example.test")
+///     .source("http://localhost/")
+///     .extension("authtoken", "s3cr3t")
+///     .build()
+///     .unwrap();
+/// event.mark_sensitive_extension("authtoken");
+///
+/// let json = serde_json::to_string(&WithSecrets(&event)).unwrap();
+/// assert!(json.contains(r#""authtoken":"s3cr3t""#));
+/// ```
+pub struct WithSecrets<T>(pub T);
+
+impl Event {
+    /// Mark the extension named `name` as sensitive, e.g. because it carries a token.
+    ///
+    /// The values of the sensitive extensions are replaced by [`SENSITIVE_EXTENSION_MASK`] by
+    /// all the serializers: the JSON event format, the headers of the protocol bindings, the
+    /// [`Debug`](std::fmt::Debug) and the [`Display`](std::fmt::Display) formats, so they don't
+    /// leak into logs and dumps. Wrap the event in [`WithSecrets`] to serialize their actual
+    /// values, e.g. to send it to a trusted service.
+    ///
+    /// The mark is kept by the clones of the event, but isn't sent along with it.
+    ///
+    /// ```
+    /// use cloudevents::{EventBuilder, EventBuilderV10};
+    ///
+    /// let mut event = EventBuilderV10::new()
+    ///     .id("0001")
+    ///     .ty("example.test")
+    ///     .source("http://localhost/")
+    ///     .extension("authtoken", "s3cr3t")
+    ///     .build()
+    ///     .unwrap();
+    /// event.mark_sensitive_extension("authtoken");
+    ///
+    /// assert!(!format!("{:?}", event).contains("s3cr3t"));
+    /// let json = serde_json::to_string(&event).unwrap();
+    /// assert!(json.contains(r#""authtoken":"***""#));
+    /// ```
+    pub fn mark_sensitive_extension(&mut self, name: impl Into<String>) {
+        self.extensions.mark_sensitive(name.into());
+    }
+
+    /// Returns `true` if the extension named `name` is marked as sensitive.
+    pub fn is_sensitive_extension(&self, name: &str) -> bool {
+        self.extensions.is_sensitive(name)
+    }
+
+    /// Make a copy of this event where the values of the sensitive extensions are replaced by
+    /// [`SENSITIVE_EXTENSION_MASK`].
+    pub fn masked(&self) -> Event {
+        let mut masked = self.clone();
+        for (name, value) in masked.extensions.iter_mut() {
+            if self.extensions.is_sensitive(name) {
+                *value = ExtensionValue::from(SENSITIVE_EXTENSION_MASK);
+            }
+        }
+        masked
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{EventBinarySerializer, EventStructuredSerializer};
+    use crate::message::{BinaryDeserializer, StructuredDeserializer};
+    use crate::test::fixtures;
+
+    #[test]
+    fn masked_unless_secrets_are_included() {
+        let mut event = fixtures::v10::minimal();
+        event.set_extension("sensitivetest", "s3cr3t");
+        event.mark_sensitive_extension("sensitivetest");
+        assert!(event.is_sensitive_extension("sensitivetest"));
+        let mask = Some(ExtensionValue::from(SENSITIVE_EXTENSION_MASK));
+
+        assert!(!format!("{:?}", event).contains("s3cr3t"));
+        assert!(!event.to_string().contains("s3cr3t"));
+        assert!(event.to_string().contains("sensitivetest: '***'"));
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["sensitivetest"], SENSITIVE_EXTENSION_MASK);
+        let binary: Event = event
+            .clone()
+            .deserialize_binary(EventBinarySerializer::new())
+            .unwrap();
+        assert_eq!(binary.extension("sensitivetest"), mask.as_ref());
+
+        let json = serde_json::to_value(WithSecrets(&event)).unwrap();
+        assert_eq!(json["sensitivetest"], "s3cr3t");
+        let binary: Event = WithSecrets(event.clone())
+            .deserialize_binary(EventBinarySerializer::new())
+            .unwrap();
+        assert_eq!(binary, event);
+        assert!(!binary.is_sensitive_extension("sensitivetest"));
+        let structured: Event = WithSecrets(event.clone())
+            .deserialize_structured(EventStructuredSerializer {})
+            .unwrap();
+        assert_eq!(structured, event);
+
+        let masked = event.masked();
+        assert_eq!(masked.extension("sensitivetest"), mask.as_ref());
+        assert_eq!(event.extension("sensitivetest"), Some(&"s3cr3t".into()));
+    }
+}
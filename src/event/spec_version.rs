@@ -17,7 +17,7 @@ pub enum SpecVersion {
 impl SpecVersion {
     /// Returns the string representation of [`SpecVersion`].
     #[inline]
-    pub fn as_str(&self) -> &str {
+    pub fn as_str(&self) -> &'static str {
         match self {
             SpecVersion::V03 => "0.3",
             SpecVersion::V10 => "1.0",
@@ -44,11 +44,38 @@ impl fmt::Display for SpecVersion {
 #[derive(Debug)]
 pub struct UnknownSpecVersion {
     spec_version_value: String,
+    attribute_name: Option<&'static str>,
+}
+
+impl UnknownSpecVersion {
+    /// The unsupported value.
+    pub fn value(&self) -> &str {
+        &self.spec_version_value
+    }
+
+    /// The header or field the value was read from, when known.
+    pub fn attribute_name(&self) -> Option<&'static str> {
+        self.attribute_name
+    }
+
+    /// The spec versions supported by this crate.
+    pub fn supported_versions(&self) -> &'static [&'static str] {
+        &SPEC_VERSIONS
+    }
+
+    pub(crate) fn in_attribute(mut self, attribute_name: &'static str) -> Self {
+        self.attribute_name = Some(attribute_name);
+        self
+    }
 }
 
 impl fmt::Display for UnknownSpecVersion {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "Invalid specversion {}", self.spec_version_value)
+        write!(f, "Invalid specversion `{}`", self.spec_version_value)?;
+        if let Some(attribute_name) = self.attribute_name {
+            write!(f, " in `{}`", attribute_name)?;
+        }
+        write!(f, ", supported versions: {}", SPEC_VERSIONS.join(", "))
     }
 }
 
@@ -63,6 +90,7 @@ impl TryFrom<&str> for SpecVersion {
             "1.0" => Ok(SpecVersion::V10),
             _ => Err(UnknownSpecVersion {
                 spec_version_value: value.to_string(),
+                attribute_name: None,
             }),
         }
     }
@@ -0,0 +1,244 @@
+use super::{
+    Attributes, AttributesReader, AttributesWriter, Data, Event, ExtensionValue, Extensions,
+    SpecVersion, UriReference,
+};
+use chrono::{DateTime, Utc};
+use delegate_attr::delegate;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::convert::TryFrom;
+use url::Url;
+
+/// Content type set by the conversion to [`Event`] when the [`CloudEvent`] has none.
+const JSON_CONTENT_TYPE: &str = "application/json";
+
+/// [`Event`] whose data is a value of type `T`, so the application works with its own types
+/// and only the protocol bindings deal with the [`Data`] of the [`Event`].
+///
+/// The conversion from [`Event`] deserializes the data from JSON, the one to [`Event`]
+/// serializes it as [`Data::Json`], setting the `datacontenttype` to `application/json` unless
+/// already set. An event without data converts to `T` as JSON `null`, which suits `Option`
+/// payloads, and a payload serialized as `null` gives an event without data.
+///
+/// ```
+/// use cloudevents::event::CloudEvent;
+/// use cloudevents::{AttributesReader, Event, EventBuilder, EventBuilderV10};
+/// use serde::{Deserialize, Serialize};
+/// use std::convert::TryFrom;
+///
+/// #[derive(Debug, Serialize, Deserialize, PartialEq)]
+/// struct OrderCreated {
+///     id: u64,
+/// }
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .ty("com.example.order.created")
+///     .source("http://localhost/")
+///     .data("application/json", serde_json::json!({"id": 42}))
+///     .build()
+///     .unwrap();
+///
+/// let mut order = CloudEvent::<OrderCreated>::try_from(event).unwrap();
+/// assert_eq!(order.ty(), "com.example.order.created");
+/// order.data_mut().id += 1;
+///
+/// let event = Event::try_from(order).unwrap();
+/// assert_eq!(
+///     event.data_as::<serde_json::Value>().unwrap(),
+///     Some(serde_json::json!({"id": 43}))
+/// );
+/// ```
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct CloudEvent<T> {
+    attributes: Attributes,
+    data: T,
+    extensions: Extensions,
+}
+
+#[delegate(self.attributes)]
+impl<T> AttributesReader for CloudEvent<T> {
+    fn id(&self) -> &str {}
+    fn source(&self) -> &UriReference {}
+    fn specversion(&self) -> SpecVersion {}
+    fn ty(&self) -> &str {}
+    fn datacontenttype(&self) -> Option<&str> {}
+    fn dataschema(&self) -> Option<&Url> {}
+    fn subject(&self) -> Option<&str> {}
+    fn time(&self) -> Option<&DateTime<Utc>> {}
+}
+
+#[delegate(self.attributes)]
+impl<T> AttributesWriter for CloudEvent<T> {
+    fn set_id(&mut self, id: impl Into<String>) -> String {}
+    fn set_source(&mut self, source: impl Into<UriReference>) -> UriReference {}
+    fn set_type(&mut self, ty: impl Into<String>) -> String {}
+    fn set_subject(&mut self, subject: Option<impl Into<String>>) -> Option<String> {}
+    fn set_time(&mut self, time: Option<impl Into<DateTime<Utc>>>) -> Option<DateTime<Utc>> {}
+    fn set_datacontenttype(
+        &mut self,
+        datacontenttype: Option<impl Into<String>>,
+    ) -> Option<String> {
+    }
+    fn set_dataschema(&mut self, dataschema: Option<impl Into<Url>>) -> Option<Url> {}
+}
+
+impl<T> CloudEvent<T> {
+    /// Create a [`CloudEvent`] with the attributes and extensions of `event`, whose data is
+    /// replaced by `data`.
+    pub fn new(event: Event, data: T) -> Self {
+        let (attributes, _, extensions) = event.into_parts();
+        CloudEvent::from_parts(attributes, data, extensions)
+    }
+
+    /// Get the data of this event.
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    /// Get a mutable reference to the data of this event.
+    pub fn data_mut(&mut self) -> &mut T {
+        &mut self.data
+    }
+
+    /// Take the data of this event, dropping the attributes and the extensions.
+    pub fn into_data(self) -> T {
+        self.data
+    }
+
+    /// Replace the data of this event with `f` applied to it, keeping the attributes and the
+    /// extensions.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> CloudEvent<U> {
+        CloudEvent::from_parts(self.attributes, f(self.data), self.extensions)
+    }
+
+    /// Take this event apart into its attributes, data and extensions.
+    pub fn into_parts(self) -> (Attributes, T, Extensions) {
+        (self.attributes, self.data, self.extensions)
+    }
+
+    /// Assemble an event from the parts returned by [`CloudEvent::into_parts()`].
+    pub fn from_parts(attributes: Attributes, data: T, extensions: Extensions) -> Self {
+        CloudEvent {
+            attributes,
+            data,
+            extensions,
+        }
+    }
+
+    /// Get all the [extensions](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes)
+    pub fn iter_extensions(&self) -> impl Iterator<Item = (&str, &ExtensionValue)> {
+        self.extensions.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Get the [extension](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes) named `extension_name`
+    pub fn extension(&self, extension_name: &str) -> Option<&ExtensionValue> {
+        self.extensions.get(extension_name)
+    }
+
+    /// Set the [extension](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes) named `extension_name` with `extension_value`
+    pub fn set_extension(
+        &mut self,
+        extension_name: &str,
+        extension_value: impl Into<ExtensionValue>,
+    ) {
+        self.extensions
+            .insert(extension_name.to_owned(), extension_value.into());
+    }
+
+    /// Remove the [extension](https://github.com/cloudevents/spec/blob/master/spec.md#extension-context-attributes) named `extension_name`
+    pub fn remove_extension(&mut self, extension_name: &str) -> Option<ExtensionValue> {
+        self.extensions.remove(extension_name)
+    }
+}
+
+impl<T: DeserializeOwned> TryFrom<Event> for CloudEvent<T> {
+    type Error = serde_json::Error;
+
+    fn try_from(event: Event) -> Result<Self, Self::Error> {
+        let data = match event.data_as()? {
+            Some(data) => data,
+            None => T::deserialize(serde_json::Value::Null)?,
+        };
+        Ok(CloudEvent::new(event, data))
+    }
+}
+
+impl<T: Serialize> TryFrom<CloudEvent<T>> for Event {
+    type Error = serde_json::Error;
+
+    fn try_from(event: CloudEvent<T>) -> Result<Self, Self::Error> {
+        let (mut attributes, data, extensions) = event.into_parts();
+        let data = match serde_json::to_value(data)? {
+            serde_json::Value::Null => None,
+            value => {
+                if attributes.datacontenttype().is_none() {
+                    attributes.set_datacontenttype(Some(JSON_CONTENT_TYPE));
+                }
+                Some(Data::Json(value))
+            }
+        };
+        Ok(Event::from_parts(attributes, data, extensions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Payload {
+        hello: String,
+    }
+
+    #[test]
+    fn event_roundtrip() {
+        let mut event = fixtures::v10::minimal();
+        event.set_data("application/json", json!({"hello": "world"}));
+
+        let typed = CloudEvent::<Payload>::try_from(event.clone()).unwrap();
+        assert_eq!(typed.id(), event.id());
+        assert_eq!(
+            typed.data(),
+            &Payload {
+                hello: "world".to_string()
+            }
+        );
+
+        assert_eq!(Event::try_from(typed).unwrap(), event);
+    }
+
+    #[test]
+    fn sets_json_content_type() {
+        let typed = CloudEvent::new(
+            fixtures::v10::minimal(),
+            Payload {
+                hello: "world".to_string(),
+            },
+        );
+
+        let event = Event::try_from(typed).unwrap();
+        assert_eq!(event.datacontenttype(), Some("application/json"));
+        assert_eq!(event.data(), Some(&Data::Json(json!({"hello": "world"}))));
+    }
+
+    #[test]
+    fn without_data() {
+        let typed = CloudEvent::<Option<Payload>>::try_from(fixtures::v10::minimal()).unwrap();
+        assert_eq!(typed.data(), &None);
+        assert_eq!(Event::try_from(typed).unwrap(), fixtures::v10::minimal());
+
+        assert!(CloudEvent::<Payload>::try_from(fixtures::v10::minimal()).is_err());
+    }
+
+    #[test]
+    fn invalid_data() {
+        let mut event = fixtures::v10::minimal();
+        event.set_data("application/json", json!({"hello": 42}));
+
+        assert!(CloudEvent::<Payload>::try_from(event).is_err());
+    }
+}
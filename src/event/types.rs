@@ -1,4 +1,8 @@
-use chrono::{DateTime, Utc};
+use crate::message::MessageAttributeValue;
+use chrono::{DateTime, FixedOffset, Offset, SecondsFormat, Utc};
+use serde::{Serialize, Serializer};
+use std::borrow::Cow;
+use std::convert::TryFrom;
 use url::Url;
 
 /// Trait to define conversion to [`Url`]
@@ -27,6 +31,14 @@ impl TryIntoUrl for String {
 /// Trait to define conversion to [`DateTime`]
 pub trait TryIntoTime {
     fn into_time(self) -> Result<DateTime<Utc>, chrono::ParseError>;
+
+    /// Convert to a [`DateTime`] keeping the offset it's expressed in, UTC by default.
+    fn into_time_with_offset(self) -> Result<DateTime<FixedOffset>, chrono::ParseError>
+    where
+        Self: Sized,
+    {
+        self.into_time().map(DateTime::from)
+    }
 }
 
 impl TryIntoTime for DateTime<Utc> {
@@ -35,16 +47,141 @@ impl TryIntoTime for DateTime<Utc> {
     }
 }
 
+impl TryIntoTime for DateTime<FixedOffset> {
+    fn into_time(self) -> Result<DateTime<Utc>, chrono::ParseError> {
+        Ok(self.with_timezone(&Utc))
+    }
+
+    fn into_time_with_offset(self) -> Result<DateTime<FixedOffset>, chrono::ParseError> {
+        Ok(self)
+    }
+}
+
 impl TryIntoTime for &str {
     fn into_time(self) -> Result<DateTime<Utc>, chrono::ParseError> {
         Ok(DateTime::<Utc>::from(DateTime::parse_from_rfc3339(self)?))
     }
+
+    fn into_time_with_offset(self) -> Result<DateTime<FixedOffset>, chrono::ParseError> {
+        DateTime::parse_from_rfc3339(self)
+    }
 }
 
 impl TryIntoTime for String {
     fn into_time(self) -> Result<DateTime<Utc>, chrono::ParseError> {
         self.as_str().into_time()
     }
+
+    fn into_time_with_offset(self) -> Result<DateTime<FixedOffset>, chrono::ParseError> {
+        self.as_str().into_time_with_offset()
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryIntoTime for time_lib::OffsetDateTime {
+    fn into_time(self) -> Result<DateTime<Utc>, chrono::ParseError> {
+        Ok(TimeType::into_utc(self))
+    }
+
+    fn into_time_with_offset(self) -> Result<DateTime<FixedOffset>, chrono::ParseError> {
+        Ok(TimeType::into_fixed_offset(self))
+    }
+}
+
+/// Date time types the `time` attribute can be read and written as, with
+/// [`Event::time_as()`](super::Event::time_as) and
+/// [`Event::set_time_as()`](super::Event::set_time_as).
+///
+/// Implemented for chrono's [`DateTime<Utc>`] and [`DateTime<FixedOffset>`] and, with the
+/// `time` feature, for `time::OffsetDateTime`. The types carrying an offset read and write the
+/// offset the `time` is expressed in.
+///
+/// This trait is sealed and cannot be implemented for types outside of this crate.
+pub trait TimeType: Sized + private::Sealed {
+    /// Convert from the stored value, or `None` if it's out of the range of `Self`.
+    fn from_utc(time: &DateTime<Utc>) -> Option<Self>;
+
+    /// Convert to the stored value.
+    fn into_utc(self) -> DateTime<Utc>;
+
+    /// Convert from the stored value and its offset, or `None` if it's out of the range of
+    /// `Self`.
+    fn from_fixed_offset(time: &DateTime<FixedOffset>) -> Option<Self> {
+        Self::from_utc(&time.with_timezone(&Utc))
+    }
+
+    /// Convert to the stored value and its offset.
+    fn into_fixed_offset(self) -> DateTime<FixedOffset> {
+        self.into_utc().into()
+    }
+}
+
+impl TimeType for DateTime<Utc> {
+    fn from_utc(time: &DateTime<Utc>) -> Option<Self> {
+        Some(*time)
+    }
+
+    fn into_utc(self) -> DateTime<Utc> {
+        self
+    }
+}
+
+impl TimeType for DateTime<FixedOffset> {
+    fn from_utc(time: &DateTime<Utc>) -> Option<Self> {
+        Some((*time).into())
+    }
+
+    fn into_utc(self) -> DateTime<Utc> {
+        self.with_timezone(&Utc)
+    }
+
+    fn from_fixed_offset(time: &DateTime<FixedOffset>) -> Option<Self> {
+        Some(*time)
+    }
+
+    fn into_fixed_offset(self) -> DateTime<FixedOffset> {
+        self
+    }
+}
+
+#[cfg(feature = "time")]
+impl TimeType for time_lib::OffsetDateTime {
+    fn from_utc(time: &DateTime<Utc>) -> Option<Self> {
+        time_lib::OffsetDateTime::from_unix_timestamp(time.timestamp())
+            .and_then(|t| t.replace_nanosecond(time.timestamp_subsec_nanos()))
+            .ok()
+    }
+
+    fn into_utc(self) -> DateTime<Utc> {
+        // chrono covers a larger range of years than time
+        DateTime::from_timestamp(self.unix_timestamp(), self.nanosecond())
+            .expect("time::OffsetDateTime out of the range of chrono::DateTime")
+    }
+
+    fn from_fixed_offset(time: &DateTime<FixedOffset>) -> Option<Self> {
+        let offset =
+            time_lib::UtcOffset::from_whole_seconds(time.offset().local_minus_utc()).ok()?;
+        Self::from_utc(&time.with_timezone(&Utc)).map(|t| t.to_offset(offset))
+    }
+
+    fn into_fixed_offset(self) -> DateTime<FixedOffset> {
+        // time offsets are below 26 hours, chrono ones below 24 hours
+        let offset = FixedOffset::east_opt(self.offset().whole_seconds());
+        let utc = self.into_utc();
+        match offset {
+            Some(offset) => utc.with_timezone(&offset),
+            None => utc.into(),
+        }
+    }
+}
+
+mod private {
+    // Sealing the TimeType
+    pub trait Sealed {}
+    impl Sealed for chrono::DateTime<chrono::Utc> {}
+    impl Sealed for chrono::DateTime<chrono::FixedOffset> {}
+    #[cfg(feature = "time")]
+    impl Sealed for time_lib::OffsetDateTime {}
 }
 
 /// The URI-reference type.
@@ -52,9 +189,119 @@ impl TryIntoTime for String {
 /// The URI reference can be a URI, or just a relative path.
 ///
 /// As the [`url::Url`] type can only represent an absolute URL, we are falling back to a string
-/// here.
+/// here. The builders check it with [`validate_uri_reference`](super::validate_uri_reference).
 ///
 /// Also see:
 /// * <https://github.com/cloudevents/spec/blob/v1.0.1/spec.md#type-system>
 /// * <https://tools.ietf.org/html/rfc3986#section-4.1>
 pub type UriReference = String;
+
+/// Value of the `time` attribute, keeping the offset it's expressed in and the text it was
+/// received as.
+///
+/// Serializing the event writes back the original text, so that round-tripping it doesn't
+/// change the offset, the precision nor the formatting of the timestamp. Equality compares
+/// only the parsed value.
+#[derive(Clone, Debug)]
+pub(crate) struct Time {
+    pub(crate) value: DateTime<Utc>,
+    offset: FixedOffset,
+    text: Option<String>,
+}
+
+impl Time {
+    pub(crate) fn parse(text: String) -> Result<Self, chrono::ParseError> {
+        let time = text.as_str().into_time_with_offset()?;
+        Ok(Time {
+            value: time.with_timezone(&Utc),
+            offset: *time.offset(),
+            text: Some(text),
+        })
+    }
+
+    /// The value in the offset it's expressed in.
+    pub(crate) fn with_offset(&self) -> DateTime<FixedOffset> {
+        self.value.with_timezone(&self.offset)
+    }
+
+    fn is_utc(&self) -> bool {
+        self.offset.local_minus_utc() == 0
+    }
+
+    /// The original text, or the RFC 3339 representation with all the non-zero sub-second
+    /// digits.
+    pub(crate) fn to_text(&self) -> Cow<'_, str> {
+        match &self.text {
+            Some(text) => Cow::Borrowed(text),
+            None => Cow::Owned(
+                self.with_offset()
+                    .to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            ),
+        }
+    }
+}
+
+impl From<DateTime<Utc>> for Time {
+    fn from(value: DateTime<Utc>) -> Self {
+        Time {
+            value,
+            offset: Utc.fix(),
+            text: None,
+        }
+    }
+}
+
+impl From<DateTime<FixedOffset>> for Time {
+    fn from(time: DateTime<FixedOffset>) -> Self {
+        Time {
+            value: time.with_timezone(&Utc),
+            offset: *time.offset(),
+            text: None,
+        }
+    }
+}
+
+impl From<Time> for DateTime<Utc> {
+    fn from(time: Time) -> Self {
+        time.value
+    }
+}
+
+impl From<Time> for MessageAttributeValue {
+    fn from(time: Time) -> Self {
+        match time.text {
+            Some(text) => MessageAttributeValue::String(text),
+            None if time.is_utc() => MessageAttributeValue::DateTime(time.value),
+            None => MessageAttributeValue::String(time.to_text().into_owned()),
+        }
+    }
+}
+
+impl TryFrom<MessageAttributeValue> for Time {
+    type Error = crate::message::Error;
+
+    fn try_from(value: MessageAttributeValue) -> Result<Self, Self::Error> {
+        match value {
+            MessageAttributeValue::DateTime(d) => Ok(d.into()),
+            v => Ok(Time::parse(v.into())?),
+        }
+    }
+}
+
+impl PartialEq for Time {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for Time {}
+
+impl Serialize for Time {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.text {
+            Some(text) => serializer.serialize_str(text),
+            None if self.is_utc() => self.value.serialize(serializer),
+            None => serializer.serialize_str(&self.to_text()),
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use crate::event::attributes::{default_hostname, AttributeValue, AttributesConverter};
-use crate::event::{AttributesReader, AttributesV10, AttributesWriter, SpecVersion, UriReference};
+use crate::event::{
+    AttributesReader, AttributesV10, AttributesWriter, SpecVersion, Time, UriReference,
+};
 use crate::message::{BinarySerializer, MessageAttributeValue};
 use chrono::{DateTime, Utc};
 use url::Url;
@@ -25,7 +27,7 @@ pub struct Attributes {
     pub(crate) datacontenttype: Option<String>,
     pub(crate) schemaurl: Option<Url>,
     pub(crate) subject: Option<String>,
-    pub(crate) time: Option<DateTime<Utc>>,
+    pub(crate) time: Option<Time>,
 }
 
 impl<'a> IntoIterator for &'a Attributes {
@@ -73,7 +75,7 @@ impl<'a> Iterator for AttributesIntoIterator<'a> {
                 .attributes
                 .time
                 .as_ref()
-                .map(|v| ("time", AttributeValue::Time(v))),
+                .map(|v| ("time", AttributeValue::Time(&v.value))),
             _ => return None,
         };
         self.index += 1;
@@ -114,7 +116,7 @@ impl AttributesReader for Attributes {
     }
 
     fn time(&self) -> Option<&DateTime<Utc>> {
-        self.time.as_ref()
+        self.time.as_ref().map(|t| &t.value)
     }
 }
 
@@ -136,7 +138,7 @@ impl AttributesWriter for Attributes {
     }
 
     fn set_time(&mut self, time: Option<impl Into<DateTime<Utc>>>) -> Option<DateTime<Utc>> {
-        std::mem::replace(&mut self.time, time.map(Into::into))
+        std::mem::replace(&mut self.time, time.map(|t| t.into().into())).map(Into::into)
     }
 
     fn set_datacontenttype(
@@ -160,7 +162,7 @@ impl Default for Attributes {
             datacontenttype: None,
             schemaurl: None,
             subject: None,
-            time: Some(Utc::now()),
+            time: Some(Utc::now().into()),
         }
     }
 }
@@ -191,27 +193,20 @@ impl crate::event::message::AttributesDeserializer for super::Attributes {
         visitor = visitor.set_attribute("id", MessageAttributeValue::String(self.id))?;
         visitor = visitor.set_attribute("type", MessageAttributeValue::String(self.ty))?;
         visitor = visitor.set_attribute("source", MessageAttributeValue::UriRef(self.source))?;
-        if self.datacontenttype.is_some() {
+        if let Some(datacontenttype) = self.datacontenttype {
             visitor = visitor.set_attribute(
                 "datacontenttype",
-                MessageAttributeValue::String(self.datacontenttype.unwrap()),
+                MessageAttributeValue::String(datacontenttype),
             )?;
         }
-        if self.schemaurl.is_some() {
-            visitor = visitor.set_attribute(
-                "schemaurl",
-                MessageAttributeValue::Uri(self.schemaurl.unwrap()),
-            )?;
+        if let Some(schemaurl) = self.schemaurl {
+            visitor = visitor.set_attribute("schemaurl", MessageAttributeValue::Uri(schemaurl))?;
         }
-        if self.subject.is_some() {
-            visitor = visitor.set_attribute(
-                "subject",
-                MessageAttributeValue::String(self.subject.unwrap()),
-            )?;
+        if let Some(subject) = self.subject {
+            visitor = visitor.set_attribute("subject", MessageAttributeValue::String(subject))?;
         }
-        if self.time.is_some() {
-            visitor = visitor
-                .set_attribute("time", MessageAttributeValue::DateTime(self.time.unwrap()))?;
+        if let Some(time) = self.time {
+            visitor = visitor.set_attribute("time", time.into())?;
         }
         Ok(visitor)
     }
@@ -243,7 +238,7 @@ mod tests {
             datacontenttype: None,
             schemaurl: None,
             subject: None,
-            time: DateTime::from_timestamp(61, 0),
+            time: DateTime::from_timestamp(61, 0).map(Into::into),
         };
         let b = &mut a.into_iter();
         let time = DateTime::from_timestamp(61, 0).unwrap();
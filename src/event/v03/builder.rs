@@ -1,11 +1,14 @@
 use super::Attributes as AttributesV03;
+#[cfg(feature = "schema")]
+use crate::event::builder::check_registered_schema;
+use crate::event::builder::check_required_attribute;
 use crate::event::{
-    Attributes, Data, Event, EventBuilderError, ExtensionValue, TryIntoTime, TryIntoUrl,
-    UriReference,
+    validate_extension, validate_uri_reference, Attributes, Data, Event, EventBuilderError,
+    ExtensionValue, Extensions, Time, TryIntoTime, TryIntoUrl, UriReference,
 };
 use crate::message::MessageAttributeValue;
-use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+#[cfg(feature = "schema")]
+use crate::schema::SchemaRegistry;
 use std::convert::TryInto;
 use url::Url;
 
@@ -18,10 +21,12 @@ pub struct EventBuilder {
     datacontenttype: Option<String>,
     schemaurl: Option<Url>,
     subject: Option<String>,
-    time: Option<DateTime<Utc>>,
+    time: Option<Time>,
     data: Option<Data>,
-    extensions: HashMap<String, ExtensionValue>,
-    error: Option<EventBuilderError>,
+    extensions: Extensions,
+    errors: Vec<EventBuilderError>,
+    #[cfg(feature = "schema")]
+    registry: Option<SchemaRegistry>,
 }
 
 impl EventBuilder {
@@ -32,12 +37,12 @@ impl EventBuilder {
 
     pub fn source(mut self, source: impl Into<String>) -> Self {
         let source = source.into();
-        if source.is_empty() {
-            self.error = Some(EventBuilderError::InvalidUriRefError {
+        match validate_uri_reference(&source) {
+            Ok(()) => self.source = Some(source),
+            Err(e) => self.errors.push(EventBuilderError::InvalidUriRefError {
                 attribute_name: "source",
-            });
-        } else {
-            self.source = Some(source);
+                source: e,
+            }),
         }
         self
     }
@@ -53,14 +58,12 @@ impl EventBuilder {
     }
 
     pub fn time(mut self, time: impl TryIntoTime) -> Self {
-        match time.into_time() {
-            Ok(u) => self.time = Some(u),
-            Err(e) => {
-                self.error = Some(EventBuilderError::ParseTimeError {
-                    attribute_name: "time",
-                    source: e,
-                })
-            }
+        match time.into_time_with_offset() {
+            Ok(t) => self.time = Some(t.into()),
+            Err(e) => self.errors.push(EventBuilderError::ParseTimeError {
+                attribute_name: "time",
+                source: e,
+            }),
         };
         self
     }
@@ -70,8 +73,14 @@ impl EventBuilder {
         extension_name: &str,
         extension_value: impl Into<ExtensionValue>,
     ) -> Self {
-        self.extensions
-            .insert(extension_name.to_owned(), extension_value.into());
+        let extension_value = extension_value.into();
+        match validate_extension(extension_name, &extension_value) {
+            Ok(()) => {
+                self.extensions
+                    .push(extension_name.to_owned(), extension_value);
+            }
+            Err(e) => self.errors.push(e.into()),
+        }
         self
     }
 
@@ -95,16 +104,30 @@ impl EventBuilder {
         self.datacontenttype = Some(datacontenttype.into());
         match schemaurl.into_url() {
             Ok(u) => self.schemaurl = Some(u),
-            Err(e) => {
-                self.error = Some(EventBuilderError::ParseUrlError {
-                    attribute_name: "schemaurl",
-                    source: e,
-                })
-            }
+            Err(e) => self.errors.push(EventBuilderError::ParseUrlError {
+                attribute_name: "schemaurl",
+                source: e,
+            }),
         };
         self.data = Some(data.into());
         self
     }
+
+    /// Set the data, validated when building the event against the schema registered in
+    /// `registry` for the event type, whose URI is set as the `schemaurl`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "schema")))]
+    #[cfg(feature = "schema")]
+    pub fn data_with_registry(
+        mut self,
+        datacontenttype: impl Into<String>,
+        data: impl Into<Data>,
+        registry: &SchemaRegistry,
+    ) -> Self {
+        self.datacontenttype = Some(datacontenttype.into());
+        self.data = Some(data.into());
+        self.registry = Some(registry.clone());
+        self
+    }
 }
 
 impl From<Event> for EventBuilder {
@@ -126,7 +149,9 @@ impl From<Event> for EventBuilder {
             time: attributes.time,
             data: event.data,
             extensions: event.extensions,
-            error: None,
+            errors: Vec::new(),
+            #[cfg(feature = "schema")]
+            registry: None,
         }
     }
 }
@@ -149,35 +174,41 @@ impl crate::event::builder::EventBuilder for EventBuilder {
             time: None,
             data: None,
             extensions: Default::default(),
-            error: None,
+            errors: Vec::new(),
+            #[cfg(feature = "schema")]
+            registry: None,
         }
     }
 
-    fn build(self) -> Result<Event, EventBuilderError> {
-        match self.error {
-            Some(e) => Err(e),
-            None => Ok(Event {
-                attributes: Attributes::V03(AttributesV03 {
-                    id: self.id.ok_or(EventBuilderError::MissingRequiredAttribute {
-                        attribute_name: "id",
-                    })?,
-                    ty: self.ty.ok_or(EventBuilderError::MissingRequiredAttribute {
-                        attribute_name: "type",
-                    })?,
-                    source: self
-                        .source
-                        .ok_or(EventBuilderError::MissingRequiredAttribute {
-                            attribute_name: "source",
-                        })?,
-                    datacontenttype: self.datacontenttype,
-                    schemaurl: self.schemaurl,
-                    subject: self.subject,
-                    time: self.time,
-                }),
-                data: self.data,
-                extensions: self.extensions,
-            }),
+    fn build(mut self) -> Result<Event, EventBuilderError> {
+        let id = check_required_attribute(&mut self.errors, "id", self.id);
+        let ty = check_required_attribute(&mut self.errors, "type", self.ty);
+        #[cfg(feature = "schema")]
+        if let (Some(registry), Some(ty)) = (&self.registry, &ty) {
+            let data = self.data.as_ref();
+            if let Some(uri) = check_registered_schema(&mut self.errors, registry, ty, data) {
+                self.schemaurl = Some(uri);
+            }
         }
+        let source = check_required_attribute(&mut self.errors, "source", self.source);
+        let (id, ty, source) = match (id, ty, source) {
+            (Some(id), Some(ty), Some(source)) if self.errors.is_empty() => (id, ty, source),
+            _ => return Err(EventBuilderError::from_errors(self.errors)),
+        };
+        self.extensions.dedup();
+        Ok(Event {
+            attributes: Attributes::V03(AttributesV03 {
+                id,
+                ty,
+                source,
+                datacontenttype: self.datacontenttype,
+                schemaurl: self.schemaurl,
+                subject: self.subject,
+                time: self.time,
+            }),
+            data: self.data,
+            extensions: self.extensions,
+        })
     }
 }
 
@@ -188,12 +219,12 @@ impl crate::event::message::AttributesSerializer for EventBuilder {
         value: MessageAttributeValue,
     ) -> crate::message::Result<()> {
         match name {
-            "id" => self.id = Some(value.to_string()),
-            "type" => self.ty = Some(value.to_string()),
-            "source" => self.source = Some(value.to_string()),
-            "datacontenttype" => self.datacontenttype = Some(value.to_string()),
+            "id" => self.id = Some(value.into()),
+            "type" => self.ty = Some(value.into()),
+            "source" => self.source = Some(value.into()),
+            "datacontenttype" => self.datacontenttype = Some(value.into()),
             "schemaurl" => self.schemaurl = Some(value.try_into()?),
-            "subject" => self.subject = Some(value.to_string()),
+            "subject" => self.subject = Some(value.into()),
             "time" => self.time = Some(value.try_into()?),
             _ => {
                 return Err(crate::message::Error::UnknownAttribute {
@@ -275,6 +306,7 @@ mod tests {
     fn build_missing_id() {
         let res = EventBuilderV03::new()
             .source("http://localhost:8080")
+            .ty("type")
             .build();
         assert_match_pattern!(
             res,
@@ -286,11 +318,16 @@ mod tests {
 
     #[test]
     fn source_invalid_url() {
-        let res = EventBuilderV03::new().source("").build();
+        let res = EventBuilderV03::new()
+            .id("id1")
+            .ty("type")
+            .source("")
+            .build();
         assert_match_pattern!(
             res,
             Err(EventBuilderError::InvalidUriRefError {
                 attribute_name: "source",
+                source: crate::event::UriReferenceError::Unsupported { .. },
             })
         );
     }
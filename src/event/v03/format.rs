@@ -1,18 +1,18 @@
 use super::Attributes;
 use crate::event::data::is_json_content_type;
 use crate::event::format::{
-    parse_data_base64, parse_data_base64_json, parse_data_json, parse_data_string,
+    parse_data_base64, parse_data_base64_json, parse_data_json, parse_data_string, Base64Data,
 };
-use crate::event::{Data, ExtensionValue};
-use base64::prelude::*;
-use chrono::{DateTime, Utc};
+use crate::event::{Data, Extensions, Time};
 use serde::de::IntoDeserializer;
 use serde::ser::SerializeMap;
 use serde::{Deserialize, Serializer};
 use serde_json::{Map, Value};
-use std::collections::HashMap;
 use url::Url;
 
+const DATA_CONTENT_ENCODING: &str = "datacontentencoding";
+const DATA_CONTENT_ENCODING_BASE64: &str = "base64";
+
 pub(crate) struct EventFormatDeserializer {}
 
 impl crate::event::format::EventFormatDeserializer for EventFormatDeserializer {
@@ -28,9 +28,7 @@ impl crate::event::format::EventFormatDeserializer for EventFormatDeserializer {
                 Url::parse(&s)
             })?,
             subject: extract_optional_field!(map, "subject", String, E)?,
-            time: extract_optional_field!(map, "time", String, E, |s: String| {
-                DateTime::parse_from_rfc3339(&s).map(DateTime::<Utc>::from)
-            })?,
+            time: extract_optional_field!(map, "time", String, E, Time::parse)?,
         }))
     }
 
@@ -39,13 +37,21 @@ impl crate::event::format::EventFormatDeserializer for EventFormatDeserializer {
         map: &mut Map<String, Value>,
     ) -> Result<Option<Data>, E> {
         let data = map.remove("data");
-        let is_base64 = map
+        let is_base64 = match map
             .remove("datacontentencoding")
             .map(String::deserialize)
             .transpose()
             .map_err(E::custom)?
-            .map(|dce| dce.to_lowercase() == "base64")
-            .unwrap_or(false);
+        {
+            Some(dce) if dce.eq_ignore_ascii_case(DATA_CONTENT_ENCODING_BASE64) => true,
+            Some(dce) => {
+                return Err(E::custom(format_args!(
+                    "unsupported datacontentencoding `{}`",
+                    dce
+                )))
+            }
+            None => false,
+        };
         let is_json = is_json_content_type(content_type);
 
         Ok(match (data, is_base64, is_json) {
@@ -66,9 +72,15 @@ impl<S: serde::Serializer> crate::event::format::EventFormatSerializer<S, Attrib
     fn serialize(
         attributes: &Attributes,
         data: &Option<Data>,
-        extensions: &HashMap<String, ExtensionValue>,
+        extensions: &Extensions,
+        include_secrets: bool,
         serializer: S,
     ) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error> {
+        let is_base64 = matches!(data, Some(Data::Binary(_)) | Some(Data::Bytes(_)));
+        // datacontentencoding is determined by the data, so it can't be an extension
+        let extensions = extensions
+            .iter_masked(include_secrets)
+            .filter(|(k, _)| k.as_str() != DATA_CONTENT_ENCODING);
         let num = 4
             + [
                 attributes.datacontenttype.is_some(),
@@ -76,11 +88,12 @@ impl<S: serde::Serializer> crate::event::format::EventFormatSerializer<S, Attrib
                 attributes.subject.is_some(),
                 attributes.time.is_some(),
                 data.is_some(),
+                is_base64,
             ]
             .iter()
             .filter(|&b| *b)
             .count()
-            + extensions.len();
+            + extensions.clone().count();
 
         let mut state = serializer.serialize_map(Some(num))?;
         state.serialize_entry("specversion", "0.3")?;
@@ -103,14 +116,63 @@ impl<S: serde::Serializer> crate::event::format::EventFormatSerializer<S, Attrib
             Some(Data::Json(j)) => state.serialize_entry("data", j)?,
             Some(Data::String(s)) => state.serialize_entry("data", s)?,
             Some(Data::Binary(v)) => {
-                state.serialize_entry("data", &BASE64_STANDARD.encode(v))?;
-                state.serialize_entry("datacontentencoding", "base64")?;
+                state.serialize_entry("data", &Base64Data(v))?;
+                state.serialize_entry(DATA_CONTENT_ENCODING, DATA_CONTENT_ENCODING_BASE64)?;
+            }
+            Some(Data::Bytes(b)) => {
+                state.serialize_entry("data", &Base64Data(b))?;
+                state.serialize_entry(DATA_CONTENT_ENCODING, DATA_CONTENT_ENCODING_BASE64)?;
             }
             _ => (),
         };
         for (k, v) in extensions {
-            state.serialize_entry(k, v)?;
+            state.serialize_entry(k, &v)?;
         }
         state.end()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test::fixtures;
+    use crate::{AttributesReader, Event};
+    use serde_json::json;
+
+    #[test]
+    fn decode_base64_string_data() {
+        let mut json = fixtures::v03::full_xml_base64_data_json();
+        json["datacontentencoding"] = json!("Base64");
+
+        let event: Event = serde_json::from_value(json).unwrap();
+
+        assert_eq!(event, fixtures::v03::full_xml_binary_data());
+    }
+
+    #[test]
+    fn unsupported_encoding() {
+        let mut json = fixtures::v03::full_xml_base64_data_json();
+        json["datacontentencoding"] = json!("quoted-printable");
+
+        assert!(serde_json::from_value::<Event>(json).is_err());
+    }
+
+    #[test]
+    fn encoding_extension_is_not_serialized() {
+        let mut event = fixtures::v03::full_xml_binary_data();
+        event.set_extension("datacontentencoding", "base64");
+        let json = serde_json::to_string(&event).unwrap();
+
+        assert_eq!(json.matches("datacontentencoding").count(), 1);
+
+        let mut event = fixtures::v03::full_xml_string_data();
+        event.set_extension("datacontentencoding", "base64");
+        let deserialized: Event =
+            serde_json::from_value(serde_json::to_value(&event).unwrap()).unwrap();
+
+        assert_eq!(
+            deserialized.data(),
+            fixtures::v03::full_xml_string_data().data()
+        );
+        assert_eq!(deserialized.specversion(), crate::event::SpecVersion::V03);
+    }
+}
@@ -1,5 +1,7 @@
 use crate::event::attributes::{default_hostname, AttributeValue, AttributesConverter};
-use crate::event::{AttributesReader, AttributesV03, AttributesWriter, SpecVersion, UriReference};
+use crate::event::{
+    AttributesReader, AttributesV03, AttributesWriter, SpecVersion, Time, UriReference,
+};
 use crate::message::{BinarySerializer, MessageAttributeValue};
 use chrono::{DateTime, Utc};
 use core::fmt::Debug;
@@ -26,7 +28,7 @@ pub struct Attributes {
     pub(crate) datacontenttype: Option<String>,
     pub(crate) dataschema: Option<Url>,
     pub(crate) subject: Option<String>,
-    pub(crate) time: Option<DateTime<Utc>>,
+    pub(crate) time: Option<Time>,
 }
 
 impl<'a> IntoIterator for &'a Attributes {
@@ -74,7 +76,7 @@ impl<'a> Iterator for AttributesIntoIterator<'a> {
                 .attributes
                 .time
                 .as_ref()
-                .map(|v| ("time", AttributeValue::Time(v))),
+                .map(|v| ("time", AttributeValue::Time(&v.value))),
             _ => return None,
         };
         self.index += 1;
@@ -115,7 +117,7 @@ impl AttributesReader for Attributes {
     }
 
     fn time(&self) -> Option<&DateTime<Utc>> {
-        self.time.as_ref()
+        self.time.as_ref().map(|t| &t.value)
     }
 }
 
@@ -137,7 +139,7 @@ impl AttributesWriter for Attributes {
     }
 
     fn set_time(&mut self, time: Option<impl Into<DateTime<Utc>>>) -> Option<DateTime<Utc>> {
-        std::mem::replace(&mut self.time, time.map(Into::into))
+        std::mem::replace(&mut self.time, time.map(|t| t.into().into())).map(Into::into)
     }
 
     fn set_datacontenttype(
@@ -161,7 +163,7 @@ impl Default for Attributes {
             datacontenttype: None,
             dataschema: None,
             subject: None,
-            time: Some(Utc::now()),
+            time: Some(Utc::now().into()),
         }
     }
 }
@@ -174,27 +176,21 @@ impl crate::event::message::AttributesDeserializer for super::Attributes {
         visitor = visitor.set_attribute("id", MessageAttributeValue::String(self.id))?;
         visitor = visitor.set_attribute("type", MessageAttributeValue::String(self.ty))?;
         visitor = visitor.set_attribute("source", MessageAttributeValue::UriRef(self.source))?;
-        if self.datacontenttype.is_some() {
+        if let Some(datacontenttype) = self.datacontenttype {
             visitor = visitor.set_attribute(
                 "datacontenttype",
-                MessageAttributeValue::String(self.datacontenttype.unwrap()),
+                MessageAttributeValue::String(datacontenttype),
             )?;
         }
-        if self.dataschema.is_some() {
-            visitor = visitor.set_attribute(
-                "dataschema",
-                MessageAttributeValue::Uri(self.dataschema.unwrap()),
-            )?;
+        if let Some(dataschema) = self.dataschema {
+            visitor =
+                visitor.set_attribute("dataschema", MessageAttributeValue::Uri(dataschema))?;
         }
-        if self.subject.is_some() {
-            visitor = visitor.set_attribute(
-                "subject",
-                MessageAttributeValue::String(self.subject.unwrap()),
-            )?;
+        if let Some(subject) = self.subject {
+            visitor = visitor.set_attribute("subject", MessageAttributeValue::String(subject))?;
         }
-        if self.time.is_some() {
-            visitor = visitor
-                .set_attribute("time", MessageAttributeValue::DateTime(self.time.unwrap()))?;
+        if let Some(time) = self.time {
+            visitor = visitor.set_attribute("time", time.into())?;
         }
         Ok(visitor)
     }
@@ -243,7 +239,7 @@ mod tests {
             datacontenttype: None,
             dataschema: None,
             subject: None,
-            time: DateTime::from_timestamp(61, 0),
+            time: DateTime::from_timestamp(61, 0).map(Into::into),
         };
         let b = &mut a.into_iter();
         let time = DateTime::from_timestamp(61, 0).unwrap();
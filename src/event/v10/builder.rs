@@ -1,11 +1,14 @@
 use super::Attributes as AttributesV10;
+#[cfg(feature = "schema")]
+use crate::event::builder::check_registered_schema;
+use crate::event::builder::check_required_attribute;
 use crate::event::{
-    Attributes, Data, Event, EventBuilderError, ExtensionValue, TryIntoTime, TryIntoUrl,
-    UriReference,
+    validate_extension, validate_uri_reference, Attributes, Data, Event, EventBuilderError,
+    ExtensionValue, Extensions, Time, TryIntoTime, TryIntoUrl, UriReference,
 };
 use crate::message::MessageAttributeValue;
-use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+#[cfg(feature = "schema")]
+use crate::schema::SchemaRegistry;
 use std::convert::TryInto;
 use url::Url;
 
@@ -18,10 +21,12 @@ pub struct EventBuilder {
     datacontenttype: Option<String>,
     dataschema: Option<Url>,
     subject: Option<String>,
-    time: Option<DateTime<Utc>>,
+    time: Option<Time>,
     data: Option<Data>,
-    extensions: HashMap<String, ExtensionValue>,
-    error: Option<EventBuilderError>,
+    extensions: Extensions,
+    errors: Vec<EventBuilderError>,
+    #[cfg(feature = "schema")]
+    registry: Option<SchemaRegistry>,
 }
 
 impl EventBuilder {
@@ -32,12 +37,12 @@ impl EventBuilder {
 
     pub fn source(mut self, source: impl Into<String>) -> Self {
         let source = source.into();
-        if source.is_empty() {
-            self.error = Some(EventBuilderError::InvalidUriRefError {
+        match validate_uri_reference(&source) {
+            Ok(()) => self.source = Some(source),
+            Err(e) => self.errors.push(EventBuilderError::InvalidUriRefError {
                 attribute_name: "source",
-            });
-        } else {
-            self.source = Some(source);
+                source: e,
+            }),
         }
         self
     }
@@ -53,14 +58,12 @@ impl EventBuilder {
     }
 
     pub fn time(mut self, time: impl TryIntoTime) -> Self {
-        match time.into_time() {
-            Ok(u) => self.time = Some(u),
-            Err(e) => {
-                self.error = Some(EventBuilderError::ParseTimeError {
-                    attribute_name: "time",
-                    source: e,
-                })
-            }
+        match time.into_time_with_offset() {
+            Ok(t) => self.time = Some(t.into()),
+            Err(e) => self.errors.push(EventBuilderError::ParseTimeError {
+                attribute_name: "time",
+                source: e,
+            }),
         };
         self
     }
@@ -70,8 +73,14 @@ impl EventBuilder {
         extension_name: &str,
         extension_value: impl Into<ExtensionValue>,
     ) -> Self {
-        self.extensions
-            .insert(extension_name.to_owned(), extension_value.into());
+        let extension_value = extension_value.into();
+        match validate_extension(extension_name, &extension_value) {
+            Ok(()) => {
+                self.extensions
+                    .push(extension_name.to_owned(), extension_value);
+            }
+            Err(e) => self.errors.push(e.into()),
+        }
         self
     }
 
@@ -95,16 +104,30 @@ impl EventBuilder {
         self.datacontenttype = Some(datacontenttype.into());
         match schemaurl.into_url() {
             Ok(u) => self.dataschema = Some(u),
-            Err(e) => {
-                self.error = Some(EventBuilderError::ParseUrlError {
-                    attribute_name: "dataschema",
-                    source: e,
-                })
-            }
+            Err(e) => self.errors.push(EventBuilderError::ParseUrlError {
+                attribute_name: "dataschema",
+                source: e,
+            }),
         };
         self.data = Some(data.into());
         self
     }
+
+    /// Set the data, validated when building the event against the schema registered in
+    /// `registry` for the event type, whose URI is set as the `dataschema`.
+    #[cfg_attr(docsrs, doc(cfg(feature = "schema")))]
+    #[cfg(feature = "schema")]
+    pub fn data_with_registry(
+        mut self,
+        datacontenttype: impl Into<String>,
+        data: impl Into<Data>,
+        registry: &SchemaRegistry,
+    ) -> Self {
+        self.datacontenttype = Some(datacontenttype.into());
+        self.data = Some(data.into());
+        self.registry = Some(registry.clone());
+        self
+    }
 }
 
 impl From<Event> for EventBuilder {
@@ -126,7 +149,9 @@ impl From<Event> for EventBuilder {
             time: attributes.time,
             data: event.data,
             extensions: event.extensions,
-            error: None,
+            errors: Vec::new(),
+            #[cfg(feature = "schema")]
+            registry: None,
         }
     }
 }
@@ -149,35 +174,41 @@ impl crate::event::builder::EventBuilder for EventBuilder {
             time: None,
             data: None,
             extensions: Default::default(),
-            error: None,
+            errors: Vec::new(),
+            #[cfg(feature = "schema")]
+            registry: None,
         }
     }
 
-    fn build(self) -> Result<Event, EventBuilderError> {
-        match self.error {
-            Some(e) => Err(e),
-            None => Ok(Event {
-                attributes: Attributes::V10(AttributesV10 {
-                    id: self.id.ok_or(EventBuilderError::MissingRequiredAttribute {
-                        attribute_name: "id",
-                    })?,
-                    ty: self.ty.ok_or(EventBuilderError::MissingRequiredAttribute {
-                        attribute_name: "type",
-                    })?,
-                    source: self
-                        .source
-                        .ok_or(EventBuilderError::MissingRequiredAttribute {
-                            attribute_name: "source",
-                        })?,
-                    datacontenttype: self.datacontenttype,
-                    dataschema: self.dataschema,
-                    subject: self.subject,
-                    time: self.time,
-                }),
-                data: self.data,
-                extensions: self.extensions,
-            }),
+    fn build(mut self) -> Result<Event, EventBuilderError> {
+        let id = check_required_attribute(&mut self.errors, "id", self.id);
+        let ty = check_required_attribute(&mut self.errors, "type", self.ty);
+        #[cfg(feature = "schema")]
+        if let (Some(registry), Some(ty)) = (&self.registry, &ty) {
+            let data = self.data.as_ref();
+            if let Some(uri) = check_registered_schema(&mut self.errors, registry, ty, data) {
+                self.dataschema = Some(uri);
+            }
         }
+        let source = check_required_attribute(&mut self.errors, "source", self.source);
+        let (id, ty, source) = match (id, ty, source) {
+            (Some(id), Some(ty), Some(source)) if self.errors.is_empty() => (id, ty, source),
+            _ => return Err(EventBuilderError::from_errors(self.errors)),
+        };
+        self.extensions.dedup();
+        Ok(Event {
+            attributes: Attributes::V10(AttributesV10 {
+                id,
+                ty,
+                source,
+                datacontenttype: self.datacontenttype,
+                dataschema: self.dataschema,
+                subject: self.subject,
+                time: self.time,
+            }),
+            data: self.data,
+            extensions: self.extensions,
+        })
     }
 }
 
@@ -188,12 +219,12 @@ impl crate::event::message::AttributesSerializer for EventBuilder {
         value: MessageAttributeValue,
     ) -> crate::message::Result<()> {
         match name {
-            "id" => self.id = Some(value.to_string()),
-            "type" => self.ty = Some(value.to_string()),
-            "source" => self.source = Some(value.to_string()),
-            "datacontenttype" => self.datacontenttype = Some(value.to_string()),
+            "id" => self.id = Some(value.into()),
+            "type" => self.ty = Some(value.into()),
+            "source" => self.source = Some(value.into()),
+            "datacontenttype" => self.datacontenttype = Some(value.into()),
             "dataschema" => self.dataschema = Some(value.try_into()?),
-            "subject" => self.subject = Some(value.to_string()),
+            "subject" => self.subject = Some(value.into()),
             "time" => self.time = Some(value.try_into()?),
             _ => {
                 return Err(crate::message::Error::UnknownAttribute {
@@ -275,6 +306,7 @@ mod tests {
     fn build_missing_id() {
         let res = EventBuilderV10::new()
             .source("http://localhost:8080")
+            .ty("type")
             .build();
         assert_match_pattern!(
             res,
@@ -286,18 +318,117 @@ mod tests {
 
     #[test]
     fn source_invalid_url() {
-        let res = EventBuilderV10::new().source("").build();
+        let res = EventBuilderV10::new()
+            .id("id1")
+            .ty("type")
+            .source("")
+            .build();
         assert_match_pattern!(
             res,
             Err(EventBuilderError::InvalidUriRefError {
                 attribute_name: "source",
+                source: crate::event::UriReferenceError::Unsupported { .. },
             })
         );
     }
 
+    #[test]
+    fn build_multiple_errors() {
+        let err = EventBuilderV10::new()
+            .source("")
+            .time("not a time")
+            .extension("Bad_Name", "value")
+            .build()
+            .unwrap_err();
+
+        assert_match_pattern!(err, EventBuilderError::Multiple { .. });
+        assert_eq!(
+            err.errors()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec![
+                "Invalid value setting attribute 'source' with uriref type: Unsupported URI-reference ``: the reference is empty",
+                "Error while setting attribute 'time' with timestamp type: premature end of input",
+                "Invalid extension name `Bad_Name`, expected 1 to 20 lowercase ASCII letters or digits",
+                "Missing required attribute id",
+                "Missing required attribute type",
+            ]
+        );
+    }
+
     #[test]
     fn default_builds() {
         let res = EventBuilderV10::default().build();
         assert_match_pattern!(res, Ok(_));
     }
+
+    #[test]
+    fn source_relative_uri_reference() {
+        let event = EventBuilderV10::new()
+            .id("0001")
+            .ty("example.test")
+            .source("/cluster/node-1")
+            .build()
+            .unwrap();
+
+        assert_eq!(event.source(), "/cluster/node-1");
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["source"], "/cluster/node-1");
+        assert_eq!(serde_json::from_value::<crate::Event>(json).unwrap(), event);
+    }
+
+    #[cfg(feature = "schema")]
+    fn order_registry() -> crate::schema::SchemaRegistry {
+        fn require_object(
+            data: &crate::event::Data,
+        ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+            match data {
+                crate::event::Data::Json(value) if value.is_object() => Ok(()),
+                _ => Err("not an object".into()),
+            }
+        }
+        crate::schema::SchemaRegistry::new().register(
+            "order.created",
+            Url::parse("http://localhost/schemas/order.json").unwrap(),
+            require_object,
+        )
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn data_with_registry() {
+        let registry = order_registry();
+        let builder = EventBuilderV10::new()
+            .id("0001")
+            .ty("order.created")
+            .source("http://localhost/");
+
+        let event = builder
+            .clone()
+            .data_with_registry("application/json", serde_json::json!({}), &registry)
+            .build()
+            .unwrap();
+        assert_eq!(
+            event.dataschema().map(Url::as_str),
+            Some("http://localhost/schemas/order.json")
+        );
+
+        let err = builder
+            .clone()
+            .data_with_registry("application/json", serde_json::json!([]), &registry)
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The data doesn't conform to the schema http://localhost/schemas/order.json of the event type order.created: not an object"
+        );
+
+        let err = builder
+            .ty("order.deleted")
+            .data_with_registry("application/json", serde_json::json!({}), &registry)
+            .build()
+            .unwrap_err();
+        assert_match_pattern!(err, EventBuilderError::UnregisteredSchema { .. });
+    }
 }
@@ -1,16 +1,13 @@
 use super::Attributes;
 use crate::event::data::is_json_content_type;
 use crate::event::format::{
-    parse_data_base64, parse_data_base64_json, parse_data_json, parse_data_string,
+    parse_data_base64, parse_data_base64_json, parse_data_json, parse_data_string, Base64Data,
 };
-use crate::event::{Data, ExtensionValue};
-use base64::prelude::*;
-use chrono::{DateTime, Utc};
+use crate::event::{Data, Extensions, Time};
 use serde::de::IntoDeserializer;
 use serde::ser::SerializeMap;
 use serde::{Deserialize, Serializer};
 use serde_json::{Map, Value};
-use std::collections::HashMap;
 use url::Url;
 
 pub(crate) struct EventFormatDeserializer {}
@@ -28,9 +25,7 @@ impl crate::event::format::EventFormatDeserializer for EventFormatDeserializer {
                 Url::parse(&s)
             })?,
             subject: extract_optional_field!(map, "subject", String, E)?,
-            time: extract_optional_field!(map, "time", String, E, |s: String| {
-                DateTime::parse_from_rfc3339(&s).map(DateTime::<Utc>::from)
-            })?,
+            time: extract_optional_field!(map, "time", String, E, Time::parse)?,
         }))
     }
 
@@ -67,7 +62,8 @@ impl<S: serde::Serializer> crate::event::format::EventFormatSerializer<S, Attrib
     fn serialize(
         attributes: &Attributes,
         data: &Option<Data>,
-        extensions: &HashMap<String, ExtensionValue>,
+        extensions: &Extensions,
+        include_secrets: bool,
         serializer: S,
     ) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error> {
         let num = 4
@@ -103,13 +99,12 @@ impl<S: serde::Serializer> crate::event::format::EventFormatSerializer<S, Attrib
         match data {
             Some(Data::Json(j)) => state.serialize_entry("data", j)?,
             Some(Data::String(s)) => state.serialize_entry("data", s)?,
-            Some(Data::Binary(v)) => {
-                state.serialize_entry("data_base64", &BASE64_STANDARD.encode(v))?
-            }
+            Some(Data::Binary(v)) => state.serialize_entry("data_base64", &Base64Data(v))?,
+            Some(Data::Bytes(b)) => state.serialize_entry("data_base64", &Base64Data(b))?,
             _ => (),
         };
-        for (k, v) in extensions {
-            state.serialize_entry(k, v)?;
+        for (k, v) in extensions.iter_masked(include_secrets) {
+            state.serialize_entry(k, &v)?;
         }
         state.end()
     }
@@ -0,0 +1,313 @@
+//! This module implements the `expirytime` extension, the time after which an [`Event`] is
+//! stale and should not be processed anymore, e.g. a command which lost its meaning during an
+//! outage.
+//!
+//! ```
+//! use cloudevents::expiry::{drop_expired, is_expired, set_expiry_time};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//! use chrono::{Duration, Utc};
+//! use futures::stream::{self, StreamExt};
+//!
+//! # async fn example() {
+//! let mut event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("example.command")
+//!     .source("http://localhost/")
+//!     .build()
+//!     .unwrap();
+//!
+//! set_expiry_time(&mut event, Utc::now() - Duration::minutes(5));
+//! assert!(is_expired(&event, Utc::now()));
+//!
+//! let events = stream::iter(vec![event]);
+//! assert_eq!(drop_expired(events).count().await, 0);
+//! # }
+//! ```
+//!
+//! [`ExpiryFilter`] drops the expired events received from an
+//! [`EventSource`](crate::transport::EventSource) or sent to an
+//! [`EventSink`](crate::transport::EventSink), optionally routing them to a dead-letter sink.
+//!
+//! With the `observer` feature, the dropped events are reported to
+//! [`EventObserver::on_expired`](crate::observer::EventObserver::on_expired).
+
+use crate::transport::{self, annotate_dead_letter, DeadLetterStage, EventSink, EventSource};
+use crate::Event;
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::{Stream, StreamExt};
+
+/// Name of the extension holding the expiry time of an event.
+pub const EXPIRY_TIME_EXTENSION: &str = "expirytime";
+
+/// Get the `expirytime` of `event`. A value which is not a valid RFC 3339 timestamp is ignored.
+pub fn expiry_time(event: &Event) -> Option<DateTime<Utc>> {
+    event
+        .extension_as::<DateTime<Utc>>(EXPIRY_TIME_EXTENSION)
+        .ok()
+        .flatten()
+}
+
+/// Set the `expirytime` of `event`.
+pub fn set_expiry_time(event: &mut Event, time: DateTime<Utc>) {
+    event.set_extension(
+        EXPIRY_TIME_EXTENSION,
+        time.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+    );
+}
+
+/// Returns `true` if the `expirytime` of `event` is before `now`. Events without a valid
+/// `expirytime` never expire.
+pub fn is_expired(event: &Event, now: DateTime<Utc>) -> bool {
+    expiry_time(event).is_some_and(|time| time < now)
+}
+
+fn expired(event: &Event) -> bool {
+    let expired = is_expired(event, Utc::now());
+    #[cfg(feature = "observer")]
+    if expired {
+        use crate::observer::{measure::Measure, Operation};
+        Measure::start("expiry", Operation::Expire, Some(event))
+            .finish(&Ok::<_, std::convert::Infallible>(()), None);
+    }
+    expired
+}
+
+/// Drop the events of `events` which expired.
+pub fn drop_expired<S>(events: S) -> impl Stream<Item = Event>
+where
+    S: Stream<Item = Event>,
+{
+    events.filter(|event| futures::future::ready(!expired(event)))
+}
+
+/// Wrapper of an [`EventSource`] or an [`EventSink`] dropping the expired events.
+///
+/// The expired events received from a source are acknowledged, so they are not redelivered.
+/// With [`ExpiryFilter::dead_letter`], they are sent to a dead-letter sink instead of being
+/// dropped, annotated with the `deadletterreason` and `deadletterstage` extensions.
+///
+/// ```
+/// use cloudevents::expiry::ExpiryFilter;
+/// use cloudevents::transport::{EventSink, EventSource, Result};
+///
+/// async fn consume(
+///     source: impl EventSource,
+///     dead_letter_sink: impl EventSink + 'static,
+/// ) -> Result<()> {
+///     let mut source = ExpiryFilter::new(source).dead_letter(dead_letter_sink);
+///     while let Some(received) = source.receive().await {
+///         let (event, ack) = received?;
+///         // Only the events which did not expire are received
+///         println!("{}", event);
+///         source.ack(ack).await?;
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct ExpiryFilter<T> {
+    inner: T,
+    dead_letter_sink: Option<Box<dyn EventSink>>,
+}
+
+impl<T> ExpiryFilter<T> {
+    /// Create a new [`ExpiryFilter`] dropping the expired events of `inner`.
+    pub fn new(inner: T) -> Self {
+        ExpiryFilter {
+            inner,
+            dead_letter_sink: None,
+        }
+    }
+
+    /// Send the expired events to `dead_letter_sink` instead of dropping them.
+    pub fn dead_letter(mut self, dead_letter_sink: impl EventSink + 'static) -> Self {
+        self.dead_letter_sink = Some(Box::new(dead_letter_sink));
+        self
+    }
+
+    /// The wrapped source or sink.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+/// Send the expired `event` to `dead_letter_sink`, if any.
+async fn discard(
+    dead_letter_sink: Option<&dyn EventSink>,
+    mut event: Event,
+) -> transport::Result<()> {
+    match dead_letter_sink {
+        Some(sink) => {
+            let reason = format!(
+                "expired at {}",
+                event
+                    .extension(EXPIRY_TIME_EXTENSION)
+                    .map(ToString::to_string)
+                    .unwrap_or_default()
+            );
+            annotate_dead_letter(&mut event, DeadLetterStage::Processing, reason);
+            sink.send(event).await
+        }
+        None => Ok(()),
+    }
+}
+
+#[async_trait]
+impl<S: EventSink> EventSink for ExpiryFilter<S> {
+    /// Send `event` to the wrapped sink, unless it expired.
+    async fn send(&self, event: Event) -> transport::Result<()> {
+        if expired(&event) {
+            return discard(self.dead_letter_sink.as_deref(), event).await;
+        }
+        self.inner.send(event).await
+    }
+}
+
+#[async_trait]
+impl<Src: EventSource> EventSource for ExpiryFilter<Src> {
+    type Ack = Src::Ack;
+
+    /// Receive the next event which did not expire, acknowledging the expired ones.
+    async fn receive(&mut self) -> Option<transport::Result<(Event, Src::Ack)>> {
+        loop {
+            let (event, ack) = match self.inner.receive().await? {
+                Ok(received) => received,
+                Err(e) => return Some(Err(e)),
+            };
+            if !expired(&event) {
+                return Some(Ok((event, ack)));
+            }
+            if let Err(e) = discard(self.dead_letter_sink.as_deref(), event).await {
+                return Some(Err(e));
+            }
+            if let Err(e) = self.inner.ack(ack).await {
+                return Some(Err(e));
+            }
+        }
+    }
+
+    async fn ack(&mut self, ack: Src::Ack) -> transport::Result<()> {
+        self.inner.ack(ack).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use chrono::Duration;
+    use futures::stream;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default, Clone)]
+    struct VecSink(Arc<Mutex<Vec<Event>>>);
+
+    #[async_trait]
+    impl EventSink for VecSink {
+        async fn send(&self, event: Event) -> transport::Result<()> {
+            self.0.lock().unwrap().push(event);
+            Ok(())
+        }
+    }
+
+    struct VecSource {
+        events: Vec<Event>,
+        acked: Vec<usize>,
+    }
+
+    #[async_trait]
+    impl EventSource for VecSource {
+        type Ack = usize;
+
+        async fn receive(&mut self) -> Option<transport::Result<(Event, usize)>> {
+            let event = self.events.pop()?;
+            Some(Ok((event, self.events.len())))
+        }
+
+        async fn ack(&mut self, ack: usize) -> transport::Result<()> {
+            self.acked.push(ack);
+            Ok(())
+        }
+    }
+
+    fn expiring(offset: Duration) -> Event {
+        let mut event = fixtures::v10::minimal();
+        set_expiry_time(&mut event, Utc::now() + offset);
+        event
+    }
+
+    #[test]
+    fn expiry() {
+        let mut event = fixtures::v10::minimal();
+        let now = Utc::now();
+        assert_eq!(expiry_time(&event), None);
+        assert!(!is_expired(&event, now));
+
+        event.set_extension(EXPIRY_TIME_EXTENSION, "2020-03-19T12:00:00+01:00");
+        assert_eq!(
+            expiry_time(&event),
+            Some("2020-03-19T11:00:00Z".parse().unwrap())
+        );
+        assert!(is_expired(&event, now));
+
+        set_expiry_time(&mut event, now + Duration::hours(1));
+        assert!(!is_expired(&event, now));
+
+        event.set_extension(EXPIRY_TIME_EXTENSION, "tomorrow");
+        assert_eq!(expiry_time(&event), None);
+        assert!(!is_expired(&event, now));
+    }
+
+    #[tokio::test]
+    async fn stream() {
+        let events = vec![
+            expiring(-Duration::seconds(1)),
+            expiring(Duration::hours(1)),
+            fixtures::v10::minimal(),
+        ];
+
+        let fresh: Vec<Event> = drop_expired(stream::iter(events.clone())).collect().await;
+
+        assert_eq!(fresh, events[1..]);
+    }
+
+    #[tokio::test]
+    async fn sink() {
+        let inner = VecSink::default();
+        let dead_letter_sink = VecSink::default();
+        let sink = ExpiryFilter::new(inner.clone()).dead_letter(dead_letter_sink.clone());
+
+        sink.send(expiring(-Duration::seconds(1))).await.unwrap();
+        sink.send(fixtures::v10::minimal()).await.unwrap();
+
+        assert_eq!(*inner.0.lock().unwrap(), vec![fixtures::v10::minimal()]);
+        let dead_lettered = dead_letter_sink.0.lock().unwrap();
+        assert_eq!(dead_lettered.len(), 1);
+        assert_eq!(
+            dead_lettered[0]
+                .extension("deadletterstage")
+                .unwrap()
+                .to_string(),
+            "processing"
+        );
+    }
+
+    #[tokio::test]
+    async fn source() {
+        let mut source = ExpiryFilter::new(VecSource {
+            events: vec![
+                expiring(Duration::hours(1)),
+                expiring(-Duration::seconds(1)),
+                expiring(-Duration::seconds(1)),
+            ],
+            acked: Vec::new(),
+        });
+
+        let (event, ack) = source.receive().await.unwrap().unwrap();
+        assert!(!is_expired(&event, Utc::now()));
+        source.ack(ack).await.unwrap();
+        assert!(source.receive().await.is_none());
+
+        assert_eq!(source.inner().acked, vec![2, 1, 0]);
+    }
+}
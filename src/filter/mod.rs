@@ -0,0 +1,386 @@
+//! This module provides the [`Filter`] trait, to select [`Event`]s with the
+//! [filter dialects](https://github.com/cloudevents/spec/blob/main/subscriptions/spec.md#324-filters)
+//! of the Subscriptions API, following the semantics of Knative Triggers.
+//!
+//! Filters are composed with the [`FilterExt`] combinators:
+//!
+//! ```
+//! use cloudevents::filter::{exact, prefix, suffix, Filter, FilterExt};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//!
+//! let filter = prefix("type", "com.example.")
+//!     .and(exact("subject", "orders"))
+//!     .and(suffix("type", ".deleted").not());
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("com.example.order.created")
+//!     .source("http://localhost/")
+//!     .subject("orders")
+//!     .build()
+//!     .unwrap();
+//!
+//! assert!(filter.matches(&event));
+//! ```
+//!
+//! Attribute values are compared with their string representation, and filters on a missing
+//! attribute never match. As with Knative Triggers, empty [`all`] and [`any`] filters match
+//! every event.
+
+use crate::Event;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// A predicate on [`Event`]s.
+pub trait Filter: Send + Sync {
+    /// Returns `true` if `event` passes the filter.
+    fn matches(&self, event: &Event) -> bool;
+}
+
+impl<F> Filter for F
+where
+    F: Fn(&Event) -> bool + Send + Sync,
+{
+    fn matches(&self, event: &Event) -> bool {
+        self(event)
+    }
+}
+
+impl Filter for Box<dyn Filter> {
+    fn matches(&self, event: &Event) -> bool {
+        self.as_ref().matches(event)
+    }
+}
+
+impl Filter for Arc<dyn Filter> {
+    fn matches(&self, event: &Event) -> bool {
+        self.as_ref().matches(event)
+    }
+}
+
+/// Combinators for [`Filter`]s.
+pub trait FilterExt: Filter + Sized {
+    /// Matches the events matching both `self` and `other`.
+    fn and<F: Filter>(self, other: F) -> And<Self, F> {
+        And(self, other)
+    }
+
+    /// Matches the events matching `self`, `other` or both.
+    fn or<F: Filter>(self, other: F) -> Or<Self, F> {
+        Or(self, other)
+    }
+
+    /// Matches the events not matching `self`.
+    fn not(self) -> Not<Self> {
+        Not(self)
+    }
+
+    /// Box the filter, to store filters of different types together.
+    fn boxed(self) -> Box<dyn Filter>
+    where
+        Self: 'static,
+    {
+        Box::new(self)
+    }
+}
+
+impl<F: Filter> FilterExt for F {}
+
+/// See [`FilterExt::and`].
+#[derive(Debug, Clone)]
+pub struct And<A, B>(A, B);
+
+impl<A: Filter, B: Filter> Filter for And<A, B> {
+    fn matches(&self, event: &Event) -> bool {
+        self.0.matches(event) && self.1.matches(event)
+    }
+}
+
+/// See [`FilterExt::or`].
+#[derive(Debug, Clone)]
+pub struct Or<A, B>(A, B);
+
+impl<A: Filter, B: Filter> Filter for Or<A, B> {
+    fn matches(&self, event: &Event) -> bool {
+        self.0.matches(event) || self.1.matches(event)
+    }
+}
+
+/// See [`FilterExt::not`].
+#[derive(Debug, Clone)]
+pub struct Not<F>(F);
+
+impl<F: Filter> Filter for Not<F> {
+    fn matches(&self, event: &Event) -> bool {
+        !self.0.matches(event)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparison {
+    Exact,
+    Prefix,
+    Suffix,
+}
+
+/// Filter comparing the value of an attribute. See [`exact`], [`prefix`] and [`suffix`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeFilter {
+    attribute: String,
+    value: String,
+    comparison: Comparison,
+}
+
+impl Filter for AttributeFilter {
+    fn matches(&self, event: &Event) -> bool {
+        event
+            .iter()
+            .find(|(name, _)| *name == self.attribute)
+            .map(|(_, v)| {
+                let v = v.to_string();
+                match self.comparison {
+                    Comparison::Exact => v == self.value,
+                    Comparison::Prefix => v.starts_with(&self.value),
+                    Comparison::Suffix => v.ends_with(&self.value),
+                }
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// Matches the events whose `attribute` is exactly `value`.
+pub fn exact(attribute: impl Into<String>, value: impl Into<String>) -> AttributeFilter {
+    AttributeFilter {
+        attribute: attribute.into(),
+        value: value.into(),
+        comparison: Comparison::Exact,
+    }
+}
+
+/// Matches the events whose `attribute` starts with `value`.
+pub fn prefix(attribute: impl Into<String>, value: impl Into<String>) -> AttributeFilter {
+    AttributeFilter {
+        attribute: attribute.into(),
+        value: value.into(),
+        comparison: Comparison::Prefix,
+    }
+}
+
+/// Matches the events whose `attribute` ends with `value`.
+pub fn suffix(attribute: impl Into<String>, value: impl Into<String>) -> AttributeFilter {
+    AttributeFilter {
+        attribute: attribute.into(),
+        value: value.into(),
+        comparison: Comparison::Suffix,
+    }
+}
+
+/// Filter matching the events which match all the nested filters. See [`all`].
+pub struct All(Vec<Box<dyn Filter>>);
+
+impl Filter for All {
+    fn matches(&self, event: &Event) -> bool {
+        self.0.iter().all(|f| f.matches(event))
+    }
+}
+
+impl fmt::Debug for All {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "All({} filters)", self.0.len())
+    }
+}
+
+/// Matches the events matching all the `filters`, or every event if `filters` is empty.
+pub fn all(filters: Vec<Box<dyn Filter>>) -> All {
+    All(filters)
+}
+
+/// Filter matching the events which match at least one of the nested filters. See [`any`].
+pub struct Any(Vec<Box<dyn Filter>>);
+
+impl Filter for Any {
+    fn matches(&self, event: &Event) -> bool {
+        self.0.is_empty() || self.0.iter().any(|f| f.matches(event))
+    }
+}
+
+impl fmt::Debug for Any {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Any({} filters)", self.0.len())
+    }
+}
+
+/// Matches the events matching at least one of the `filters`, or every event if `filters` is empty.
+pub fn any(filters: Vec<Box<dyn Filter>>) -> Any {
+    Any(filters)
+}
+
+/// Matches the events with all the given attribute values, like the `filter.attributes` of a
+/// Knative Trigger: an empty value matches any value of the attribute.
+pub fn attributes(attributes: HashMap<String, String>) -> All {
+    All(attributes
+        .into_iter()
+        .filter(|(_, v)| !v.is_empty())
+        .map(|(a, v)| exact(a, v).boxed())
+        .collect())
+}
+
+#[cfg(feature = "sql")]
+impl Filter for crate::sql::Expression {
+    fn matches(&self, event: &Event) -> bool {
+        crate::sql::Expression::matches(self, event)
+    }
+}
+
+#[cfg(feature = "subscription")]
+mod subscription {
+    use super::*;
+    use crate::subscription::{Error, FilterExpression, Result};
+    use std::convert::TryFrom;
+
+    fn comparisons(
+        attributes: HashMap<String, String>,
+        f: fn(String, String) -> AttributeFilter,
+    ) -> Box<dyn Filter> {
+        all(attributes
+            .into_iter()
+            .map(|(a, v)| f(a, v).boxed())
+            .collect())
+        .boxed()
+    }
+
+    impl TryFrom<FilterExpression> for Box<dyn Filter> {
+        type Error = Error;
+
+        /// Build the [`Filter`] evaluating a Subscriptions API filter expression.
+        ///
+        /// Fails if the expression is not valid, or is a `sql` expression and the `sql`
+        /// feature is not enabled.
+        fn try_from(expression: FilterExpression) -> Result<Self> {
+            expression.validate()?;
+            Ok(match expression {
+                FilterExpression::Exact(a) => comparisons(a, exact),
+                FilterExpression::Prefix(a) => comparisons(a, prefix),
+                FilterExpression::Suffix(a) => comparisons(a, suffix),
+                FilterExpression::All(filters) => all(filters
+                    .into_iter()
+                    .map(Box::<dyn Filter>::try_from)
+                    .collect::<Result<_>>()?)
+                .boxed(),
+                FilterExpression::Any(filters) => any(filters
+                    .into_iter()
+                    .map(Box::<dyn Filter>::try_from)
+                    .collect::<Result<_>>()?)
+                .boxed(),
+                FilterExpression::Not(filter) => {
+                    Box::<dyn Filter>::try_from(*filter)?.not().boxed()
+                }
+                #[cfg(feature = "sql")]
+                FilterExpression::Sql(expression) => crate::sql::Expression::parse(&expression)
+                    .map_err(|e| Error::InvalidFilter {
+                        dialect: "sql",
+                        reason: e.to_string(),
+                    })?
+                    .boxed(),
+                #[cfg(not(feature = "sql"))]
+                FilterExpression::Sql(_) => {
+                    return Err(Error::InvalidFilter {
+                        dialect: "sql",
+                        reason: "the sql feature is not enabled".to_string(),
+                    })
+                }
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use crate::AttributesReader;
+
+    #[test]
+    fn attribute_filters() {
+        let event = fixtures::v10::full_no_data();
+
+        assert!(exact("id", "0001").matches(&event));
+        assert!(exact("intex", "10").matches(&event));
+        assert!(prefix("type", "test_event.").matches(&event));
+        assert!(suffix("source", "localhost/").matches(&event));
+        assert!(!exact("id", "00").matches(&event));
+        assert!(!prefix("dataschema", "").matches(&event));
+        assert!(!suffix("subject", "sdk2").matches(&event));
+    }
+
+    #[test]
+    fn combinators() {
+        let event = fixtures::v10::full_no_data();
+
+        assert!(exact("id", "0001")
+            .and(exact("boolex", "true"))
+            .matches(&event));
+        assert!(!exact("id", "0001")
+            .and(exact("boolex", "false"))
+            .matches(&event));
+        assert!(exact("id", "0002")
+            .or(exact("boolex", "true"))
+            .matches(&event));
+        assert!(exact("id", "0002").not().matches(&event));
+        assert!((|e: &Event| e.subject().is_some()).matches(&event));
+
+        assert!(all(vec![]).matches(&event));
+        assert!(any(vec![]).matches(&event));
+        assert!(!all(vec![
+            exact("id", "0001").boxed(),
+            exact("id", "0002").boxed()
+        ])
+        .matches(&event));
+        assert!(any(vec![
+            exact("id", "0001").boxed(),
+            exact("id", "0002").boxed()
+        ])
+        .matches(&event));
+    }
+
+    #[test]
+    fn knative_attributes() {
+        let event = fixtures::v10::full_no_data();
+        let filter = |a: &[(&str, &str)]| {
+            attributes(
+                a.iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            )
+        };
+
+        assert!(filter(&[("type", fixtures::ty().as_str()), ("source", "")]).matches(&event));
+        assert!(filter(&[("dataschema", "")]).matches(&event));
+        assert!(!filter(&[("type", fixtures::ty().as_str()), ("subject", "x")]).matches(&event));
+    }
+
+    #[cfg(feature = "subscription")]
+    #[test]
+    fn from_filter_expression() {
+        use crate::subscription::FilterExpression;
+        use serde_json::json;
+        use std::convert::TryFrom;
+
+        let event = fixtures::v10::full_no_data();
+        let filter = |v: serde_json::Value| {
+            Box::<dyn Filter>::try_from(serde_json::from_value::<FilterExpression>(v).unwrap())
+        };
+
+        assert!(filter(json!({"all": [
+            {"exact": {"id": "0001"}},
+            {"any": [{"prefix": {"type": "x"}}, {"suffix": {"type": "application"}}]},
+            {"not": {"exact": {"subject": "x"}}}
+        ]}))
+        .unwrap()
+        .matches(&event));
+        assert!(filter(json!({"exact": {}})).is_err());
+        #[cfg(feature = "sql")]
+        assert!(filter(json!({"sql": "intex > 5"})).unwrap().matches(&event));
+    }
+}
@@ -0,0 +1,192 @@
+use super::{Error, InvalidLineSnafu, Result, SerializationSnafu};
+use crate::Event;
+use bytes::{BufMut, BytesMut};
+use snafu::ResultExt;
+use tokio_util::codec::{Decoder, Encoder};
+
+/// [`Decoder`] and [`Encoder`] of newline delimited events in the JSON format, to stream
+/// events over pipes, files or TCP connections with a [`Framed`](tokio_util::codec::Framed).
+///
+/// Empty lines and trailing `\r` are ignored when decoding. The last line doesn't need to be
+/// terminated by a newline.
+///
+/// ```
+/// use cloudevents::jsonl::codec::EventLinesCodec;
+/// use futures::StreamExt;
+/// use tokio_util::codec::FramedRead;
+///
+/// # async fn example() {
+/// let input: &[u8] = br#"{"specversion":"1.0","id":"0001","type":"example.test","source":"http://localhost/"}"#;
+/// let mut events = FramedRead::new(input, EventLinesCodec::new());
+/// while let Some(event) = events.next().await {
+///     println!("{}", event.unwrap());
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct EventLinesCodec {
+    max_line_length: usize,
+    next_index: usize,
+    line: usize,
+}
+
+impl EventLinesCodec {
+    /// Create a new [`EventLinesCodec`] without a limit on the line length.
+    pub fn new() -> Self {
+        EventLinesCodec {
+            max_line_length: usize::MAX,
+            next_index: 0,
+            line: 0,
+        }
+    }
+
+    /// Fail the decoding with [`Error::LineTooLong`] when a line is longer than
+    /// `max_line_length` bytes, to bound the memory used by the decoder.
+    pub fn max_line_length(mut self, max_line_length: usize) -> Self {
+        self.max_line_length = max_line_length;
+        self
+    }
+
+    fn decode_line(&mut self, line: &[u8]) -> Result<Option<Event>> {
+        self.line += 1;
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if line.iter().all(u8::is_ascii_whitespace) {
+            return Ok(None);
+        }
+        serde_json::from_slice(line)
+            .context(InvalidLineSnafu { line: self.line })
+            .map(Some)
+    }
+}
+
+impl Default for EventLinesCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder for EventLinesCodec {
+    type Item = Event;
+    type Error = Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Event>> {
+        loop {
+            let newline = buf[self.next_index..].iter().position(|b| *b == b'\n');
+            match newline {
+                Some(offset) => {
+                    let line = buf.split_to(self.next_index + offset + 1);
+                    self.next_index = 0;
+                    if line.len() - 1 > self.max_line_length {
+                        return Err(Error::LineTooLong {
+                            max_length: self.max_line_length,
+                        });
+                    }
+                    if let Some(event) = self.decode_line(&line[..line.len() - 1])? {
+                        return Ok(Some(event));
+                    }
+                }
+                None if buf.len() > self.max_line_length => {
+                    return Err(Error::LineTooLong {
+                        max_length: self.max_line_length,
+                    })
+                }
+                None => {
+                    self.next_index = buf.len();
+                    return Ok(None);
+                }
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Event>> {
+        if let Some(event) = self.decode(buf)? {
+            return Ok(Some(event));
+        }
+        self.next_index = 0;
+        if buf.is_empty() {
+            return Ok(None);
+        }
+        let line = buf.split();
+        self.decode_line(&line)
+    }
+}
+
+impl Encoder<&Event> for EventLinesCodec {
+    type Error = Error;
+
+    fn encode(&mut self, event: &Event, buf: &mut BytesMut) -> Result<()> {
+        serde_json::to_writer(buf.writer(), event).context(SerializationSnafu)?;
+        buf.put_u8(b'\n');
+        Ok(())
+    }
+}
+
+impl Encoder<Event> for EventLinesCodec {
+    type Error = Error;
+
+    fn encode(&mut self, event: Event, buf: &mut BytesMut) -> Result<()> {
+        self.encode(&event, buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use futures::{SinkExt, StreamExt};
+    use tokio_util::codec::{FramedRead, FramedWrite};
+
+    #[tokio::test]
+    async fn encode_and_decode() {
+        let events = vec![
+            fixtures::v10::full_json_data(),
+            fixtures::v03::minimal(),
+            fixtures::v10::full_xml_binary_data(),
+        ];
+
+        let mut writer = FramedWrite::new(Vec::new(), EventLinesCodec::new());
+        for event in &events {
+            writer.send(event).await.unwrap();
+        }
+        let mut output = writer.into_inner();
+        assert_eq!(output.iter().filter(|b| **b == b'\n').count(), 3);
+
+        // The last line doesn't need a newline
+        output.pop();
+        let decoded: Vec<Event> = FramedRead::with_capacity(&output[..], EventLinesCodec::new(), 8)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+
+        assert_eq!(decoded, events);
+    }
+
+    #[test]
+    fn invalid_lines() {
+        let mut codec = EventLinesCodec::new();
+        let mut buf = BytesMut::from(
+            format!(
+                "\r\n{}\r\n{{\"id\": 1}}\n",
+                serde_json::to_string(&fixtures::v10::minimal()).unwrap()
+            )
+            .as_str(),
+        );
+
+        assert_eq!(
+            codec.decode(&mut buf).unwrap(),
+            Some(fixtures::v10::minimal())
+        );
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(Error::InvalidLine { line: 3, .. })
+        ));
+        assert_eq!(codec.decode_eof(&mut buf).unwrap(), None);
+
+        let mut codec = EventLinesCodec::new().max_line_length(8);
+        let mut buf = BytesMut::from("{\"specversion\"");
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(Error::LineTooLong { max_length: 8 })
+        ));
+    }
+}
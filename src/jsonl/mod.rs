@@ -0,0 +1,256 @@
+//! This module provides an event log backed by files in the
+//! [JSON lines](https://jsonlines.org/) format, one [`Event`] in the JSON format per line,
+//! to capture event flows locally and replay them in tests or while debugging.
+//!
+//! ```no_run
+//! use cloudevents::jsonl::{replay, EventLogWriter};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//! use futures::StreamExt;
+//!
+//! # async fn example() -> cloudevents::jsonl::Result<()> {
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("example.test")
+//!     .source("http://localhost/")
+//!     .build()
+//!     .unwrap();
+//!
+//! let mut writer = EventLogWriter::create("events.jsonl").await?;
+//! writer.append(&event).await?;
+//! writer.finish().await?;
+//!
+//! let mut events = Box::pin(replay("events.jsonl").await?);
+//! while let Some(event) = events.next().await {
+//!     println!("{}", event?);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! With the `jsonl-gzip` feature, [`EventLogWriter::create_gzip`] and [`replay_gzip`]
+//! read and write gzip compressed logs. Every writer appends a new gzip member to the file.
+//!
+//! With the `jsonl-codec` feature, [`codec::EventLinesCodec`] frames newline delimited events
+//! on any tokio stream.
+
+use crate::Event;
+use futures::Stream;
+use snafu::{ResultExt, Snafu};
+use std::path::Path;
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonl-codec")))]
+#[cfg(feature = "jsonl-codec")]
+pub mod codec;
+
+/// Represents an error while writing or replaying an event log
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("IO Error: {}", source))]
+    #[snafu(context(false))]
+    IOError { source: std::io::Error },
+    #[snafu(display("Error while serializing the event: {}", source))]
+    SerializationError { source: serde_json::Error },
+    #[snafu(display("Invalid event at line {}: {}", line, source))]
+    InvalidLine {
+        line: usize,
+        source: serde_json::Error,
+    },
+    #[snafu(display("Line longer than the maximum length of {} bytes", max_length))]
+    LineTooLong { max_length: usize },
+}
+
+/// Result type alias for return values of the event log
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Writer appending events to a JSON lines log.
+#[derive(Debug)]
+pub struct EventLogWriter<W> {
+    writer: W,
+}
+
+impl<W: AsyncWrite + Unpin> EventLogWriter<W> {
+    /// Create a new [`EventLogWriter`] writing to `writer`.
+    pub fn new(writer: W) -> Self {
+        EventLogWriter { writer }
+    }
+
+    /// Append `event` to the log.
+    pub async fn append(&mut self, event: &Event) -> Result<()> {
+        let mut line = serde_json::to_vec(event).context(SerializationSnafu)?;
+        line.push(b'\n');
+        self.writer.write_all(&line).await?;
+        Ok(())
+    }
+
+    /// Flush the buffered events.
+    pub async fn flush(&mut self) -> Result<()> {
+        Ok(self.writer.flush().await?)
+    }
+
+    /// Flush and close the log, returning the underlying writer.
+    ///
+    /// The writer must be finished for the gzip compressed logs to be complete.
+    pub async fn finish(mut self) -> Result<W> {
+        self.writer.shutdown().await?;
+        Ok(self.writer)
+    }
+
+    /// Return the underlying writer, without flushing nor closing it.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl EventLogWriter<File> {
+    /// Open the log file at `path` to append events to it, creating it if it doesn't exist.
+    pub async fn create(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::new(open_append(path).await?))
+    }
+}
+
+async fn open_append(path: impl AsRef<Path>) -> std::io::Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonl-gzip")))]
+#[cfg(feature = "jsonl-gzip")]
+impl EventLogWriter<async_compression::tokio::write::GzipEncoder<File>> {
+    /// Open the gzip compressed log file at `path` to append events to it, creating it if it
+    /// doesn't exist.
+    pub async fn create_gzip(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::new(
+            async_compression::tokio::write::GzipEncoder::new(open_append(path).await?),
+        ))
+    }
+}
+
+/// Replay the events of the JSON lines log read from `reader`. Empty lines are skipped.
+pub fn replay_reader<R>(reader: R) -> impl Stream<Item = Result<Event>>
+where
+    R: AsyncBufRead + Unpin,
+{
+    futures::stream::unfold(
+        (reader.lines(), 0usize),
+        |(mut lines, mut number)| async move {
+            loop {
+                number += 1;
+                let result = match lines.next_line().await {
+                    Ok(Some(line)) if line.trim().is_empty() => continue,
+                    Ok(Some(line)) => {
+                        serde_json::from_str(&line).context(InvalidLineSnafu { line: number })
+                    }
+                    Ok(None) => return None,
+                    Err(e) => Err(e.into()),
+                };
+                return Some((result, (lines, number)));
+            }
+        },
+    )
+}
+
+/// Replay the events of the log file at `path`.
+pub async fn replay(path: impl AsRef<Path>) -> Result<impl Stream<Item = Result<Event>>> {
+    Ok(replay_reader(BufReader::new(File::open(path).await?)))
+}
+
+/// Replay the events of the gzip compressed log file at `path`.
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonl-gzip")))]
+#[cfg(feature = "jsonl-gzip")]
+pub async fn replay_gzip(path: impl AsRef<Path>) -> Result<impl Stream<Item = Result<Event>>> {
+    let mut decoder = async_compression::tokio::bufread::GzipDecoder::new(BufReader::new(
+        File::open(path).await?,
+    ));
+    decoder.multiple_members(true);
+    Ok(replay_reader(BufReader::new(decoder)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use futures::StreamExt;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "cloudevents-{}-{}-{}",
+            std::process::id(),
+            uuid::Uuid::new_v4(),
+            name
+        ))
+    }
+
+    #[tokio::test]
+    async fn write_and_replay() {
+        let path = temp_path("events.jsonl");
+        let events = vec![
+            fixtures::v10::full_json_data(),
+            fixtures::v03::minimal(),
+            fixtures::v10::full_xml_binary_data(),
+        ];
+
+        let mut writer = EventLogWriter::create(&path).await.unwrap();
+        writer.append(&events[0]).await.unwrap();
+        writer.finish().await.unwrap();
+        let mut writer = EventLogWriter::create(&path).await.unwrap();
+        writer.append(&events[1]).await.unwrap();
+        writer.append(&events[2]).await.unwrap();
+        writer.finish().await.unwrap();
+
+        let replayed: Vec<Event> = replay(&path)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(replayed, events);
+    }
+
+    #[tokio::test]
+    async fn invalid_line() {
+        let log = format!(
+            "{}\n\n{{\"id\": 1}}\n",
+            serde_json::to_string(&fixtures::v10::minimal()).unwrap()
+        );
+
+        let replayed: Vec<Result<Event>> = replay_reader(log.as_bytes()).collect().await;
+
+        assert_eq!(replayed.len(), 2);
+        assert_eq!(replayed[0].as_ref().unwrap(), &fixtures::v10::minimal());
+        assert!(matches!(
+            replayed[1],
+            Err(Error::InvalidLine { line: 3, .. })
+        ));
+    }
+
+    #[cfg(feature = "jsonl-gzip")]
+    #[tokio::test]
+    async fn gzip() {
+        let path = temp_path("events.jsonl.gz");
+        let events = vec![fixtures::v10::full_json_data(), fixtures::v03::minimal()];
+
+        for event in &events {
+            let mut writer = EventLogWriter::create_gzip(&path).await.unwrap();
+            writer.append(event).await.unwrap();
+            writer.finish().await.unwrap();
+        }
+
+        let replayed: Vec<Event> = replay_gzip(&path)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(replayed, events);
+    }
+}
@@ -0,0 +1,291 @@
+//! This module encrypts the `data` of an [`Event`] in a
+//! [JWE](https://www.rfc-editor.org/rfc/rfc7516) compact serialization, so sensitive payloads
+//! can traverse shared brokers encrypted while the attributes stay readable for routing.
+//!
+//! The data is encrypted with a shared 256-bit key, using the `dir` key management mode and the
+//! `A256GCM` content encryption. The `datacontenttype` is set to `application/jose`, and the
+//! original one is recorded in the `encdatacontenttype` extension.
+//!
+//! ```
+//! use cloudevents::jwe::{decrypt, encrypt, JweKey, JWE_CONTENT_TYPE};
+//! use cloudevents::{AttributesReader, EventBuilder, EventBuilderV10};
+//! use serde_json::json;
+//!
+//! let key = JweKey::new(&[7; 32]).key_id("2024-01");
+//! let mut event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("example.test")
+//!     .source("http://localhost/")
+//!     .data("application/json", json!({"card": "4111111111111111"}))
+//!     .build()
+//!     .unwrap();
+//!
+//! encrypt(&mut event, &key).unwrap();
+//! assert_eq!(event.datacontenttype(), Some(JWE_CONTENT_TYPE));
+//!
+//! decrypt(&mut event, &key).unwrap();
+//! assert_eq!(event.datacontenttype(), Some("application/json"));
+//! assert_eq!(
+//!     event.data_as::<serde_json::Value>().unwrap(),
+//!     Some(json!({"card": "4111111111111111"}))
+//! );
+//! ```
+
+use crate::event::{AttributesReader, AttributesWriter, Data, ExtensionValue};
+use crate::Event;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::prelude::{Engine, BASE64_URL_SAFE_NO_PAD};
+use serde_json::{json, Value};
+use snafu::Snafu;
+use std::fmt;
+
+/// Media type of the JWE compact serialization, set as `datacontenttype` of the encrypted events.
+pub const JWE_CONTENT_TYPE: &str = "application/jose";
+
+/// Name of the extension recording the `datacontenttype` of the encrypted data.
+pub const ORIGINAL_CONTENT_TYPE_EXTENSION: &str = "encdatacontenttype";
+
+/// Represents an error while encrypting or decrypting the data of an event
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("The data is already encrypted"))]
+    AlreadyEncrypted {},
+    #[snafu(display("Invalid JWE compact serialization: {}", reason))]
+    InvalidJwe { reason: &'static str },
+    #[snafu(display("Unsupported JWE algorithm {} with encryption {}", alg, enc))]
+    UnsupportedAlgorithm { alg: String, enc: String },
+    #[snafu(display("The data is encrypted with the key {}, not {}", found, expected))]
+    KeyIdMismatch { expected: String, found: String },
+    #[snafu(display("Error while encrypting or decrypting the data"))]
+    CryptoError {},
+    #[snafu(display("Error while decoding base64: {}", source))]
+    #[snafu(context(false))]
+    Base64DecodingError { source: base64::DecodeError },
+    #[snafu(display("Error while serializing/deserializing the JWE header: {}", source))]
+    #[snafu(context(false))]
+    SerializationError { source: serde_json::Error },
+}
+
+/// Result type alias for return values of the JWE functions
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Shared 256-bit key encrypting the data, optionally identified by a key ID, written as the
+/// `kid` JWE header to support key rotation.
+#[derive(Clone)]
+pub struct JweKey {
+    cipher: Aes256Gcm,
+    key_id: Option<String>,
+}
+
+impl JweKey {
+    /// Create a new [`JweKey`] from the raw key bytes.
+    pub fn new(key: &[u8; 32]) -> Self {
+        JweKey {
+            cipher: Aes256Gcm::new(key.into()),
+            key_id: None,
+        }
+    }
+
+    /// Set the ID of the key. When decrypting, the `kid` header must match it if present.
+    pub fn key_id(mut self, key_id: impl Into<String>) -> Self {
+        self.key_id = Some(key_id.into());
+        self
+    }
+}
+
+impl fmt::Debug for JweKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JweKey")
+            .field("key_id", &self.key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Encrypt the data of `event` with `key` in a JWE compact serialization, setting the
+/// `datacontenttype` to [`JWE_CONTENT_TYPE`] and the `encdatacontenttype` extension to the
+/// original one.
+///
+/// Events without data are left unchanged.
+pub fn encrypt(event: &mut Event, key: &JweKey) -> Result<()> {
+    if event.datacontenttype() == Some(JWE_CONTENT_TYPE) {
+        return Err(Error::AlreadyEncrypted {});
+    }
+    let data = match event.data.take() {
+        Some(data) => data,
+        None => return Ok(()),
+    };
+
+    let mut header = json!({"alg": "dir", "enc": "A256GCM"});
+    if let Some(key_id) = &key.key_id {
+        header["kid"] = Value::from(key_id.as_str());
+    }
+    if let Some(content_type) = event.datacontenttype() {
+        header["cty"] = Value::from(content_type);
+    }
+    let header = BASE64_URL_SAFE_NO_PAD.encode(header.to_string());
+
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let plaintext = data.into_bytes();
+    let mut ciphertext = key
+        .cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: &plaintext,
+                aad: header.as_bytes(),
+            },
+        )
+        .map_err(|_| Error::CryptoError {})?;
+    let tag = ciphertext.split_off(ciphertext.len() - 16);
+
+    let jwe = [
+        header.as_str(),
+        "",
+        &BASE64_URL_SAFE_NO_PAD.encode(nonce),
+        &BASE64_URL_SAFE_NO_PAD.encode(ciphertext),
+        &BASE64_URL_SAFE_NO_PAD.encode(tag),
+    ]
+    .join(".");
+    if let Some(content_type) = event.set_datacontenttype(Some(JWE_CONTENT_TYPE)) {
+        event.set_extension(ORIGINAL_CONTENT_TYPE_EXTENSION, content_type);
+    }
+    event.data = Some(Data::String(jwe));
+    Ok(())
+}
+
+/// Decrypt the data of `event` with `key`, restoring the `datacontenttype` recorded by the
+/// `encdatacontenttype` extension and removing the extension. The decrypted data is binary.
+///
+/// Events whose `datacontenttype` isn't [`JWE_CONTENT_TYPE`] are left unchanged.
+pub fn decrypt(event: &mut Event, key: &JweKey) -> Result<()> {
+    if event.datacontenttype() != Some(JWE_CONTENT_TYPE) {
+        return Ok(());
+    }
+    let jwe = match event.data() {
+        Some(Data::String(s)) => s.as_bytes(),
+        Some(data) => data.as_bytes().ok_or(Error::InvalidJwe {
+            reason: "the data is JSON",
+        })?,
+        None => return Ok(()),
+    };
+    let jwe = std::str::from_utf8(jwe).map_err(|_| Error::InvalidJwe {
+        reason: "the data is not UTF-8",
+    })?;
+    let parts: Vec<&str> = jwe.trim().split('.').collect();
+    let (header, encrypted_key, iv, ciphertext, tag) = match parts[..] {
+        [header, encrypted_key, iv, ciphertext, tag] => {
+            (header, encrypted_key, iv, ciphertext, tag)
+        }
+        _ => {
+            return Err(Error::InvalidJwe {
+                reason: "expected 5 parts",
+            })
+        }
+    };
+
+    let decoded_header: Value = serde_json::from_slice(&BASE64_URL_SAFE_NO_PAD.decode(header)?)?;
+    let field = |name: &str| decoded_header[name].as_str().unwrap_or_default().to_owned();
+    let (alg, enc) = (field("alg"), field("enc"));
+    if alg != "dir" || enc != "A256GCM" || !encrypted_key.is_empty() {
+        return Err(Error::UnsupportedAlgorithm { alg, enc });
+    }
+    if let (Some(expected), Some(found)) = (&key.key_id, decoded_header["kid"].as_str()) {
+        if expected != found {
+            return Err(Error::KeyIdMismatch {
+                expected: expected.clone(),
+                found: found.to_owned(),
+            });
+        }
+    }
+
+    let iv = BASE64_URL_SAFE_NO_PAD.decode(iv)?;
+    if iv.len() != 12 {
+        return Err(Error::InvalidJwe {
+            reason: "the IV must be 96 bits long",
+        });
+    }
+    let mut message = BASE64_URL_SAFE_NO_PAD.decode(ciphertext)?;
+    message.extend(BASE64_URL_SAFE_NO_PAD.decode(tag)?);
+    let plaintext = key
+        .cipher
+        .decrypt(
+            Nonce::from_slice(&iv),
+            Payload {
+                msg: &message,
+                aad: header.as_bytes(),
+            },
+        )
+        .map_err(|_| Error::CryptoError {})?;
+
+    let content_type = match event.remove_extension(ORIGINAL_CONTENT_TYPE_EXTENSION) {
+        Some(ExtensionValue::String(s)) => Some(s),
+        _ => None,
+    };
+    event.set_datacontenttype(content_type);
+    event.data = Some(Data::Binary(plaintext));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+
+    #[test]
+    fn round_trip() {
+        let key = JweKey::new(&[1; 32]).key_id("k1");
+        let mut event = fixtures::v10::full_json_data();
+        encrypt(&mut event, &key).unwrap();
+
+        assert_eq!(event.datacontenttype(), Some(JWE_CONTENT_TYPE));
+        assert_eq!(
+            event.extension(ORIGINAL_CONTENT_TYPE_EXTENSION),
+            Some(&ExtensionValue::from("application/json"))
+        );
+        let jwe = event.data().unwrap().clone().into_string().unwrap();
+        let header: Value = serde_json::from_slice(
+            &BASE64_URL_SAFE_NO_PAD
+                .decode(jwe.split('.').next().unwrap())
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            header,
+            json!({"alg": "dir", "enc": "A256GCM", "kid": "k1", "cty": "application/json"})
+        );
+        assert!(matches!(
+            encrypt(&mut event, &key),
+            Err(Error::AlreadyEncrypted {})
+        ));
+
+        decrypt(&mut event, &key).unwrap();
+        let expected = fixtures::v10::full_json_data();
+        assert_eq!(event.datacontenttype(), expected.datacontenttype());
+        assert_eq!(event.extension(ORIGINAL_CONTENT_TYPE_EXTENSION), None);
+        assert_eq!(
+            event.data_as::<Value>().unwrap(),
+            expected.data_as::<Value>().unwrap()
+        );
+    }
+
+    #[test]
+    fn wrong_key_or_tampered_data() {
+        let mut event = fixtures::v10::full_json_data();
+        encrypt(&mut event, &JweKey::new(&[1; 32]).key_id("k1")).unwrap();
+
+        assert!(matches!(
+            decrypt(&mut event.clone(), &JweKey::new(&[2; 32])),
+            Err(Error::CryptoError {})
+        ));
+        assert!(matches!(
+            decrypt(&mut event.clone(), &JweKey::new(&[1; 32]).key_id("k2")),
+            Err(Error::KeyIdMismatch { .. })
+        ));
+
+        let mut jwe = event.data().unwrap().clone().into_string().unwrap();
+        jwe.insert(jwe.rfind('.').unwrap() - 1, 'A');
+        event.set_data_unchecked(jwe);
+        assert!(decrypt(&mut event, &JweKey::new(&[1; 32])).is_err());
+    }
+}
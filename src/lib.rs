@@ -37,18 +37,125 @@
 //! only the module associated with the Protocol Binding you need:
 //!
 //! - `actix`: Enables the [`binding::actix`] protocol binding module. This
-//! extends the [`actix_web::HttpRequest`] with a
-//! [`to_event`](binding::actix::HttpRequestExt::to_event) function, the
-//! [`actix_web::HttpResponseBuilder`] with an
-//! [`event`](binding::actix::HttpResponseBuilderExt::event) function,
-//! and implementations for [`actix_web::FromRequest`] and
-//! [`actix_web::Responder`] in order to take advantage of actix-web's
-//! [Extractors] and [Responders]
+//!   extends the [`actix_web::HttpRequest`] with a
+//!   [`to_event`](binding::actix::HttpRequestExt::to_event) function, the
+//!   [`actix_web::HttpResponseBuilder`] with an
+//!   [`event`](binding::actix::HttpResponseBuilderExt::event) function,
+//!   and implementations for [`actix_web::FromRequest`] and
+//!   [`actix_web::Responder`] in order to take advantage of actix-web's
+//!   [Extractors] and [Responders]. The [`ce_type`](binding::actix::ce_type) guard routes
+//!   the events to handlers by type.
+//! - `actix-ws`: Enables the [`binding::actix::ws`] module, to exchange events over
+//!   [actix-ws](https://docs.rs/actix-ws) WebSocket sessions. Implies `actix`.
 //! - `reqwest`: Enables the [`binding::reqwest`] protocol binding module.
 //! - `warp`: Enables the [`binding::warp`] protocol binding module.
 //! - `axum`: Enables the [`binding::axum`] protocol binding module.
+//! - `http-common`: Enables the [`binding::http_compat`] module and the
+//!   [`binding::DuplicateHeaders`] shared by the HTTP bindings. Implied by all of them.
+//! - `http-body`: Enables the [`binding::http::body`] module, to convert events from/to
+//!   HTTP messages with any `http_body::Body`, e.g. of hyper 1.x, axum or tonic.
 //! - `rdkafka`: Enables the [`binding::rdkafka`] protocol binding module to
-//! seamlessly consume/produce cloudevents within Kafka messages.
+//!   seamlessly consume/produce cloudevents within Kafka messages.
+//! - `lapin`: Enables the [`binding::lapin`] protocol binding module to
+//!   consume/produce cloudevents within AMQP 0.9.1 (e.g. RabbitMQ) messages.
+//! - `amqprs`: Enables the [`binding::amqprs`] protocol binding module, the
+//!   equivalent of `lapin` for the [amqprs](https://docs.rs/amqprs) client.
+//! - `eventbridge`: Enables the [`binding::eventbridge`] module, to convert from/to the
+//!   Amazon EventBridge event envelope.
+//! - `eventgrid`: Enables the [`binding::eventgrid`] module, to convert from/to the
+//!   Azure Event Grid event schema.
+//! - `nsq`: Enables the [`binding::nsq`] module, to publish and consume events in structured
+//!   mode with the [tokio-nsq](https://docs.rs/tokio-nsq) client.
+//! - `pubsub`: Enables the [`binding::pubsub`] protocol binding module for Google Cloud Pub/Sub,
+//!   using the [google-cloud-pubsub](https://docs.rs/google-cloud-pubsub) client.
+//! - `redis`: Enables the [`binding::redis`] module, to publish and subscribe to
+//!   [redis](https://docs.rs/redis) pub/sub channels in structured mode.
+//! - `coap`: Enables the [`binding::coap`] module, to carry events in
+//!   [coap-lite](https://docs.rs/coap-lite) packets for constrained devices.
+//! - `pipe`: Enables the [`binding::pipe`] module, reading the events from stdin and writing
+//!   them to stdout one per line, to compose CloudEvents processors with Unix pipes. Implies `jsonl`.
+//! - `rumqttc`: Enables the [`binding::rumqttc`] module, to carry events in structured mode in
+//!   the MQTT 3.1.1 messages of [rumqttc](https://docs.rs/rumqttc), mapping the topics to the
+//!   event attributes with templates.
+//! - `tonic`: Enables the [`binding::tonic`] module, to carry the event attributes in the
+//!   `ce-` metadata of [tonic](https://docs.rs/tonic) gRPC requests. Implies `protobuf`.
+//! - `bus`: Enables the [`bus`] module, an in-memory event bus to deliver events between the
+//!   components of a single process.
+//! - `knative`: Enables the [`binding::knative`] module, a client posting events to the sink
+//!   injected by a Knative SinkBinding. Implies `reqwest`.
+//! - `sql`: Enables the [`sql`] module, a CloudEvents SQL (CESQL) expression parser and
+//!   evaluator to filter events.
+//! - `subscription`: Enables the [`subscription`] module, the data model of the CloudEvents
+//!   Subscriptions API.
+//! - `discovery`: Enables the [`discovery`] module, the data model of the CloudEvents
+//!   Discovery API.
+//! - `filter`: Enables the [`filter`] module, to filter events with the dialects of the
+//!   Subscriptions API.
+//! - `router`: Enables the [`router`] module, to dispatch events to async handlers by
+//!   `type`/`source` pattern. Implies `filter`.
+//! - `dedup`: Enables the [`dedup`] module, to drop the duplicate events delivered by
+//!   at-least-once transports.
+//! - `expiry`: Enables the [`expiry`] module, the `expirytime` extension and adapters dropping
+//!   the expired events of a stream, an [`transport::EventSource`] or an [`transport::EventSink`].
+//! - `tenant`: Enables the [`tenant`] module, to namespace the `source` and `subject` of the
+//!   events with a tenant prefix on ingress and strip it on egress.
+//! - `sampling`: Enables the [`sampling`] module, to forward a fraction of the events selected
+//!   by filters, recording the rate in the `sampledrate` extension. Implies `filter`.
+//! - `outbox`: Enables the [`outbox`] module, a transactional outbox on Postgres using
+//!   [sqlx](https://docs.rs/sqlx), publishing the events to any [`transport::EventSink`].
+//! - `batching`: Enables the `transport::BatchingSink`, buffering the events sent to any
+//!   [`transport::EventSink`] and sending them in batches.
+//! - `rate-limit`: Enables the `transport::RateLimited` sink, limiting the rate of events
+//!   and the concurrent sends to any [`transport::EventSink`].
+//! - `replay`: Enables the `transport::ReplayingSink`, buffering the events sent to any
+//!   [`transport::EventSink`] in memory or in a file until they are delivered, and replaying
+//!   them after a connection loss.
+//! - `bridge`: Enables the `transport::Bridge`, forwarding the events of any
+//!   [`transport::EventSource`] to any [`transport::EventSink`] concurrently, with dead-lettering,
+//!   retries of the failed receives and graceful shutdown.
+//! - `blocking`: Enables the `transport::blocking` module, blocking versions of the
+//!   [`transport::EventSink`] and [`transport::EventSource`] traits, implemented with the
+//!   `reqwest` and `rdkafka` features by the `binding::reqwest::blocking` and
+//!   `binding::rdkafka::blocking` modules, and the `transport::blocking::PollSource` trait to
+//!   poll events from an application event loop, implemented for the rdkafka `BaseConsumer`
+//!   and the rumqttc `Connection`.
+//! - `eventstore`: Enables the [`store`] module, an append-only store of event streams for
+//!   event-sourced services, with helpers to load aggregates and execute commands.
+//! - `eventstore-postgres`: Enables the Postgres implementation of the [`store::EventStore`],
+//!   using [sqlx](https://docs.rs/sqlx). Implies `eventstore`.
+//! - `jsonl`: Enables the [`jsonl`] module, to write events to JSON lines files and replay them.
+//! - `jsonl-gzip`: Adds gzip compression support to the [`jsonl`] module. Implies `jsonl`.
+//! - `jsonl-codec`: Adds a tokio-util codec of newline delimited events to the [`jsonl`] module. Implies `jsonl`.
+//! - `opentelemetry`: Enables the [`opentelemetry`] module, propagating the
+//!   [OpenTelemetry](https://docs.rs/opentelemetry) trace context in the `traceparent` and
+//!   `tracestate` extensions.
+//! - `tracing`: Instruments the HTTP, Kafka and NATS bindings with [tracing](https://docs.rs/tracing)
+//!   spans around the serialization, deserialization, send and receive operations.
+//! - `observer`: Enables the [`observer`] module, to invoke an [`observer::EventObserver`] from the
+//!   HTTP, Kafka and NATS bindings, e.g. to collect metrics.
+//! - `content-encoding`: Enables the [`content_encoding`] module, to compress the event data with
+//!   gzip or deflate.
+//! - `protobuf`: Adds [`EventBuilderV10::data_proto`] and [`Event::data_as_proto`], to use
+//!   [prost](https://docs.rs/prost) messages as event data, and enables the [`protobuf`] module,
+//!   converting events from and to the `io.cloudevents.v1.CloudEvent` Protobuf message.
+//! - `schema`: Enables the [`schema`] module, to resolve the schemas referenced by the
+//!   `dataschema` attribute with a TTL cache. Fetching them over HTTP also requires `reqwest`.
+//!   The `SchemaRegistry` validates the data of the events built with `data_with_registry`.
+//! - `jsonschema`: Adds `JsonSchemaValidator` to the [`schema`] module, to validate the data
+//!   of the events against a [JSON Schema](https://json-schema.org).
+//! - `derive`: Adds `#[derive(EventDispatch)]`, to convert events to an enum whose variants
+//!   match the event types, see [`event::EventDispatch`].
+//! - `registry`: Enables the [`registry`] module, the message definitions of the xRegistry
+//!   CloudEvents registry, to validate events against them. The client also requires `reqwest`.
+//! - `simd-json`: Parses the structured mode messages of all the protocol bindings with
+//!   [simd-json](https://docs.rs/simd-json) instead of `serde_json`.
+//! - `redact`: Enables the [`redact`] module and [`Event::redacted`], to make privacy-safe
+//!   copies of events for logs and error reports.
+//! - `jwe`: Enables the [`jwe`] module, to encrypt the event data in a JWE compact
+//!   serialization while the attributes stay readable.
+//! - `time`: Implements [`event::TimeType`] and [`event::TryIntoTime`] for
+//!   [`time::OffsetDateTime`](https://docs.rs/time), to read and write the `time` attribute with
+//!   [`Event::time_as`] and [`Event::set_time_as`] without using chrono.
 //!
 //! [feature flags]: https://doc.rust-lang.org/cargo/reference/manifest.html#the-features-section
 //! [Extractors]: https://actix.rs/docs/extractors/
@@ -59,9 +166,114 @@
 #![cfg_attr(docsrs, feature(doc_cfg))] // Show feature gate in doc
 
 pub mod binding;
+#[cfg_attr(docsrs, doc(cfg(feature = "bus")))]
+#[cfg(feature = "bus")]
+pub mod bus;
+#[cfg_attr(docsrs, doc(cfg(feature = "content-encoding")))]
+#[cfg(feature = "content-encoding")]
+pub mod content_encoding;
+#[cfg_attr(docsrs, doc(cfg(feature = "dedup")))]
+#[cfg(feature = "dedup")]
+pub mod dedup;
+#[cfg_attr(docsrs, doc(cfg(feature = "discovery")))]
+#[cfg(feature = "discovery")]
+pub mod discovery;
 pub mod event;
+#[cfg_attr(docsrs, doc(cfg(feature = "expiry")))]
+#[cfg(feature = "expiry")]
+pub mod expiry;
+#[cfg_attr(docsrs, doc(cfg(feature = "filter")))]
+#[cfg(feature = "filter")]
+pub mod filter;
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonl")))]
+#[cfg(feature = "jsonl")]
+pub mod jsonl;
+#[cfg_attr(docsrs, doc(cfg(feature = "jwe")))]
+#[cfg(feature = "jwe")]
+pub mod jwe;
 pub mod message;
+#[cfg_attr(docsrs, doc(cfg(feature = "observer")))]
+#[cfg(feature = "observer")]
+pub mod observer;
+#[cfg_attr(docsrs, doc(cfg(feature = "opentelemetry")))]
+#[cfg(feature = "opentelemetry")]
+pub mod opentelemetry;
+#[cfg_attr(docsrs, doc(cfg(feature = "outbox")))]
+#[cfg(feature = "outbox")]
+pub mod outbox;
+#[cfg(any(feature = "outbox", feature = "eventstore-postgres"))]
+mod pg;
+#[cfg_attr(docsrs, doc(cfg(feature = "protobuf")))]
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
+#[cfg_attr(docsrs, doc(cfg(feature = "redact")))]
+#[cfg(feature = "redact")]
+pub mod redact;
+#[cfg_attr(docsrs, doc(cfg(feature = "registry")))]
+#[cfg(feature = "registry")]
+pub mod registry;
+#[cfg_attr(docsrs, doc(cfg(feature = "router")))]
+#[cfg(feature = "router")]
+pub mod router;
+#[cfg_attr(docsrs, doc(cfg(feature = "sampling")))]
+#[cfg(feature = "sampling")]
+pub mod sampling;
+#[cfg_attr(docsrs, doc(cfg(feature = "schema")))]
+#[cfg(feature = "schema")]
+pub mod schema;
+#[cfg_attr(docsrs, doc(cfg(feature = "sql")))]
+#[cfg(feature = "sql")]
+pub mod sql;
+#[cfg_attr(docsrs, doc(cfg(feature = "subscription")))]
+#[cfg(feature = "subscription")]
+pub mod subscription;
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(
+        feature = "reqwest",
+        feature = "rdkafka",
+        feature = "nats",
+        feature = "lapin",
+        feature = "amqprs",
+        feature = "outbox",
+        feature = "batching",
+        feature = "rate-limit",
+        feature = "bridge",
+        feature = "replay",
+        feature = "expiry",
+        feature = "tenant",
+        feature = "sampling",
+        feature = "blocking",
+        feature = "pipe",
+        feature = "rumqttc"
+    )))
+)]
+#[cfg(any(
+    feature = "reqwest",
+    feature = "rdkafka",
+    feature = "nats",
+    feature = "lapin",
+    feature = "amqprs",
+    feature = "outbox",
+    feature = "batching",
+    feature = "rate-limit",
+    feature = "bridge",
+    feature = "replay",
+    feature = "expiry",
+    feature = "tenant",
+    feature = "sampling",
+    feature = "blocking",
+    feature = "pipe",
+    feature = "rumqttc"
+))]
+pub mod transport;
 
+#[cfg_attr(docsrs, doc(cfg(feature = "eventstore")))]
+#[cfg(feature = "eventstore")]
+pub mod store;
+#[cfg_attr(docsrs, doc(cfg(feature = "tenant")))]
+#[cfg(feature = "tenant")]
+pub mod tenant;
 #[cfg(test)]
 pub mod test;
 
@@ -17,6 +17,16 @@ pub enum Error {
     EventBuilderError {
         source: crate::event::EventBuilderError,
     },
+    #[snafu(display("{}", source))]
+    #[snafu(context(false))]
+    InvalidExtension {
+        source: crate::event::ExtensionError,
+    },
+    #[snafu(display("{}", source))]
+    #[snafu(context(false))]
+    InvalidExtensionType {
+        source: crate::event::ExtensionTypeError,
+    },
     #[snafu(display("Error while parsing a time string: {}", source))]
     #[snafu(context(false))]
     ParseTimeError { source: chrono::ParseError },
@@ -29,6 +39,31 @@ pub enum Error {
     #[snafu(display("Error while serializing/deserializing to json: {}", source))]
     #[snafu(context(false))]
     SerdeJsonError { source: serde_json::Error },
+    #[cfg(feature = "simd-json")]
+    #[snafu(display("Error while deserializing json with simd-json: {}", source))]
+    #[snafu(context(false))]
+    SimdJsonError { source: simd_json_lib::Error },
+    #[snafu(display("Invalid value of the header {}: {}", name, source))]
+    InvalidHeaderValue {
+        name: String,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[snafu(display("The header {} is repeated", name))]
+    DuplicateHeader { name: String },
+    #[snafu(display("The message has more than {} extensions", max))]
+    TooManyExtensions { max: usize },
+    #[snafu(display("The value of the attribute {} is longer than {} bytes", name, max))]
+    AttributeValueTooLong { name: String, max: usize },
+    #[snafu(display("The attributes of the message are longer than {} bytes", max))]
+    AttributesTooLong { max: usize },
+    #[snafu(display("The structured mode message is longer than {} bytes", max))]
+    StructuredMessageTooLong { max: usize },
+    #[snafu(display("Invalid topic template {}: {}", template, reason))]
+    InvalidTopicTemplate { template: String, reason: String },
+    #[snafu(display("The topic {} does not match the template {}", topic, template))]
+    TopicMismatch { topic: String, template: String },
+    #[snafu(display("Cannot derive the topic from the attribute {}: {}", name, reason))]
+    InvalidTopicAttribute { name: String, reason: String },
     #[snafu(display("IO Error: {}", source))]
     #[snafu(context(false))]
     IOError { source: std::io::Error },
@@ -1,5 +1,6 @@
 use super::{MessageAttributeValue, Result};
 use crate::event::SpecVersion;
+use bytes::Bytes;
 
 /// Serializer for structured mode messages.
 pub trait StructuredSerializer<RETURN: Sized> {
@@ -19,5 +20,13 @@ where
 
     fn end_with_data(self, bytes: Vec<u8>) -> Result<RETURN>;
 
+    /// End with data held in a [`Bytes`] buffer.
+    ///
+    /// The default implementation converts it to a `Vec<u8>`, which copies the data unless the
+    /// buffer is not shared.
+    fn end_with_bytes(self, bytes: Bytes) -> Result<RETURN> {
+        self.end_with_data(Vec::from(bytes))
+    }
+
     fn end(self) -> Result<RETURN>;
 }
@@ -1,6 +1,6 @@
 use crate::event::{ExtensionValue, UriReference};
 use base64::prelude::*;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, SecondsFormat, Utc};
 use std::convert::TryInto;
 use std::fmt;
 use url::Url;
@@ -53,12 +53,21 @@ impl fmt::Display for MessageAttributeValue {
             MessageAttributeValue::Uri(u) => write!(f, "{}", u),
             MessageAttributeValue::UriRef(u) => write!(f, "{}", u),
             MessageAttributeValue::DateTime(d) => {
-                write!(f, "{}", d.to_rfc3339())
+                write!(f, "{}", d.to_rfc3339_opts(SecondsFormat::AutoSi, true))
             }
         }
     }
 }
 
+impl From<MessageAttributeValue> for String {
+    fn from(that: MessageAttributeValue) -> Self {
+        match that {
+            MessageAttributeValue::String(s) => s,
+            v => v.to_string(),
+        }
+    }
+}
+
 impl From<ExtensionValue> for MessageAttributeValue {
     fn from(that: ExtensionValue) -> Self {
         match that {
@@ -74,7 +83,7 @@ impl From<MessageAttributeValue> for ExtensionValue {
         match that {
             MessageAttributeValue::Integer(i) => ExtensionValue::Integer(i),
             MessageAttributeValue::Boolean(b) => ExtensionValue::Boolean(b),
-            v => ExtensionValue::String(v.to_string()),
+            v => ExtensionValue::String(v.into()),
         }
     }
 }
@@ -0,0 +1,68 @@
+use super::{Observation, Operation, Outcome, OBSERVER};
+use crate::event::AttributesReader;
+use crate::Event;
+use std::fmt::Display;
+use std::time::Instant;
+
+/// Measure of an operation in progress, reported to the installed observer once finished.
+pub(crate) struct Measure {
+    binding: &'static str,
+    operation: Operation,
+    start: Instant,
+    /// `type` and `source` of the event, when known before the operation.
+    attributes: Option<(String, String)>,
+}
+
+impl Measure {
+    pub(crate) fn start(
+        binding: &'static str,
+        operation: Operation,
+        event: Option<&Event>,
+    ) -> Self {
+        Measure {
+            binding,
+            operation,
+            start: Instant::now(),
+            attributes: event
+                .filter(|_| OBSERVER.get().is_some())
+                .map(|e| (e.ty().to_string(), e.source().to_string())),
+        }
+    }
+
+    /// Report the result of the operation, with the event it produced if any.
+    pub(crate) fn finish<T, E: Display>(
+        self,
+        result: &std::result::Result<T, E>,
+        event: Option<&Event>,
+    ) {
+        let observer = match OBSERVER.get() {
+            Some(observer) => observer,
+            None => return,
+        };
+        let (event_type, event_source) = match (&self.attributes, event) {
+            (Some((ty, source)), _) => (Some(ty.as_str()), Some(source.as_str())),
+            (None, Some(event)) => (Some(event.ty()), Some(event.source().as_str())),
+            (None, None) => (None, None),
+        };
+        let mut observation = Observation {
+            binding: self.binding,
+            operation: self.operation,
+            outcome: Outcome::Success,
+            event_type,
+            event_source,
+            elapsed: self.start.elapsed(),
+        };
+
+        match (result, self.operation) {
+            (Err(e), _) => {
+                observation.outcome = Outcome::Failure;
+                observer.on_error(&observation, e);
+            }
+            (Ok(_), Operation::Serialize | Operation::Send) => observer.on_produced(&observation),
+            (Ok(_), Operation::Deserialize | Operation::Receive) => {
+                observer.on_consumed(&observation)
+            }
+            (Ok(_), Operation::Expire) => observer.on_expired(&observation),
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! This module provides the [`EventObserver`] hooks, invoked by the bindings when events are
+//! produced, consumed or fail to be, to feed the metrics system of your choice.
+//!
+//! ```
+//! use cloudevents::observer::{set_observer, EventObserver, Observation};
+//! use std::fmt::Display;
+//! use std::sync::atomic::{AtomicU64, Ordering};
+//!
+//! #[derive(Default)]
+//! struct Counters {
+//!     produced: AtomicU64,
+//!     consumed: AtomicU64,
+//!     errors: AtomicU64,
+//! }
+//!
+//! impl EventObserver for Counters {
+//!     fn on_produced(&self, _: &Observation<'_>) {
+//!         self.produced.fetch_add(1, Ordering::Relaxed);
+//!     }
+//!
+//!     fn on_consumed(&self, _: &Observation<'_>) {
+//!         self.consumed.fetch_add(1, Ordering::Relaxed);
+//!     }
+//!
+//!     fn on_error(&self, _: &Observation<'_>, _: &dyn Display) {
+//!         self.errors.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! set_observer(Counters::default()).unwrap();
+//! ```
+//!
+//! The HTTP, Kafka and NATS bindings report:
+//!
+//! * [`Operation::Serialize`] and [`Operation::Send`] to [`EventObserver::on_produced`],
+//! * [`Operation::Deserialize`] and [`Operation::Receive`] to [`EventObserver::on_consumed`],
+//! * the failures of all the operations to [`EventObserver::on_error`].
+//!
+//! With the `expiry` feature, the adapters of the [`expiry`](crate::expiry) module report the
+//! events dropped because their `expirytime` has passed to [`EventObserver::on_expired`], with
+//! the `expiry` binding and [`Operation::Expire`].
+//!
+//! An event sent by a sink is serialized then sent, hence reported once for each operation: use
+//! [`Observation::operation`] to tell them apart.
+
+use snafu::Snafu;
+use std::fmt::{self, Display};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Measures of the instrumented bindings and of the expiry adapters.
+#[cfg(any(
+    test,
+    feature = "http-common",
+    feature = "rdkafka",
+    feature = "rumqttc",
+    feature = "nats",
+    feature = "coap",
+    feature = "expiry"
+))]
+pub(crate) mod measure;
+
+/// Represents an error while installing an [`EventObserver`]
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("An observer is already installed"))]
+    AlreadySet {},
+}
+
+/// Result type alias for return values of [`set_observer`]
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Operation of a binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    Serialize,
+    Deserialize,
+    Send,
+    Receive,
+    Expire,
+}
+
+impl Operation {
+    /// Name of the operation, usable as a metric label.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operation::Serialize => "serialize",
+            Operation::Deserialize => "deserialize",
+            Operation::Send => "send",
+            Operation::Receive => "receive",
+            Operation::Expire => "expire",
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Outcome of an operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Success,
+    Failure,
+}
+
+impl Outcome {
+    /// Name of the outcome, usable as a metric label.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Outcome::Success => "success",
+            Outcome::Failure => "failure",
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Attributes of an operation reported to an [`EventObserver`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Observation<'a> {
+    /// Name of the binding, e.g. `http`, `kafka` or `nats`.
+    pub binding: &'static str,
+    pub operation: Operation,
+    pub outcome: Outcome,
+    /// `type` of the event, unknown when an event failed to be deserialized or received.
+    pub event_type: Option<&'a str>,
+    /// `source` of the event, unknown when an event failed to be deserialized or received.
+    pub event_source: Option<&'a str>,
+    /// Duration of the operation.
+    pub elapsed: Duration,
+}
+
+/// Hooks invoked by the bindings. All the methods do nothing by default.
+pub trait EventObserver: Send + Sync {
+    /// Invoked when an event has been serialized or sent.
+    fn on_produced(&self, _observation: &Observation<'_>) {}
+
+    /// Invoked when an event has been deserialized or received.
+    fn on_consumed(&self, _observation: &Observation<'_>) {}
+
+    /// Invoked when an operation failed with `error`.
+    fn on_error(&self, _observation: &Observation<'_>, _error: &dyn Display) {}
+
+    /// Invoked when an event has been dropped or dead-lettered because it expired.
+    fn on_expired(&self, _observation: &Observation<'_>) {}
+}
+
+static OBSERVER: OnceLock<Box<dyn EventObserver>> = OnceLock::new();
+
+/// Install the global [`EventObserver`] invoked by the bindings.
+///
+/// The observer can be installed only once, the following calls return [`Error::AlreadySet`].
+pub fn set_observer(observer: impl EventObserver + 'static) -> Result<()> {
+    OBSERVER
+        .set(Box::new(observer))
+        .map_err(|_| Error::AlreadySet {})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::measure::Measure;
+    use super::*;
+    use crate::test::fixtures;
+    use std::sync::Mutex;
+
+    /// Observer recording the observations of the `test` binding.
+    struct Recorder(&'static Mutex<Vec<String>>);
+
+    impl Recorder {
+        fn push(&self, hook: &str, observation: &Observation<'_>) {
+            if observation.binding == "test" {
+                self.0.lock().unwrap().push(format!(
+                    "{} {} {} {:?}",
+                    hook, observation.operation, observation.outcome, observation.event_type
+                ));
+            }
+        }
+    }
+
+    impl EventObserver for Recorder {
+        fn on_produced(&self, observation: &Observation<'_>) {
+            self.push("produced", observation);
+        }
+
+        fn on_consumed(&self, observation: &Observation<'_>) {
+            self.push("consumed", observation);
+        }
+
+        fn on_error(&self, observation: &Observation<'_>, _: &dyn Display) {
+            self.push("error", observation);
+        }
+
+        fn on_expired(&self, observation: &Observation<'_>) {
+            self.push("expired", observation);
+        }
+    }
+
+    #[test]
+    fn observe() {
+        static RECORDED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+        set_observer(Recorder(&RECORDED)).unwrap();
+        assert!(matches!(
+            set_observer(Recorder(&RECORDED)),
+            Err(Error::AlreadySet {})
+        ));
+
+        let event = fixtures::v10::minimal();
+        Measure::start("test", Operation::Send, Some(&event))
+            .finish(&Ok::<_, crate::message::Error>(()), None);
+        Measure::start("test", Operation::Deserialize, None)
+            .finish(&Ok::<_, crate::message::Error>(()), Some(&event));
+        Measure::start("test", Operation::Receive, None)
+            .finish(&Err::<(), _>(crate::message::Error::WrongEncoding {}), None);
+        Measure::start("test", Operation::Expire, Some(&event))
+            .finish(&Ok::<_, crate::message::Error>(()), None);
+
+        assert_eq!(
+            *RECORDED.lock().unwrap(),
+            vec![
+                "produced send success Some(\"test_event.test_application\")",
+                "consumed deserialize success Some(\"test_event.test_application\")",
+                "error receive failure None",
+                "expired expire success Some(\"test_event.test_application\")",
+            ]
+        );
+    }
+}
@@ -0,0 +1,245 @@
+//! This module implements the [Distributed Tracing extension](https://github.com/cloudevents/spec/blob/main/cloudevents/extensions/distributed-tracing.md)
+//! with [OpenTelemetry](https://docs.rs/opentelemetry), propagating the W3C trace context of
+//! the producer to the consumers in the `traceparent` and `tracestate` extensions.
+//!
+//! ```
+//! use cloudevents::opentelemetry::{inject_context, start_consumer_span};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//! # use opentelemetry_lib as opentelemetry;
+//! use opentelemetry::trace::noop::NoopTracer;
+//! use opentelemetry::Context;
+//!
+//! let mut event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("example.test")
+//!     .source("http://localhost/")
+//!     .build()
+//!     .unwrap();
+//!
+//! // Producer side
+//! inject_context(&mut event, &Context::current());
+//!
+//! // Consumer side
+//! let span = start_consumer_span(&NoopTracer::new(), "process example.test", &event);
+//! ```
+//!
+//! [`EventInjector`] and [`EventExtractor`] adapt an [`Event`] to any other
+//! [`TextMapPropagator`](opentelemetry_lib::propagation::TextMapPropagator).
+
+use crate::event::{AttributesReader, ExtensionValue};
+use crate::Event;
+use opentelemetry_lib::propagation::{Extractor, Injector};
+use opentelemetry_lib::trace::{
+    SpanContext, SpanId, SpanKind, TraceContextExt, TraceFlags, TraceId, TraceState, Tracer,
+};
+use opentelemetry_lib::{Context, KeyValue};
+
+/// Name of the extension carrying the W3C `traceparent`.
+pub const TRACEPARENT_EXTENSION: &str = "traceparent";
+/// Name of the extension carrying the W3C `tracestate`.
+pub const TRACESTATE_EXTENSION: &str = "tracestate";
+
+const SUPPORTED_VERSION: u8 = 0;
+
+/// [`Injector`] writing the propagated fields to the extensions of an [`Event`].
+///
+/// Fields whose name is not a valid extension name are ignored.
+#[derive(Debug)]
+pub struct EventInjector<'a>(pub &'a mut Event);
+
+impl Injector for EventInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        let key = key.to_ascii_lowercase();
+        if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric()) {
+            self.0.set_extension(&key, value);
+        }
+    }
+}
+
+/// [`Extractor`] reading the propagated fields from the string extensions of an [`Event`].
+#[derive(Debug)]
+pub struct EventExtractor<'a>(pub &'a Event);
+
+impl Extractor for EventExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        match self.0.extension(&key.to_ascii_lowercase()) {
+            Some(ExtensionValue::String(s)) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0
+            .iter_extensions()
+            .filter(|(_, v)| matches!(v, ExtensionValue::String(_)))
+            .map(|(k, _)| k)
+            .collect()
+    }
+}
+
+/// Write the span context of `cx` to the `traceparent` and `tracestate` extensions of `event`.
+///
+/// Nothing is written if `cx` has no valid span context.
+pub fn inject_context(event: &mut Event, cx: &Context) {
+    let span = cx.span();
+    let span_context = span.span_context();
+    if !span_context.is_valid() {
+        return;
+    }
+
+    event.set_extension(
+        TRACEPARENT_EXTENSION,
+        format!(
+            "{:02x}-{}-{}-{:02x}",
+            SUPPORTED_VERSION,
+            span_context.trace_id(),
+            span_context.span_id(),
+            span_context.trace_flags() & TraceFlags::SAMPLED
+        ),
+    );
+    let trace_state = span_context.trace_state().header();
+    if !trace_state.is_empty() {
+        event.set_extension(TRACESTATE_EXTENSION, trace_state);
+    }
+}
+
+/// Read the remote span context from the `traceparent` and `tracestate` extensions of `event`.
+///
+/// Returns an empty [`Context`] if `traceparent` is missing or invalid.
+pub fn extract_context(event: &Event) -> Context {
+    match extract_span_context(event) {
+        Some(span_context) => Context::new().with_remote_span_context(span_context),
+        None => Context::new(),
+    }
+}
+
+fn extract_span_context(event: &Event) -> Option<SpanContext> {
+    let extractor = EventExtractor(event);
+    let parts: Vec<&str> = extractor
+        .get(TRACEPARENT_EXTENSION)?
+        .trim()
+        .split('-')
+        .collect();
+    if parts.len() != 4 {
+        return None;
+    }
+
+    let version = u8::from_str_radix(parts[0], 16).ok()?;
+    if parts[0].len() != 2 || version != SUPPORTED_VERSION {
+        return None;
+    }
+    if parts[1].len() != 32 || parts[2].len() != 16 || parts[3].len() != 2 {
+        return None;
+    }
+    let trace_id = TraceId::from_hex(parts[1]).ok()?;
+    let span_id = SpanId::from_hex(parts[2]).ok()?;
+    let flags = TraceFlags::new(u8::from_str_radix(parts[3], 16).ok()?) & TraceFlags::SAMPLED;
+    let trace_state = extractor
+        .get(TRACESTATE_EXTENSION)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or_else(TraceState::default);
+
+    let span_context = SpanContext::new(trace_id, span_id, flags, true, trace_state);
+    span_context.is_valid().then_some(span_context)
+}
+
+/// Start a [`SpanKind::Consumer`] span named `name` to process `event`, parented on the trace
+/// context propagated by the event.
+///
+/// The span has the `cloudevents.event_*` attributes of the OpenTelemetry semantic conventions.
+pub fn start_consumer_span<T: Tracer>(
+    tracer: &T,
+    name: impl Into<String>,
+    event: &Event,
+) -> T::Span {
+    let mut attributes = vec![
+        KeyValue::new("cloudevents.event_id", event.id().to_string()),
+        KeyValue::new("cloudevents.event_source", event.source().to_string()),
+        KeyValue::new(
+            "cloudevents.event_spec_version",
+            event.specversion().to_string(),
+        ),
+        KeyValue::new("cloudevents.event_type", event.ty().to_string()),
+    ];
+    if let Some(subject) = event.subject() {
+        attributes.push(KeyValue::new(
+            "cloudevents.event_subject",
+            subject.to_string(),
+        ));
+    }
+
+    let builder = tracer
+        .span_builder(name.into())
+        .with_kind(SpanKind::Consumer)
+        .with_attributes(attributes);
+    tracer.build_with_context(builder, &extract_context(event))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use opentelemetry_lib::trace::noop::NoopTracer;
+    use opentelemetry_lib::trace::Span;
+
+    fn span_context() -> SpanContext {
+        SpanContext::new(
+            TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap(),
+            SpanId::from_hex("00f067aa0ba902b7").unwrap(),
+            TraceFlags::SAMPLED,
+            false,
+            "congo=t61rcWkgMzE".parse().unwrap(),
+        )
+    }
+
+    #[test]
+    fn inject_and_extract() {
+        let mut event = fixtures::v10::minimal();
+        inject_context(
+            &mut event,
+            &Context::new().with_remote_span_context(span_context()),
+        );
+
+        assert_eq!(
+            EventExtractor(&event).get(TRACEPARENT_EXTENSION),
+            Some("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+        );
+        assert_eq!(
+            EventExtractor(&event).get(TRACESTATE_EXTENSION),
+            Some("congo=t61rcWkgMzE")
+        );
+
+        let cx = extract_context(&event);
+        let span = cx.span();
+        let extracted = span.span_context();
+        assert!(extracted.is_remote());
+        assert_eq!(extracted.trace_id(), span_context().trace_id());
+        assert_eq!(extracted.span_id(), span_context().span_id());
+        assert_eq!(extracted.trace_state(), span_context().trace_state());
+        assert!(extracted.is_sampled());
+    }
+
+    #[test]
+    fn no_context() {
+        let mut event = fixtures::v10::minimal();
+        inject_context(&mut event, &Context::new());
+        assert_eq!(event, fixtures::v10::minimal());
+        assert!(!extract_context(&event).has_active_span());
+
+        event.set_extension(TRACEPARENT_EXTENSION, "00-invalid-00f067aa0ba902b7-01");
+        assert!(!extract_context(&event).has_active_span());
+    }
+
+    #[test]
+    fn consumer_span() {
+        let mut event = fixtures::v10::minimal();
+        inject_context(
+            &mut event,
+            &Context::new().with_remote_span_context(span_context()),
+        );
+
+        let span = start_consumer_span(&NoopTracer::new(), "process", &event);
+
+        assert_eq!(span.span_context().trace_id(), span_context().trace_id());
+    }
+}
@@ -0,0 +1,273 @@
+//! This module implements the [transactional outbox](https://microservices.io/patterns/data/transactional-outbox.html)
+//! pattern on Postgres with [sqlx](https://docs.rs/sqlx).
+//!
+//! Events are written to an outbox table in the same database transaction as the application
+//! state changes, then an [`OutboxRelay`] publishes them to an [`EventSink`] and marks them as
+//! delivered, so events are published if and only if the transaction commits.
+//!
+//! ```no_run
+//! use cloudevents::outbox::{Outbox, OutboxRelay};
+//! use cloudevents::transport::EventSink;
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//! use sqlx::PgPool;
+//!
+//! # async fn example(pool: PgPool, sink: impl EventSink + 'static) -> Result<(), Box<dyn std::error::Error>> {
+//! let outbox = Outbox::new();
+//! outbox.create_table(&pool).await?;
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("com.example.order.created")
+//!     .source("http://localhost/")
+//!     .build()?;
+//!
+//! let mut tx = pool.begin().await?;
+//! sqlx::query("INSERT INTO orders (id) VALUES ('0001')").execute(&mut *tx).await?;
+//! outbox.enqueue(&mut *tx, &event).await?;
+//! tx.commit().await?;
+//!
+//! tokio::spawn(OutboxRelay::new(pool, outbox, sink).run());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The relay locks the rows it publishes with `FOR UPDATE SKIP LOCKED`, so several relays
+//! can run concurrently. Events are published in insertion order, and delivery is
+//! at-least-once: an event is published again if the relay stops before marking it as
+//! delivered.
+
+use crate::pg::is_valid_table_name;
+use crate::transport::{self, EventSink};
+use crate::Event;
+use snafu::Snafu;
+use sqlx::types::Json;
+use sqlx::{PgExecutor, PgPool};
+use std::time::Duration;
+
+/// Default name of the outbox table.
+pub static DEFAULT_TABLE: &str = "outbox";
+
+/// Represents an error of the outbox
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Invalid table name {}", name))]
+    InvalidTableName { name: String },
+    #[snafu(display("Database error: {}", source))]
+    #[snafu(context(false))]
+    DatabaseError { source: sqlx::Error },
+    #[snafu(display("Error while publishing the event: {}", source))]
+    #[snafu(context(false))]
+    TransportError { source: transport::Error },
+}
+
+/// Result type alias for return values of the outbox
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// An outbox table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outbox {
+    table: String,
+}
+
+impl Default for Outbox {
+    fn default() -> Self {
+        Outbox {
+            table: DEFAULT_TABLE.to_string(),
+        }
+    }
+}
+
+impl Outbox {
+    /// Create a new [`Outbox`] using the [`DEFAULT_TABLE`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new [`Outbox`] using the table `name`, optionally qualified by a schema.
+    pub fn with_table(name: impl Into<String>) -> Result<Self> {
+        let name = name.into();
+        if !is_valid_table_name(&name) {
+            return Err(Error::InvalidTableName { name });
+        }
+        Ok(Outbox { table: name })
+    }
+
+    /// Name of the outbox table.
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    /// DDL statements creating the outbox table and its index, if they don't exist.
+    pub fn schema(&self) -> Vec<String> {
+        vec![
+            format!(
+                "CREATE TABLE IF NOT EXISTS {} (\
+                 position BIGSERIAL PRIMARY KEY, \
+                 event JSONB NOT NULL, \
+                 created_at TIMESTAMPTZ NOT NULL DEFAULT now(), \
+                 delivered_at TIMESTAMPTZ)",
+                self.table
+            ),
+            format!(
+                "CREATE INDEX IF NOT EXISTS {}_pending_idx ON {} (position) WHERE delivered_at IS NULL",
+                self.table.replace('.', "_"),
+                self.table
+            ),
+        ]
+    }
+
+    /// Create the outbox table and its index, if they don't exist.
+    pub async fn create_table(&self, pool: &PgPool) -> Result<()> {
+        for statement in self.schema() {
+            sqlx::query(&statement).execute(pool).await?;
+        }
+        Ok(())
+    }
+
+    /// Write `event` to the outbox, returning its position.
+    ///
+    /// `executor` is usually the application transaction, e.g. `&mut *tx`.
+    pub async fn enqueue<'c, E: PgExecutor<'c>>(&self, executor: E, event: &Event) -> Result<i64> {
+        let sql = format!(
+            "INSERT INTO {} (event) VALUES ($1) RETURNING position",
+            self.table
+        );
+        let (position,): (i64,) = sqlx::query_as(&sql)
+            .bind(Json(event))
+            .fetch_one(executor)
+            .await?;
+        Ok(position)
+    }
+
+    /// Delete the delivered events older than `age`, returning the number of deleted events.
+    pub async fn purge_delivered(&self, pool: &PgPool, age: Duration) -> Result<u64> {
+        let sql = format!(
+            "DELETE FROM {} WHERE delivered_at < now() - make_interval(secs => $1)",
+            self.table
+        );
+        Ok(sqlx::query(&sql)
+            .bind(age.as_secs_f64())
+            .execute(pool)
+            .await?
+            .rows_affected())
+    }
+}
+
+/// Task publishing the events of an [`Outbox`] to an [`EventSink`].
+pub struct OutboxRelay<S> {
+    pool: PgPool,
+    outbox: Outbox,
+    sink: S,
+    batch_size: i64,
+    poll_interval: Duration,
+}
+
+impl<S: EventSink> OutboxRelay<S> {
+    /// Create a new [`OutboxRelay`], publishing batches of 100 events and polling the outbox
+    /// every second when it is empty.
+    pub fn new(pool: PgPool, outbox: Outbox, sink: S) -> Self {
+        OutboxRelay {
+            pool,
+            outbox,
+            sink,
+            batch_size: 100,
+            poll_interval: Duration::from_secs(1),
+        }
+    }
+
+    /// Set the maximum number of events published by [`relay_once`](Self::relay_once).
+    pub fn with_batch_size(mut self, batch_size: i64) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Set the interval between the polls of an empty outbox.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Publish a batch of pending events, returning the number of published events.
+    ///
+    /// Stops at the first event which cannot be published, keeping it and the following events
+    /// pending, and returns the error.
+    pub async fn relay_once(&self) -> Result<usize> {
+        let table = self.outbox.table();
+        let mut tx = self.pool.begin().await?;
+        let pending: Vec<(i64, Json<Event>)> = sqlx::query_as(&format!(
+            "SELECT position, event FROM {} WHERE delivered_at IS NULL \
+             ORDER BY position LIMIT $1 FOR UPDATE SKIP LOCKED",
+            table
+        ))
+        .bind(self.batch_size)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mark_delivered = format!(
+            "UPDATE {} SET delivered_at = now() WHERE position = $1",
+            table
+        );
+        let mut published = 0;
+        let mut result = Ok(());
+        for (position, Json(event)) in pending {
+            if let Err(e) = self.sink.send(event).await {
+                result = Err(e.into());
+                break;
+            }
+            sqlx::query(&mark_delivered)
+                .bind(position)
+                .execute(&mut *tx)
+                .await?;
+            published += 1;
+        }
+        tx.commit().await?;
+
+        result.map(|_| published)
+    }
+
+    /// Publish the pending events until an error occurs.
+    pub async fn run(self) -> Result<()> {
+        loop {
+            if self.relay_once().await? == 0 {
+                tokio::time::sleep(self.poll_interval).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_name() {
+        assert_eq!(Outbox::new().table(), "outbox");
+        assert_eq!(
+            Outbox::with_table("events.order_outbox").unwrap().table(),
+            "events.order_outbox"
+        );
+        for name in [
+            "",
+            "1outbox",
+            "a.b.c",
+            "outbox; DROP TABLE orders",
+            "out-box",
+        ] {
+            assert!(matches!(
+                Outbox::with_table(name),
+                Err(Error::InvalidTableName { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn schema() {
+        let schema = Outbox::with_table("events.outbox").unwrap().schema();
+
+        assert!(schema[0].starts_with("CREATE TABLE IF NOT EXISTS events.outbox ("));
+        assert_eq!(
+            schema[1],
+            "CREATE INDEX IF NOT EXISTS events_outbox_pending_idx ON events.outbox (position) WHERE delivered_at IS NULL"
+        );
+    }
+}
@@ -0,0 +1,14 @@
+//! Helpers shared by the Postgres integrations.
+
+/// Table name made of ASCII alphanumeric characters and `_`, optionally qualified by a schema.
+///
+/// Table names cannot be bound as query parameters, so they are validated before being
+/// interpolated in the SQL statements.
+pub(crate) fn is_valid_table_name(name: &str) -> bool {
+    let parts: Vec<&str> = name.split('.').collect();
+    parts.len() <= 2
+        && parts.iter().all(|p| {
+            p.chars().next().is_some_and(|c| !c.is_ascii_digit())
+                && p.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+}
@@ -0,0 +1,487 @@
+//! This module implements the [Protobuf event format](https://github.com/cloudevents/spec/blob/main/cloudevents/formats/protobuf-format.md),
+//! converting [`Event`] from and to the [`v1::CloudEvent`] message of the
+//! `io.cloudevents.v1` package, e.g. to exchange events with tonic services.
+//!
+//! ```
+//! use cloudevents::protobuf::v1::CloudEvent;
+//! use cloudevents::{AttributesReader, Event, EventBuilder, EventBuilderV10};
+//! use std::convert::TryFrom;
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("example.test")
+//!     .source("http://localhost/")
+//!     .data("text/plain", "hello")
+//!     .build()
+//!     .unwrap();
+//!
+//! let proto = CloudEvent::try_from(event.clone()).unwrap();
+//! assert_eq!(proto.spec_version, "1.0");
+//! assert_eq!(Event::try_from(proto).unwrap().id(), event.id());
+//! ```
+//!
+//! The data is sent as `text_data` if the `datacontenttype` is a text or JSON media type, as
+//! `proto_data` if it's [`PROTOBUF_CONTENT_TYPE`], and as `binary_data` otherwise. Integers out of
+//! the `int32` range of the format are sent as strings.
+//!
+//! Projects that already compile `cloudevents.proto` with their own `prost-build` setup can
+//! implement the same conversions for their generated type with [`impl_proto_conversions`](crate::impl_proto_conversions).
+
+use crate::binding::is_media_type;
+use crate::event::{SpecVersion, PROTOBUF_CONTENT_TYPE};
+use crate::message::{BinaryDeserializer, BinarySerializer, Error, MessageAttributeValue, Result};
+use crate::Event;
+use chrono::{DateTime, TimeZone, Utc};
+use prost::Message;
+use std::convert::TryFrom;
+use url::Url;
+use v1::cloud_event::cloud_event_attribute_value::Attr;
+use v1::cloud_event::{CloudEventAttributeValue, Data};
+use v1::CloudEvent;
+
+pub mod v1;
+
+fn invalid(name: &str, msg: impl Into<String>) -> Error {
+    Error::InvalidHeaderValue {
+        name: name.to_string(),
+        source: msg.into().into(),
+    }
+}
+
+fn to_timestamp(time: DateTime<Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: time.timestamp(),
+        nanos: time.timestamp_subsec_nanos() as i32,
+    }
+}
+
+fn from_timestamp(name: &str, ts: prost_types::Timestamp) -> Result<DateTime<Utc>> {
+    u32::try_from(ts.nanos)
+        .ok()
+        .and_then(|nanos| Utc.timestamp_opt(ts.seconds, nanos).single())
+        .ok_or_else(|| invalid(name, format!("timestamp out of range: {}", ts)))
+}
+
+impl From<MessageAttributeValue> for CloudEventAttributeValue {
+    fn from(value: MessageAttributeValue) -> Self {
+        let attr = match value {
+            MessageAttributeValue::Boolean(b) => Attr::CeBoolean(b),
+            MessageAttributeValue::Integer(i) => match i32::try_from(i) {
+                Ok(i) => Attr::CeInteger(i),
+                Err(_) => Attr::CeString(i.to_string()),
+            },
+            MessageAttributeValue::String(s) => Attr::CeString(s),
+            MessageAttributeValue::Binary(b) => Attr::CeBytes(b),
+            MessageAttributeValue::Uri(u) => Attr::CeUri(u.into()),
+            MessageAttributeValue::UriRef(u) => Attr::CeUriRef(u),
+            MessageAttributeValue::DateTime(t) => Attr::CeTimestamp(to_timestamp(t)),
+        };
+        CloudEventAttributeValue { attr: Some(attr) }
+    }
+}
+
+impl CloudEventAttributeValue {
+    fn into_message_attribute_value(self, name: &str) -> Result<MessageAttributeValue> {
+        Ok(match self.attr {
+            Some(Attr::CeBoolean(b)) => MessageAttributeValue::Boolean(b),
+            Some(Attr::CeInteger(i)) => MessageAttributeValue::Integer(i.into()),
+            Some(Attr::CeString(s)) => MessageAttributeValue::String(s),
+            Some(Attr::CeBytes(b)) => MessageAttributeValue::Binary(b),
+            Some(Attr::CeUri(u)) => MessageAttributeValue::Uri(Url::parse(&u)?),
+            Some(Attr::CeUriRef(u)) => MessageAttributeValue::UriRef(u),
+            Some(Attr::CeTimestamp(ts)) => {
+                MessageAttributeValue::DateTime(from_timestamp(name, ts)?)
+            }
+            None => return Err(invalid(name, "missing attribute value")),
+        })
+    }
+}
+
+impl CloudEvent {
+    fn attribute_str(&self, name: &str) -> Option<&str> {
+        match self.attributes.get(name)?.attr.as_ref()? {
+            Attr::CeString(s) | Attr::CeUri(s) | Attr::CeUriRef(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl BinaryDeserializer for CloudEvent {
+    fn deserialize_binary<R: Sized, V: BinarySerializer<R>>(self, mut visitor: V) -> Result<R> {
+        let spec_version = SpecVersion::try_from(self.spec_version.as_str())
+            .map_err(|e| e.in_attribute("specversion"))?;
+        let attribute_names = spec_version.attribute_names();
+        let schema_attribute = if attribute_names.contains(&"dataschema") {
+            "dataschema"
+        } else {
+            "schemaurl"
+        };
+
+        visitor = visitor.set_spec_version(spec_version)?;
+        visitor = visitor.set_attribute("id", MessageAttributeValue::String(self.id))?;
+        visitor = visitor.set_attribute("type", MessageAttributeValue::String(self.r#type))?;
+        visitor = visitor.set_attribute("source", MessageAttributeValue::UriRef(self.source))?;
+
+        let mut attributes = self.attributes;
+        if let Some(Data::ProtoData(any)) = &self.data {
+            // The attributes of a message packed in an `Any` default to its type.
+            attributes
+                .entry("datacontenttype".to_string())
+                .or_insert_with(|| {
+                    MessageAttributeValue::String(PROTOBUF_CONTENT_TYPE.to_string()).into()
+                });
+            if !any.type_url.is_empty() && !attributes.contains_key(schema_attribute) {
+                let type_url = if any.type_url.contains("://") {
+                    any.type_url.clone()
+                } else {
+                    format!("https://{}", any.type_url)
+                };
+                attributes.insert(
+                    schema_attribute.to_string(),
+                    MessageAttributeValue::Uri(Url::parse(&type_url)?).into(),
+                );
+            }
+        }
+
+        for (name, value) in attributes {
+            let value = value.into_message_attribute_value(&name)?;
+            if attribute_names.contains(&name.as_str()) {
+                visitor = visitor.set_attribute(&name, value)?
+            } else {
+                visitor = visitor.set_extension(&name, value)?
+            }
+        }
+
+        match self.data {
+            Some(Data::BinaryData(b)) => visitor.end_with_data(b),
+            Some(Data::TextData(s)) => visitor.end_with_data(s.into_bytes()),
+            Some(Data::ProtoData(any)) => visitor.end_with_data(any.value),
+            None => visitor.end(),
+        }
+    }
+}
+
+impl BinarySerializer<CloudEvent> for CloudEvent {
+    fn set_spec_version(mut self, spec_version: SpecVersion) -> Result<Self> {
+        self.spec_version = spec_version.to_string();
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        match name {
+            "id" => self.id = value.to_string(),
+            "source" => self.source = value.to_string(),
+            "type" => self.r#type = value.to_string(),
+            "specversion" => {}
+            _ => {
+                self.attributes.insert(name.to_string(), value.into());
+            }
+        }
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: MessageAttributeValue) -> Result<Self> {
+        self.attributes.insert(name.to_string(), value.into());
+        Ok(self)
+    }
+
+    fn end_with_data(mut self, bytes: Vec<u8>) -> Result<CloudEvent> {
+        let content_type = self.attribute_str("datacontenttype").unwrap_or_default();
+        let data = if is_media_type(content_type, PROTOBUF_CONTENT_TYPE) {
+            Data::ProtoData(prost_types::Any {
+                type_url: self
+                    .attribute_str("dataschema")
+                    .or_else(|| self.attribute_str("schemaurl"))
+                    .unwrap_or_default()
+                    .to_string(),
+                value: bytes,
+            })
+        } else if is_text(content_type) {
+            match String::from_utf8(bytes) {
+                Ok(s) => Data::TextData(s),
+                Err(e) => Data::BinaryData(e.into_bytes()),
+            }
+        } else {
+            Data::BinaryData(bytes)
+        };
+        self.data = Some(data);
+        Ok(self)
+    }
+
+    fn end(self) -> Result<CloudEvent> {
+        Ok(self)
+    }
+}
+
+/// Whether data of the `content_type` can be sent as `text_data`.
+fn is_text(content_type: &str) -> bool {
+    let media_type = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    media_type.starts_with("text/")
+        || media_type == "application/json"
+        || media_type.ends_with("+json")
+        || media_type == "application/xml"
+        || media_type.ends_with("+xml")
+}
+
+impl TryFrom<Event> for CloudEvent {
+    type Error = Error;
+
+    fn try_from(event: Event) -> Result<Self> {
+        event.deserialize_binary(CloudEvent::default())
+    }
+}
+
+impl TryFrom<CloudEvent> for Event {
+    type Error = Error;
+
+    fn try_from(event: CloudEvent) -> Result<Self> {
+        BinaryDeserializer::into_event(event)
+    }
+}
+
+#[doc(hidden)]
+pub fn __encode(event: Event) -> Result<Vec<u8>> {
+    Ok(CloudEvent::try_from(event)?.encode_to_vec())
+}
+
+#[doc(hidden)]
+pub fn __decode(bytes: &[u8]) -> Result<Event> {
+    let event = CloudEvent::decode(bytes).map_err(|e| Error::Other {
+        source: Box::new(e),
+    })?;
+    Event::try_from(event)
+}
+
+/// Implement `TryFrom<Event>` and `TryFrom<$ty> for Event` for `$ty`, an `io.cloudevents.v1.CloudEvent`
+/// message generated by the `prost` version of the calling crate, e.g. alongside a tonic service.
+///
+/// The conversions go through the Protobuf encoding of the message, so they work whatever the
+/// `prost` version the type was generated with.
+///
+/// ```ignore
+/// mod pb {
+///     tonic::include_proto!("io.cloudevents.v1");
+/// }
+///
+/// cloudevents::impl_proto_conversions!(pb::CloudEvent);
+/// ```
+#[macro_export]
+macro_rules! impl_proto_conversions {
+    ($ty:ty) => {
+        impl ::std::convert::TryFrom<$crate::Event> for $ty {
+            type Error = $crate::message::Error;
+
+            fn try_from(event: $crate::Event) -> ::std::result::Result<Self, Self::Error> {
+                let bytes = $crate::protobuf::__encode(event)?;
+                <$ty as ::prost::Message>::decode(bytes.as_slice()).map_err(|e| {
+                    $crate::message::Error::Other {
+                        source: ::std::boxed::Box::new(e),
+                    }
+                })
+            }
+        }
+
+        impl ::std::convert::TryFrom<$ty> for $crate::Event {
+            type Error = $crate::message::Error;
+
+            fn try_from(event: $ty) -> ::std::result::Result<Self, Self::Error> {
+                $crate::protobuf::__decode(&::prost::Message::encode_to_vec(&event))
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Data as EventData;
+    use crate::test::fixtures;
+    use crate::{AttributesReader, EventBuilder, EventBuilderV10};
+    use serde_json::json;
+
+    #[test]
+    fn event_to_proto() {
+        let event = fixtures::v10::full_json_data();
+
+        let proto = CloudEvent::try_from(event).unwrap();
+
+        assert_eq!(proto.spec_version, "1.0");
+        assert_eq!(proto.id, fixtures::id());
+        assert_eq!(proto.r#type, fixtures::ty());
+        assert_eq!(proto.source, fixtures::source());
+        assert_eq!(
+            proto.attributes["time"].attr,
+            Some(Attr::CeTimestamp(to_timestamp(fixtures::time())))
+        );
+        assert_eq!(
+            proto.attributes["dataschema"].attr,
+            Some(Attr::CeUri(fixtures::dataschema().to_string()))
+        );
+        assert_eq!(proto.attributes["boolex"].attr, Some(Attr::CeBoolean(true)));
+        assert_eq!(proto.attributes["intex"].attr, Some(Attr::CeInteger(10)));
+        assert!(matches!(proto.data, Some(Data::TextData(_))));
+    }
+
+    #[test]
+    fn roundtrip() {
+        let expected = fixtures::v10::full_binary_json_data_string_extension();
+
+        let bytes = __encode(expected.clone()).unwrap();
+        let event = __decode(&bytes).unwrap();
+
+        assert_eq!(event.id(), expected.id());
+        assert_eq!(event.time(), expected.time());
+        assert_eq!(event.dataschema(), expected.dataschema());
+        assert_eq!(event.extension("stringex"), expected.extension("stringex"));
+        assert_eq!(
+            event.data().cloned().unwrap().into_json().unwrap(),
+            fixtures::json_data()
+        );
+    }
+
+    #[test]
+    fn roundtrip_v03() {
+        let expected = fixtures::v03::full_json_data();
+
+        let proto = CloudEvent::try_from(expected.clone()).unwrap();
+        assert_eq!(proto.spec_version, "0.3");
+        let event = Event::try_from(proto).unwrap();
+
+        assert_eq!(event.specversion(), SpecVersion::V03);
+        assert_eq!(event.dataschema(), expected.dataschema());
+    }
+
+    #[test]
+    fn large_integer_extension() {
+        let mut event = fixtures::v10::minimal();
+        event.set_extension("bigex", i64::from(i32::MAX) + 1);
+
+        let proto = CloudEvent::try_from(event).unwrap();
+
+        assert_eq!(
+            proto.attributes["bigex"].attr,
+            Some(Attr::CeString("2147483648".to_string()))
+        );
+    }
+
+    #[test]
+    fn proto_data() {
+        let event = EventBuilderV10::from(fixtures::v10::minimal())
+            .data_with_schema(
+                PROTOBUF_CONTENT_TYPE,
+                "https://type.googleapis.com/example.v1.Order",
+                vec![8, 1],
+            )
+            .build()
+            .unwrap();
+
+        let proto = CloudEvent::try_from(event.clone()).unwrap();
+        assert_eq!(
+            proto.data,
+            Some(Data::ProtoData(prost_types::Any {
+                type_url: "https://type.googleapis.com/example.v1.Order".to_string(),
+                value: vec![8, 1],
+            }))
+        );
+        assert_eq!(Event::try_from(proto).unwrap(), event);
+    }
+
+    #[test]
+    fn proto_data_without_attributes() {
+        let mut proto = CloudEvent::try_from(fixtures::v10::minimal()).unwrap();
+        proto.data = Some(Data::ProtoData(prost_types::Any {
+            type_url: "type.googleapis.com/example.v1.Order".to_string(),
+            value: vec![8, 1],
+        }));
+
+        let event = Event::try_from(proto).unwrap();
+
+        assert_eq!(event.datacontenttype(), Some(PROTOBUF_CONTENT_TYPE));
+        assert_eq!(
+            event.dataschema().map(Url::as_str),
+            Some("https://type.googleapis.com/example.v1.Order")
+        );
+        assert_eq!(event.data(), Some(&EventData::Binary(vec![8, 1])));
+    }
+
+    #[test]
+    fn binary_data() {
+        let event = EventBuilderV10::from(fixtures::v10::minimal())
+            .data("application/octet-stream", vec![0xff, 0x00])
+            .build()
+            .unwrap();
+
+        let proto = CloudEvent::try_from(event).unwrap();
+
+        assert_eq!(proto.data, Some(Data::BinaryData(vec![0xff, 0x00])));
+    }
+
+    #[test]
+    fn invalid_spec_version() {
+        let mut proto = CloudEvent::try_from(fixtures::v10::minimal()).unwrap();
+        proto.spec_version = "2.0".to_string();
+
+        assert!(matches!(
+            Event::try_from(proto),
+            Err(Error::UnknownSpecVersion { .. })
+        ));
+    }
+
+    #[test]
+    fn missing_attribute_value() {
+        let mut proto = CloudEvent::try_from(fixtures::v10::minimal()).unwrap();
+        proto.attributes.insert(
+            "subject".to_string(),
+            CloudEventAttributeValue { attr: None },
+        );
+
+        assert!(matches!(
+            Event::try_from(proto),
+            Err(Error::InvalidHeaderValue { .. })
+        ));
+    }
+
+    mod vendored {
+        /// A subset of `io.cloudevents.v1.CloudEvent`, as a project would generate it.
+        #[derive(Clone, PartialEq, ::prost::Message)]
+        pub struct CloudEvent {
+            #[prost(string, tag = "1")]
+            pub id: String,
+            #[prost(string, tag = "2")]
+            pub source: String,
+            #[prost(string, tag = "3")]
+            pub spec_version: String,
+            #[prost(string, tag = "4")]
+            pub r#type: String,
+            #[prost(string, optional, tag = "7")]
+            pub text_data: Option<String>,
+        }
+
+        crate::impl_proto_conversions!(CloudEvent);
+    }
+
+    #[test]
+    fn impl_proto_conversions() {
+        let event = EventBuilderV10::from(fixtures::v10::minimal())
+            .data("application/json", json!({"hello": "world"}))
+            .build()
+            .unwrap();
+
+        let proto = vendored::CloudEvent::try_from(event).unwrap();
+        assert_eq!(proto.id, fixtures::id());
+        assert_eq!(proto.spec_version, "1.0");
+        assert_eq!(proto.text_data.as_deref(), Some(r#"{"hello":"world"}"#));
+
+        let event = Event::try_from(proto).unwrap();
+        assert_eq!(event.id(), fixtures::id());
+        assert_eq!(
+            event.data(),
+            Some(&EventData::Binary(br#"{"hello":"world"}"#.to_vec()))
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! The messages of the `io.cloudevents.v1` package, defined by the
+//! [`cloudevents.proto`](https://github.com/cloudevents/spec/blob/main/cloudevents/formats/cloudevents.proto)
+//! schema of the Protobuf event format, as generated by `prost-build`.
+
+/// A CloudEvent in the Protobuf event format.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CloudEvent {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub source: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub spec_version: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub r#type: ::prost::alloc::string::String,
+    /// The optional and extension attributes.
+    #[prost(map = "string, message", tag = "5")]
+    pub attributes: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        cloud_event::CloudEventAttributeValue,
+    >,
+    #[prost(oneof = "cloud_event::Data", tags = "6, 7, 8")]
+    pub data: ::core::option::Option<cloud_event::Data>,
+}
+
+/// Nested message and enum types in `CloudEvent`.
+pub mod cloud_event {
+    /// The value of an attribute, with its CloudEvents type.
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct CloudEventAttributeValue {
+        #[prost(
+            oneof = "cloud_event_attribute_value::Attr",
+            tags = "1, 2, 3, 4, 5, 6, 7"
+        )]
+        pub attr: ::core::option::Option<cloud_event_attribute_value::Attr>,
+    }
+
+    /// Nested message and enum types in `CloudEventAttributeValue`.
+    pub mod cloud_event_attribute_value {
+        #[derive(Clone, PartialEq, ::prost::Oneof)]
+        pub enum Attr {
+            #[prost(bool, tag = "1")]
+            CeBoolean(bool),
+            #[prost(int32, tag = "2")]
+            CeInteger(i32),
+            #[prost(string, tag = "3")]
+            CeString(::prost::alloc::string::String),
+            #[prost(bytes, tag = "4")]
+            CeBytes(::prost::alloc::vec::Vec<u8>),
+            #[prost(string, tag = "5")]
+            CeUri(::prost::alloc::string::String),
+            #[prost(string, tag = "6")]
+            CeUriRef(::prost::alloc::string::String),
+            #[prost(message, tag = "7")]
+            CeTimestamp(::prost_types::Timestamp),
+        }
+    }
+
+    /// The data of the event.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Data {
+        #[prost(bytes, tag = "6")]
+        BinaryData(::prost::alloc::vec::Vec<u8>),
+        #[prost(string, tag = "7")]
+        TextData(::prost::alloc::string::String),
+        #[prost(message, tag = "8")]
+        ProtoData(::prost_types::Any),
+    }
+}
+
+/// A batch of CloudEvents, as sent in the batched content mode.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CloudEventBatch {
+    #[prost(message, repeated, tag = "1")]
+    pub events: ::prost::alloc::vec::Vec<CloudEvent>,
+}
+
+impl ::prost::Name for CloudEvent {
+    const NAME: &'static str = "CloudEvent";
+    const PACKAGE: &'static str = "io.cloudevents.v1";
+}
+
+impl ::prost::Name for CloudEventBatch {
+    const NAME: &'static str = "CloudEventBatch";
+    const PACKAGE: &'static str = "io.cloudevents.v1";
+}
@@ -0,0 +1,287 @@
+//! This module provides privacy-safe copies of [`Event`]s, to write them to logs and error
+//! reports without leaking the personal data they carry.
+//!
+//! A [`Redactor`] strips the data, hashes or drops the listed extensions and truncates the
+//! subject, while [`Event::redacted`] only strips the data.
+//!
+//! ```
+//! use cloudevents::redact::Redactor;
+//! use cloudevents::{AttributesReader, EventBuilder, EventBuilderV10};
+//! use serde_json::json;
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("example.user.created")
+//!     .source("http://localhost/")
+//!     .subject("users/jane.doe@example.com")
+//!     .extension("userid", "jane.doe")
+//!     .extension("sessiontoken", "s3cr3t")
+//!     .data("application/json", json!({"email": "jane.doe@example.com"}))
+//!     .build()
+//!     .unwrap();
+//!
+//! let redactor = Redactor::new()
+//!     .with_hash_key(b"kept out of the logs".to_vec())
+//!     .hash_extension("userid")
+//!     .drop_extension("sessiontoken")
+//!     .truncate_subject(6);
+//! let redacted = redactor.redact(&event);
+//!
+//! assert_eq!(redacted.data(), None);
+//! assert_eq!(redacted.subject(), Some("users/…"));
+//! assert!(redacted
+//!     .extension("userid")
+//!     .unwrap()
+//!     .to_string()
+//!     .starts_with("hmac-sha256:"));
+//! assert_eq!(redacted.extension("sessiontoken"), None);
+//! ```
+
+use crate::event::{AttributesReader, AttributesWriter, ExtensionValue, SENSITIVE_EXTENSION_MASK};
+use crate::Event;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt;
+
+/// What a [`Redactor`] does with an extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensionPolicy {
+    /// Keep the extension as is, except for the value of a
+    /// [sensitive](Event::mark_sensitive_extension) extension, replaced by
+    /// [`SENSITIVE_EXTENSION_MASK`].
+    Keep,
+    /// Replace the value with a digest of its string representation, so events can still be
+    /// correlated by it: `hmac-sha256:` followed by the hex HMAC-SHA256 of the value when the
+    /// [`Redactor`] has a [hash key](Redactor::with_hash_key), `sha256:` followed by its hex
+    /// SHA-256 digest otherwise.
+    ///
+    /// Hashing without a key is a pseudonymization rather than an anonymization: anyone can
+    /// hash the likely values, such as user ids or email addresses, and look up the digests.
+    Hash,
+    /// Remove the extension.
+    Drop,
+}
+
+/// Policies to make a privacy-safe copy of an [`Event`].
+///
+/// By default the data is stripped, while the `datacontenttype`, the subject and the
+/// extensions are kept.
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    strip_data: bool,
+    hash_key: Option<HashKey>,
+    extensions: HashMap<String, ExtensionPolicy>,
+    default_extension_policy: ExtensionPolicy,
+    subject_max_chars: Option<usize>,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Redactor {
+            strip_data: true,
+            hash_key: None,
+            extensions: HashMap::new(),
+            default_extension_policy: ExtensionPolicy::Keep,
+            subject_max_chars: None,
+        }
+    }
+}
+
+impl Redactor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether the data is stripped, which is the default.
+    pub fn strip_data(mut self, strip: bool) -> Self {
+        self.strip_data = strip;
+        self
+    }
+
+    /// Hash the extensions with HMAC-SHA256 keyed with `key`, see [`ExtensionPolicy::Hash`].
+    /// The key must be kept secret, as it is enough to look up the digests of likely values.
+    pub fn with_hash_key(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.hash_key = Some(HashKey(key.into()));
+        self
+    }
+
+    /// Set the `policy` of the extension `name`.
+    pub fn extension(mut self, name: impl Into<String>, policy: ExtensionPolicy) -> Self {
+        self.extensions.insert(name.into(), policy);
+        self
+    }
+
+    /// Hash the value of the extension `name`, see [`ExtensionPolicy::Hash`].
+    pub fn hash_extension(self, name: impl Into<String>) -> Self {
+        self.extension(name, ExtensionPolicy::Hash)
+    }
+
+    /// Remove the extension `name`.
+    pub fn drop_extension(self, name: impl Into<String>) -> Self {
+        self.extension(name, ExtensionPolicy::Drop)
+    }
+
+    /// Set the policy of the extensions without one, [`ExtensionPolicy::Keep`] by default.
+    /// E.g. [`ExtensionPolicy::Drop`] only keeps the extensions explicitly allowed.
+    pub fn default_extension_policy(mut self, policy: ExtensionPolicy) -> Self {
+        self.default_extension_policy = policy;
+        self
+    }
+
+    /// Truncate the subject to `max_chars` characters, followed by `…` when truncated.
+    pub fn truncate_subject(mut self, max_chars: usize) -> Self {
+        self.subject_max_chars = Some(max_chars);
+        self
+    }
+
+    /// Make a redacted copy of `event`.
+    pub fn redact(&self, event: &Event) -> Event {
+        let mut redacted = Event {
+            attributes: event.attributes.clone(),
+            data: None,
+            extensions: Default::default(),
+        };
+        if !self.strip_data {
+            redacted.data = event.data.clone();
+        }
+        if let (Some(max_chars), Some(subject)) = (self.subject_max_chars, event.subject()) {
+            if let Some((end, _)) = subject.char_indices().nth(max_chars) {
+                redacted.set_subject(Some([&subject[..end], "…"].concat()));
+            }
+        }
+        for (name, value) in event.iter_extensions() {
+            let policy = self
+                .extensions
+                .get(name)
+                .unwrap_or(&self.default_extension_policy);
+            let value = match policy {
+                ExtensionPolicy::Keep if event.is_sensitive_extension(name) => {
+                    ExtensionValue::from(SENSITIVE_EXTENSION_MASK)
+                }
+                ExtensionPolicy::Keep => value.clone(),
+                ExtensionPolicy::Hash => hash(self.hash_key.as_ref(), value),
+                ExtensionPolicy::Drop => continue,
+            };
+            redacted.extensions.insert(name.to_string(), value);
+        }
+        redacted
+    }
+}
+
+/// Key of the HMAC, kept out of the [`Debug`] output of the [`Redactor`].
+#[derive(Clone)]
+struct HashKey(Vec<u8>);
+
+impl fmt::Debug for HashKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("HashKey(..)")
+    }
+}
+
+fn hash(key: Option<&HashKey>, value: &ExtensionValue) -> ExtensionValue {
+    let value = value.to_string();
+    ExtensionValue::String(match key {
+        Some(key) => {
+            let mut mac =
+                Hmac::<Sha256>::new_from_slice(&key.0).expect("HMAC accepts keys of any length");
+            mac.update(value.as_bytes());
+            format!("hmac-sha256:{:x}", mac.finalize().into_bytes())
+        }
+        None => format!("sha256:{:x}", Sha256::digest(value.as_bytes())),
+    })
+}
+
+impl Event {
+    /// Make a copy of this event without its data, to write it to logs and error reports.
+    /// Use a [`Redactor`] to redact the extensions and the subject as well.
+    pub fn redacted(&self) -> Event {
+        Redactor::default().redact(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+
+    #[test]
+    fn redacted_strips_data() {
+        let event = fixtures::v10::full_json_data_string_extension();
+
+        let redacted = event.redacted();
+
+        assert_eq!(redacted.data(), None);
+        assert_eq!(redacted.datacontenttype(), event.datacontenttype());
+        assert_eq!(redacted.subject(), event.subject());
+        assert!(redacted.iter_extensions().eq(event.iter_extensions()));
+    }
+
+    #[test]
+    fn redact_extensions_and_subject() {
+        let mut event = fixtures::v10::full_json_data_string_extension();
+        event.set_subject(Some("héllo"));
+        let (string_ext, _) = fixtures::string_extension();
+        let (bool_ext, _) = fixtures::bool_extension();
+        let (int_ext, _) = fixtures::int_extension();
+
+        let redacted = Redactor::new()
+            .strip_data(false)
+            .hash_extension(&string_ext)
+            .drop_extension(&bool_ext)
+            .truncate_subject(2)
+            .redact(&event);
+
+        assert_eq!(redacted.data(), event.data());
+        assert_eq!(redacted.subject(), Some("hé…"));
+        assert_eq!(
+            redacted.extension(&string_ext),
+            Some(&ExtensionValue::from(
+                "sha256:97dfc65f74283f60c606bda3f75a6a6bec3fc1e513b8b40797b5ecb86c824ee2"
+            ))
+        );
+        assert_eq!(redacted.extension(&bool_ext), None);
+        assert_eq!(redacted.extension(&int_ext), event.extension(&int_ext));
+
+        let redacted = Redactor::new()
+            .with_hash_key(b"key".to_vec())
+            .hash_extension(&string_ext)
+            .redact(&event);
+
+        assert_eq!(
+            redacted.extension(&string_ext),
+            Some(&ExtensionValue::from(
+                "hmac-sha256:054832550af15c39ef04b3d925814b68021c17ef9b6fd1f1f603fea529d7e050"
+            ))
+        );
+
+        let redacted = Redactor::new()
+            .default_extension_policy(ExtensionPolicy::Drop)
+            .extension(&int_ext, ExtensionPolicy::Keep)
+            .truncate_subject(5)
+            .redact(&event);
+
+        assert_eq!(redacted.subject(), Some("héllo"));
+        assert_eq!(redacted.iter_extensions().count(), 1);
+    }
+
+    #[test]
+    fn redacted_masks_sensitive_extensions() {
+        let mut event = fixtures::v10::full_json_data_string_extension();
+        event.set_extension("authtoken", "s3cr3t");
+        event.mark_sensitive_extension("authtoken");
+
+        let redacted = event.redacted();
+        assert_eq!(
+            redacted.extension("authtoken"),
+            Some(&ExtensionValue::from(SENSITIVE_EXTENSION_MASK))
+        );
+        for json in [
+            serde_json::to_string(&redacted).unwrap(),
+            serde_json::to_string(&crate::event::WithSecrets(&redacted)).unwrap(),
+        ] {
+            assert!(!json.contains("s3cr3t"));
+            assert!(json.contains(r#""authtoken":"***""#));
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use super::{MessageDefinition, MessageGroup, ValidationError};
+use crate::event::AttributesReader;
+use crate::Event;
+use bytes::Bytes;
+use reqwest_lib as reqwest;
+use serde::de::DeserializeOwned;
+use snafu::Snafu;
+use std::collections::HashMap;
+use url::Url;
+
+/// Represents an error of a [`RegistryClient`]
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Resource {} not found", url))]
+    NotFound { url: Url },
+    #[snafu(display("Error while fetching {}: {}", url, source))]
+    HttpError { url: Url, source: reqwest::Error },
+    #[snafu(display("Invalid response from {}: {}", url, source))]
+    InvalidResponse { url: Url, source: serde_json::Error },
+    #[snafu(display("No message definition for the event type {}", ty))]
+    UnknownEventType { ty: String },
+    #[snafu(display("Invalid event: {}", source))]
+    #[snafu(context(false))]
+    InvalidEvent { source: ValidationError },
+}
+
+/// Result type alias for return values of [`RegistryClient`]
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Client of the message groups and schema groups of an xRegistry, using [`reqwest::Client`].
+///
+/// ```no_run
+/// # use reqwest_lib as reqwest;
+/// use cloudevents::registry::{RegistryClient, Result};
+/// use cloudevents::Event;
+/// use url::Url;
+///
+/// async fn check(event: &Event) -> Result<()> {
+///     let registry = RegistryClient::new(
+///         reqwest::Client::new(),
+///         Url::parse("https://registry.example.com/").unwrap(),
+///     );
+///     registry.validate("orders", event).await
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RegistryClient {
+    client: reqwest::Client,
+    base_url: Url,
+}
+
+impl RegistryClient {
+    /// Create a new [`RegistryClient`] of the registry at `base_url`.
+    pub fn new(client: reqwest::Client, base_url: Url) -> Self {
+        RegistryClient { client, base_url }
+    }
+
+    /// List the message groups, by ID.
+    pub async fn message_groups(&self) -> Result<HashMap<String, MessageGroup>> {
+        self.get_json(&["messagegroups"]).await
+    }
+
+    /// List the message definitions of the group `group_id`, by ID.
+    pub async fn messages(&self, group_id: &str) -> Result<HashMap<String, MessageDefinition>> {
+        self.get_json(&["messagegroups", group_id, "messages"])
+            .await
+    }
+
+    /// Get the message definition `message_id` of the group `group_id`.
+    pub async fn message(&self, group_id: &str, message_id: &str) -> Result<MessageDefinition> {
+        self.get_json(&["messagegroups", group_id, "messages", message_id])
+            .await
+    }
+
+    /// Find the message definition of the group `group_id` for the type of `event`.
+    pub async fn message_for_event(
+        &self,
+        group_id: &str,
+        event: &Event,
+    ) -> Result<Option<MessageDefinition>> {
+        Ok(self
+            .messages(group_id)
+            .await?
+            .into_values()
+            .find(|m| m.event_type() == Some(event.ty())))
+    }
+
+    /// Validate `event` against the message definition of the group `group_id` for its type.
+    pub async fn validate(&self, group_id: &str, event: &Event) -> Result<()> {
+        let definition = self
+            .message_for_event(group_id, event)
+            .await?
+            .ok_or_else(|| Error::UnknownEventType {
+                ty: event.ty().to_string(),
+            })?;
+        Ok(definition.validate(event)?)
+    }
+
+    /// Get the document of the latest version of the schema `schema_id` of the schema group
+    /// `group_id`.
+    pub async fn schema(&self, group_id: &str, schema_id: &str) -> Result<Bytes> {
+        let url = self.url(&["schemagroups", group_id, "schemas", schema_id]);
+        let response = self.get(&url).await?;
+        response
+            .bytes()
+            .await
+            .map_err(|source| Error::HttpError { url, source })
+    }
+
+    fn url(&self, segments: &[&str]) -> Url {
+        let mut url = self.base_url.clone();
+        if let Ok(mut path) = url.path_segments_mut() {
+            path.pop_if_empty().extend(segments);
+        }
+        url
+    }
+
+    async fn get(&self, url: &Url) -> Result<reqwest::Response> {
+        let http_error = |source| Error::HttpError {
+            url: url.clone(),
+            source,
+        };
+        let response = self
+            .client
+            .get(url.clone())
+            .send()
+            .await
+            .map_err(http_error)?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(Error::NotFound { url: url.clone() });
+        }
+        response.error_for_status().map_err(http_error)
+    }
+
+    async fn get_json<T: DeserializeOwned>(&self, segments: &[&str]) -> Result<T> {
+        let url = self.url(segments);
+        let response = self.get(&url).await?;
+        let body = response.bytes().await.map_err(|source| Error::HttpError {
+            url: url.clone(),
+            source,
+        })?;
+        serde_json::from_slice(&body).map_err(|source| Error::InvalidResponse { url, source })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::AttributesWriter;
+    use crate::test::fixtures;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn validate_event() {
+        let _messages = mockito::mock("GET", "/registry/messagegroups/test/messages")
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "test": {
+                        "messageid": "test",
+                        "envelope": "CloudEvents/1.0",
+                        "envelopemetadata": {
+                            "type": {"value": fixtures::ty(), "required": true},
+                            "subject": {"required": true}
+                        }
+                    }
+                })
+                .to_string(),
+            )
+            .create();
+        let _schema = mockito::mock("GET", "/registry/schemagroups/test/schemas/test")
+            .with_body(r#"{"type": "object"}"#)
+            .create();
+        let _missing = mockito::mock("GET", "/registry/messagegroups/missing/messages")
+            .with_status(404)
+            .create();
+        let registry = RegistryClient::new(
+            reqwest::Client::new(),
+            Url::parse(&mockito::server_url())
+                .unwrap()
+                .join("/registry/")
+                .unwrap(),
+        );
+
+        registry
+            .validate("test", &fixtures::v10::full_no_data())
+            .await
+            .unwrap();
+        assert!(matches!(
+            registry.validate("test", &fixtures::v10::minimal()).await,
+            Err(Error::InvalidEvent { .. })
+        ));
+        let mut other = fixtures::v10::minimal();
+        other.set_type("com.example.other");
+        assert!(matches!(
+            registry.validate("test", &other).await,
+            Err(Error::UnknownEventType { .. })
+        ));
+        assert!(matches!(
+            registry.messages("missing").await,
+            Err(Error::NotFound { .. })
+        ));
+        assert_eq!(
+            registry.schema("test", "test").await.unwrap().as_ref(),
+            br#"{"type": "object"}"#
+        );
+    }
+}
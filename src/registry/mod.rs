@@ -0,0 +1,288 @@
+//! This module provides the data model of the message definitions of the
+//! [xRegistry](https://github.com/xregistry/spec) CloudEvents registry, to look up the
+//! definitions of the events at runtime and validate events against them.
+//!
+//! `RegistryClient` (feature `reqwest`) fetches the message groups, the message definitions and
+//! the schemas from a registry.
+//!
+//! ```
+//! use cloudevents::registry::MessageDefinition;
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//! use serde_json::json;
+//!
+//! let definition: MessageDefinition = serde_json::from_value(json!({
+//!     "messageid": "orders.created",
+//!     "envelope": "CloudEvents/1.0",
+//!     "envelopemetadata": {
+//!         "type": {"value": "com.example.order.created", "required": true},
+//!         "source": {"required": true},
+//!         "partitionkey": {"type": "string", "required": true}
+//!     },
+//!     "datacontenttype": "application/json"
+//! }))
+//! .unwrap();
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("com.example.order.created")
+//!     .source("/orders")
+//!     .data("application/json", json!({}))
+//!     .build()
+//!     .unwrap();
+//!
+//! assert!(definition.validate(&event).is_err());
+//! ```
+
+use crate::event::{AttributesReader, SpecVersion};
+use crate::Event;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::collections::HashMap;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "reqwest")))]
+#[cfg(feature = "reqwest")]
+mod client;
+
+#[cfg(feature = "reqwest")]
+pub use client::{Error, RegistryClient, Result};
+
+/// Envelope of the message definitions describing CloudEvents 1.0 events.
+pub const CLOUDEVENTS_ENVELOPE: &str = "CloudEvents/1.0";
+
+/// Group of message definitions.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageGroup {
+    #[serde(rename = "messagegroupid")]
+    pub id: String,
+    #[serde(default)]
+    pub epoch: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Envelope of the messages of the group, e.g. [`CLOUDEVENTS_ENVELOPE`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub envelope: Option<String>,
+    /// Message definitions of the group, when inlined.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub messages: Option<HashMap<String, MessageDefinition>>,
+}
+
+/// Definition of a message, describing the attributes and the data of the events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageDefinition {
+    #[serde(rename = "messageid")]
+    pub id: String,
+    #[serde(default)]
+    pub epoch: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Envelope of the message, e.g. [`CLOUDEVENTS_ENVELOPE`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub envelope: Option<String>,
+    /// Definitions of the context attributes and extensions, by name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub envelopemetadata: HashMap<String, MetadataDefinition>,
+    /// Value of the `datacontenttype` attribute of the events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub datacontenttype: Option<String>,
+    /// Format of the schema of the data, e.g. `JsonSchema/draft-07`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dataschemaformat: Option<String>,
+    /// Reference to the schema of the data, e.g. in a schema group of the registry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dataschemauri: Option<String>,
+}
+
+/// Definition of a context attribute or extension of a [`MessageDefinition`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MetadataDefinition {
+    /// Type of the attribute, e.g. `string`.
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub ty: Option<String>,
+    /// Fixed value of the attribute. Values containing `{` are URI templates, and are not
+    /// checked by [`MessageDefinition::validate`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Represents an event not matching a [`MessageDefinition`]
+#[derive(Debug, Clone, PartialEq, Eq, Snafu)]
+pub enum ValidationError {
+    #[snafu(display("Unsupported envelope {}", envelope))]
+    UnsupportedEnvelope { envelope: String },
+    #[snafu(display("Expected specversion 1.0, found {}", specversion))]
+    WrongSpecVersion { specversion: SpecVersion },
+    #[snafu(display("Missing required attribute {}", name))]
+    MissingAttribute { name: String },
+    #[snafu(display("Expected {} for attribute {}, found {}", expected, name, actual))]
+    WrongValue {
+        name: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl MessageDefinition {
+    /// Value of the `type` attribute of the events, if fixed by the definition.
+    pub fn event_type(&self) -> Option<&str> {
+        self.envelopemetadata
+            .get("type")
+            .and_then(|m| m.value.as_deref())
+    }
+
+    /// Check that `event` has the required attributes with the values set by the definition.
+    pub fn validate(&self, event: &Event) -> std::result::Result<(), ValidationError> {
+        match self.envelope.as_deref() {
+            None | Some(CLOUDEVENTS_ENVELOPE) => {}
+            Some(envelope) => {
+                return Err(ValidationError::UnsupportedEnvelope {
+                    envelope: envelope.to_string(),
+                })
+            }
+        }
+        if event.specversion() != SpecVersion::V10 {
+            return Err(ValidationError::WrongSpecVersion {
+                specversion: event.specversion(),
+            });
+        }
+
+        let mut expected: Vec<(&str, &MetadataDefinition)> = self
+            .envelopemetadata
+            .iter()
+            .map(|(name, m)| (name.as_str(), m))
+            .collect();
+        expected.sort_by_key(|(name, _)| *name);
+        let datacontenttype = self.datacontenttype.as_ref().map(|ct| MetadataDefinition {
+            value: Some(ct.clone()),
+            required: true,
+            ..Default::default()
+        });
+        expected.extend(datacontenttype.iter().map(|m| ("datacontenttype", m)));
+
+        for (name, definition) in expected {
+            let actual = event
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, v)| v.to_string());
+            match (actual, &definition.value) {
+                (None, _) if definition.required => {
+                    return Err(ValidationError::MissingAttribute {
+                        name: name.to_string(),
+                    })
+                }
+                (Some(actual), Some(value)) if !matches_value(name, &actual, value) => {
+                    return Err(ValidationError::WrongValue {
+                        name: name.to_string(),
+                        expected: value.clone(),
+                        actual,
+                    })
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether the `actual` value of the attribute `name` matches the `expected` one, ignoring the
+/// URI templates and the parameters of the `datacontenttype`.
+fn matches_value(name: &str, actual: &str, expected: &str) -> bool {
+    if expected.contains('{') {
+        return true;
+    }
+    if name == "datacontenttype" {
+        let media_type = actual.split(';').next().unwrap_or_default().trim();
+        return media_type.eq_ignore_ascii_case(expected);
+    }
+    actual == expected
+}
+
+impl MessageGroup {
+    /// Get the inlined definition of the messages whose `type` attribute is `ty`.
+    pub fn message_for_type(&self, ty: &str) -> Option<&MessageDefinition> {
+        self.messages
+            .as_ref()?
+            .values()
+            .find(|m| m.event_type() == Some(ty))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::AttributesWriter;
+    use crate::test::fixtures;
+    use serde_json::json;
+
+    fn definition() -> MessageDefinition {
+        serde_json::from_value(json!({
+            "messageid": "test",
+            "epoch": 2,
+            "envelope": "CloudEvents/1.0",
+            "envelopemetadata": {
+                "type": {"value": fixtures::ty(), "required": true},
+                "source": {"value": "{+source}", "required": true},
+                "subject": {"required": true},
+                "stringex": {"type": "string", "required": true}
+            },
+            "datacontenttype": "application/json",
+            "dataschemaformat": "JsonSchema/draft-07",
+            "dataschemauri": "#/schemagroups/test/schemas/test"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn validate() {
+        let definition = definition();
+        assert_eq!(definition.event_type(), Some(fixtures::ty().as_str()));
+
+        assert_eq!(
+            definition.validate(&fixtures::v10::full_json_data_string_extension()),
+            Ok(())
+        );
+        assert_eq!(
+            definition.validate(&fixtures::v10::full_no_data()),
+            Err(ValidationError::MissingAttribute {
+                name: "datacontenttype".to_string()
+            })
+        );
+        assert_eq!(
+            definition.validate(&fixtures::v10::minimal()),
+            Err(ValidationError::MissingAttribute {
+                name: "stringex".to_string()
+            })
+        );
+        assert!(matches!(
+            definition.validate(&fixtures::v03::full_json_data()),
+            Err(ValidationError::WrongSpecVersion { .. })
+        ));
+
+        let mut event = fixtures::v10::full_json_data_string_extension();
+        event.set_type("com.example.other");
+        assert!(matches!(
+            definition.validate(&event),
+            Err(ValidationError::WrongValue { .. })
+        ));
+    }
+
+    #[test]
+    fn message_group() {
+        let group: MessageGroup = serde_json::from_value(json!({
+            "messagegroupid": "test",
+            "envelope": "CloudEvents/1.0",
+            "messages": {"test": serde_json::to_value(definition()).unwrap()}
+        }))
+        .unwrap();
+
+        assert_eq!(group.message_for_type(&fixtures::ty()), Some(&definition()));
+        assert_eq!(group.message_for_type("com.example.other"), None);
+    }
+}
@@ -0,0 +1,372 @@
+//! This module provides [`Router`], which dispatches [`Event`]s to async handlers registered
+//! under `type`/`source` glob patterns or arbitrary [`Filter`]s.
+//!
+//! ```
+//! use cloudevents::router::Router;
+//! use cloudevents::{AttributesReader, Event, EventBuilder, EventBuilderV10};
+//!
+//! # async fn example() -> Result<(), cloudevents::router::Error> {
+//! let router = Router::new()
+//!     .route("com.example.order.*", |event: Event| async move {
+//!         println!("Order event {}", event.id());
+//!         Ok::<(), std::io::Error>(())
+//!     })
+//!     .route_source("/payments/*", |event: Event| async move {
+//!         println!("Payment event {}", event.id());
+//!         Ok::<(), std::io::Error>(())
+//!     })
+//!     .fallback(|event: Event| async move {
+//!         println!("Unhandled event {}", event.id());
+//!         Ok::<(), std::io::Error>(())
+//!     })
+//!     .max_concurrency(16);
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("com.example.order.created")
+//!     .source("http://localhost/")
+//!     .build()
+//!     .unwrap();
+//!
+//! router.dispatch(event).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Handlers registered with [`Router::route_typed`] receive the event data deserialized
+//! to their argument type instead of the [`Event`]:
+//!
+//! ```
+//! use cloudevents::router::Router;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize)]
+//! struct OrderCreated {
+//!     order_id: String,
+//! }
+//!
+//! async fn on_order_created(order: OrderCreated) -> Result<(), std::io::Error> {
+//!     println!("Order {} created", order.order_id);
+//!     Ok(())
+//! }
+//!
+//! let router = Router::new().route_typed("com.example.order.created", on_order_created);
+//! ```
+//!
+//! Routes are evaluated in registration order and the event is dispatched to the first
+//! matching handler. The router can be cloned cheaply to dispatch events from several tasks,
+//! the clones sharing the concurrency limit.
+
+use crate::event::AttributesReader;
+use crate::filter::{Filter, FilterExt};
+use crate::Event;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use snafu::Snafu;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+mod typed;
+
+pub use typed::decode_data;
+
+/// Represents an error while dispatching an [`Event`]
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("No route for event {} of type {}", id, ty))]
+    NoRoute { id: String, ty: String },
+    #[snafu(display("Error while handling the event: {}", source))]
+    HandlerError {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[snafu(display("Event {} of type {} has no data", id, ty))]
+    MissingData { id: String, ty: String },
+    #[snafu(display("Cannot decode the data of event {} of type {}: {}", id, ty, source))]
+    InvalidData {
+        id: String,
+        ty: String,
+        source: serde_json::Error,
+    },
+}
+
+/// Result type alias for return values of the [`Router`]
+pub type Result<T> = std::result::Result<T, Error>;
+
+type BoxHandler = Arc<dyn Fn(Event) -> BoxFuture<'static, Result<()>> + Send + Sync>;
+
+fn box_handler<H, Fut, E>(handler: H) -> BoxHandler
+where
+    H: Fn(Event) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = std::result::Result<(), E>> + Send + 'static,
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    Arc::new(move |event| {
+        handler(event)
+            .map(|r| r.map_err(|e| Error::HandlerError { source: e.into() }))
+            .boxed()
+    })
+}
+
+#[derive(Clone)]
+struct Route {
+    filter: Arc<dyn Filter>,
+    handler: BoxHandler,
+}
+
+/// Dispatches events to the handler of the first matching route.
+#[derive(Clone, Default)]
+pub struct Router {
+    routes: Arc<Vec<Route>>,
+    fallback: Option<BoxHandler>,
+    semaphore: Option<Arc<Semaphore>>,
+}
+
+impl Router {
+    /// Create a new [`Router`] without routes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Route the events whose `type` matches the glob `pattern` to `handler`.
+    pub fn route<H, Fut, E>(self, pattern: &str, handler: H) -> Self
+    where
+        H: Fn(Event) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = std::result::Result<(), E>> + Send + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let pattern = Glob::new(pattern);
+        self.route_filter(move |e: &Event| pattern.matches(e.ty()), handler)
+    }
+
+    /// Route the events whose `source` matches the glob `pattern` to `handler`.
+    pub fn route_source<H, Fut, E>(self, pattern: &str, handler: H) -> Self
+    where
+        H: Fn(Event) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = std::result::Result<(), E>> + Send + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let pattern = Glob::new(pattern);
+        self.route_filter(
+            move |e: &Event| pattern.matches(e.source().as_str()),
+            handler,
+        )
+    }
+
+    /// Route the events whose `type` and `source` match the glob patterns to `handler`.
+    pub fn route_type_source<H, Fut, E>(
+        self,
+        type_pattern: &str,
+        source_pattern: &str,
+        handler: H,
+    ) -> Self
+    where
+        H: Fn(Event) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = std::result::Result<(), E>> + Send + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let type_pattern = Glob::new(type_pattern);
+        let source_pattern = Glob::new(source_pattern);
+        self.route_filter(
+            (move |e: &Event| type_pattern.matches(e.ty()))
+                .and(move |e: &Event| source_pattern.matches(e.source().as_str())),
+            handler,
+        )
+    }
+
+    /// Route the events matching `filter` to `handler`.
+    pub fn route_filter<F, H, Fut, E>(self, filter: F, handler: H) -> Self
+    where
+        F: Filter + 'static,
+        H: Fn(Event) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = std::result::Result<(), E>> + Send + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.push_route(filter, box_handler(handler))
+    }
+
+    fn push_route<F: Filter + 'static>(mut self, filter: F, handler: BoxHandler) -> Self {
+        Arc::make_mut(&mut self.routes).push(Route {
+            filter: Arc::new(filter),
+            handler,
+        });
+        self
+    }
+
+    /// Dispatch the events matching no route to `handler`, instead of failing with
+    /// [`Error::NoRoute`].
+    pub fn fallback<H, Fut, E>(mut self, handler: H) -> Self
+    where
+        H: Fn(Event) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = std::result::Result<(), E>> + Send + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.fallback = Some(box_handler(handler));
+        self
+    }
+
+    /// Limit the number of handlers running at the same time. Further dispatches wait for a
+    /// running handler to complete.
+    pub fn max_concurrency(mut self, max: usize) -> Self {
+        self.semaphore = Some(Arc::new(Semaphore::new(max)));
+        self
+    }
+
+    /// Dispatch `event` to the handler of the first matching route, or to the fallback handler.
+    pub async fn dispatch(&self, event: Event) -> Result<()> {
+        let handler = match self.routes.iter().find(|r| r.filter.matches(&event)) {
+            Some(route) => route.handler.clone(),
+            None => match &self.fallback {
+                Some(fallback) => fallback.clone(),
+                None => {
+                    return Err(Error::NoRoute {
+                        id: event.id().to_string(),
+                        ty: event.ty().to_string(),
+                    })
+                }
+            },
+        };
+
+        let _permit = match &self.semaphore {
+            Some(s) => Some(s.acquire().await.expect("the semaphore is never closed")),
+            None => None,
+        };
+        handler(event).await
+    }
+}
+
+/// Glob pattern where `*` matches any sequence of characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Glob(Vec<String>);
+
+impl Glob {
+    fn new(pattern: &str) -> Self {
+        Glob(pattern.split('*').map(String::from).collect())
+    }
+
+    fn matches(&self, value: &str) -> bool {
+        let (first, rest) = self
+            .0
+            .split_first()
+            .expect("split yields at least one part");
+        let mut value = match value.strip_prefix(first.as_str()) {
+            Some(v) => v,
+            None => return false,
+        };
+        let (last, middle) = match rest.split_last() {
+            Some(parts) => parts,
+            None => return value.is_empty(),
+        };
+        for part in middle {
+            match value.find(part.as_str()) {
+                Some(i) => value = &value[i + part.len()..],
+                None => return false,
+            }
+        }
+        value.ends_with(last.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use crate::AttributesWriter;
+    use std::io;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn glob() {
+        assert!(Glob::new("com.example.*").matches("com.example.order"));
+        assert!(Glob::new("*.created").matches("com.example.created"));
+        assert!(Glob::new("com.*.order.*").matches("com.example.order.created"));
+        assert!(Glob::new("*").matches(""));
+        assert!(Glob::new("exact").matches("exact"));
+        assert!(Glob::new("a*a").matches("aa"));
+        assert!(!Glob::new("a*a").matches("a"));
+        assert!(!Glob::new("exact").matches("exactly"));
+        assert!(!Glob::new("com.*.order").matches("com.example.payment"));
+    }
+
+    fn counting_handler(
+        counter: &Arc<AtomicUsize>,
+    ) -> impl Fn(Event) -> BoxFuture<'static, io::Result<()>> + Send + Sync + 'static {
+        let counter = counter.clone();
+        move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async { Ok(()) }.boxed()
+        }
+    }
+
+    #[tokio::test]
+    async fn dispatch() {
+        let by_type = Arc::new(AtomicUsize::new(0));
+        let by_source = Arc::new(AtomicUsize::new(0));
+        let fallback = Arc::new(AtomicUsize::new(0));
+        let router = Router::new()
+            .route("test_event.*", counting_handler(&by_type))
+            .route_source("http://localhost/*", counting_handler(&by_source))
+            .fallback(counting_handler(&fallback));
+
+        let mut event = fixtures::v10::minimal();
+        router.dispatch(event.clone()).await.unwrap();
+        event.set_type("other");
+        router.dispatch(event.clone()).await.unwrap();
+        event.set_source("http://example.com/");
+        router.dispatch(event).await.unwrap();
+
+        assert_eq!(by_type.load(Ordering::SeqCst), 1);
+        assert_eq!(by_source.load(Ordering::SeqCst), 1);
+        assert_eq!(fallback.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        let router = Router::new().route_type_source("*", "http://localhost/", |_| async {
+            Err(io::Error::other("failed"))
+        });
+
+        let mut event = fixtures::v10::minimal();
+        assert!(matches!(
+            router.dispatch(event.clone()).await,
+            Err(Error::HandlerError { .. })
+        ));
+        event.set_source("http://example.com/");
+        assert!(matches!(
+            router.dispatch(event).await,
+            Err(Error::NoRoute { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn max_concurrency() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+        let (r, m) = (running.clone(), max_running.clone());
+        let router = Router::new()
+            .route("*", move |_| {
+                let (running, max_running) = (r.clone(), m.clone());
+                async move {
+                    let current = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(current, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    Ok::<(), io::Error>(())
+                }
+            })
+            .max_concurrency(2);
+
+        let tasks: Vec<_> = (0..6)
+            .map(|_| {
+                let router = router.clone();
+                tokio::spawn(async move { router.dispatch(fixtures::v10::minimal()).await })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+
+        assert_eq!(max_running.load(Ordering::SeqCst), 2);
+    }
+}
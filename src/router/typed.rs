@@ -0,0 +1,148 @@
+use super::{BoxHandler, Error, Glob, Result, Router};
+use crate::event::{AttributesReader, Data};
+use crate::filter::Filter;
+use crate::Event;
+use futures::FutureExt;
+use serde::de::DeserializeOwned;
+use std::future::Future;
+use std::sync::Arc;
+
+/// Deserialize the data of `event` to `T`.
+///
+/// JSON data is deserialized from its value, string and binary data from their JSON text.
+pub fn decode_data<T: DeserializeOwned>(event: &Event) -> Result<T> {
+    let result = match event.data() {
+        Some(Data::Json(v)) => T::deserialize(v),
+        Some(Data::String(s)) => serde_json::from_str(s),
+        Some(Data::Binary(b)) => serde_json::from_slice(b),
+        Some(Data::Bytes(b)) => serde_json::from_slice(b),
+        None => {
+            return Err(Error::MissingData {
+                id: event.id().to_string(),
+                ty: event.ty().to_string(),
+            })
+        }
+    };
+    result.map_err(|source| Error::InvalidData {
+        id: event.id().to_string(),
+        ty: event.ty().to_string(),
+        source,
+    })
+}
+
+fn box_typed_handler<T, H, Fut, E>(handler: H) -> BoxHandler
+where
+    T: DeserializeOwned + Send + 'static,
+    H: Fn(T) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = std::result::Result<(), E>> + Send + 'static,
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    Arc::new(move |event| match decode_data::<T>(&event) {
+        Ok(data) => handler(data)
+            .map(|r| r.map_err(|e| Error::HandlerError { source: e.into() }))
+            .boxed(),
+        Err(e) => futures::future::ready(Err(e)).boxed(),
+    })
+}
+
+impl Router {
+    /// Route the events whose `type` matches the glob `pattern` to `handler`, which receives
+    /// the event data deserialized to `T`.
+    ///
+    /// If the event has no data, or its data cannot be deserialized to `T`, the handler is not
+    /// called and the dispatch fails with [`Error::MissingData`] or [`Error::InvalidData`].
+    pub fn route_typed<T, H, Fut, E>(self, pattern: &str, handler: H) -> Self
+    where
+        T: DeserializeOwned + Send + 'static,
+        H: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = std::result::Result<(), E>> + Send + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let pattern = Glob::new(pattern);
+        self.push_route(
+            move |e: &Event| pattern.matches(e.ty()),
+            box_typed_handler(handler),
+        )
+    }
+
+    /// Route the events matching `filter` to `handler`, which receives the event data
+    /// deserialized to `T`. See [`Router::route_typed`].
+    pub fn route_filter_typed<F, T, H, Fut, E>(self, filter: F, handler: H) -> Self
+    where
+        F: Filter + 'static,
+        T: DeserializeOwned + Send + 'static,
+        H: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = std::result::Result<(), E>> + Send + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        self.push_route(filter, box_typed_handler(handler))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use crate::{EventBuilder, EventBuilderV10};
+    use serde::Deserialize;
+    use std::io;
+    use std::sync::Mutex;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Hello {
+        hello: String,
+    }
+
+    #[test]
+    fn decode() {
+        let expected = Hello {
+            hello: "world".to_string(),
+        };
+
+        assert_eq!(
+            decode_data::<Hello>(&fixtures::v10::full_json_data()).unwrap(),
+            expected
+        );
+        assert_eq!(
+            decode_data::<Hello>(&fixtures::v10::full_binary_json_data_string_extension()).unwrap(),
+            expected
+        );
+        assert!(matches!(
+            decode_data::<Hello>(&fixtures::v10::full_xml_string_data()),
+            Err(Error::InvalidData { .. })
+        ));
+        assert!(matches!(
+            decode_data::<Hello>(&fixtures::v10::minimal()),
+            Err(Error::MissingData { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn dispatch_typed() {
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let r = received.clone();
+        let router = Router::new().route_typed("test_event.*", move |hello: Hello| {
+            r.lock().unwrap().push(hello.hello);
+            async { Ok::<(), io::Error>(()) }
+        });
+
+        router
+            .dispatch(fixtures::v10::full_json_data())
+            .await
+            .unwrap();
+
+        let mismatch = EventBuilderV10::new()
+            .id("0002")
+            .ty("test_event.test_application")
+            .source("http://localhost/")
+            .data("application/json", serde_json::json!({"hello": 1}))
+            .build()
+            .unwrap();
+        assert!(matches!(
+            router.dispatch(mismatch).await,
+            Err(Error::InvalidData { ref id, .. }) if id == "0002"
+        ));
+
+        assert_eq!(*received.lock().unwrap(), vec!["world".to_string()]);
+    }
+}
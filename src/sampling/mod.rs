@@ -0,0 +1,341 @@
+//! This module samples [`Event`]s, forwarding only a fraction of the events selected by
+//! [`Filter`]s, for high-volume topics where only a sample is needed downstream.
+//!
+//! The forwarded events record the sampling rate in the
+//! [`sampledrate`](https://github.com/cloudevents/spec/blob/main/cloudevents/extensions/sampledrate.md)
+//! extension: an event forwarded with a rate of 10 stands for 10 similar events.
+//!
+//! ```
+//! use cloudevents::filter::{exact, prefix};
+//! use cloudevents::sampling::Sampler;
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//!
+//! let sampler = Sampler::new()
+//!     // Forward all the errors
+//!     .rule(exact("type", "com.example.telemetry.error"), 1)
+//!     // and 1% of the other telemetry events
+//!     .rule(prefix("type", "com.example.telemetry."), 100);
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("com.example.telemetry.error")
+//!     .source("http://localhost/")
+//!     .build()
+//!     .unwrap();
+//!
+//! assert!(sampler.sample(event).is_some());
+//! ```
+//!
+//! The events are selected by hashing their `source` and `id`, so the decision is the same for
+//! the duplicates of an event and across the instances of a service.
+//!
+//! [`Sampled`] samples the events received from an
+//! [`EventSource`](crate::transport::EventSource) or sent to an
+//! [`EventSink`](crate::transport::EventSink), and [`sample`] the events of a stream.
+
+use crate::event::{AttributesReader, ExtensionValue};
+use crate::filter::Filter;
+use crate::transport::{self, EventSink, EventSource};
+use crate::Event;
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use std::fmt;
+
+/// Name of the extension recording the sampling rate of an event.
+pub const SAMPLED_RATE_EXTENSION: &str = "sampledrate";
+
+/// Forwards 1 out of `rate` events, according to the first rule matching each event.
+///
+/// The events matching no rule are all forwarded, unless [`Sampler::default_rate`] is set.
+pub struct Sampler {
+    rules: Vec<(Box<dyn Filter>, u32)>,
+    default_rate: u32,
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Sampler {
+            rules: Vec::new(),
+            default_rate: 1,
+        }
+    }
+}
+
+impl fmt::Debug for Sampler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sampler")
+            .field(
+                "rates",
+                &self.rules.iter().map(|(_, rate)| rate).collect::<Vec<_>>(),
+            )
+            .field("default_rate", &self.default_rate)
+            .finish()
+    }
+}
+
+impl Sampler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forward 1 out of `rate` of the events matching `filter`. A rate of 1 forwards all of
+    /// them.
+    pub fn rule(mut self, filter: impl Filter + 'static, rate: u32) -> Self {
+        self.rules.push((Box::new(filter), rate.max(1)));
+        self
+    }
+
+    /// Forward 1 out of `rate` of the events matching no rule. Defaults to 1.
+    pub fn default_rate(mut self, rate: u32) -> Self {
+        self.default_rate = rate.max(1);
+        self
+    }
+
+    /// The sampling rate of `event`.
+    pub fn rate(&self, event: &Event) -> u32 {
+        self.rules
+            .iter()
+            .find(|(filter, _)| filter.matches(event))
+            .map_or(self.default_rate, |(_, rate)| *rate)
+    }
+
+    /// Returns `event` if it is selected, with its `sampledrate` multiplied by the sampling
+    /// rate, or `None` if it is dropped.
+    pub fn sample(&self, mut event: Event) -> Option<Event> {
+        let rate = self.rate(&event);
+        if rate == 1 {
+            return Some(event);
+        }
+        if !hash(&event).is_multiple_of(u64::from(rate)) {
+            return None;
+        }
+        let previous = match event.extension(SAMPLED_RATE_EXTENSION) {
+            Some(ExtensionValue::Integer(previous)) if *previous > 0 => *previous,
+            _ => 1,
+        };
+        event.set_extension(
+            SAMPLED_RATE_EXTENSION,
+            previous.saturating_mul(i64::from(rate)),
+        );
+        Some(event)
+    }
+}
+
+/// 64 bit FNV-1a hash of the `source` and `id` of `event`, stable across processes.
+fn hash(event: &Event) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let source = event.source().as_bytes();
+    let id = event.id().as_bytes();
+    for byte in source.iter().chain(&[0]).chain(id) {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Forward the events of `events` selected by `sampler`.
+pub fn sample<S>(events: S, sampler: Sampler) -> impl Stream<Item = Event>
+where
+    S: Stream<Item = Event>,
+{
+    events.filter_map(move |event| futures::future::ready(sampler.sample(event)))
+}
+
+/// Wrapper of an [`EventSource`] or an [`EventSink`] forwarding only the events selected by a
+/// [`Sampler`].
+///
+/// The events dropped from a source are acknowledged, so they are not redelivered.
+///
+/// ```
+/// use cloudevents::filter::prefix;
+/// use cloudevents::sampling::{Sampled, Sampler};
+/// use cloudevents::transport::EventSink;
+///
+/// fn telemetry_sink(sink: impl EventSink) -> impl EventSink {
+///     Sampled::new(
+///         sink,
+///         Sampler::new().rule(prefix("type", "com.example.telemetry."), 10),
+///     )
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Sampled<T> {
+    inner: T,
+    sampler: Sampler,
+}
+
+impl<T> Sampled<T> {
+    /// Create a new [`Sampled`] source or sink, sampling the events of `inner` with `sampler`.
+    pub fn new(inner: T, sampler: Sampler) -> Self {
+        Sampled { inner, sampler }
+    }
+
+    /// The wrapped source or sink.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+}
+
+#[async_trait]
+impl<S: EventSink> EventSink for Sampled<S> {
+    /// Send `event` to the wrapped sink if it is selected.
+    async fn send(&self, event: Event) -> transport::Result<()> {
+        match self.sampler.sample(event) {
+            Some(event) => self.inner.send(event).await,
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl<Src: EventSource> EventSource for Sampled<Src> {
+    type Ack = Src::Ack;
+
+    /// Receive the next selected event, acknowledging the dropped ones.
+    async fn receive(&mut self) -> Option<transport::Result<(Event, Src::Ack)>> {
+        loop {
+            let (event, ack) = match self.inner.receive().await? {
+                Ok(received) => received,
+                Err(e) => return Some(Err(e)),
+            };
+            if let Some(event) = self.sampler.sample(event) {
+                return Some(Ok((event, ack)));
+            }
+            if let Err(e) = self.inner.ack(ack).await {
+                return Some(Err(e));
+            }
+        }
+    }
+
+    async fn ack(&mut self, ack: Src::Ack) -> transport::Result<()> {
+        self.inner.ack(ack).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::exact;
+    use crate::test::fixtures;
+    use crate::AttributesWriter;
+    use futures::stream;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct VecSink(Mutex<Vec<Event>>);
+
+    #[async_trait]
+    impl EventSink for VecSink {
+        async fn send(&self, event: Event) -> transport::Result<()> {
+            self.0.lock().unwrap().push(event);
+            Ok(())
+        }
+    }
+
+    struct VecSource {
+        events: Vec<Event>,
+        acked: Vec<usize>,
+    }
+
+    #[async_trait]
+    impl EventSource for VecSource {
+        type Ack = usize;
+
+        async fn receive(&mut self) -> Option<transport::Result<(Event, usize)>> {
+            let event = self.events.pop()?;
+            Some(Ok((event, self.events.len())))
+        }
+
+        async fn ack(&mut self, ack: usize) -> transport::Result<()> {
+            self.acked.push(ack);
+            Ok(())
+        }
+    }
+
+    fn events(ty: &str, count: usize) -> Vec<Event> {
+        (0..count)
+            .map(|i| {
+                let mut event = fixtures::v10::minimal();
+                event.set_id(i.to_string());
+                event.set_type(ty);
+                event
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rules() {
+        let sampler = Sampler::new()
+            .rule(exact("type", "error"), 1)
+            .rule(exact("type", "metric"), 10)
+            .default_rate(0);
+
+        let errors: Vec<_> = events("error", 100)
+            .into_iter()
+            .filter_map(|e| sampler.sample(e))
+            .collect();
+        assert_eq!(errors.len(), 100);
+        assert!(errors[0].extension(SAMPLED_RATE_EXTENSION).is_none());
+
+        let metrics: Vec<_> = events("metric", 1000)
+            .into_iter()
+            .filter_map(|e| sampler.sample(e))
+            .collect();
+        assert!(metrics.len() > 50 && metrics.len() < 150);
+        assert_eq!(
+            metrics[0].extension(SAMPLED_RATE_EXTENSION),
+            Some(&ExtensionValue::Integer(10))
+        );
+
+        // Deterministic, and multiplied with the rate of a previous sampling
+        let mut resampled = metrics[0].clone();
+        resampled.set_extension(SAMPLED_RATE_EXTENSION, 3);
+        assert_eq!(
+            sampler
+                .sample(resampled)
+                .unwrap()
+                .extension(SAMPLED_RATE_EXTENSION),
+            Some(&ExtensionValue::Integer(30))
+        );
+
+        // The default rate of 0 is raised to 1
+        assert_eq!(sampler.rate(&fixtures::v10::minimal()), 1);
+    }
+
+    #[tokio::test]
+    async fn adapters() {
+        let sampler = || Sampler::new().default_rate(4);
+        let input = events("metric", 200);
+        let expected: Vec<_> = input
+            .iter()
+            .filter_map(|e| sampler().sample(e.clone()))
+            .collect();
+
+        let streamed: Vec<_> = sample(stream::iter(input.clone()), sampler())
+            .collect()
+            .await;
+        assert_eq!(streamed, expected);
+
+        let sink = Sampled::new(VecSink::default(), sampler());
+        for event in input.clone() {
+            sink.send(event).await.unwrap();
+        }
+        assert_eq!(*sink.inner().0.lock().unwrap(), expected);
+
+        let mut source = Sampled::new(
+            VecSource {
+                events: input.into_iter().rev().collect(),
+                acked: Vec::new(),
+            },
+            sampler(),
+        );
+        let mut received = Vec::new();
+        while let Some(result) = source.receive().await {
+            let (event, ack) = result.unwrap();
+            received.push(event);
+            source.ack(ack).await.unwrap();
+        }
+        assert_eq!(received, expected);
+        assert_eq!(source.inner().acked.len(), 200);
+    }
+}
@@ -0,0 +1,85 @@
+use super::{Error, Result, Schema, SchemaResolver};
+use async_trait::async_trait;
+use reqwest_lib as reqwest;
+use url::Url;
+
+/// [`SchemaResolver`] fetching the schemas with an HTTP GET, using [`reqwest::Client`].
+///
+/// Wrap it in a [`CachedSchemaResolver`](super::CachedSchemaResolver) to avoid fetching the
+/// schema of every event.
+#[derive(Debug, Clone, Default)]
+pub struct HttpSchemaResolver {
+    client: reqwest::Client,
+}
+
+impl HttpSchemaResolver {
+    /// Create a new [`HttpSchemaResolver`] using `client`.
+    pub fn new(client: reqwest::Client) -> Self {
+        HttpSchemaResolver { client }
+    }
+}
+
+#[async_trait]
+impl SchemaResolver for HttpSchemaResolver {
+    async fn resolve(&self, uri: &Url) -> Result<Schema> {
+        let http_error = |source| Error::HttpError {
+            uri: uri.clone(),
+            source,
+        };
+        let response = self
+            .client
+            .get(uri.clone())
+            .send()
+            .await
+            .map_err(http_error)?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(Error::NotFound { uri: uri.clone() });
+        }
+        let response = response.error_for_status().map_err(http_error)?;
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        let content = response.bytes().await.map_err(http_error)?;
+        Ok(Schema {
+            content_type,
+            content,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn fetch_schema() {
+        let m = mockito::mock("GET", "/schemas/order.json")
+            .with_header("content-type", "application/schema+json")
+            .with_body(r#"{"type": "object"}"#)
+            .create();
+        let _missing = mockito::mock("GET", "/schemas/missing.json")
+            .with_status(404)
+            .create();
+        let url = Url::parse(&mockito::server_url()).unwrap();
+
+        let schema = HttpSchemaResolver::default()
+            .resolve(&url.join("/schemas/order.json").unwrap())
+            .await
+            .unwrap();
+
+        m.assert();
+        assert_eq!(
+            schema.content_type.as_deref(),
+            Some("application/schema+json")
+        );
+        assert_eq!(schema.content.as_ref(), br#"{"type": "object"}"#);
+        assert!(matches!(
+            HttpSchemaResolver::default()
+                .resolve(&url.join("/schemas/missing.json").unwrap())
+                .await,
+            Err(Error::NotFound { .. })
+        ));
+    }
+}
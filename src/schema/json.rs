@@ -0,0 +1,74 @@
+use super::DataValidator;
+use crate::event::Data;
+use jsonschema_lib as jsonschema;
+
+/// [`DataValidator`] checking that the data is a JSON document valid against a JSON Schema.
+///
+/// The string and binary data are parsed as JSON before being validated.
+#[derive(Debug)]
+pub struct JsonSchemaValidator {
+    validator: jsonschema::Validator,
+}
+
+impl JsonSchemaValidator {
+    /// Compile `schema`, failing if it isn't a valid JSON Schema.
+    pub fn new(
+        schema: &serde_json::Value,
+    ) -> Result<Self, Box<jsonschema::ValidationError<'static>>> {
+        Ok(JsonSchemaValidator {
+            validator: jsonschema::validator_for(schema).map_err(Box::new)?,
+        })
+    }
+}
+
+impl DataValidator for JsonSchemaValidator {
+    fn validate(&self, data: &Data) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let parsed;
+        let instance = match data {
+            Data::Json(value) => value,
+            Data::String(s) => {
+                parsed = serde_json::from_str(s)?;
+                &parsed
+            }
+            Data::Binary(_) | Data::Bytes(_) => {
+                parsed = serde_json::from_slice(data.as_bytes().unwrap_or_default())?;
+                &parsed
+            }
+        };
+        self.validator
+            .validate(instance)
+            .map_err(|e| e.to_string().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn validate() {
+        let validator = JsonSchemaValidator::new(&json!({
+            "type": "object",
+            "required": ["id"],
+            "properties": {"id": {"type": "integer"}}
+        }))
+        .unwrap();
+
+        assert!(validator.validate(&Data::Json(json!({"id": 1}))).is_ok());
+        assert!(validator
+            .validate(&Data::String(r#"{"id": 2}"#.to_string()))
+            .is_ok());
+        assert!(validator
+            .validate(&Data::Binary(br#"{"id": 3}"#.to_vec()))
+            .is_ok());
+
+        assert!(validator.validate(&Data::Json(json!({"id": "1"}))).is_err());
+        assert!(validator.validate(&Data::String("id".to_string())).is_err());
+    }
+
+    #[test]
+    fn invalid_schema() {
+        assert!(JsonSchemaValidator::new(&json!({"type": 42})).is_err());
+    }
+}
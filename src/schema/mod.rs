@@ -0,0 +1,295 @@
+//! This module provides the [`SchemaResolver`] trait, to fetch the schema referenced by the
+//! `dataschema` attribute of an [`Event`].
+//!
+//! [`CachedSchemaResolver`] keeps the resolved schemas for a TTL, so looking up the schema of
+//! every event doesn't hit a remote registry each time, while `HttpSchemaResolver` (feature
+//! `reqwest`) fetches the schemas with an HTTP GET.
+//!
+//! [`SchemaRegistry`] instead holds the schemas known in advance, by event type, to validate
+//! the data of the events while building them with `data_with_registry`.
+//!
+//! ```
+//! use cloudevents::schema::{CachedSchemaResolver, Schema, SchemaResolver};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//! use std::collections::HashMap;
+//! use std::time::Duration;
+//! use url::Url;
+//!
+//! # async fn example() -> cloudevents::schema::Result<()> {
+//! let uri = Url::parse("https://example.com/schemas/order.json").unwrap();
+//! let schemas = HashMap::from([(uri.clone(), Schema::new(r#"{"type": "object"}"#))]);
+//! let resolver = CachedSchemaResolver::new(schemas, Duration::from_secs(300));
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("com.example.order.created")
+//!     .source("http://localhost/")
+//!     .data_with_schema("application/json", uri, serde_json::json!({}))
+//!     .build()
+//!     .unwrap();
+//!
+//! let schema = resolver.resolve_dataschema(&event).await?.unwrap();
+//! assert_eq!(schema.content.as_ref(), br#"{"type": "object"}"#);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::event::AttributesReader;
+use crate::Event;
+use async_trait::async_trait;
+use bytes::Bytes;
+use snafu::Snafu;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use url::Url;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "reqwest")))]
+#[cfg(feature = "reqwest")]
+mod http;
+
+#[cfg(feature = "reqwest")]
+pub use http::HttpSchemaResolver;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "jsonschema")))]
+#[cfg(feature = "jsonschema")]
+mod json;
+mod registry;
+
+#[cfg(feature = "jsonschema")]
+pub use json::JsonSchemaValidator;
+pub use registry::{DataValidator, RegisteredSchema, SchemaRegistry};
+
+/// Represents an error of a [`SchemaResolver`]
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Schema {} not found", uri))]
+    NotFound { uri: Url },
+    #[cfg(feature = "reqwest")]
+    #[snafu(display("Error while fetching schema {}: {}", uri, source))]
+    HttpError {
+        uri: Url,
+        source: reqwest_lib::Error,
+    },
+    #[snafu(display("Error while resolving schema {}: {}", uri, source))]
+    Other {
+        uri: Url,
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+/// Result type alias for return values of [`SchemaResolver`]
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// A schema document, as fetched by a [`SchemaResolver`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schema {
+    /// Media type of the document, if known.
+    pub content_type: Option<String>,
+    pub content: Bytes,
+}
+
+impl Schema {
+    /// Create a new [`Schema`] with an unknown content type.
+    pub fn new(content: impl Into<Bytes>) -> Self {
+        Schema {
+            content_type: None,
+            content: content.into(),
+        }
+    }
+}
+
+/// Resolver of the schemas referenced by URI.
+#[async_trait]
+pub trait SchemaResolver: Send + Sync {
+    /// Fetch the schema identified by `uri`.
+    async fn resolve(&self, uri: &Url) -> Result<Schema>;
+
+    /// Fetch the schema referenced by the `dataschema` (or `schemaurl` in v0.3) attribute of
+    /// `event`, returning `None` if it isn't set.
+    async fn resolve_dataschema(&self, event: &Event) -> Result<Option<Schema>> {
+        match event.dataschema() {
+            Some(uri) => self.resolve(uri).await.map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+#[async_trait]
+impl<R: SchemaResolver + ?Sized> SchemaResolver for Arc<R> {
+    async fn resolve(&self, uri: &Url) -> Result<Schema> {
+        self.as_ref().resolve(uri).await
+    }
+}
+
+/// [`SchemaResolver`] serving a fixed set of schemas, e.g. embedded in the application.
+#[async_trait]
+impl SchemaResolver for HashMap<Url, Schema> {
+    async fn resolve(&self, uri: &Url) -> Result<Schema> {
+        self.get(uri)
+            .cloned()
+            .ok_or_else(|| Error::NotFound { uri: uri.clone() })
+    }
+}
+
+/// [`SchemaResolver`] caching the schemas resolved by another resolver for `ttl`.
+///
+/// Errors are not cached, so the next lookup of a failed schema hits the inner resolver again.
+#[derive(Debug)]
+pub struct CachedSchemaResolver<R> {
+    inner: R,
+    ttl: Duration,
+    cache: Mutex<HashMap<Url, (Instant, Schema)>>,
+}
+
+impl<R: SchemaResolver> CachedSchemaResolver<R> {
+    /// Create a new [`CachedSchemaResolver`] caching the schemas resolved by `inner` for `ttl`.
+    pub fn new(inner: R, ttl: Duration) -> Self {
+        CachedSchemaResolver {
+            inner,
+            ttl,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The resolver used on cache misses.
+    pub fn inner(&self) -> &R {
+        &self.inner
+    }
+
+    /// Remove `uri` from the cache, so the next lookup resolves it again.
+    pub fn invalidate(&self, uri: &Url) {
+        self.cache.lock().unwrap().remove(uri);
+    }
+
+    /// Remove all the schemas from the cache.
+    pub fn clear(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    fn cached(&self, uri: &Url, now: Instant) -> Option<Schema> {
+        let cache = self.cache.lock().unwrap();
+        cache
+            .get(uri)
+            .filter(|(fetched, _)| now.duration_since(*fetched) < self.ttl)
+            .map(|(_, schema)| schema.clone())
+    }
+
+    fn insert(&self, uri: &Url, schema: Schema, now: Instant) {
+        let mut cache = self.cache.lock().unwrap();
+        cache.retain(|_, (fetched, _)| now.duration_since(*fetched) < self.ttl);
+        cache.insert(uri.clone(), (now, schema));
+    }
+
+    async fn resolve_at(&self, uri: &Url, now: Instant) -> Result<Schema> {
+        if let Some(schema) = self.cached(uri, now) {
+            return Ok(schema);
+        }
+        let schema = self.inner.resolve(uri).await?;
+        self.insert(uri, schema.clone(), now);
+        Ok(schema)
+    }
+}
+
+#[async_trait]
+impl<R: SchemaResolver> SchemaResolver for CachedSchemaResolver<R> {
+    async fn resolve(&self, uri: &Url) -> Result<Schema> {
+        self.resolve_at(uri, Instant::now()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct CountingResolver {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl SchemaResolver for CountingResolver {
+        async fn resolve(&self, uri: &Url) -> Result<Schema> {
+            let calls = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if uri.path().ends_with("missing.json") {
+                return Err(Error::NotFound { uri: uri.clone() });
+            }
+            Ok(Schema::new(format!("{} #{}", uri, calls)))
+        }
+    }
+
+    fn uri(name: &str) -> Url {
+        Url::parse("https://example.com/schemas/")
+            .unwrap()
+            .join(name)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn cache_hit_and_expiration() {
+        let resolver =
+            CachedSchemaResolver::new(CountingResolver::default(), Duration::from_secs(60));
+        let now = Instant::now();
+
+        let first = resolver.resolve_at(&uri("a.json"), now).await.unwrap();
+        let second = resolver
+            .resolve_at(&uri("a.json"), now + Duration::from_secs(30))
+            .await
+            .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(resolver.inner().calls.load(Ordering::SeqCst), 1);
+
+        let expired = resolver
+            .resolve_at(&uri("a.json"), now + Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_ne!(first, expired);
+        assert_eq!(resolver.inner().calls.load(Ordering::SeqCst), 2);
+
+        resolver.invalidate(&uri("a.json"));
+        resolver
+            .resolve_at(&uri("a.json"), now + Duration::from_secs(61))
+            .await
+            .unwrap();
+        assert_eq!(resolver.inner().calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn errors_are_not_cached() {
+        let resolver =
+            CachedSchemaResolver::new(CountingResolver::default(), Duration::from_secs(60));
+
+        for _ in 0..2 {
+            assert!(matches!(
+                resolver.resolve(&uri("missing.json")).await,
+                Err(Error::NotFound { .. })
+            ));
+        }
+        assert_eq!(resolver.inner().calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn resolve_dataschema() {
+        let schemas = HashMap::from([(
+            Url::parse(&fixtures::dataschema()).unwrap(),
+            Schema::new("{}"),
+        )]);
+
+        assert_eq!(
+            schemas
+                .resolve_dataschema(&fixtures::v10::full_json_data())
+                .await
+                .unwrap(),
+            Some(Schema::new("{}"))
+        );
+        assert_eq!(
+            schemas
+                .resolve_dataschema(&fixtures::v10::minimal())
+                .await
+                .unwrap(),
+            None
+        );
+    }
+}
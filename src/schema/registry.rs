@@ -0,0 +1,122 @@
+use crate::event::Data;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use url::Url;
+
+/// Validator of the data of the events, against a schema known in advance.
+///
+/// `JsonSchemaValidator` (feature `jsonschema`) validates the data against a JSON Schema, other
+/// formats such as Avro can be supported by implementing this trait with the library of choice.
+pub trait DataValidator: Send + Sync {
+    /// Check that `data` conforms to the schema, returning the reason why it doesn't otherwise.
+    fn validate(&self, data: &Data) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+
+impl<F> DataValidator for F
+where
+    F: Fn(&Data) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send + Sync,
+{
+    fn validate(&self, data: &Data) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self(data)
+    }
+}
+
+/// Schema registered in a [`SchemaRegistry`] for an event type.
+#[derive(Clone)]
+pub struct RegisteredSchema {
+    /// URI of the schema, set as the `dataschema` of the events.
+    pub dataschema: Url,
+    pub validator: Arc<dyn DataValidator>,
+}
+
+impl fmt::Debug for RegisteredSchema {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RegisteredSchema")
+            .field("dataschema", &self.dataschema)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Schemas of the data of the events, by event type, used by the `data_with_registry` method
+/// of the event builders to validate the data and set the `dataschema` attribute.
+///
+/// The registry is cheap to clone, the clones sharing the registered validators.
+///
+/// ```
+/// use cloudevents::event::Data;
+/// use cloudevents::schema::{DataValidator, SchemaRegistry};
+/// use cloudevents::{AttributesReader, EventBuilder, EventBuilderV10};
+/// use std::error::Error;
+/// use url::Url;
+///
+/// struct OrderValidator;
+///
+/// impl DataValidator for OrderValidator {
+///     fn validate(&self, data: &Data) -> Result<(), Box<dyn Error + Send + Sync>> {
+///         match data {
+///             Data::Json(value) if value.is_object() => Ok(()),
+///             _ => Err("the order must be a JSON object".into()),
+///         }
+///     }
+/// }
+///
+/// let registry = SchemaRegistry::new().register(
+///     "com.example.order.created",
+///     Url::parse("https://example.com/schemas/order.json").unwrap(),
+///     OrderValidator,
+/// );
+///
+/// let event = EventBuilderV10::new()
+///     .id("0001")
+///     .ty("com.example.order.created")
+///     .source("http://localhost/")
+///     .data_with_registry("application/json", serde_json::json!({}), &registry)
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     event.dataschema().map(Url::as_str),
+///     Some("https://example.com/schemas/order.json")
+/// );
+///
+/// assert!(EventBuilderV10::new()
+///     .id("0002")
+///     .ty("com.example.order.created")
+///     .source("http://localhost/")
+///     .data_with_registry("application/json", serde_json::json!([]), &registry)
+///     .build()
+///     .is_err());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SchemaRegistry {
+    schemas: Arc<HashMap<String, RegisteredSchema>>,
+}
+
+impl SchemaRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the schema identified by `dataschema` for the events of type `ty`, replacing
+    /// the schema previously registered for this type.
+    pub fn register(
+        mut self,
+        ty: impl Into<String>,
+        dataschema: Url,
+        validator: impl DataValidator + 'static,
+    ) -> Self {
+        Arc::make_mut(&mut self.schemas).insert(
+            ty.into(),
+            RegisteredSchema {
+                dataschema,
+                validator: Arc::new(validator),
+            },
+        );
+        self
+    }
+
+    /// The schema registered for the events of type `ty`.
+    pub fn get(&self, ty: &str) -> Option<&RegisteredSchema> {
+        self.schemas.get(ty)
+    }
+}
@@ -0,0 +1,448 @@
+use super::parser::{BinaryOp, Expr, UnaryOp};
+use super::{Error, Result, Value};
+use crate::event::AttributeValue;
+use crate::Event;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+
+/// Built-in CESQL functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Function {
+    Abs,
+    Length,
+    Concat,
+    ConcatWs,
+    Lower,
+    Upper,
+    Trim,
+    Left,
+    Right,
+    Substring,
+    Int,
+    Bool,
+    String,
+    IsBool,
+    IsInt,
+}
+
+impl Function {
+    pub(crate) fn lookup(name: &str) -> Option<Function> {
+        Some(match name.to_ascii_uppercase().as_str() {
+            "ABS" => Function::Abs,
+            "LENGTH" => Function::Length,
+            "CONCAT" => Function::Concat,
+            "CONCAT_WS" => Function::ConcatWs,
+            "LOWER" => Function::Lower,
+            "UPPER" => Function::Upper,
+            "TRIM" => Function::Trim,
+            "LEFT" => Function::Left,
+            "RIGHT" => Function::Right,
+            "SUBSTRING" => Function::Substring,
+            "INT" => Function::Int,
+            "BOOL" => Function::Bool,
+            "STRING" => Function::String,
+            "IS_BOOL" => Function::IsBool,
+            "IS_INT" => Function::IsInt,
+            _ => return None,
+        })
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Function::Abs => "ABS",
+            Function::Length => "LENGTH",
+            Function::Concat => "CONCAT",
+            Function::ConcatWs => "CONCAT_WS",
+            Function::Lower => "LOWER",
+            Function::Upper => "UPPER",
+            Function::Trim => "TRIM",
+            Function::Left => "LEFT",
+            Function::Right => "RIGHT",
+            Function::Substring => "SUBSTRING",
+            Function::Int => "INT",
+            Function::Bool => "BOOL",
+            Function::String => "STRING",
+            Function::IsBool => "IS_BOOL",
+            Function::IsInt => "IS_INT",
+        }
+    }
+
+    pub(crate) fn accepts(self, arity: usize) -> bool {
+        match self {
+            Function::Concat => true,
+            Function::ConcatWs => arity >= 1,
+            Function::Left | Function::Right => arity == 2,
+            Function::Substring => arity == 2 || arity == 3,
+            _ => arity == 1,
+        }
+    }
+
+    fn error<T>(self, message: impl Into<String>) -> Result<T> {
+        Err(Error::FunctionError {
+            function: self.name(),
+            message: message.into(),
+        })
+    }
+
+    fn call(self, args: Vec<Value>) -> Result<Value> {
+        let mut args = args.into_iter();
+
+        Ok(match self {
+            Function::Abs => match cast_to_integer(next_arg(&mut args))?.checked_abs() {
+                Some(i) => Value::Integer(i),
+                None => return overflow(),
+            },
+            Function::Length => Value::Integer(
+                i32::try_from(cast_to_string(next_arg(&mut args)).chars().count())
+                    .unwrap_or(i32::MAX),
+            ),
+            Function::Concat => Value::String(args.map(cast_to_string).collect()),
+            Function::ConcatWs => {
+                let separator = cast_to_string(next_arg(&mut args));
+                Value::String(
+                    args.map(cast_to_string)
+                        .collect::<Vec<String>>()
+                        .join(&separator),
+                )
+            }
+            Function::Lower => Value::String(cast_to_string(next_arg(&mut args)).to_lowercase()),
+            Function::Upper => Value::String(cast_to_string(next_arg(&mut args)).to_uppercase()),
+            Function::Trim => Value::String(cast_to_string(next_arg(&mut args)).trim().to_string()),
+            Function::Left | Function::Right => {
+                let s = cast_to_string(next_arg(&mut args));
+                let length = match usize::try_from(cast_to_integer(next_arg(&mut args))?) {
+                    Ok(l) => l,
+                    Err(_) => return self.error("length must be positive"),
+                };
+                let count = s.chars().count();
+                if self == Function::Left {
+                    Value::String(s.chars().take(length).collect())
+                } else {
+                    Value::String(s.chars().skip(count.saturating_sub(length)).collect())
+                }
+            }
+            Function::Substring => {
+                let s = cast_to_string(next_arg(&mut args));
+                let count = s.chars().count() as i64;
+                let position = cast_to_integer(next_arg(&mut args))? as i64;
+                let start = match position {
+                    p if p > 0 && p <= count => p - 1,
+                    p if p < 0 && -p <= count => count + p,
+                    _ => return self.error(format!("position {} out of bounds", position)),
+                };
+                let length = match args.next() {
+                    Some(l) => match usize::try_from(cast_to_integer(l)?) {
+                        Ok(l) => l,
+                        Err(_) => return self.error("length must be positive"),
+                    },
+                    None => usize::MAX,
+                };
+                Value::String(s.chars().skip(start as usize).take(length).collect())
+            }
+            Function::Int => Value::Integer(cast_to_integer(next_arg(&mut args))?),
+            Function::Bool => Value::Boolean(cast_to_boolean(next_arg(&mut args))?),
+            Function::String => Value::String(cast_to_string(next_arg(&mut args))),
+            Function::IsBool => Value::Boolean(cast_to_boolean(next_arg(&mut args)).is_ok()),
+            Function::IsInt => Value::Boolean(cast_to_integer(next_arg(&mut args)).is_ok()),
+        })
+    }
+}
+
+// The number of arguments is checked by the parser
+fn next_arg(args: &mut impl Iterator<Item = Value>) -> Value {
+    args.next().expect("wrong number of arguments")
+}
+
+fn cast_error<T>(value: Value, target: &'static str) -> Result<T> {
+    Err(Error::CastError {
+        value: value.to_string(),
+        target,
+    })
+}
+
+fn overflow<T>() -> Result<T> {
+    Err(Error::MathError {
+        message: "integer overflow",
+    })
+}
+
+pub(crate) fn cast_to_boolean(value: Value) -> Result<bool> {
+    match value {
+        Value::Boolean(b) => Ok(b),
+        Value::String(s) if s.eq_ignore_ascii_case("true") => Ok(true),
+        Value::String(s) if s.eq_ignore_ascii_case("false") => Ok(false),
+        v => cast_error(v, "Boolean"),
+    }
+}
+
+pub(crate) fn cast_to_integer(value: Value) -> Result<i32> {
+    match value {
+        Value::Integer(i) => Ok(i),
+        Value::String(s) => match s.parse() {
+            Ok(i) => Ok(i),
+            Err(_) => cast_error(Value::String(s), "Integer"),
+        },
+        v => cast_error(v, "Integer"),
+    }
+}
+
+pub(crate) fn cast_to_string(value: Value) -> String {
+    match value {
+        Value::String(s) => s,
+        v => v.to_string(),
+    }
+}
+
+impl<'a> From<AttributeValue<'a>> for Value {
+    fn from(value: AttributeValue<'a>) -> Self {
+        match value {
+            AttributeValue::Boolean(b) => Value::Boolean(*b),
+            AttributeValue::Integer(i) => match i32::try_from(*i) {
+                Ok(i) => Value::Integer(i),
+                Err(_) => Value::String(i.to_string()),
+            },
+            v => Value::String(v.to_string()),
+        }
+    }
+}
+
+/// Compare two values for equality, casting the operands if their types differ.
+fn equals(left: Value, right: Value) -> Result<bool> {
+    Ok(match (left, right) {
+        (Value::String(l), Value::String(r)) => l == r,
+        (Value::Integer(l), r) => l == cast_to_integer(r)?,
+        (l, Value::Integer(r)) => cast_to_integer(l)? == r,
+        (Value::Boolean(l), r) => l == cast_to_boolean(r)?,
+        (l, Value::Boolean(r)) => cast_to_boolean(l)? == r,
+    })
+}
+
+/// Match `value` against a LIKE `pattern`, where `%` matches any sequence of characters and `_`
+/// exactly one character.
+fn like(value: &str, pattern: &str) -> bool {
+    #[derive(PartialEq)]
+    enum Pattern {
+        Any,
+        One,
+        Char(char),
+    }
+
+    let mut compiled = Vec::new();
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        compiled.push(match c {
+            '%' => Pattern::Any,
+            '_' => Pattern::One,
+            '\\' => Pattern::Char(chars.next().unwrap_or('\\')),
+            c => Pattern::Char(c),
+        });
+    }
+
+    let value: Vec<char> = value.chars().collect();
+    let (mut v, mut p) = (0, 0);
+    let mut backtrack = None;
+    while v < value.len() {
+        match compiled.get(p) {
+            Some(Pattern::Char(c)) if *c == value[v] => {
+                v += 1;
+                p += 1;
+            }
+            Some(Pattern::One) => {
+                v += 1;
+                p += 1;
+            }
+            Some(Pattern::Any) => {
+                backtrack = Some((p, v));
+                p += 1;
+            }
+            _ => match backtrack {
+                Some((bp, bv)) => {
+                    backtrack = Some((bp, bv + 1));
+                    p = bp + 1;
+                    v = bv + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    compiled[p..].iter().all(|p| *p == Pattern::Any)
+}
+
+impl Expr {
+    pub(crate) fn evaluate(&self, event: &Event) -> Result<Value> {
+        match self {
+            Expr::Literal(v) => Ok(v.clone()),
+            Expr::Attribute(name) => event
+                .iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| Value::from(v))
+                .ok_or_else(|| Error::MissingAttribute { name: name.clone() }),
+            Expr::Exists(name) => Ok(Value::Boolean(event.iter().any(|(n, _)| n == name))),
+            Expr::Unary(UnaryOp::Not, e) => {
+                Ok(Value::Boolean(!cast_to_boolean(e.evaluate(event)?)?))
+            }
+            Expr::Unary(UnaryOp::Negate, e) => {
+                match cast_to_integer(e.evaluate(event)?)?.checked_neg() {
+                    Some(i) => Ok(Value::Integer(i)),
+                    None => overflow(),
+                }
+            }
+            Expr::Binary(BinaryOp::And, l, r) => Ok(Value::Boolean(
+                cast_to_boolean(l.evaluate(event)?)? && cast_to_boolean(r.evaluate(event)?)?,
+            )),
+            Expr::Binary(BinaryOp::Or, l, r) => Ok(Value::Boolean(
+                cast_to_boolean(l.evaluate(event)?)? || cast_to_boolean(r.evaluate(event)?)?,
+            )),
+            Expr::Binary(op, l, r) => evaluate_binary(*op, l.evaluate(event)?, r.evaluate(event)?),
+            Expr::Like {
+                expr,
+                pattern,
+                negated,
+            } => Ok(Value::Boolean(
+                like(&cast_to_string(expr.evaluate(event)?), pattern) != *negated,
+            )),
+            Expr::In { expr, set, negated } => {
+                let value = expr.evaluate(event)?;
+                for item in set {
+                    if equals(value.clone(), item.evaluate(event)?)? {
+                        return Ok(Value::Boolean(!*negated));
+                    }
+                }
+                Ok(Value::Boolean(*negated))
+            }
+            Expr::Function(function, args) => function.call(
+                args.iter()
+                    .map(|a| a.evaluate(event))
+                    .collect::<Result<Vec<Value>>>()?,
+            ),
+        }
+    }
+}
+
+fn evaluate_binary(op: BinaryOp, left: Value, right: Value) -> Result<Value> {
+    let compare = |left: Value, right: Value| -> Result<Ordering> {
+        Ok(cast_to_integer(left)?.cmp(&cast_to_integer(right)?))
+    };
+    let arithmetic = |left: Value, right: Value, f: fn(i32, i32) -> Option<i32>| -> Result<Value> {
+        match f(cast_to_integer(left)?, cast_to_integer(right)?) {
+            Some(i) => Ok(Value::Integer(i)),
+            None => overflow(),
+        }
+    };
+
+    match op {
+        BinaryOp::Xor => Ok(Value::Boolean(
+            cast_to_boolean(left)? != cast_to_boolean(right)?,
+        )),
+        BinaryOp::Equal => Ok(Value::Boolean(equals(left, right)?)),
+        BinaryOp::NotEqual => Ok(Value::Boolean(!equals(left, right)?)),
+        BinaryOp::Less => Ok(Value::Boolean(compare(left, right)?.is_lt())),
+        BinaryOp::LessOrEqual => Ok(Value::Boolean(compare(left, right)?.is_le())),
+        BinaryOp::Greater => Ok(Value::Boolean(compare(left, right)?.is_gt())),
+        BinaryOp::GreaterOrEqual => Ok(Value::Boolean(compare(left, right)?.is_ge())),
+        BinaryOp::Add => arithmetic(left, right, i32::checked_add),
+        BinaryOp::Subtract => arithmetic(left, right, i32::checked_sub),
+        BinaryOp::Multiply => arithmetic(left, right, i32::checked_mul),
+        BinaryOp::Divide | BinaryOp::Modulo => {
+            let (l, r) = (cast_to_integer(left)?, cast_to_integer(right)?);
+            if r == 0 {
+                return Err(Error::MathError {
+                    message: "division by zero",
+                });
+            }
+            match if op == BinaryOp::Divide {
+                l.checked_div(r)
+            } else {
+                l.checked_rem(r)
+            } {
+                Some(i) => Ok(Value::Integer(i)),
+                None => overflow(),
+            }
+        }
+        BinaryOp::And | BinaryOp::Or => unreachable!("short-circuited by Expr::evaluate"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql::parser::parse;
+
+    fn evaluate(expression: &str) -> Result<Value> {
+        parse(expression).unwrap().evaluate(&Event::default())
+    }
+
+    #[test]
+    fn like_patterns() {
+        assert!(like("com.example.order", "com.example.%"));
+        assert!(like("abc", "a_c"));
+        assert!(like("abc", "%"));
+        assert!(like("", "%%"));
+        assert!(like("a%c", "a\\%c"));
+        assert!(like("aXbXc", "%b%c"));
+        assert!(!like("abc", "a_"));
+        assert!(!like("abc", "a\\%c"));
+        assert!(!like("ABC", "abc"));
+    }
+
+    #[test]
+    fn operators() {
+        assert_eq!(evaluate("1 + 2 * 3 - 10 / 3 % 2"), Ok(Value::Integer(6)));
+        assert_eq!(evaluate("-(3 - 5)"), Ok(Value::Integer(2)));
+        assert_eq!(evaluate("TRUE XOR 'true'"), Ok(Value::Boolean(false)));
+        assert_eq!(evaluate("'10' = 10 AND 3 <> 4"), Ok(Value::Boolean(true)));
+        assert_eq!(evaluate("'TRUE' = TRUE"), Ok(Value::Boolean(true)));
+        assert_eq!(evaluate("'2' < 10"), Ok(Value::Boolean(true)));
+        assert_eq!(evaluate("3 IN ('1', 2, 3)"), Ok(Value::Boolean(true)));
+        assert_eq!(evaluate("'a' NOT IN ('b', 'c')"), Ok(Value::Boolean(true)));
+        assert_eq!(evaluate("FALSE AND 1"), Ok(Value::Boolean(false)));
+        assert_eq!(evaluate("EXISTS id"), Ok(Value::Boolean(true)));
+        assert!(matches!(evaluate("1 / 0"), Err(Error::MathError { .. })));
+        assert!(matches!(
+            evaluate("2147483647 + 1"),
+            Err(Error::MathError { .. })
+        ));
+        assert!(matches!(
+            evaluate("'abc' < 1"),
+            Err(Error::CastError {
+                target: "Integer",
+                ..
+            })
+        ));
+        assert!(matches!(evaluate("TRUE = 1"), Err(Error::CastError { .. })));
+    }
+
+    #[test]
+    fn functions() {
+        assert_eq!(evaluate("ABS(-5)"), Ok(Value::Integer(5)));
+        assert_eq!(evaluate("LENGTH('héllo')"), Ok(Value::Integer(5)));
+        assert_eq!(evaluate("CONCAT('a', 1, TRUE)"), Ok(Value::from("a1true")));
+        assert_eq!(evaluate("CONCAT_WS(',', 'a', 'b')"), Ok(Value::from("a,b")));
+        assert_eq!(evaluate("lower('ABC')"), Ok(Value::from("abc")));
+        assert_eq!(evaluate("UPPER('abc')"), Ok(Value::from("ABC")));
+        assert_eq!(evaluate("TRIM('  a  ')"), Ok(Value::from("a")));
+        assert_eq!(evaluate("LEFT('abc', 2)"), Ok(Value::from("ab")));
+        assert_eq!(evaluate("RIGHT('abc', 5)"), Ok(Value::from("abc")));
+        assert_eq!(
+            evaluate("SUBSTRING('abcdef', 2, 3)"),
+            Ok(Value::from("bcd"))
+        );
+        assert_eq!(evaluate("SUBSTRING('abcdef', -2)"), Ok(Value::from("ef")));
+        assert_eq!(evaluate("INT('42')"), Ok(Value::Integer(42)));
+        assert_eq!(evaluate("BOOL('false')"), Ok(Value::Boolean(false)));
+        assert_eq!(evaluate("STRING(42)"), Ok(Value::from("42")));
+        assert_eq!(evaluate("IS_BOOL('x')"), Ok(Value::Boolean(false)));
+        assert_eq!(evaluate("IS_INT('-3')"), Ok(Value::Boolean(true)));
+        assert!(matches!(
+            evaluate("SUBSTRING('abc', 0)"),
+            Err(Error::FunctionError {
+                function: "SUBSTRING",
+                ..
+            })
+        ));
+        assert!(matches!(
+            evaluate("LEFT('abc', -1)"),
+            Err(Error::FunctionError { .. })
+        ));
+    }
+}
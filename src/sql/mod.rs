@@ -0,0 +1,203 @@
+//! This module implements the [CloudEvents SQL (CESQL) v1](https://github.com/cloudevents/spec/blob/main/cesql/spec.md)
+//! expression language, to filter [`Event`]s with expressions like
+//! `type LIKE 'com.example.%' AND subject = 'orders'`.
+//!
+//! ```
+//! use cloudevents::sql::{Expression, Value};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//!
+//! let expression: Expression = "type LIKE 'com.example.%' AND subject = 'orders'".parse().unwrap();
+//!
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("com.example.order.created")
+//!     .source("http://localhost/")
+//!     .subject("orders")
+//!     .build()
+//!     .unwrap();
+//!
+//! assert_eq!(expression.evaluate(&event).unwrap(), Value::Boolean(true));
+//! assert!(expression.matches(&event));
+//! ```
+//!
+//! The supported syntax is:
+//!
+//! * Literals: strings (`'value'` or `"value"`), 32 bit integers and booleans (`TRUE`, `FALSE`).
+//! * Identifiers, evaluated to the value of the event attribute or extension with the same name.
+//! * Logical operators: `NOT`, `AND`, `OR` and `XOR`.
+//! * Comparison operators: `=`, `!=`, `<>`, `<`, `<=`, `>` and `>=`.
+//! * Arithmetic operators: unary `-`, `+`, `-`, `*`, `/` and `%`.
+//! * `x [NOT] LIKE 'pattern'`, where `%` matches any sequence of characters and `_` matches
+//!   exactly one character. Both can be escaped with `\`.
+//! * `x [NOT] IN (y, z, ...)` and `EXISTS attribute`.
+//! * The built-in functions `ABS`, `LENGTH`, `CONCAT`, `CONCAT_WS`, `LOWER`, `UPPER`, `TRIM`,
+//!   `LEFT`, `RIGHT`, `SUBSTRING`, `INT`, `BOOL`, `STRING`, `IS_BOOL` and `IS_INT`.
+//!
+//! Keywords and function names are case insensitive.
+//!
+//! When the operands of an operator have different types, they are implicitly cast as defined
+//! by the specification. Integer attribute values which don't fit in 32 bits and every other
+//! non boolean attribute value are evaluated as strings.
+
+mod eval;
+mod parser;
+
+use crate::Event;
+use snafu::Snafu;
+use std::fmt;
+use std::str::FromStr;
+
+/// Represents an error while parsing or evaluating a CESQL [`Expression`]
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+pub enum Error {
+    #[snafu(display("Parse error at position {}: {}", position, message))]
+    ParseError { position: usize, message: String },
+    #[snafu(display("Missing attribute {}", name))]
+    MissingAttribute { name: String },
+    #[snafu(display("Cannot cast {} to {}", value, target))]
+    CastError { value: String, target: &'static str },
+    #[snafu(display("Math error: {}", message))]
+    MathError { message: &'static str },
+    #[snafu(display("Error while evaluating {}: {}", function, message))]
+    FunctionError {
+        function: &'static str,
+        message: String,
+    },
+}
+
+/// Result type alias for return values of the CESQL parser and evaluator
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// A value of the CESQL type system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    String(String),
+    Integer(i32),
+    Boolean(bool),
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(i: i32) -> Self {
+        Value::Integer(i)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Boolean(b)
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::String(s) => f.write_str(s),
+            Value::Integer(i) => write!(f, "{}", i),
+            Value::Boolean(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+/// A parsed CESQL expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expression(parser::Expr);
+
+impl Expression {
+    /// Parse a CESQL expression.
+    pub fn parse(input: &str) -> Result<Self> {
+        parser::parse(input).map(Expression)
+    }
+
+    /// Evaluate the expression against `event`.
+    pub fn evaluate(&self, event: &Event) -> Result<Value> {
+        self.0.evaluate(event)
+    }
+
+    /// Evaluate the expression against `event` as a filter.
+    ///
+    /// Returns `false` if the evaluation fails or its result cannot be cast to a boolean.
+    pub fn matches(&self, event: &Event) -> bool {
+        matches!(
+            self.evaluate(event).and_then(eval::cast_to_boolean),
+            Ok(true)
+        )
+    }
+}
+
+impl FromStr for Expression {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Expression::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+
+    fn evaluate(expression: &str) -> Result<Value> {
+        Expression::parse(expression)
+            .unwrap()
+            .evaluate(&fixtures::v10::full_no_data())
+    }
+
+    #[test]
+    fn attributes() {
+        assert_eq!(evaluate("id").unwrap(), Value::from("0001"));
+        assert_eq!(evaluate("specversion").unwrap(), Value::from("1.0"));
+        assert_eq!(
+            evaluate("source").unwrap(),
+            Value::from("http://localhost/")
+        );
+        assert_eq!(
+            evaluate("time").unwrap(),
+            Value::from(fixtures::time().to_rfc3339())
+        );
+        assert_eq!(evaluate("stringex").unwrap(), Value::from("val"));
+        assert_eq!(evaluate("intex").unwrap(), Value::Integer(10));
+        assert_eq!(evaluate("boolex").unwrap(), Value::Boolean(true));
+        assert_eq!(
+            evaluate("dataschema"),
+            Err(Error::MissingAttribute {
+                name: "dataschema".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn filter() {
+        let event = fixtures::v10::full_no_data();
+        let matches = |e: &str| Expression::parse(e).unwrap().matches(&event);
+
+        assert!(matches(
+            "type LIKE 'test_event.%' AND subject = 'cloudevents-sdk'"
+        ));
+        assert!(matches("EXISTS stringex AND NOT EXISTS dataschema"));
+        assert!(matches("intex + 1 = 11 AND boolex"));
+        assert!(!matches("type LIKE 'com.example.%'"));
+        assert!(!matches("dataschema = 'http://localhost/schema'"));
+        assert!(!matches("subject"));
+    }
+
+    #[test]
+    fn parse_error() {
+        assert!(matches!(
+            "type = ".parse::<Expression>(),
+            Err(Error::ParseError { position: 7, .. })
+        ));
+    }
+}
@@ -0,0 +1,603 @@
+use super::eval::Function;
+use super::{Error, Result, Value};
+use std::convert::TryFrom;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum UnaryOp {
+    Not,
+    Negate,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BinaryOp {
+    And,
+    Or,
+    Xor,
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Expr {
+    Literal(Value),
+    Attribute(String),
+    Exists(String),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Like {
+        expr: Box<Expr>,
+        pattern: String,
+        negated: bool,
+    },
+    In {
+        expr: Box<Expr>,
+        set: Vec<Expr>,
+        negated: bool,
+    },
+    Function(Function, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Identifier(String),
+    Integer(i64),
+    String(String),
+    LeftParen,
+    RightParen,
+    Comma,
+    Operator(&'static str),
+    End,
+}
+
+static KEYWORDS: &[&str] = &[
+    "AND", "OR", "XOR", "NOT", "LIKE", "IN", "EXISTS", "TRUE", "FALSE",
+];
+
+static OPERATORS: &[&str] = &[
+    "!=", "<>", "<=", ">=", "=", "<", ">", "+", "-", "*", "/", "%",
+];
+
+/// Maximum depth of the expression tree, counting the parentheses, the function calls, the unary
+/// operators and the chained binary operators, so a malicious expression can't overflow the
+/// stack of the recursive descent, nor of the evaluation.
+const MAX_DEPTH: usize = 64;
+
+fn parse_error<T>(position: usize, message: impl Into<String>) -> Result<T> {
+    Err(Error::ParseError {
+        position,
+        message: message.into(),
+    })
+}
+
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>> {
+    let chars: Vec<(usize, char)> = input.char_indices().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while let Some(&(position, c)) = chars.get(i) {
+        let token = match c {
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' => {
+                i += 1;
+                Token::LeftParen
+            }
+            ')' => {
+                i += 1;
+                Token::RightParen
+            }
+            ',' => {
+                i += 1;
+                Token::Comma
+            }
+            '\'' | '"' => {
+                let mut s = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None => return parse_error(position, "unterminated string literal"),
+                        Some(&(_, '\\')) if matches!(chars.get(i + 1), Some(&(_, q)) if q == c) => {
+                            s.push(c);
+                            i += 2;
+                        }
+                        Some(&(_, q)) if q == c => {
+                            i += 1;
+                            break;
+                        }
+                        Some(&(_, ch)) => {
+                            s.push(ch);
+                            i += 1;
+                        }
+                    }
+                }
+                Token::String(s)
+            }
+            c if c.is_ascii_digit() => {
+                let start = i;
+                while matches!(chars.get(i), Some((_, d)) if d.is_ascii_digit()) {
+                    i += 1;
+                }
+                let digits: String = chars[start..i].iter().map(|(_, d)| d).collect();
+                match digits.parse() {
+                    Ok(v) => Token::Integer(v),
+                    Err(_) => return parse_error(position, "integer literal out of range"),
+                }
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let start = i;
+                while matches!(chars.get(i), Some((_, d)) if d.is_ascii_alphanumeric() || *d == '_')
+                {
+                    i += 1;
+                }
+                Token::Identifier(chars[start..i].iter().map(|(_, d)| d).collect())
+            }
+            _ => match OPERATORS
+                .iter()
+                .find(|op| input[position..].starts_with(**op))
+            {
+                Some(op) => {
+                    i += op.len();
+                    Token::Operator(op)
+                }
+                None => return parse_error(position, format!("unexpected character '{}'", c)),
+            },
+        };
+        tokens.push((position, token));
+    }
+
+    tokens.push((input.len(), Token::End));
+    Ok(tokens)
+}
+
+pub(crate) fn parse(input: &str) -> Result<Expr> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        current: 0,
+        depth: 0,
+    };
+    let expr = parser.parse_or()?;
+    match parser.peek() {
+        Token::End => Ok(expr),
+        t => parse_error(parser.position(), format!("unexpected {:?}", t)),
+    }
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    current: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, offset: usize) -> &Token {
+        self.tokens
+            .get(self.current + offset)
+            .map(|(_, t)| t)
+            .unwrap_or(&Token::End)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens[self.current.min(self.tokens.len() - 1)].0
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.peek().clone();
+        if token != Token::End {
+            self.current += 1;
+        }
+        token
+    }
+
+    fn is_keyword(token: &Token, keyword: &str) -> bool {
+        matches!(token, Token::Identifier(i) if i.eq_ignore_ascii_case(keyword))
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = Self::is_keyword(self.peek(), keyword);
+        if found {
+            self.next();
+        }
+        found
+    }
+
+    fn eat_operator(&mut self, operators: &[&'static str]) -> Option<&'static str> {
+        match self.peek() {
+            Token::Operator(op) if operators.contains(op) => {
+                let op = *op;
+                self.next();
+                Some(op)
+            }
+            _ => None,
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<()> {
+        let position = self.position();
+        match self.next() {
+            t if t == expected => Ok(()),
+            t => parse_error(position, format!("expected {:?}, found {:?}", expected, t)),
+        }
+    }
+
+    /// Go one level deeper in the expression tree, failing past [`MAX_DEPTH`].
+    fn enter(&mut self) -> Result<()> {
+        if self.depth == MAX_DEPTH {
+            return parse_error(self.position(), "expression nested too deeply");
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Run `parse` one level deeper in the expression tree.
+    fn nested(&mut self, parse: impl FnOnce(&mut Self) -> Result<Expr>) -> Result<Expr> {
+        self.enter()?;
+        let expr = parse(self);
+        self.depth -= 1;
+        expr
+    }
+
+    fn parse_or(&mut self) -> Result<Expr> {
+        let depth = self.depth;
+        let mut left = self.parse_and()?;
+        loop {
+            let op = if self.eat_keyword("OR") {
+                BinaryOp::Or
+            } else if self.eat_keyword("XOR") {
+                BinaryOp::Xor
+            } else {
+                self.depth = depth;
+                return Ok(left);
+            };
+            self.enter()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(self.parse_and()?));
+        }
+    }
+
+    fn parse_and(&mut self) -> Result<Expr> {
+        let depth = self.depth;
+        let mut left = self.parse_in()?;
+        while self.eat_keyword("AND") {
+            self.enter()?;
+            left = Expr::Binary(BinaryOp::And, Box::new(left), Box::new(self.parse_in()?));
+        }
+        self.depth = depth;
+        Ok(left)
+    }
+
+    fn parse_in(&mut self) -> Result<Expr> {
+        let depth = self.depth;
+        let mut expr = self.parse_like()?;
+        loop {
+            let negated =
+                Self::is_keyword(self.peek(), "NOT") && Self::is_keyword(self.peek_at(1), "IN");
+            if negated {
+                self.next();
+            }
+            if !self.eat_keyword("IN") {
+                self.depth = depth;
+                return Ok(expr);
+            }
+            self.enter()?;
+            self.expect(Token::LeftParen)?;
+            let mut set = vec![self.nested(Self::parse_or)?];
+            while self.peek() == &Token::Comma {
+                self.next();
+                set.push(self.nested(Self::parse_or)?);
+            }
+            self.expect(Token::RightParen)?;
+            expr = Expr::In {
+                expr: Box::new(expr),
+                set,
+                negated,
+            };
+        }
+    }
+
+    fn parse_like(&mut self) -> Result<Expr> {
+        let depth = self.depth;
+        let mut expr = self.parse_comparison()?;
+        loop {
+            let negated =
+                Self::is_keyword(self.peek(), "NOT") && Self::is_keyword(self.peek_at(1), "LIKE");
+            if negated {
+                self.next();
+            }
+            if !self.eat_keyword("LIKE") {
+                self.depth = depth;
+                return Ok(expr);
+            }
+            self.enter()?;
+            let position = self.position();
+            let pattern = match self.next() {
+                Token::String(s) => s,
+                t => {
+                    return parse_error(
+                        position,
+                        format!("expected a string literal pattern, found {:?}", t),
+                    )
+                }
+            };
+            expr = Expr::Like {
+                expr: Box::new(expr),
+                pattern,
+                negated,
+            };
+        }
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr> {
+        let depth = self.depth;
+        let mut left = self.parse_additive()?;
+        while let Some(op) = self.eat_operator(&["=", "!=", "<>", "<", "<=", ">", ">="]) {
+            self.enter()?;
+            let op = match op {
+                "=" => BinaryOp::Equal,
+                "<" => BinaryOp::Less,
+                "<=" => BinaryOp::LessOrEqual,
+                ">" => BinaryOp::Greater,
+                ">=" => BinaryOp::GreaterOrEqual,
+                _ => BinaryOp::NotEqual,
+            };
+            left = Expr::Binary(op, Box::new(left), Box::new(self.parse_additive()?));
+        }
+        self.depth = depth;
+        Ok(left)
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr> {
+        let depth = self.depth;
+        let mut left = self.parse_multiplicative()?;
+        while let Some(op) = self.eat_operator(&["+", "-"]) {
+            self.enter()?;
+            let op = if op == "+" {
+                BinaryOp::Add
+            } else {
+                BinaryOp::Subtract
+            };
+            left = Expr::Binary(op, Box::new(left), Box::new(self.parse_multiplicative()?));
+        }
+        self.depth = depth;
+        Ok(left)
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr> {
+        let depth = self.depth;
+        let mut left = self.parse_unary()?;
+        while let Some(op) = self.eat_operator(&["*", "/", "%"]) {
+            self.enter()?;
+            let op = match op {
+                "*" => BinaryOp::Multiply,
+                "/" => BinaryOp::Divide,
+                _ => BinaryOp::Modulo,
+            };
+            left = Expr::Binary(op, Box::new(left), Box::new(self.parse_unary()?));
+        }
+        self.depth = depth;
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr> {
+        if self.eat_keyword("NOT") {
+            let expr = self.nested(Self::parse_unary)?;
+            return Ok(Expr::Unary(UnaryOp::Not, Box::new(expr)));
+        }
+        if self.eat_operator(&["-"]).is_some() {
+            if let Token::Integer(i) = *self.peek() {
+                let position = self.position();
+                self.next();
+                return integer_literal(position, -i);
+            }
+            let expr = self.nested(Self::parse_unary)?;
+            return Ok(Expr::Unary(UnaryOp::Negate, Box::new(expr)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr> {
+        let position = self.position();
+        match self.next() {
+            Token::LeftParen => {
+                let expr = self.nested(Self::parse_or)?;
+                self.expect(Token::RightParen)?;
+                Ok(expr)
+            }
+            Token::Integer(i) => integer_literal(position, i),
+            Token::String(s) => Ok(Expr::Literal(Value::String(s))),
+            Token::Identifier(i) if i.eq_ignore_ascii_case("TRUE") => {
+                Ok(Expr::Literal(Value::Boolean(true)))
+            }
+            Token::Identifier(i) if i.eq_ignore_ascii_case("FALSE") => {
+                Ok(Expr::Literal(Value::Boolean(false)))
+            }
+            Token::Identifier(i) if i.eq_ignore_ascii_case("EXISTS") => {
+                let position = self.position();
+                match self.next() {
+                    Token::Identifier(name) if !is_reserved(&name) => Ok(Expr::Exists(name)),
+                    t => parse_error(position, format!("expected an identifier, found {:?}", t)),
+                }
+            }
+            Token::Identifier(name) if self.peek() == &Token::LeftParen => {
+                let function = match Function::lookup(&name) {
+                    Some(f) => f,
+                    None => return parse_error(position, format!("unknown function {}", name)),
+                };
+                self.next();
+                let mut args = Vec::new();
+                if self.peek() != &Token::RightParen {
+                    args.push(self.nested(Self::parse_or)?);
+                    while self.peek() == &Token::Comma {
+                        self.next();
+                        args.push(self.nested(Self::parse_or)?);
+                    }
+                }
+                self.expect(Token::RightParen)?;
+                if !function.accepts(args.len()) {
+                    return parse_error(
+                        position,
+                        format!(
+                            "wrong number of arguments for {}: {}",
+                            function.name(),
+                            args.len()
+                        ),
+                    );
+                }
+                Ok(Expr::Function(function, args))
+            }
+            Token::Identifier(name) if !is_reserved(&name) => Ok(Expr::Attribute(name)),
+            t => parse_error(position, format!("unexpected {:?}", t)),
+        }
+    }
+}
+
+fn is_reserved(identifier: &str) -> bool {
+    KEYWORDS.iter().any(|k| identifier.eq_ignore_ascii_case(k))
+}
+
+fn integer_literal(position: usize, value: i64) -> Result<Expr> {
+    match i32::try_from(value) {
+        Ok(v) => Ok(Expr::Literal(Value::Integer(v))),
+        Err(_) => parse_error(position, "integer literal out of range"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attribute(name: &str) -> Box<Expr> {
+        Box::new(Expr::Attribute(name.to_string()))
+    }
+
+    fn literal(value: impl Into<Value>) -> Box<Expr> {
+        Box::new(Expr::Literal(value.into()))
+    }
+
+    #[test]
+    fn literals() {
+        assert_eq!(parse(r"'it\'s'").unwrap(), *literal("it's"));
+        assert_eq!(parse(r#""say \"hi\"""#).unwrap(), *literal("say \"hi\""));
+        assert_eq!(parse("-2147483648").unwrap(), *literal(i32::MIN));
+        assert_eq!(parse("tRuE").unwrap(), *literal(true));
+        assert!(parse("2147483648").is_err());
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(
+            parse("a OR b AND c = 1 + 2 * 3").unwrap(),
+            Expr::Binary(
+                BinaryOp::Or,
+                attribute("a"),
+                Box::new(Expr::Binary(
+                    BinaryOp::And,
+                    attribute("b"),
+                    Box::new(Expr::Binary(
+                        BinaryOp::Equal,
+                        attribute("c"),
+                        Box::new(Expr::Binary(
+                            BinaryOp::Add,
+                            literal(1),
+                            Box::new(Expr::Binary(BinaryOp::Multiply, literal(2), literal(3)))
+                        ))
+                    ))
+                ))
+            )
+        );
+        assert_eq!(
+            parse("NOT (a) NOT LIKE 'x%'").unwrap(),
+            Expr::Like {
+                expr: Box::new(Expr::Unary(UnaryOp::Not, attribute("a"))),
+                pattern: "x%".to_string(),
+                negated: true
+            }
+        );
+    }
+
+    #[test]
+    fn in_and_functions() {
+        assert_eq!(
+            parse("subject NOT IN ('a', upper('b')) AND EXISTS id").unwrap(),
+            Expr::Binary(
+                BinaryOp::And,
+                Box::new(Expr::In {
+                    expr: attribute("subject"),
+                    set: vec![
+                        *literal("a"),
+                        Expr::Function(Function::Upper, vec![*literal("b")])
+                    ],
+                    negated: true
+                }),
+                Box::new(Expr::Exists("id".to_string()))
+            )
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(
+            parse("type = 'abc"),
+            Err(Error::ParseError { position: 7, .. })
+        ));
+        assert!(matches!(
+            parse("type LIKE subject"),
+            Err(Error::ParseError { position: 10, .. })
+        ));
+        assert!(matches!(
+            parse("UNKNOWN(1)"),
+            Err(Error::ParseError { position: 0, .. })
+        ));
+        assert!(matches!(
+            parse("LEFT('a')"),
+            Err(Error::ParseError { position: 0, .. })
+        ));
+        assert!(matches!(
+            parse("a b"),
+            Err(Error::ParseError { position: 2, .. })
+        ));
+        assert!(matches!(
+            parse("EXISTS AND"),
+            Err(Error::ParseError { position: 7, .. })
+        ));
+    }
+
+    #[test]
+    fn nesting_limit() {
+        let nested = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert_eq!(parse(&nested(MAX_DEPTH)), Ok(*literal(1)));
+        assert!(matches!(
+            parse(&nested(1000)),
+            Err(Error::ParseError { position: 65, .. })
+        ));
+        assert!(matches!(
+            parse(&format!("{}TRUE", "NOT ".repeat(1000))),
+            Err(Error::ParseError { position: 260, .. })
+        ));
+        assert!(matches!(
+            parse(&format!("{}1{}", "ABS(".repeat(1000), ")".repeat(1000))),
+            Err(Error::ParseError { .. })
+        ));
+        assert!(matches!(
+            parse(&format!("1{}", " + 1".repeat(100_000))),
+            Err(Error::ParseError { .. })
+        ));
+        assert!(matches!(
+            parse(&format!("TRUE{}", " AND TRUE".repeat(100_000))),
+            Err(Error::ParseError { .. })
+        ));
+    }
+}
@@ -0,0 +1,215 @@
+use super::{EventStore, ExpectedVersion, Result};
+use crate::Event;
+
+/// Event-sourced aggregate, whose state is rebuilt by applying the [`Event`]s of its stream, and
+/// which handles commands by deciding the new events to append to it.
+///
+/// ```
+/// use cloudevents::store::{execute, load, Aggregate, InMemoryEventStore};
+/// use cloudevents::{AttributesReader, Event, EventBuilder, EventBuilderV10};
+/// use std::fmt;
+///
+/// #[derive(Debug)]
+/// struct AlreadyOpened;
+///
+/// impl fmt::Display for AlreadyOpened {
+///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         f.write_str("the account is already opened")
+///     }
+/// }
+///
+/// impl std::error::Error for AlreadyOpened {}
+///
+/// #[derive(Default)]
+/// struct Account {
+///     opened: bool,
+/// }
+///
+/// impl Aggregate for Account {
+///     type Command = ();
+///     type Error = AlreadyOpened;
+///
+///     fn apply(self, event: &Event) -> Self {
+///         match event.ty() {
+///             "com.example.account.opened" => Account { opened: true },
+///             _ => self,
+///         }
+///     }
+///
+///     fn decide(&self, _: ()) -> Result<Vec<Event>, AlreadyOpened> {
+///         if self.opened {
+///             return Err(AlreadyOpened);
+///         }
+///         Ok(vec![EventBuilderV10::new()
+///             .id("0001")
+///             .ty("com.example.account.opened")
+///             .source("http://localhost/")
+///             .build()
+///             .unwrap()])
+///     }
+/// }
+///
+/// # async fn example() -> cloudevents::store::Result<()> {
+/// let store = InMemoryEventStore::new();
+/// let (account, version) = execute::<Account, _>(&store, "account-42", ()).await?;
+/// assert!(account.opened);
+/// assert_eq!(version, 1);
+///
+/// assert!(execute::<Account, _>(&store, "account-42", ()).await.is_err());
+/// assert_eq!(load::<Account, _>(&store, "account-42").await?.1, 1);
+/// # Ok(())
+/// # }
+/// ```
+pub trait Aggregate: Default + Send {
+    /// Command handled by [`Self::decide`].
+    type Command: Send;
+    /// Error returned when a command is rejected.
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Return the state after `event`.
+    fn apply(self, event: &Event) -> Self;
+
+    /// Decide the events resulting from `command`, given the current state.
+    fn decide(&self, command: Self::Command) -> std::result::Result<Vec<Event>, Self::Error>;
+}
+
+/// Rebuild the state of an [`Aggregate`] by applying `events`, in order, to its default state.
+pub fn replay<'a, A: Aggregate>(events: impl IntoIterator<Item = &'a Event>) -> A {
+    events.into_iter().fold(A::default(), A::apply)
+}
+
+/// Load the state of the [`Aggregate`] stored in `stream`, with the version of the stream.
+pub async fn load<A, S>(store: &S, stream: &str) -> Result<(A, u64)>
+where
+    A: Aggregate,
+    S: EventStore + ?Sized,
+{
+    let recorded = store.read_stream(stream).await?;
+    let version = recorded.last().map_or(0, |r| r.version);
+    Ok((replay(recorded.iter().map(|r| &r.event)), version))
+}
+
+/// Load the [`Aggregate`] stored in `stream`, decide the events resulting from `command` and
+/// append them to the stream, returning the new state and version.
+///
+/// The events are appended only if no other event has been appended to the stream since it was
+/// loaded, otherwise [`super::Error::WrongExpectedVersion`] is returned and the command can be
+/// retried.
+pub async fn execute<A, S>(store: &S, stream: &str, command: A::Command) -> Result<(A, u64)>
+where
+    A: Aggregate,
+    S: EventStore + ?Sized,
+{
+    let (state, version) = load::<A, S>(store, stream).await?;
+    let events = state
+        .decide(command)
+        .map_err(|e| super::Error::CommandRejected {
+            source: Box::new(e),
+        })?;
+    if events.is_empty() {
+        return Ok((state, version));
+    }
+
+    let state = events.iter().fold(state, A::apply);
+    let version = store
+        .append(stream, ExpectedVersion::Exact(version), events)
+        .await?;
+    Ok((state, version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::{Error, InMemoryEventStore};
+    use crate::test::fixtures;
+    use crate::{AttributesReader, AttributesWriter};
+    use snafu::Snafu;
+
+    #[derive(Debug, Snafu)]
+    #[snafu(display("insufficient funds"))]
+    struct InsufficientFunds;
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Account {
+        balance: i64,
+    }
+
+    fn event(ty: &str, amount: i64) -> Event {
+        let mut event = fixtures::v10::minimal();
+        event.set_type(ty);
+        event.set_extension("amount", amount);
+        event
+    }
+
+    fn amount(event: &Event) -> i64 {
+        match event.extension("amount") {
+            Some(crate::event::ExtensionValue::Integer(i)) => *i,
+            _ => 0,
+        }
+    }
+
+    impl Aggregate for Account {
+        type Command = i64;
+        type Error = InsufficientFunds;
+
+        fn apply(self, event: &Event) -> Self {
+            match event.ty() {
+                "deposited" => Account {
+                    balance: self.balance + amount(event),
+                },
+                "withdrawn" => Account {
+                    balance: self.balance - amount(event),
+                },
+                _ => self,
+            }
+        }
+
+        fn decide(&self, command: i64) -> std::result::Result<Vec<Event>, InsufficientFunds> {
+            match command {
+                0 => Ok(Vec::new()),
+                c if c > 0 => Ok(vec![event("deposited", c)]),
+                c if self.balance + c >= 0 => Ok(vec![event("withdrawn", -c)]),
+                _ => Err(InsufficientFunds),
+            }
+        }
+    }
+
+    #[test]
+    fn replay_events() {
+        let events = [event("deposited", 10), event("withdrawn", 3)];
+
+        assert_eq!(replay::<Account>(&events), Account { balance: 7 });
+        assert_eq!(replay::<Account>(&[]), Account::default());
+    }
+
+    #[tokio::test]
+    async fn execute_commands() {
+        let store = InMemoryEventStore::new();
+
+        assert_eq!(
+            execute::<Account, _>(&store, "a", 10).await.unwrap(),
+            (Account { balance: 10 }, 1)
+        );
+        assert_eq!(
+            execute::<Account, _>(&store, "a", -4).await.unwrap(),
+            (Account { balance: 6 }, 2)
+        );
+        assert_eq!(
+            execute::<Account, _>(&store, "a", 0).await.unwrap(),
+            (Account { balance: 6 }, 2)
+        );
+        assert!(matches!(
+            execute::<Account, _>(&store, "a", -7).await,
+            Err(Error::CommandRejected { .. })
+        ));
+
+        assert_eq!(
+            load::<Account, _>(&store, "a").await.unwrap(),
+            (Account { balance: 6 }, 2)
+        );
+        assert_eq!(
+            load::<Account, _>(&store, "b").await.unwrap(),
+            (Account::default(), 0)
+        );
+    }
+}
@@ -0,0 +1,382 @@
+//! This module provides the [`EventStore`] trait, to persist [`Event`]s in append-only streams
+//! for event-sourced services, and read them back by stream, type or time range.
+//!
+//! [`InMemoryEventStore`] is meant for tests, while `PostgresEventStore` (feature
+//! `eventstore-postgres`) stores the events in the JSON format in Postgres.
+//!
+//! ```
+//! use cloudevents::store::{EventQuery, EventStore, ExpectedVersion, InMemoryEventStore};
+//! use cloudevents::{EventBuilder, EventBuilderV10};
+//!
+//! # async fn example() -> cloudevents::store::Result<()> {
+//! let store = InMemoryEventStore::new();
+//! let event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("com.example.order.created")
+//!     .source("http://localhost/")
+//!     .build()
+//!     .unwrap();
+//!
+//! let version = store
+//!     .append("order-42", ExpectedVersion::NoStream, vec![event])
+//!     .await?;
+//! assert_eq!(version, 1);
+//!
+//! let created = store
+//!     .read(&EventQuery::new().ty("com.example.order.created"))
+//!     .await?;
+//! assert_eq!(created[0].stream, "order-42");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The [`Aggregate`] trait, with [`load`] and [`execute`], builds event-sourced aggregates on top
+//! of an [`EventStore`].
+
+use crate::event::AttributesReader;
+use crate::Event;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use snafu::Snafu;
+use std::fmt;
+use std::sync::Mutex;
+
+mod aggregate;
+#[cfg_attr(docsrs, doc(cfg(feature = "eventstore-postgres")))]
+#[cfg(feature = "eventstore-postgres")]
+mod postgres;
+
+pub use aggregate::{execute, load, replay, Aggregate};
+
+#[cfg(feature = "eventstore-postgres")]
+pub use postgres::PostgresEventStore;
+
+/// Represents an error of an [`EventStore`]
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display(
+        "Wrong expected version of stream {}: expected {}, actual {}",
+        stream,
+        expected,
+        actual
+    ))]
+    WrongExpectedVersion {
+        stream: String,
+        expected: ExpectedVersion,
+        actual: u64,
+    },
+    #[snafu(display("Command rejected: {}", source))]
+    CommandRejected {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[snafu(display("Invalid table name {}", name))]
+    InvalidTableName { name: String },
+    #[cfg(feature = "eventstore-postgres")]
+    #[snafu(display("Database error: {}", source))]
+    #[snafu(context(false))]
+    DatabaseError { source: sqlx::Error },
+}
+
+/// Result type alias for return values of [`EventStore`]
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Version a stream must have for an [`EventStore::append`] to succeed, to detect concurrent
+/// writers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExpectedVersion {
+    /// Append regardless of the stream version.
+    Any,
+    /// The stream must not exist.
+    NoStream,
+    /// The stream must have exactly this version, i.e. number of events.
+    Exact(u64),
+}
+
+impl ExpectedVersion {
+    fn check(self, stream: &str, actual: u64) -> Result<()> {
+        match self {
+            ExpectedVersion::Any => Ok(()),
+            ExpectedVersion::NoStream if actual == 0 => Ok(()),
+            ExpectedVersion::Exact(v) if v == actual => Ok(()),
+            expected => Err(Error::WrongExpectedVersion {
+                stream: stream.to_string(),
+                expected,
+                actual,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for ExpectedVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpectedVersion::Any => f.write_str("any"),
+            ExpectedVersion::NoStream => f.write_str("no stream"),
+            ExpectedVersion::Exact(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// An [`Event`] read from an [`EventStore`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedEvent {
+    /// Stream the event was appended to.
+    pub stream: String,
+    /// Version of the stream after the event was appended, starting from 1.
+    pub version: u64,
+    /// Position of the event in the store, across all the streams.
+    pub position: u64,
+    /// Time of the event: its `time` attribute, or the time it was appended if unset.
+    pub time: DateTime<Utc>,
+    pub event: Event,
+}
+
+/// Criteria of the events returned by [`EventStore::read`], in position order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EventQuery {
+    pub stream: Option<String>,
+    pub ty: Option<String>,
+    /// Inclusive lower bound of the event time.
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive upper bound of the event time.
+    pub to: Option<DateTime<Utc>>,
+    /// Only return the events after this position.
+    pub after_position: Option<u64>,
+    pub limit: Option<usize>,
+}
+
+impl EventQuery {
+    /// Create a new [`EventQuery`] matching all the events.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn stream(mut self, stream: impl Into<String>) -> Self {
+        self.stream = Some(stream.into());
+        self
+    }
+
+    pub fn ty(mut self, ty: impl Into<String>) -> Self {
+        self.ty = Some(ty.into());
+        self
+    }
+
+    /// Only return the events with a time in `from..to`.
+    pub fn time_range(mut self, from: DateTime<Utc>, to: DateTime<Utc>) -> Self {
+        self.from = Some(from);
+        self.to = Some(to);
+        self
+    }
+
+    pub fn after_position(mut self, position: u64) -> Self {
+        self.after_position = Some(position);
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    fn matches(&self, recorded: &RecordedEvent) -> bool {
+        self.stream.as_ref().is_none_or(|s| *s == recorded.stream)
+            && self.ty.as_ref().is_none_or(|t| t == recorded.event.ty())
+            && self.from.is_none_or(|from| recorded.time >= from)
+            && self.to.is_none_or(|to| recorded.time < to)
+            && self.after_position.is_none_or(|p| recorded.position > p)
+    }
+}
+
+/// Append-only store of [`Event`] streams.
+#[async_trait]
+pub trait EventStore: Send + Sync {
+    /// Append `events` to `stream`, if its version is `expected`, returning the new version of
+    /// the stream.
+    async fn append(
+        &self,
+        stream: &str,
+        expected: ExpectedVersion,
+        events: Vec<Event>,
+    ) -> Result<u64>;
+
+    /// Read the events matching `query`, in position order.
+    async fn read(&self, query: &EventQuery) -> Result<Vec<RecordedEvent>>;
+
+    /// Read all the events of `stream`, in version order.
+    async fn read_stream(&self, stream: &str) -> Result<Vec<RecordedEvent>> {
+        self.read(&EventQuery::new().stream(stream)).await
+    }
+}
+
+/// [`EventStore`] keeping the events in memory.
+#[derive(Debug, Default)]
+pub struct InMemoryEventStore {
+    events: Mutex<Vec<RecordedEvent>>,
+}
+
+impl InMemoryEventStore {
+    /// Create a new empty [`InMemoryEventStore`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl EventStore for InMemoryEventStore {
+    async fn append(
+        &self,
+        stream: &str,
+        expected: ExpectedVersion,
+        events: Vec<Event>,
+    ) -> Result<u64> {
+        let mut recorded = self.events.lock().unwrap();
+        let mut version = recorded.iter().filter(|r| r.stream == stream).count() as u64;
+        expected.check(stream, version)?;
+
+        let now = Utc::now();
+        for event in events {
+            version += 1;
+            let position = recorded.len() as u64 + 1;
+            recorded.push(RecordedEvent {
+                stream: stream.to_string(),
+                version,
+                position,
+                time: event.time().copied().unwrap_or(now),
+                event,
+            });
+        }
+        Ok(version)
+    }
+
+    async fn read(&self, query: &EventQuery) -> Result<Vec<RecordedEvent>> {
+        Ok(self
+            .events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| query.matches(r))
+            .take(query.limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use crate::AttributesWriter;
+    use chrono::Duration;
+
+    fn event(id: &str, ty: &str, time: DateTime<Utc>) -> Event {
+        let mut event = fixtures::v10::minimal();
+        event.set_id(id);
+        event.set_type(ty);
+        event.set_time(Some(time));
+        event
+    }
+
+    #[tokio::test]
+    async fn append_and_read() {
+        let store = InMemoryEventStore::new();
+        let t = fixtures::time();
+
+        assert_eq!(
+            store
+                .append(
+                    "a",
+                    ExpectedVersion::NoStream,
+                    vec![
+                        event("1", "created", t),
+                        event("2", "updated", t + Duration::hours(1))
+                    ]
+                )
+                .await
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            store
+                .append(
+                    "b",
+                    ExpectedVersion::Any,
+                    vec![event("3", "created", t + Duration::hours(2))]
+                )
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            store
+                .append(
+                    "a",
+                    ExpectedVersion::Exact(2),
+                    vec![event("4", "deleted", t + Duration::hours(3))]
+                )
+                .await
+                .unwrap(),
+            3
+        );
+
+        let ids = |recorded: Vec<RecordedEvent>| -> Vec<String> {
+            recorded
+                .into_iter()
+                .map(|r| r.event.id().to_string())
+                .collect()
+        };
+        assert_eq!(
+            ids(store.read_stream("a").await.unwrap()),
+            vec!["1", "2", "4"]
+        );
+        assert_eq!(
+            ids(store.read(&EventQuery::new().ty("created")).await.unwrap()),
+            vec!["1", "3"]
+        );
+        assert_eq!(
+            ids(store
+                .read(&EventQuery::new().time_range(t + Duration::hours(1), t + Duration::hours(3)))
+                .await
+                .unwrap()),
+            vec!["2", "3"]
+        );
+        assert_eq!(
+            ids(store
+                .read(&EventQuery::new().after_position(1).limit(2))
+                .await
+                .unwrap()),
+            vec!["2", "3"]
+        );
+    }
+
+    #[tokio::test]
+    async fn wrong_expected_version() {
+        let store = InMemoryEventStore::new();
+        let t = fixtures::time();
+        store
+            .append("a", ExpectedVersion::Any, vec![event("1", "created", t)])
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            store
+                .append(
+                    "a",
+                    ExpectedVersion::NoStream,
+                    vec![event("2", "created", t)]
+                )
+                .await,
+            Err(Error::WrongExpectedVersion { actual: 1, .. })
+        ));
+        assert!(matches!(
+            store
+                .append(
+                    "a",
+                    ExpectedVersion::Exact(2),
+                    vec![event("2", "created", t)]
+                )
+                .await,
+            Err(Error::WrongExpectedVersion { actual: 1, .. })
+        ));
+        assert_eq!(store.read_stream("a").await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,226 @@
+use super::{Error, EventQuery, EventStore, ExpectedVersion, RecordedEvent, Result};
+use crate::event::AttributesReader;
+use crate::pg::is_valid_table_name;
+use crate::Event;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+
+/// Default name of the events table.
+pub static DEFAULT_TABLE: &str = "events";
+
+/// [`EventStore`] storing the events in the JSON format in a Postgres table, using
+/// [sqlx](https://docs.rs/sqlx).
+///
+/// Concurrent appends to the same stream are detected through the unique `(stream, version)`
+/// constraint, and fail with [`Error::WrongExpectedVersion`].
+#[derive(Debug, Clone)]
+pub struct PostgresEventStore {
+    pool: PgPool,
+    table: String,
+}
+
+type Row = (String, i64, i64, DateTime<Utc>, Json<Event>);
+
+impl PostgresEventStore {
+    /// Create a new [`PostgresEventStore`] using the [`DEFAULT_TABLE`].
+    pub fn new(pool: PgPool) -> Self {
+        PostgresEventStore {
+            pool,
+            table: DEFAULT_TABLE.to_string(),
+        }
+    }
+
+    /// Create a new [`PostgresEventStore`] using the table `name`, optionally qualified by a schema.
+    pub fn with_table(pool: PgPool, name: impl Into<String>) -> Result<Self> {
+        let name = name.into();
+        if !is_valid_table_name(&name) {
+            return Err(Error::InvalidTableName { name });
+        }
+        Ok(PostgresEventStore { pool, table: name })
+    }
+
+    /// DDL statements creating the events table and its indexes, if they don't exist.
+    pub fn schema(&self) -> Vec<String> {
+        let prefix = self.table.replace('.', "_");
+        vec![
+            format!(
+                "CREATE TABLE IF NOT EXISTS {} (\
+                 position BIGSERIAL PRIMARY KEY, \
+                 stream TEXT NOT NULL, \
+                 version BIGINT NOT NULL, \
+                 type TEXT NOT NULL, \
+                 time TIMESTAMPTZ NOT NULL, \
+                 event JSONB NOT NULL, \
+                 UNIQUE (stream, version))",
+                self.table
+            ),
+            format!(
+                "CREATE INDEX IF NOT EXISTS {}_type_idx ON {} (type, position)",
+                prefix, self.table
+            ),
+            format!(
+                "CREATE INDEX IF NOT EXISTS {}_time_idx ON {} (time)",
+                prefix, self.table
+            ),
+        ]
+    }
+
+    /// Create the events table and its indexes, if they don't exist.
+    pub async fn create_table(&self) -> Result<()> {
+        for statement in self.schema() {
+            sqlx::query(&statement).execute(&self.pool).await?;
+        }
+        Ok(())
+    }
+
+    async fn version(&self, stream: &str) -> Result<u64> {
+        let (version,): (i64,) = sqlx::query_as(&format!(
+            "SELECT COALESCE(MAX(version), 0) FROM {} WHERE stream = $1",
+            self.table
+        ))
+        .bind(stream)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(version as u64)
+    }
+
+    fn select(&self, query: &EventQuery) -> QueryBuilder<'static, Postgres> {
+        let mut builder = QueryBuilder::new(format!(
+            "SELECT stream, version, position, time, event FROM {} WHERE TRUE",
+            self.table
+        ));
+        if let Some(stream) = &query.stream {
+            builder.push(" AND stream = ").push_bind(stream.clone());
+        }
+        if let Some(ty) = &query.ty {
+            builder.push(" AND type = ").push_bind(ty.clone());
+        }
+        if let Some(from) = query.from {
+            builder.push(" AND time >= ").push_bind(from);
+        }
+        if let Some(to) = query.to {
+            builder.push(" AND time < ").push_bind(to);
+        }
+        if let Some(position) = query.after_position {
+            builder.push(" AND position > ").push_bind(position as i64);
+        }
+        builder.push(" ORDER BY position");
+        if let Some(limit) = query.limit {
+            builder.push(" LIMIT ").push_bind(limit as i64);
+        }
+        builder
+    }
+}
+
+fn is_unique_violation(error: &sqlx::Error) -> bool {
+    matches!(error, sqlx::Error::Database(e) if e.is_unique_violation())
+}
+
+#[async_trait]
+impl EventStore for PostgresEventStore {
+    async fn append(
+        &self,
+        stream: &str,
+        expected: ExpectedVersion,
+        events: Vec<Event>,
+    ) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        let (current,): (i64,) = sqlx::query_as(&format!(
+            "SELECT COALESCE(MAX(version), 0) FROM {} WHERE stream = $1",
+            self.table
+        ))
+        .bind(stream)
+        .fetch_one(&mut *tx)
+        .await?;
+        let mut version = current as u64;
+        expected.check(stream, version)?;
+
+        let insert = format!(
+            "INSERT INTO {} (stream, version, type, time, event) \
+             VALUES ($1, $2, $3, COALESCE($4, now()), $5)",
+            self.table
+        );
+        for event in events {
+            version += 1;
+            let result = sqlx::query(&insert)
+                .bind(stream)
+                .bind(version as i64)
+                .bind(event.ty())
+                .bind(event.time().copied())
+                .bind(Json(&event))
+                .execute(&mut *tx)
+                .await;
+            match result {
+                Err(e) if is_unique_violation(&e) => {
+                    tx.rollback().await?;
+                    return Err(Error::WrongExpectedVersion {
+                        stream: stream.to_string(),
+                        expected,
+                        actual: self.version(stream).await?,
+                    });
+                }
+                r => r?,
+            };
+        }
+        tx.commit().await?;
+        Ok(version)
+    }
+
+    async fn read(&self, query: &EventQuery) -> Result<Vec<RecordedEvent>> {
+        let rows: Vec<Row> = self
+            .select(query)
+            .build_query_as()
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(
+                |(stream, version, position, time, Json(event))| RecordedEvent {
+                    stream,
+                    version: version as u64,
+                    position: position as u64,
+                    time,
+                    event,
+                },
+            )
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+
+    #[tokio::test]
+    async fn select() {
+        let pool = PgPool::connect_lazy("postgres://localhost/test").unwrap();
+        let store = PostgresEventStore::with_table(pool.clone(), "es.events").unwrap();
+
+        assert_eq!(
+            store
+                .select(
+                    &EventQuery::new()
+                        .stream("a")
+                        .ty("created")
+                        .time_range(fixtures::time(), fixtures::time())
+                        .after_position(10)
+                        .limit(5)
+                )
+                .sql(),
+            "SELECT stream, version, position, time, event FROM es.events WHERE TRUE \
+             AND stream = $1 AND type = $2 AND time >= $3 AND time < $4 AND position > $5 \
+             ORDER BY position LIMIT $6"
+        );
+        assert_eq!(
+            store.schema()[1],
+            "CREATE INDEX IF NOT EXISTS es_events_type_idx ON es.events (type, position)"
+        );
+        assert!(matches!(
+            PostgresEventStore::with_table(pool, "events;"),
+            Err(Error::InvalidTableName { .. })
+        ));
+    }
+}
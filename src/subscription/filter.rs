@@ -0,0 +1,96 @@
+use super::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A filter expression of a [`Subscription`](super::Subscription), in one of the
+/// [filter dialects](https://github.com/cloudevents/spec/blob/main/subscriptions/spec.md#324-filters)
+/// defined by the Subscriptions API.
+///
+/// The dialect is serialized as the name of the single property of the JSON object, e.g.
+/// `{"prefix": {"type": "com.example."}}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterExpression {
+    /// The value of the attribute must be exactly the given value.
+    Exact(HashMap<String, String>),
+    /// The value of the attribute must start with the given value.
+    Prefix(HashMap<String, String>),
+    /// The value of the attribute must end with the given value.
+    Suffix(HashMap<String, String>),
+    /// All the nested filter expressions must match.
+    All(Vec<FilterExpression>),
+    /// At least one of the nested filter expressions must match.
+    Any(Vec<FilterExpression>),
+    /// The nested filter expression must not match.
+    Not(Box<FilterExpression>),
+    /// The CloudEvents SQL expression must evaluate to `true`.
+    Sql(String),
+}
+
+impl FilterExpression {
+    /// Name of the filter dialect.
+    pub fn dialect(&self) -> &'static str {
+        match self {
+            FilterExpression::Exact(_) => "exact",
+            FilterExpression::Prefix(_) => "prefix",
+            FilterExpression::Suffix(_) => "suffix",
+            FilterExpression::All(_) => "all",
+            FilterExpression::Any(_) => "any",
+            FilterExpression::Not(_) => "not",
+            FilterExpression::Sql(_) => "sql",
+        }
+    }
+
+    /// Check the filter expression is valid for its dialect.
+    ///
+    /// `exact`, `prefix` and `suffix` must have exactly one attribute with a valid name, and
+    /// `prefix`/`suffix` values must not be empty; `all` and `any` must have at least one
+    /// nested expression. When the `sql` feature is enabled, `sql` expressions are parsed.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |reason: &str| {
+            Err(Error::InvalidFilter {
+                dialect: self.dialect(),
+                reason: reason.to_string(),
+            })
+        };
+
+        match self {
+            FilterExpression::Exact(m)
+            | FilterExpression::Prefix(m)
+            | FilterExpression::Suffix(m) => {
+                let (name, value) = match m.iter().next() {
+                    Some(entry) if m.len() == 1 => entry,
+                    _ => return invalid("expected exactly one attribute"),
+                };
+                if !super::is_valid_attribute_name(name) {
+                    return invalid(&format!("invalid attribute name '{}'", name));
+                }
+                if value.is_empty() && !matches!(self, FilterExpression::Exact(_)) {
+                    return invalid("value must not be empty");
+                }
+                Ok(())
+            }
+            FilterExpression::All(filters) | FilterExpression::Any(filters) => {
+                if filters.is_empty() {
+                    return invalid("expected at least one filter expression");
+                }
+                filters.iter().try_for_each(FilterExpression::validate)
+            }
+            FilterExpression::Not(filter) => filter.validate(),
+            #[cfg(feature = "sql")]
+            FilterExpression::Sql(expression) => crate::sql::Expression::parse(expression)
+                .map(|_| ())
+                .map_err(|e| Error::InvalidFilter {
+                    dialect: "sql",
+                    reason: e.to_string(),
+                }),
+            #[cfg(not(feature = "sql"))]
+            FilterExpression::Sql(expression) => {
+                if expression.trim().is_empty() {
+                    return invalid("expression must not be empty");
+                }
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,284 @@
+//! This module provides the data model of the
+//! [CloudEvents Subscriptions API](https://github.com/cloudevents/spec/blob/main/subscriptions/spec.md),
+//! so control planes can accept and emit spec compliant subscription resources.
+//!
+//! ```
+//! use cloudevents::subscription::{FilterExpression, Protocol, Subscription};
+//!
+//! let subscription: Subscription = serde_json::from_str(r#"{
+//!     "id": "sub-1",
+//!     "source": "http://example.com/orders",
+//!     "types": ["com.example.order.created"],
+//!     "filters": [{"prefix": {"subject": "orders/"}}],
+//!     "sink": "http://localhost:8080/events",
+//!     "protocol": "HTTP"
+//! }"#).unwrap();
+//!
+//! assert_eq!(subscription.protocol, Protocol::Http);
+//! assert_eq!(subscription.filters[0].dialect(), "prefix");
+//! subscription.validate().unwrap();
+//! ```
+
+mod filter;
+
+pub use filter::FilterExpression;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use snafu::Snafu;
+use std::collections::HashMap;
+use url::Url;
+
+/// Represents an invalid [`Subscription`]
+#[derive(Debug, Snafu, Clone, PartialEq, Eq)]
+pub enum Error {
+    #[snafu(display("Attribute {} must not be empty", attribute_name))]
+    EmptyAttribute { attribute_name: &'static str },
+    #[snafu(display("Invalid {} filter: {}", dialect, reason))]
+    InvalidFilter {
+        dialect: &'static str,
+        reason: String,
+    },
+}
+
+/// Result type alias for return values of the subscriptions validation
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Delivery protocol of a [`Subscription`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Protocol {
+    Http,
+    Mqtt3,
+    Mqtt5,
+    Amqp,
+    Kafka,
+    Nats,
+}
+
+/// Credential used by the subscription manager to deliver events to the sink.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SinkCredential {
+    pub credentialtype: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accesstoken: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accesstokenexpiresat: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accesstokentype: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refreshtoken: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refreshtokenurl: Option<Url>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+/// A subscription resource of the Subscriptions API.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Subscription {
+    /// Identifier of the subscription, unique within the subscription manager.
+    pub id: String,
+    /// Source of the events the subscriber is interested in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Types of the events the subscriber is interested in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub types: Option<Vec<String>>,
+    /// Source specific configuration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<HashMap<String, String>>,
+    /// Filters the events must all match to be delivered.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub filters: Vec<FilterExpression>,
+    /// Address the events are delivered to.
+    pub sink: Url,
+    /// Credential used to deliver the events to the sink.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sinkcredential: Option<SinkCredential>,
+    /// Delivery protocol.
+    pub protocol: Protocol,
+    /// Protocol specific delivery settings, e.g. the `topicname` for [`Protocol::Kafka`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocolsettings: Option<HashMap<String, Value>>,
+}
+
+impl Subscription {
+    /// Create a new [`Subscription`] delivering all the events to `sink` using `protocol`.
+    pub fn new(id: impl Into<String>, sink: Url, protocol: Protocol) -> Self {
+        Subscription {
+            id: id.into(),
+            source: None,
+            types: None,
+            config: None,
+            filters: Vec::new(),
+            sink,
+            sinkcredential: None,
+            protocol,
+            protocolsettings: None,
+        }
+    }
+
+    /// Check the subscription complies with the Subscriptions API.
+    pub fn validate(&self) -> Result<()> {
+        if self.id.is_empty() {
+            return Err(Error::EmptyAttribute {
+                attribute_name: "id",
+            });
+        }
+        if matches!(&self.source, Some(s) if s.is_empty()) {
+            return Err(Error::EmptyAttribute {
+                attribute_name: "source",
+            });
+        }
+        if self.types.iter().flatten().any(String::is_empty) {
+            return Err(Error::EmptyAttribute {
+                attribute_name: "types",
+            });
+        }
+        if matches!(&self.sinkcredential, Some(c) if c.credentialtype.is_empty()) {
+            return Err(Error::EmptyAttribute {
+                attribute_name: "credentialtype",
+            });
+        }
+        self.filters.iter().try_for_each(FilterExpression::validate)
+    }
+}
+
+/// Attribute names are made of lower-case ASCII letters and digits.
+pub(crate) fn is_valid_attribute_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn subscription_json() -> Value {
+        json!({
+            "id": "sub-1",
+            "source": "http://example.com/orders",
+            "types": ["com.example.order.created", "com.example.order.deleted"],
+            "config": {"interval": "PT1M"},
+            "filters": [
+                {"all": [
+                    {"exact": {"subject": "orders"}},
+                    {"not": {"suffix": {"type": ".deleted"}}}
+                ]},
+                {"any": [
+                    {"prefix": {"type": "com.example."}},
+                    {"sql": "EXISTS traceparent"}
+                ]}
+            ],
+            "sink": "http://localhost:8080/events",
+            "sinkcredential": {
+                "credentialtype": "ACCESSTOKEN",
+                "accesstoken": "secret"
+            },
+            "protocol": "KAFKA",
+            "protocolsettings": {"topicname": "orders"}
+        })
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        let subscription: Subscription = serde_json::from_value(subscription_json()).unwrap();
+
+        assert_eq!(subscription.protocol, Protocol::Kafka);
+        assert_eq!(subscription.filters.len(), 2);
+        assert_eq!(
+            subscription.filters[0],
+            FilterExpression::All(vec![
+                FilterExpression::Exact(
+                    vec![("subject".to_string(), "orders".to_string())]
+                        .into_iter()
+                        .collect()
+                ),
+                FilterExpression::Not(Box::new(FilterExpression::Suffix(
+                    vec![("type".to_string(), ".deleted".to_string())]
+                        .into_iter()
+                        .collect()
+                )))
+            ])
+        );
+        subscription.validate().unwrap();
+
+        assert_eq!(
+            serde_json::to_value(subscription).unwrap(),
+            subscription_json()
+        );
+    }
+
+    #[test]
+    fn minimal() {
+        let subscription = Subscription::new(
+            "sub-1",
+            Url::parse("nats://localhost:4222").unwrap(),
+            Protocol::Nats,
+        );
+        subscription.validate().unwrap();
+
+        assert_eq!(
+            serde_json::to_value(subscription).unwrap(),
+            json!({
+                "id": "sub-1",
+                "sink": "nats://localhost:4222",
+                "protocol": "NATS"
+            })
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let mut subscription =
+            Subscription::new("", Url::parse("http://localhost").unwrap(), Protocol::Http);
+        assert_eq!(
+            subscription.validate(),
+            Err(Error::EmptyAttribute {
+                attribute_name: "id"
+            })
+        );
+
+        subscription.id = "sub-1".to_string();
+        for filter in [
+            json!({"exact": {}}),
+            json!({"exact": {"type": "a", "subject": "b"}}),
+            json!({"prefix": {"Type": "a"}}),
+            json!({"suffix": {"type": ""}}),
+            json!({"all": []}),
+            json!({"not": {"any": []}}),
+        ] {
+            subscription.filters = vec![serde_json::from_value(filter).unwrap()];
+            assert!(matches!(
+                subscription.validate(),
+                Err(Error::InvalidFilter { .. })
+            ));
+        }
+
+        assert!(
+            serde_json::from_value::<FilterExpression>(json!({"regex": {"type": "a"}})).is_err()
+        );
+        assert!(serde_json::from_value::<Subscription>(json!({
+            "id": "sub-1",
+            "sink": "http://localhost",
+            "protocol": "SMTP"
+        }))
+        .is_err());
+    }
+
+    #[cfg(feature = "sql")]
+    #[test]
+    fn invalid_sql() {
+        let filter = FilterExpression::Sql("type =".to_string());
+
+        assert!(matches!(
+            filter.validate(),
+            Err(Error::InvalidFilter { dialect: "sql", .. })
+        ));
+    }
+}
@@ -0,0 +1,322 @@
+//! This module namespaces the `source` and optionally the `subject` of [`Event`]s with a tenant
+//! prefix, so many tenants can share the same brokers without their events colliding.
+//!
+//! A [`TenantRewriter`] is configured per pipeline: events entering the shared infrastructure
+//! are namespaced on ingress, and the prefix is stripped on egress, the events of the other
+//! tenants being rejected.
+//!
+//! ```
+//! use cloudevents::tenant::TenantRewriter;
+//! use cloudevents::{AttributesReader, EventBuilder, EventBuilderV10};
+//!
+//! let mut event = EventBuilderV10::new()
+//!     .id("0001")
+//!     .ty("example.order.created")
+//!     .source("/orders")
+//!     .subject("order/42")
+//!     .build()
+//!     .unwrap();
+//!
+//! let acme = TenantRewriter::new("urn:tenant:acme:").subject_prefix("acme/");
+//! acme.namespace(&mut event);
+//! assert_eq!(event.source(), "urn:tenant:acme:/orders");
+//! assert_eq!(event.subject(), Some("acme/order/42"));
+//!
+//! let globex = TenantRewriter::new("urn:tenant:globex:");
+//! assert!(globex.strip(&mut event).is_err());
+//!
+//! acme.strip(&mut event).unwrap();
+//! assert_eq!(event.source(), "/orders");
+//! assert_eq!(event.subject(), Some("order/42"));
+//! ```
+//!
+//! [`TenantRewriter::ingress`] and [`TenantRewriter::egress`] wrap an
+//! [`EventSource`](crate::transport::EventSource) or an
+//! [`EventSink`](crate::transport::EventSink) to rewrite the events flowing through it.
+
+use crate::event::{AttributesReader, AttributesWriter};
+use crate::transport::{self, EventSink, EventSource};
+use crate::Event;
+use async_trait::async_trait;
+use snafu::Snafu;
+
+/// Represents an error while stripping the tenant prefix of an event
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("The event source {} does not belong to the tenant", event_source))]
+    ForeignTenant { event_source: String },
+}
+
+/// Result type alias for return values of [`TenantRewriter::strip`]
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Rewrites the `source` and optionally the `subject` of the events of a tenant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TenantRewriter {
+    source_prefix: String,
+    subject_prefix: Option<String>,
+}
+
+impl TenantRewriter {
+    /// Create a new [`TenantRewriter`] prefixing the `source` with `source_prefix`.
+    ///
+    /// The prefix is prepended as is, so it should keep the `source` a valid URI-reference,
+    /// e.g. `urn:tenant:acme:`.
+    pub fn new(source_prefix: impl Into<String>) -> Self {
+        TenantRewriter {
+            source_prefix: source_prefix.into(),
+            subject_prefix: None,
+        }
+    }
+
+    /// Prefix the `subject` with `subject_prefix` too, when the event has one.
+    pub fn subject_prefix(mut self, subject_prefix: impl Into<String>) -> Self {
+        self.subject_prefix = Some(subject_prefix.into());
+        self
+    }
+
+    /// Returns `true` if the `source` of `event` has the prefix of the tenant.
+    pub fn owns(&self, event: &Event) -> bool {
+        event.source().starts_with(&self.source_prefix)
+    }
+
+    /// Prefix the `source` and the `subject` of `event`.
+    ///
+    /// Events already carrying a tenant prefix are prefixed again, so a tenant cannot
+    /// impersonate another one.
+    pub fn namespace(&self, event: &mut Event) {
+        let source = format!("{}{}", self.source_prefix, event.source());
+        event.set_source(source);
+        if let (Some(prefix), Some(subject)) = (&self.subject_prefix, event.subject()) {
+            let subject = format!("{}{}", prefix, subject);
+            event.set_subject(Some(subject));
+        }
+    }
+
+    /// Remove the prefixes added by [`TenantRewriter::namespace`].
+    ///
+    /// Fails with [`Error::ForeignTenant`], leaving the event unchanged, if the `source` does
+    /// not have the prefix of the tenant.
+    pub fn strip(&self, event: &mut Event) -> Result<()> {
+        let source = match event.source().strip_prefix(&self.source_prefix) {
+            Some(source) => source.to_string(),
+            None => {
+                return ForeignTenantSnafu {
+                    event_source: event.source().clone(),
+                }
+                .fail()
+            }
+        };
+        event.set_source(source);
+        let subject = match (&self.subject_prefix, event.subject()) {
+            (Some(prefix), Some(subject)) => subject.strip_prefix(prefix.as_str()),
+            _ => None,
+        };
+        if let Some(subject) = subject.map(str::to_string) {
+            event.set_subject(Some(subject));
+        }
+        Ok(())
+    }
+
+    /// Wrap `inner` to namespace the events it receives or sends.
+    pub fn ingress<T>(&self, inner: T) -> Tenanted<T> {
+        Tenanted {
+            inner,
+            rewriter: self.clone(),
+            direction: Direction::Ingress,
+        }
+    }
+
+    /// Wrap `inner` to strip the prefixes of the events it receives or sends.
+    ///
+    /// The events of other tenants received from a source are acknowledged and skipped, while
+    /// sending them to a sink fails.
+    pub fn egress<T>(&self, inner: T) -> Tenanted<T> {
+        Tenanted {
+            inner,
+            rewriter: self.clone(),
+            direction: Direction::Egress,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Ingress,
+    Egress,
+}
+
+/// Wrapper of an [`EventSource`] or an [`EventSink`] rewriting the events of a tenant, created
+/// by [`TenantRewriter::ingress`] or [`TenantRewriter::egress`].
+///
+/// ```
+/// use cloudevents::tenant::TenantRewriter;
+/// use cloudevents::transport::{EventSink, EventSource, Result};
+///
+/// // Forward the events of the acme tenant to a shared broker
+/// async fn forward(mut source: impl EventSource, shared: impl EventSink) -> Result<()> {
+///     let shared = TenantRewriter::new("urn:tenant:acme:").ingress(shared);
+///     while let Some(received) = source.receive().await {
+///         let (event, ack) = received?;
+///         shared.send(event).await?;
+///         source.ack(ack).await?;
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tenanted<T> {
+    inner: T,
+    rewriter: TenantRewriter,
+    direction: Direction,
+}
+
+impl<T> Tenanted<T> {
+    /// The wrapped source or sink.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    fn rewrite(&self, event: &mut Event) -> Result<()> {
+        match self.direction {
+            Direction::Ingress => {
+                self.rewriter.namespace(event);
+                Ok(())
+            }
+            Direction::Egress => self.rewriter.strip(event),
+        }
+    }
+}
+
+#[async_trait]
+impl<S: EventSink> EventSink for Tenanted<S> {
+    async fn send(&self, mut event: Event) -> transport::Result<()> {
+        self.rewrite(&mut event)
+            .map_err(transport::Error::transport)?;
+        self.inner.send(event).await
+    }
+}
+
+#[async_trait]
+impl<Src: EventSource> EventSource for Tenanted<Src> {
+    type Ack = Src::Ack;
+
+    async fn receive(&mut self) -> Option<transport::Result<(Event, Src::Ack)>> {
+        loop {
+            let (mut event, ack) = match self.inner.receive().await? {
+                Ok(received) => received,
+                Err(e) => return Some(Err(e)),
+            };
+            if self.rewrite(&mut event).is_ok() {
+                return Some(Ok((event, ack)));
+            }
+            if let Err(e) = self.inner.ack(ack).await {
+                return Some(Err(e));
+            }
+        }
+    }
+
+    async fn ack(&mut self, ack: Src::Ack) -> transport::Result<()> {
+        self.inner.ack(ack).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct VecSink(Mutex<Vec<Event>>);
+
+    #[async_trait]
+    impl EventSink for VecSink {
+        async fn send(&self, event: Event) -> transport::Result<()> {
+            self.0.lock().unwrap().push(event);
+            Ok(())
+        }
+    }
+
+    struct VecSource {
+        events: Vec<Event>,
+        acked: Vec<usize>,
+    }
+
+    #[async_trait]
+    impl EventSource for VecSource {
+        type Ack = usize;
+
+        async fn receive(&mut self) -> Option<transport::Result<(Event, usize)>> {
+            let event = self.events.pop()?;
+            Some(Ok((event, self.events.len())))
+        }
+
+        async fn ack(&mut self, ack: usize) -> transport::Result<()> {
+            self.acked.push(ack);
+            Ok(())
+        }
+    }
+
+    fn acme() -> TenantRewriter {
+        TenantRewriter::new("urn:tenant:acme:").subject_prefix("acme/")
+    }
+
+    #[test]
+    fn namespace_and_strip() {
+        let original = fixtures::v10::full_json_data();
+        let mut event = original.clone();
+
+        acme().namespace(&mut event);
+        assert_eq!(
+            event.source(),
+            &format!("urn:tenant:acme:{}", fixtures::source())
+        );
+        assert_eq!(
+            event.subject(),
+            Some(format!("acme/{}", fixtures::subject()).as_str())
+        );
+        assert!(acme().owns(&event));
+
+        acme().strip(&mut event).unwrap();
+        assert_eq!(event, original);
+    }
+
+    #[test]
+    fn foreign_tenant() {
+        let mut event = fixtures::v10::minimal();
+        TenantRewriter::new("urn:tenant:globex:").namespace(&mut event);
+        let namespaced = event.clone();
+
+        assert!(!acme().owns(&event));
+        assert!(matches!(
+            acme().strip(&mut event),
+            Err(Error::ForeignTenant { .. })
+        ));
+        assert_eq!(event, namespaced);
+    }
+
+    #[tokio::test]
+    async fn ingress_sink_and_egress_source() {
+        let sink = acme().ingress(VecSink::default());
+        sink.send(fixtures::v10::minimal()).await.unwrap();
+        let namespaced = sink.inner().0.lock().unwrap().pop().unwrap();
+        assert!(acme().owns(&namespaced));
+
+        let mut foreign = fixtures::v10::minimal();
+        TenantRewriter::new("urn:tenant:globex:").namespace(&mut foreign);
+        let mut source = acme().egress(VecSource {
+            events: vec![namespaced, foreign],
+            acked: Vec::new(),
+        });
+
+        let (event, ack) = source.receive().await.unwrap().unwrap();
+        assert_eq!(event, fixtures::v10::minimal());
+        source.ack(ack).await.unwrap();
+        assert!(source.receive().await.is_none());
+        assert_eq!(source.inner().acked, vec![1, 0]);
+
+        let sink = acme().egress(VecSink::default());
+        assert!(sink.send(fixtures::v10::minimal()).await.is_err());
+    }
+}
@@ -49,13 +49,13 @@ pub fn time() -> DateTime<Utc> {
 }
 
 pub fn string_extension() -> (String, String) {
-    ("string_ex".to_string(), "val".to_string())
+    ("stringex".to_string(), "val".to_string())
 }
 
 pub fn bool_extension() -> (String, bool) {
-    ("bool_ex".to_string(), true)
+    ("boolex".to_string(), true)
 }
 
 pub fn int_extension() -> (String, i64) {
-    ("int_ex".to_string(), 10)
+    ("intex".to_string(), 10)
 }
@@ -0,0 +1,336 @@
+use super::{Error, EventSink, Result};
+use crate::{message, Event};
+use async_trait::async_trait;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+/// Options of a [`BatchingSink`].
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    max_events: usize,
+    max_bytes: usize,
+    linger: Duration,
+    capacity: usize,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        BatchOptions {
+            max_events: 100,
+            max_bytes: 1024 * 1024,
+            linger: Duration::from_millis(10),
+            capacity: 1000,
+        }
+    }
+}
+
+impl BatchOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Flush a batch once it holds `max_events` events. Defaults to 100.
+    pub fn max_events(mut self, max_events: usize) -> Self {
+        self.max_events = max_events.max(1);
+        self
+    }
+
+    /// Flush a batch before it exceeds `max_bytes`, the sum of the sizes of its events in the
+    /// JSON format. Defaults to 1 MiB. A bigger event is sent in a batch on its own.
+    pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Flush a batch at most `linger` after its first event was buffered. Defaults to 10ms.
+    pub fn linger(mut self, linger: Duration) -> Self {
+        self.linger = linger;
+        self
+    }
+
+    /// Number of events which can be queued while a batch is being sent, before
+    /// [`BatchingSink::send`] waits for room. Defaults to 1000.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+}
+
+enum Command {
+    Event(Box<Event>, usize),
+    Flush(oneshot::Sender<Result<()>>),
+}
+
+/// [`EventSink`] wrapper buffering the events and sending them in batches with
+/// [`EventSink::send_all`], so the transports supporting the batch content mode, like
+/// `ReqwestSink`, send each batch in a single message.
+///
+/// A batch is flushed when it reaches [`BatchOptions::max_events`] or
+/// [`BatchOptions::max_bytes`], or when its first event has waited for
+/// [`BatchOptions::linger`]. [`BatchingSink::send`] waits when
+/// [`BatchOptions::capacity`] events are already queued.
+///
+/// The batches are sent by a tokio task, so [`BatchingSink::send`] returns as soon as the event
+/// is queued: delivery failures are reported by the next [`BatchingSink::flush`] or by
+/// [`BatchingSink::shutdown`], which sends the remaining events.
+///
+/// ```
+/// use cloudevents::transport::{BatchOptions, BatchingSink, EventSink, Result};
+/// use cloudevents::Event;
+/// use std::time::Duration;
+///
+/// async fn publish(sink: impl EventSink + 'static, events: Vec<Event>) -> Result<()> {
+///     let sink = BatchingSink::new(
+///         sink,
+///         BatchOptions::new()
+///             .max_events(50)
+///             .linger(Duration::from_millis(100)),
+///     );
+///     for event in events {
+///         sink.send(event).await?;
+///     }
+///     sink.shutdown().await
+/// }
+/// ```
+#[derive(Debug)]
+pub struct BatchingSink {
+    sender: mpsc::Sender<Command>,
+    worker: JoinHandle<Result<()>>,
+}
+
+impl BatchingSink {
+    /// Create a new [`BatchingSink`] sending the batches to `sink`.
+    ///
+    /// Must be called in the context of a tokio runtime.
+    pub fn new<S: EventSink + 'static>(sink: S, options: BatchOptions) -> Self {
+        let (sender, receiver) = mpsc::channel(options.capacity);
+        BatchingSink {
+            sender,
+            worker: tokio::spawn(run(sink, options, receiver)),
+        }
+    }
+
+    /// Send the buffered events, returning the first delivery error since the previous flush.
+    pub async fn flush(&self) -> Result<()> {
+        let (reply, response) = oneshot::channel();
+        self.command(Command::Flush(reply)).await?;
+        response.await.map_err(|_| closed())?
+    }
+
+    /// Send the buffered events and stop the sink, returning the first delivery error since
+    /// the previous flush.
+    pub async fn shutdown(self) -> Result<()> {
+        let BatchingSink { sender, worker } = self;
+        drop(sender);
+        worker.await.map_err(Error::transport)?
+    }
+
+    async fn command(&self, command: Command) -> Result<()> {
+        self.sender.send(command).await.map_err(|_| closed())
+    }
+}
+
+fn closed() -> Error {
+    Error::transport("the batching sink is closed")
+}
+
+#[async_trait]
+impl EventSink for BatchingSink {
+    /// Queue `event` to be sent in the next batch.
+    async fn send(&self, event: Event) -> Result<()> {
+        let size = json_size(&event)?;
+        self.command(Command::Event(Box::new(event), size)).await
+    }
+}
+
+/// Size of `event` in the JSON format, counting the serialized bytes instead of allocating them.
+fn json_size(event: &Event) -> Result<usize> {
+    struct Counter(usize);
+
+    impl std::io::Write for Counter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter(0);
+    serde_json::to_writer(&mut counter, event).map_err(message::Error::from)?;
+    Ok(counter.0)
+}
+
+struct Batch<S> {
+    sink: S,
+    events: Vec<Event>,
+    bytes: usize,
+    error: Option<Error>,
+}
+
+impl<S: EventSink> Batch<S> {
+    async fn flush(&mut self) {
+        if self.events.is_empty() {
+            return;
+        }
+        self.bytes = 0;
+        if let Err(e) = self.sink.send_all(std::mem::take(&mut self.events)).await {
+            self.error.get_or_insert(e);
+        }
+    }
+
+    fn take_result(&mut self) -> Result<()> {
+        self.error.take().map_or(Ok(()), Err)
+    }
+}
+
+async fn run<S: EventSink>(
+    sink: S,
+    options: BatchOptions,
+    mut receiver: mpsc::Receiver<Command>,
+) -> Result<()> {
+    let mut batch = Batch {
+        sink,
+        events: Vec::new(),
+        bytes: 0,
+        error: None,
+    };
+    let mut deadline: Option<Instant> = None;
+    loop {
+        let command = match deadline {
+            Some(deadline) => match tokio::time::timeout_at(deadline, receiver.recv()).await {
+                Ok(command) => command,
+                Err(_) => Some(Command::Flush(oneshot::channel().0)),
+            },
+            None => receiver.recv().await,
+        };
+        match command {
+            Some(Command::Event(event, size)) => {
+                if !batch.events.is_empty() && batch.bytes + size > options.max_bytes {
+                    batch.flush().await;
+                }
+                if batch.events.is_empty() {
+                    deadline = Some(Instant::now() + options.linger);
+                }
+                batch.events.push(*event);
+                batch.bytes += size;
+                if batch.events.len() >= options.max_events || batch.bytes >= options.max_bytes {
+                    batch.flush().await;
+                    deadline = None;
+                }
+            }
+            Some(Command::Flush(reply)) if reply.is_closed() => {
+                // The linger time has elapsed, the error is kept for the next flush
+                batch.flush().await;
+                deadline = None;
+            }
+            Some(Command::Flush(reply)) => {
+                batch.flush().await;
+                deadline = None;
+                let _ = reply.send(batch.take_result());
+            }
+            None => {
+                batch.flush().await;
+                return batch.take_result();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default, Clone)]
+    struct BatchesSink(Arc<Mutex<Vec<Vec<Event>>>>);
+
+    #[async_trait]
+    impl EventSink for BatchesSink {
+        async fn send(&self, event: Event) -> Result<()> {
+            self.send_all(vec![event]).await
+        }
+
+        async fn send_all(&self, events: Vec<Event>) -> Result<()> {
+            if events.iter().any(|e| e.extension("fail").is_some()) {
+                return Err(Error::transport("failed"));
+            }
+            self.0.lock().unwrap().push(events);
+            Ok(())
+        }
+    }
+
+    impl BatchesSink {
+        fn sizes(&self) -> Vec<usize> {
+            self.0.lock().unwrap().iter().map(Vec::len).collect()
+        }
+    }
+
+    #[test]
+    fn json_size_matches_serialization() {
+        let event = fixtures::v10::full_binary_json_data_string_extension();
+        assert_eq!(
+            json_size(&event).unwrap(),
+            serde_json::to_vec(&event).unwrap().len()
+        );
+    }
+
+    #[tokio::test]
+    async fn max_events_and_shutdown() {
+        let batches = BatchesSink::default();
+        let sink = BatchingSink::new(
+            batches.clone(),
+            BatchOptions::new()
+                .max_events(2)
+                .linger(Duration::from_secs(60)),
+        );
+
+        for _ in 0..5 {
+            sink.send(fixtures::v10::minimal()).await.unwrap();
+        }
+        sink.shutdown().await.unwrap();
+
+        assert_eq!(batches.sizes(), vec![2, 2, 1]);
+    }
+
+    #[tokio::test]
+    async fn max_bytes_and_linger() {
+        let size = serde_json::to_vec(&fixtures::v10::minimal()).unwrap().len();
+        let batches = BatchesSink::default();
+        let sink = BatchingSink::new(
+            batches.clone(),
+            BatchOptions::new()
+                .max_bytes(size * 2 + 1)
+                .linger(Duration::from_millis(10)),
+        );
+
+        for _ in 0..3 {
+            sink.send(fixtures::v10::minimal()).await.unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(batches.sizes(), vec![2, 1]);
+        sink.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn flush_reports_errors() {
+        let batches = BatchesSink::default();
+        let sink = BatchingSink::new(batches.clone(), BatchOptions::new());
+
+        let mut failing = fixtures::v10::minimal();
+        failing.set_extension("fail", true);
+        sink.send(failing).await.unwrap();
+        assert!(sink.flush().await.is_err());
+
+        sink.send(fixtures::v10::minimal()).await.unwrap();
+        sink.flush().await.unwrap();
+        assert_eq!(batches.sizes(), vec![1]);
+        sink.shutdown().await.unwrap();
+    }
+}
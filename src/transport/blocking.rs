@@ -0,0 +1,150 @@
+//! Blocking counterparts of the [`EventSink`](super::EventSink) and
+//! [`EventSource`](super::EventSource) traits, for applications and scripts without an async
+//! runtime.
+//!
+//! ```
+//! use cloudevents::transport::blocking::{EventSink, EventSource};
+//! use cloudevents::transport::Result;
+//!
+//! // Forward all the events received from `source` to `sink`,
+//! // acknowledging each event once it has been sent.
+//! fn forward(source: &mut impl EventSource, sink: &impl EventSink) -> Result<()> {
+//!     while let Some(received) = source.receive() {
+//!         let (event, ack) = received?;
+//!         sink.send(event)?;
+//!         source.ack(ack)?;
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
+//! The traits are implemented by:
+//!
+//! | Binding   | [`EventSink`]                    | [`EventSource`]                  |
+//! | --------- | -------------------------------- | -------------------------------- |
+//! | `reqwest` | `reqwest::blocking::ReqwestSink` |                                  |
+//! | `rdkafka` | `rdkafka::blocking::KafkaSink`   | `rdkafka::blocking::KafkaSource` |
+//!
+//! Applications with their own event loop, e.g. games or embedded-like applications, can poll
+//! the events with a [`PollSource`] instead, implemented by the `BaseConsumer` of rdkafka and
+//! the `Connection` of rumqttc:
+//!
+//! ```
+//! use cloudevents::transport::blocking::PollSource;
+//! use std::time::Duration;
+//!
+//! fn tick(source: &mut impl PollSource) {
+//!     // Handle the pending events without blocking the loop
+//!     while let Some(received) = source.poll_event(Duration::ZERO) {
+//!         match received {
+//!             Ok(event) => println!("{}", event),
+//!             Err(e) => eprintln!("{}", e),
+//!         }
+//!     }
+//! }
+//! ```
+
+use super::Result;
+use crate::Event;
+use std::time::Duration;
+
+/// Blocking destination of [`Event`]s.
+pub trait EventSink: Send + Sync {
+    /// Send one event, blocking until it has been sent.
+    fn send(&self, event: Event) -> Result<()>;
+
+    /// Send many events. The default implementation sends them one by one, stopping at the first error.
+    fn send_all(&self, events: Vec<Event>) -> Result<()> {
+        for event in events {
+            self.send(event)?;
+        }
+        Ok(())
+    }
+}
+
+/// Blocking stream of [`Event`]s, to be acknowledged once processed.
+pub trait EventSource: Send {
+    /// Handle to acknowledge a received event.
+    type Ack: Send;
+
+    /// Receive the next event, blocking until one is available, returning `None` when the
+    /// source is exhausted.
+    fn receive(&mut self) -> Option<Result<(Event, Self::Ack)>>;
+
+    /// Acknowledge an event returned by [`Self::receive`].
+    fn ack(&mut self, ack: Self::Ack) -> Result<()>;
+}
+
+/// Source of [`Event`]s polled from an application event loop.
+pub trait PollSource {
+    /// Poll the next event, waiting for at most `timeout`, returning `None` if no event was
+    /// received in time. A zero `timeout` only returns the events already received.
+    fn poll_event(&mut self, timeout: Duration) -> Option<Result<Event>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct VecSink(Mutex<Vec<Event>>);
+
+    impl EventSink for VecSink {
+        fn send(&self, event: Event) -> Result<()> {
+            self.0.lock().unwrap().push(event);
+            Ok(())
+        }
+    }
+
+    struct VecSource {
+        events: Vec<Event>,
+        acked: Vec<usize>,
+    }
+
+    impl EventSource for VecSource {
+        type Ack = usize;
+
+        fn receive(&mut self) -> Option<Result<(Event, usize)>> {
+            let event = self.events.pop()?;
+            Some(Ok((event, self.events.len())))
+        }
+
+        fn ack(&mut self, ack: usize) -> Result<()> {
+            self.acked.push(ack);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn send_all() {
+        let sink = VecSink::default();
+        let events = vec![fixtures::v10::minimal(), fixtures::v03::minimal()];
+
+        sink.send_all(events.clone()).unwrap();
+
+        assert_eq!(*sink.0.lock().unwrap(), events);
+    }
+
+    #[test]
+    fn receive_and_ack() {
+        let mut source = VecSource {
+            events: vec![fixtures::v10::minimal(), fixtures::v03::minimal()],
+            acked: Vec::new(),
+        };
+        let sink = VecSink::default();
+
+        while let Some(received) = source.receive() {
+            let (event, ack) = received.unwrap();
+            sink.send(event).unwrap();
+            source.ack(ack).unwrap();
+        }
+
+        assert_eq!(
+            *sink.0.lock().unwrap(),
+            vec![fixtures::v03::minimal(), fixtures::v10::minimal()]
+        );
+        assert_eq!(source.acked, vec![1, 0]);
+    }
+}
@@ -0,0 +1,478 @@
+use super::{DeadLetter, DeadLetterStage, Error, EventSink, EventSource, Result};
+use crate::{message, AttributesWriter, Event};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::{JoinError, JoinSet};
+use tokio::time::Instant;
+
+/// Type of the events sent to the dead-letter sink of a [`Bridge`] in place of the messages
+/// which the source failed to decode, with the decoding error in the
+/// [`deadletterreason`](super::DEAD_LETTER_REASON_EXTENSION) extension.
+pub static UNDECODABLE_MESSAGE_TYPE: &str = "io.cloudevents.bridge.undecodable";
+
+/// Options of a [`Bridge`].
+#[derive(Debug, Clone)]
+pub struct BridgeOptions {
+    concurrency: usize,
+    receive_retries: u32,
+    retry_backoff: Duration,
+    max_retry_backoff: Duration,
+}
+
+impl Default for BridgeOptions {
+    fn default() -> Self {
+        BridgeOptions {
+            concurrency: 1,
+            receive_retries: 5,
+            retry_backoff: Duration::from_millis(100),
+            max_retry_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+impl BridgeOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send at most `concurrency` events concurrently. Defaults to 1, preserving the order of
+    /// the events and of their acknowledgements.
+    ///
+    /// With a greater concurrency, the events are acknowledged as soon as they are sent, in any
+    /// order: sources acknowledging a position in a log, like Kafka offsets, may then
+    /// acknowledge an event before a previous one has been sent.
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Retry receiving at most `retries` times in a row when the source fails with a transport
+    /// error, before stopping the bridge with the error. Defaults to 5.
+    pub fn receive_retries(mut self, retries: u32) -> Self {
+        self.receive_retries = retries;
+        self
+    }
+
+    /// Wait `backoff` before retrying to receive, doubling the wait after each failure in a row
+    /// up to `max`. Defaults to 100ms, up to 10s.
+    pub fn retry_backoff(mut self, backoff: Duration, max: Duration) -> Self {
+        self.retry_backoff = backoff;
+        self.max_retry_backoff = max;
+        self
+    }
+
+    fn backoff(&self, failures: u32) -> Duration {
+        self.retry_backoff
+            .saturating_mul(2u32.saturating_pow(failures.saturating_sub(1)))
+            .min(self.max_retry_backoff)
+    }
+}
+
+/// Statistics of a [`Bridge`] run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BridgeStats {
+    /// Number of events sent and acknowledged, including the dead-lettered ones.
+    pub forwarded: u64,
+    /// Number of messages which the source failed to decode, sent to the dead-letter sink as
+    /// [`UNDECODABLE_MESSAGE_TYPE`] events.
+    pub undecodable: u64,
+    /// Number of messages which the source failed to decode, skipped because no dead-letter
+    /// sink is set.
+    pub skipped: u64,
+    /// Number of receives retried after a transport error.
+    pub retries: u64,
+}
+
+/// Sends the [`UNDECODABLE_MESSAGE_TYPE`] events to the dead-letter sink of a [`Bridge`].
+type DeadLetterUndecodable<S> =
+    for<'a> fn(&'a S, message::Error) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+
+/// Forwards the events received from an [`EventSource`] to an [`EventSink`], acknowledging each
+/// event once it has been sent, e.g. to bridge MQTT to Kafka or Kafka to HTTP.
+///
+/// The events which cannot be delivered are routed to the dead-letter sink set with
+/// [`Bridge::dead_letter`], along with the messages which the source fails to decode. When the
+/// source fails with a transport error, receiving is retried with a backoff, see
+/// [`BridgeOptions::receive_retries`]. Both are counted in the [`BridgeStats`].
+///
+/// ```
+/// use cloudevents::transport::{Bridge, BridgeOptions, BridgeStats, EventSink, EventSource, Result};
+///
+/// async fn bridge<Src, S, D>(source: Src, sink: S, dead_letter_sink: D) -> Result<BridgeStats>
+/// where
+///     Src: EventSource,
+///     Src::Ack: 'static,
+///     S: EventSink + 'static,
+///     D: EventSink + 'static,
+/// {
+///     Bridge::new(source, sink, BridgeOptions::new().concurrency(16))
+///         .dead_letter(dead_letter_sink)
+///         .run_until(async {
+///             tokio::signal::ctrl_c().await.ok();
+///         })
+///         .await
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Bridge<Src, S> {
+    source: Src,
+    sink: S,
+    options: BridgeOptions,
+    dead_letter_undecodable: Option<DeadLetterUndecodable<S>>,
+}
+
+impl<Src, S> Bridge<Src, S>
+where
+    Src: EventSource,
+    Src::Ack: 'static,
+    S: EventSink + 'static,
+{
+    /// Create a new [`Bridge`] forwarding the events of `source` to `sink`.
+    pub fn new(source: Src, sink: S, options: BridgeOptions) -> Self {
+        Bridge {
+            source,
+            sink,
+            options,
+            dead_letter_undecodable: None,
+        }
+    }
+
+    /// Send the events which cannot be delivered to `dead_letter_sink`, wrapping the sink in a
+    /// [`DeadLetter`], along with an [`UNDECODABLE_MESSAGE_TYPE`] event for each message which
+    /// the source fails to decode.
+    pub fn dead_letter<D: EventSink + 'static>(
+        self,
+        dead_letter_sink: D,
+    ) -> Bridge<Src, DeadLetter<S, D>> {
+        Bridge {
+            source: self.source,
+            sink: DeadLetter::new(self.sink, dead_letter_sink),
+            options: self.options,
+            dead_letter_undecodable: Some(|sink, error| {
+                let mut event = Event::default();
+                event.set_type(UNDECODABLE_MESSAGE_TYPE);
+                Box::pin(sink.dead_letter(event, DeadLetterStage::Decoding, error))
+            }),
+        }
+    }
+
+    /// Forward the events until the source is exhausted.
+    ///
+    /// Must be called in the context of a tokio runtime.
+    pub async fn run(self) -> Result<BridgeStats> {
+        self.run_until(std::future::pending()).await
+    }
+
+    /// Forward the events until the source is exhausted or `shutdown` completes. On shutdown,
+    /// the bridge stops receiving, then waits for the events being sent and acknowledges them.
+    ///
+    /// The bridge stops at the first event which cannot be sent, to neither sink if a
+    /// dead-letter sink is set, or acknowledged, returning the error once the other events
+    /// being sent are acknowledged. It also stops when the source keeps failing with a
+    /// transport error after the retries, or when an undecodable message cannot be sent to the
+    /// dead-letter sink. The events which were not acknowledged are redelivered by
+    /// at-least-once sources.
+    ///
+    /// [`EventSource::receive`] is cancelled on shutdown, and when an event has been sent
+    /// while waiting for the next one, so it must be cancel safe, as for the sources of this
+    /// crate.
+    ///
+    /// Must be called in the context of a tokio runtime.
+    pub async fn run_until<F: Future<Output = ()>>(self, shutdown: F) -> Result<BridgeStats> {
+        let Bridge {
+            mut source,
+            sink,
+            options,
+            dead_letter_undecodable,
+        } = self;
+        let sink = Arc::new(sink);
+        let mut in_flight = JoinSet::new();
+        let mut stats = BridgeStats::default();
+        let mut result = Ok(());
+        // Consecutive transport errors of the source, and whether the bridge waits to retry
+        let mut failures = 0;
+        let mut waiting = false;
+        let retry = tokio::time::sleep(Duration::ZERO);
+        tokio::pin!(shutdown, retry);
+
+        while result.is_ok() {
+            let full = in_flight.len() >= options.concurrency;
+            tokio::select! {
+                biased;
+                _ = &mut shutdown => break,
+                Some(sent) = in_flight.join_next() => {
+                    result = settle(&mut source, sent, &mut stats).await;
+                }
+                _ = &mut retry, if waiting => waiting = false,
+                received = source.receive(), if !full && !waiting => match received {
+                    Some(Ok((event, ack))) => {
+                        failures = 0;
+                        let sink = sink.clone();
+                        in_flight.spawn(async move { (sink.send(event).await, ack) });
+                    }
+                    Some(Err(Error::MessageError { source: error })) => {
+                        failures = 0;
+                        match dead_letter_undecodable {
+                            Some(dead_letter) => {
+                                result = dead_letter(&sink, error).await;
+                                stats.undecodable += 1;
+                            }
+                            None => stats.skipped += 1,
+                        }
+                    }
+                    Some(Err(e)) if failures >= options.receive_retries => result = Err(e),
+                    Some(Err(_)) => {
+                        failures += 1;
+                        stats.retries += 1;
+                        retry.as_mut().reset(Instant::now() + options.backoff(failures));
+                        waiting = true;
+                    }
+                    None => break,
+                },
+            }
+        }
+
+        while let Some(sent) = in_flight.join_next().await {
+            let settled = settle(&mut source, sent, &mut stats).await;
+            if result.is_ok() {
+                result = settled;
+            }
+        }
+        result.map(|()| stats)
+    }
+}
+
+async fn settle<Src: EventSource>(
+    source: &mut Src,
+    sent: std::result::Result<(Result<()>, Src::Ack), JoinError>,
+    stats: &mut BridgeStats,
+) -> Result<()> {
+    let (result, ack) = sent.map_err(Error::transport)?;
+    result?;
+    source.ack(ack).await?;
+    stats.forwarded += 1;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::AttributesReader;
+    use crate::test::fixtures;
+    use crate::transport::{DEAD_LETTER_REASON_EXTENSION, DEAD_LETTER_STAGE_EXTENSION};
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    #[derive(Default, Clone)]
+    struct VecSink(Arc<Mutex<Vec<Event>>>);
+
+    #[async_trait]
+    impl EventSink for VecSink {
+        async fn send(&self, event: Event) -> Result<()> {
+            // The dead-lettered events are accepted, to be collected by a dead-letter VecSink
+            if event.extension("fail").is_some() && event.extension("deadletterstage").is_none() {
+                return Err(Error::transport("unreachable"));
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            self.0.lock().unwrap().push(event);
+            Ok(())
+        }
+    }
+
+    struct VecSource {
+        events: Vec<Option<Event>>,
+        acked: Arc<Mutex<Vec<usize>>>,
+        transport_errors: u32,
+    }
+
+    impl VecSource {
+        fn new(events: Vec<Option<Event>>) -> Self {
+            VecSource {
+                events,
+                acked: Default::default(),
+                transport_errors: 0,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl EventSource for VecSource {
+        type Ack = usize;
+
+        async fn receive(&mut self) -> Option<Result<(Event, usize)>> {
+            if self.transport_errors > 0 {
+                self.transport_errors -= 1;
+                return Some(Err(Error::transport("connection lost")));
+            }
+            match self.events.pop()? {
+                Some(event) => Some(Ok((event, self.events.len()))),
+                None => Some(Err(message::Error::WrongEncoding {}.into())),
+            }
+        }
+
+        async fn ack(&mut self, ack: usize) -> Result<()> {
+            self.acked.lock().unwrap().push(ack);
+            Ok(())
+        }
+    }
+
+    fn failing() -> Event {
+        let mut event = fixtures::v10::minimal();
+        event.set_extension("fail", true);
+        event
+    }
+
+    #[tokio::test]
+    async fn forward_in_order() {
+        let source = VecSource::new(vec![
+            Some(fixtures::v03::minimal()),
+            None,
+            Some(fixtures::v10::minimal()),
+        ]);
+        let acked = source.acked.clone();
+        let sink = VecSink::default();
+
+        let stats = Bridge::new(source, sink.clone(), BridgeOptions::new())
+            .run()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            stats,
+            BridgeStats {
+                forwarded: 2,
+                skipped: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            *sink.0.lock().unwrap(),
+            vec![fixtures::v10::minimal(), fixtures::v03::minimal()]
+        );
+        assert_eq!(*acked.lock().unwrap(), vec![2, 0]);
+    }
+
+    #[tokio::test]
+    async fn concurrency_and_dead_letter() {
+        let mut events = vec![Some(failing()); 2];
+        events.extend((0..8).map(|_| Some(fixtures::v10::minimal())));
+        let source = VecSource::new(events);
+        let acked = source.acked.clone();
+        let sink = VecSink::default();
+        let dead_letter_sink = VecSink::default();
+
+        let stats = Bridge::new(source, sink.clone(), BridgeOptions::new().concurrency(4))
+            .dead_letter(dead_letter_sink.clone())
+            .run()
+            .await
+            .unwrap();
+
+        assert_eq!(stats.forwarded, 10);
+        assert_eq!(sink.0.lock().unwrap().len(), 8);
+        assert_eq!(dead_letter_sink.0.lock().unwrap().len(), 2);
+        let mut acked = acked.lock().unwrap().clone();
+        acked.sort_unstable();
+        assert_eq!(acked, (0..10).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn undecodable_dead_lettered() {
+        let source = VecSource::new(vec![None, Some(fixtures::v10::minimal())]);
+        let acked = source.acked.clone();
+        let sink = VecSink::default();
+        let dead_letter_sink = VecSink::default();
+
+        let stats = Bridge::new(source, sink.clone(), BridgeOptions::new())
+            .dead_letter(dead_letter_sink.clone())
+            .run()
+            .await
+            .unwrap();
+
+        assert_eq!(stats.forwarded, 1);
+        assert_eq!(stats.undecodable, 1);
+        assert_eq!(stats.skipped, 0);
+        assert_eq!(*acked.lock().unwrap(), vec![1]);
+        let dead_lettered = dead_letter_sink.0.lock().unwrap();
+        assert_eq!(dead_lettered.len(), 1);
+        assert_eq!(dead_lettered[0].ty(), UNDECODABLE_MESSAGE_TYPE);
+        assert_eq!(
+            dead_lettered[0].extension(DEAD_LETTER_STAGE_EXTENSION),
+            Some(&"decoding".into())
+        );
+        assert!(dead_lettered[0]
+            .extension(DEAD_LETTER_REASON_EXTENSION)
+            .is_some());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn receive_retries() {
+        let mut source = VecSource::new(vec![Some(fixtures::v10::minimal())]);
+        source.transport_errors = 3;
+        let start = Instant::now();
+
+        let stats = Bridge::new(source, VecSink::default(), BridgeOptions::new())
+            .run()
+            .await
+            .unwrap();
+
+        assert_eq!(stats.forwarded, 1);
+        assert_eq!(stats.retries, 3);
+        // 100ms, 200ms and 400ms of backoff, then 10ms to send the event
+        assert_eq!(start.elapsed(), Duration::from_millis(710));
+
+        let mut source = VecSource::new(vec![Some(fixtures::v10::minimal())]);
+        source.transport_errors = 3;
+        let result = Bridge::new(
+            source,
+            VecSink::default(),
+            BridgeOptions::new().receive_retries(2),
+        )
+        .run()
+        .await;
+
+        assert!(matches!(result, Err(Error::TransportError { .. })));
+    }
+
+    #[tokio::test]
+    async fn delivery_failure() {
+        let source = VecSource::new(vec![
+            Some(fixtures::v10::minimal()),
+            Some(failing()),
+            Some(fixtures::v10::minimal()),
+        ]);
+        let acked = source.acked.clone();
+
+        let result = Bridge::new(source, VecSink::default(), BridgeOptions::new())
+            .run()
+            .await;
+
+        assert!(matches!(result, Err(Error::TransportError { .. })));
+        assert_eq!(*acked.lock().unwrap(), vec![2]);
+    }
+
+    #[tokio::test]
+    async fn shutdown() {
+        let source = VecSource::new(vec![Some(fixtures::v10::minimal()); 100]);
+        let acked = source.acked.clone();
+        let sink = VecSink::default();
+        let (trigger, signal) = tokio::sync::oneshot::channel::<()>();
+
+        let bridge = tokio::spawn(
+            Bridge::new(source, sink.clone(), BridgeOptions::new().concurrency(2)).run_until(
+                async {
+                    signal.await.ok();
+                },
+            ),
+        );
+        tokio::time::sleep(Duration::from_millis(35)).await;
+        trigger.send(()).unwrap();
+        let stats = bridge.await.unwrap().unwrap();
+
+        assert!(stats.forwarded > 0 && stats.forwarded < 100);
+        assert_eq!(sink.0.lock().unwrap().len() as u64, stats.forwarded);
+        assert_eq!(acked.lock().unwrap().len() as u64, stats.forwarded);
+    }
+}
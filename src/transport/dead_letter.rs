@@ -0,0 +1,206 @@
+use super::{EventSink, Result};
+use crate::Event;
+use async_trait::async_trait;
+use std::future::Future;
+
+/// Extension set on dead-lettered events with the description of the failure.
+pub static DEAD_LETTER_REASON_EXTENSION: &str = "deadletterreason";
+/// Extension set on dead-lettered events with the stage of the failure, see
+/// [`DeadLetterStage`].
+pub static DEAD_LETTER_STAGE_EXTENSION: &str = "deadletterstage";
+
+/// Stage of the failure which caused an event to be dead-lettered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadLetterStage {
+    /// The event could not be processed by the application.
+    Processing,
+    /// The event could not be delivered to the sink.
+    Delivery,
+    /// The message could not be decoded as an event.
+    Decoding,
+}
+
+impl DeadLetterStage {
+    fn as_str(self) -> &'static str {
+        match self {
+            DeadLetterStage::Processing => "processing",
+            DeadLetterStage::Delivery => "delivery",
+            DeadLetterStage::Decoding => "decoding",
+        }
+    }
+}
+
+/// [`EventSink`] wrapper forwarding the events which could not be delivered or processed
+/// to a dead-letter sink.
+///
+/// The forwarded events are the original events, annotated with the
+/// [`deadletterreason`](DEAD_LETTER_REASON_EXTENSION) and
+/// [`deadletterstage`](DEAD_LETTER_STAGE_EXTENSION) extensions.
+///
+/// ```
+/// use cloudevents::transport::{DeadLetter, EventSink, Result};
+/// use cloudevents::Event;
+///
+/// async fn process(
+///     sink: impl EventSink,
+///     dead_letter_sink: impl EventSink,
+///     event: Event,
+/// ) -> Result<()> {
+///     let sink = DeadLetter::new(sink, dead_letter_sink);
+///
+///     // Events failing the validation are dead-lettered without reaching the sink
+///     sink.process(event, |event| async move {
+///         if event.data().is_none() {
+///             return Err("missing data");
+///         }
+///         Ok(event)
+///     })
+///     .await
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeadLetter<S, D> {
+    sink: S,
+    dead_letter_sink: D,
+}
+
+impl<S: EventSink, D: EventSink> DeadLetter<S, D> {
+    /// Create a new [`DeadLetter`] sending the events to `sink`, and those failing to
+    /// `dead_letter_sink`.
+    pub fn new(sink: S, dead_letter_sink: D) -> Self {
+        DeadLetter {
+            sink,
+            dead_letter_sink,
+        }
+    }
+
+    /// The wrapped sink.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// The dead-letter sink.
+    pub fn dead_letter_sink(&self) -> &D {
+        &self.dead_letter_sink
+    }
+
+    /// Annotate `event` with the failure `reason` and send it to the dead-letter sink.
+    pub async fn dead_letter(
+        &self,
+        mut event: Event,
+        stage: DeadLetterStage,
+        reason: impl ToString,
+    ) -> Result<()> {
+        annotate_dead_letter(&mut event, stage, reason);
+        self.dead_letter_sink.send(event).await
+    }
+
+    /// Process `event` with `f` and send the resulting event to the sink.
+    ///
+    /// If `f` fails, the original event is sent to the dead-letter sink instead. Returns an
+    /// error only if the event could be sent to neither sink.
+    pub async fn process<F, Fut, E>(&self, event: Event, f: F) -> Result<()>
+    where
+        F: FnOnce(Event) -> Fut + Send,
+        Fut: Future<Output = std::result::Result<Event, E>> + Send,
+        E: std::fmt::Display,
+    {
+        match f(event.clone()).await {
+            Ok(processed) => self.send(processed).await,
+            Err(e) => {
+                self.dead_letter(event, DeadLetterStage::Processing, e)
+                    .await
+            }
+        }
+    }
+}
+
+/// Annotate `event` with the failure `stage` and `reason`, before it is dead-lettered.
+pub(crate) fn annotate_dead_letter(
+    event: &mut Event,
+    stage: DeadLetterStage,
+    reason: impl ToString,
+) {
+    event.set_extension(DEAD_LETTER_REASON_EXTENSION, reason.to_string());
+    event.set_extension(DEAD_LETTER_STAGE_EXTENSION, stage.as_str());
+}
+
+#[async_trait]
+impl<S: EventSink, D: EventSink> EventSink for DeadLetter<S, D> {
+    /// Send `event` to the sink, or to the dead-letter sink if the delivery fails. Returns an
+    /// error only if the event could be sent to neither sink.
+    async fn send(&self, event: Event) -> Result<()> {
+        match self.sink.send(event.clone()).await {
+            Ok(()) => Ok(()),
+            Err(e) => self.dead_letter(event, DeadLetterStage::Delivery, e).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use crate::transport::Error;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct VecSink(Mutex<Vec<Event>>);
+
+    #[async_trait]
+    impl EventSink for VecSink {
+        async fn send(&self, event: Event) -> Result<()> {
+            self.0.lock().unwrap().push(event);
+            Ok(())
+        }
+    }
+
+    struct FailingSink;
+
+    #[async_trait]
+    impl EventSink for FailingSink {
+        async fn send(&self, _: Event) -> Result<()> {
+            Err(Error::transport("unreachable"))
+        }
+    }
+
+    #[tokio::test]
+    async fn delivery_failure() {
+        let sink = DeadLetter::new(FailingSink, VecSink::default());
+
+        sink.send(fixtures::v10::minimal()).await.unwrap();
+
+        let mut expected = fixtures::v10::minimal();
+        expected.set_extension("deadletterreason", "Transport error: unreachable");
+        expected.set_extension("deadletterstage", "delivery");
+        assert_eq!(*sink.dead_letter_sink().0.lock().unwrap(), vec![expected]);
+
+        let sink = DeadLetter::new(FailingSink, FailingSink);
+        assert!(sink.send(fixtures::v10::minimal()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn processing_failure() {
+        let sink = DeadLetter::new(VecSink::default(), VecSink::default());
+
+        for event in [fixtures::v10::minimal(), fixtures::v10::full_json_data()] {
+            sink.process(event, |event| async move {
+                match event.data() {
+                    Some(_) => Ok(event),
+                    None => Err("missing data"),
+                }
+            })
+            .await
+            .unwrap();
+        }
+
+        let mut expected = fixtures::v10::minimal();
+        expected.set_extension("deadletterreason", "missing data");
+        expected.set_extension("deadletterstage", "processing");
+        assert_eq!(
+            *sink.sink().0.lock().unwrap(),
+            vec![fixtures::v10::full_json_data()]
+        );
+        assert_eq!(*sink.dead_letter_sink().0.lock().unwrap(), vec![expected]);
+    }
+}
@@ -0,0 +1,209 @@
+//! This module provides the [`EventSink`] and [`EventSource`] traits, abstracting over the
+//! transports of the protocol bindings, so application code can be written once and the
+//! transport can be swapped via configuration.
+//!
+//! ```
+//! use cloudevents::transport::{EventSink, EventSource, Result};
+//!
+//! // Forward all the events received from `source` to `sink`,
+//! // acknowledging each event once it has been sent.
+//! async fn forward(source: &mut impl EventSource, sink: &impl EventSink) -> Result<()> {
+//!     while let Some(received) = source.receive().await {
+//!         let (event, ack) = received?;
+//!         sink.send(event).await?;
+//!         source.ack(ack).await?;
+//!     }
+//!     Ok(())
+//! }
+//! ```
+//!
+//! The traits are implemented by:
+//!
+//! | Binding   | [`EventSink`]        | [`EventSource`]         |
+//! | --------- | -------------------- | ----------------------- |
+//! | `reqwest` | `ReqwestSink`        |                         |
+//! | `rdkafka` | `KafkaSink`          | `KafkaSource`           |
+//! | `nats`    | `NatsSink`           | `NatsSource`            |
+//! | `lapin`   | `LapinSink`          | `LapinSource`           |
+//! | `amqprs`  | `AmqprsSink`         | `AmqprsSource`          |
+//! | `pipe`    | `PipeSink`           | `PipeSource`            |
+//! | `rumqttc` | `MqttSink`           |                         |
+//!
+//! Any sink can be wrapped in a [`DeadLetter`], forwarding the events which could not be
+//! delivered or processed to a dead-letter sink.
+//!
+//! With the `batching` feature, any sink can be wrapped in a [`BatchingSink`], buffering the
+//! events and sending them in batches.
+//!
+//! With the `rate-limit` feature, any sink can be wrapped in a [`RateLimited`] sink, limiting
+//! the rate of events and the number of concurrent sends.
+//!
+//! With the `replay` feature, any sink can be wrapped in a [`ReplayingSink`], buffering the
+//! events in memory or in a file until they are delivered, and replaying them after a
+//! connection loss.
+//!
+//! With the `bridge` feature, a [`Bridge`] forwards the events of any source to any sink,
+//! sending them concurrently and routing the failed deliveries to a dead-letter sink.
+//!
+//! With the `blocking` feature, the [`blocking`] module provides blocking versions of the
+//! traits, implemented by the `reqwest` and `rdkafka` bindings.
+
+use crate::{message, Event};
+use async_trait::async_trait;
+use snafu::Snafu;
+
+#[cfg(feature = "batching")]
+mod batching;
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "bridge")]
+mod bridge;
+mod dead_letter;
+#[cfg(feature = "rate-limit")]
+mod rate_limit;
+#[cfg(feature = "replay")]
+mod replay;
+
+#[cfg_attr(docsrs, doc(cfg(feature = "batching")))]
+#[cfg(feature = "batching")]
+pub use batching::{BatchOptions, BatchingSink};
+#[cfg_attr(docsrs, doc(cfg(feature = "bridge")))]
+#[cfg(feature = "bridge")]
+pub use bridge::{Bridge, BridgeOptions, BridgeStats, UNDECODABLE_MESSAGE_TYPE};
+#[cfg(feature = "expiry")]
+pub(crate) use dead_letter::annotate_dead_letter;
+pub use dead_letter::{
+    DeadLetter, DeadLetterStage, DEAD_LETTER_REASON_EXTENSION, DEAD_LETTER_STAGE_EXTENSION,
+};
+#[cfg_attr(docsrs, doc(cfg(feature = "rate-limit")))]
+#[cfg(feature = "rate-limit")]
+pub use rate_limit::{OverflowPolicy, RateLimitOptions, RateLimited};
+#[cfg_attr(docsrs, doc(cfg(feature = "replay")))]
+#[cfg(feature = "replay")]
+pub use replay::{FileReplayBuffer, MemoryReplayBuffer, ReplayBuffer, ReplayingSink};
+
+/// Represents an error while sending or receiving events through a transport
+#[derive(Debug, Snafu)]
+pub enum Error {
+    #[snafu(display("Error while serializing/deserializing the event: {}", source))]
+    #[snafu(context(false))]
+    MessageError { source: message::Error },
+    #[snafu(display("Transport error: {}", source))]
+    TransportError {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[snafu(display("The event was rejected by the rate limiter"))]
+    RateLimited {},
+    #[snafu(display("The replay buffer is full"))]
+    ReplayBufferFull {},
+}
+
+impl Error {
+    /// Wrap an error of the underlying transport library.
+    pub fn transport(source: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Error::TransportError {
+            source: source.into(),
+        }
+    }
+}
+
+/// Result type alias for return values of [`EventSink`] and [`EventSource`]
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Destination of [`Event`]s.
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    /// Send one event.
+    async fn send(&self, event: Event) -> Result<()>;
+
+    /// Send many events. The default implementation sends them one by one, stopping at the first error.
+    async fn send_all(&self, events: Vec<Event>) -> Result<()> {
+        for event in events {
+            self.send(event).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Stream of [`Event`]s, to be acknowledged once processed.
+#[async_trait]
+pub trait EventSource: Send {
+    /// Handle to acknowledge a received event.
+    type Ack: Send;
+
+    /// Receive the next event, returning `None` when the source is exhausted.
+    async fn receive(&mut self) -> Option<Result<(Event, Self::Ack)>>;
+
+    /// Acknowledge an event returned by [`Self::receive`].
+    async fn ack(&mut self, ack: Self::Ack) -> Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct VecSink(Mutex<Vec<Event>>);
+
+    #[async_trait]
+    impl EventSink for VecSink {
+        async fn send(&self, event: Event) -> Result<()> {
+            self.0.lock().unwrap().push(event);
+            Ok(())
+        }
+    }
+
+    struct VecSource {
+        events: Vec<Event>,
+        acked: Vec<usize>,
+    }
+
+    #[async_trait]
+    impl EventSource for VecSource {
+        type Ack = usize;
+
+        async fn receive(&mut self) -> Option<Result<(Event, usize)>> {
+            let event = self.events.pop()?;
+            Some(Ok((event, self.events.len())))
+        }
+
+        async fn ack(&mut self, ack: usize) -> Result<()> {
+            self.acked.push(ack);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn send_all() {
+        let sink = VecSink::default();
+        let events = vec![fixtures::v10::minimal(), fixtures::v03::minimal()];
+
+        sink.send_all(events.clone()).await.unwrap();
+
+        assert_eq!(*sink.0.lock().unwrap(), events);
+    }
+
+    #[tokio::test]
+    async fn receive_and_ack() {
+        let mut source = VecSource {
+            events: vec![fixtures::v10::minimal(), fixtures::v03::minimal()],
+            acked: Vec::new(),
+        };
+        let sink = VecSink::default();
+
+        while let Some(received) = source.receive().await {
+            let (event, ack) = received.unwrap();
+            sink.send(event).await.unwrap();
+            source.ack(ack).await.unwrap();
+        }
+
+        assert_eq!(
+            *sink.0.lock().unwrap(),
+            vec![fixtures::v03::minimal(), fixtures::v10::minimal()]
+        );
+        assert_eq!(source.acked, vec![1, 0]);
+    }
+}
@@ -0,0 +1,397 @@
+use super::{Error, EventSink, Result};
+use crate::Event;
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::{oneshot, OwnedSemaphorePermit, Semaphore};
+use tokio::time::Instant;
+
+/// What a [`RateLimited`] sink does with an event sent while the rate or the in-flight limit
+/// is reached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Wait until the event can be sent.
+    #[default]
+    Block,
+    /// Wait until the event can be sent, but when [`RateLimitOptions::max_queued`] sends are
+    /// already waiting, drop the event waiting for the longest time, failing its send with
+    /// [`Error::RateLimited`].
+    DropOldest,
+    /// Fail the send with [`Error::RateLimited`] right away.
+    Error,
+}
+
+/// Options of a [`RateLimited`] sink.
+#[derive(Debug, Clone)]
+pub struct RateLimitOptions {
+    interval: Option<Duration>,
+    max_in_flight: Option<usize>,
+    max_queued: usize,
+    overflow: OverflowPolicy,
+}
+
+impl Default for RateLimitOptions {
+    fn default() -> Self {
+        RateLimitOptions {
+            interval: None,
+            max_in_flight: None,
+            max_queued: 1000,
+            overflow: OverflowPolicy::default(),
+        }
+    }
+}
+
+impl RateLimitOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send at most `events_per_second` events per second, evenly spaced. Unlimited by default.
+    pub fn rate(mut self, events_per_second: u32) -> Self {
+        self.interval = Some(Duration::from_secs(1) / events_per_second.max(1));
+        self
+    }
+
+    /// Send at most `max_in_flight` events concurrently. Unlimited by default.
+    pub fn max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = Some(max_in_flight.max(1));
+        self
+    }
+
+    /// Number of sends which can wait with [`OverflowPolicy::DropOldest`]. Defaults to 1000.
+    pub fn max_queued(mut self, max_queued: usize) -> Self {
+        self.max_queued = max_queued;
+        self
+    }
+
+    /// What to do with the events sent while a limit is reached. Defaults to
+    /// [`OverflowPolicy::Block`].
+    pub fn overflow(mut self, overflow: OverflowPolicy) -> Self {
+        self.overflow = overflow;
+        self
+    }
+}
+
+/// [`EventSink`] wrapper limiting the rate of events and the number of concurrent sends
+/// to the wrapped sink, e.g. a `ReqwestSink` or a `KafkaSink`, so bursty producers can't
+/// overwhelm the downstream brokers.
+///
+/// ```
+/// use cloudevents::transport::{EventSink, OverflowPolicy, RateLimitOptions, RateLimited};
+///
+/// fn limit(sink: impl EventSink) -> impl EventSink {
+///     RateLimited::new(
+///         sink,
+///         RateLimitOptions::new()
+///             .rate(100)
+///             .max_in_flight(10)
+///             .overflow(OverflowPolicy::Error),
+///     )
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RateLimited<S> {
+    sink: S,
+    interval: Option<Duration>,
+    slots: Mutex<Slots>,
+    in_flight: Option<Arc<Semaphore>>,
+    max_queued: usize,
+    overflow: OverflowPolicy,
+    queue: Mutex<Queue>,
+}
+
+/// Schedule of the slots allowed by the rate.
+#[derive(Debug)]
+struct Slots {
+    /// Slot given to the next send.
+    next: Instant,
+    /// Slots reserved by the sends cancelled before their slot came, given to the next sends.
+    released: Vec<Instant>,
+}
+
+impl Slots {
+    /// Give back `slot`, reserved by a cancelled send.
+    fn release(&mut self, slot: Instant, interval: Duration) {
+        if slot + interval != self.next {
+            self.released.push(slot);
+            return;
+        }
+        self.next = slot;
+        // Roll back the slots released before it as well
+        while let Some(i) = self
+            .released
+            .iter()
+            .position(|s| *s + interval == self.next)
+        {
+            self.next = self.released.swap_remove(i);
+        }
+    }
+}
+
+/// Slot reserved by a send, released if the send is cancelled before the slot came.
+struct SlotGuard<'a> {
+    slots: &'a Mutex<Slots>,
+    slot: Instant,
+    interval: Duration,
+}
+
+impl SlotGuard<'_> {
+    async fn wait(self) {
+        tokio::time::sleep_until(self.slot).await;
+        std::mem::forget(self);
+    }
+}
+
+impl Drop for SlotGuard<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut slots) = self.slots.lock() {
+            slots.release(self.slot, self.interval);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Queue {
+    next_id: u64,
+    waiting: VecDeque<(u64, oneshot::Sender<()>)>,
+}
+
+impl<S: EventSink> RateLimited<S> {
+    /// Create a new [`RateLimited`] sink sending the events to `sink`.
+    pub fn new(sink: S, options: RateLimitOptions) -> Self {
+        RateLimited {
+            sink,
+            interval: options.interval,
+            slots: Mutex::new(Slots {
+                next: Instant::now(),
+                released: Vec::new(),
+            }),
+            in_flight: options.max_in_flight.map(|n| Arc::new(Semaphore::new(n))),
+            max_queued: options.max_queued,
+            overflow: options.overflow,
+            queue: Mutex::default(),
+        }
+    }
+
+    /// The wrapped sink.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Reserve the next slot allowed by the rate, if any, preferring the slots released by
+    /// the cancelled sends. With `now_only`, fail instead of reserving a slot in the future.
+    fn reserve_slot(&self, now_only: bool) -> Result<Option<SlotGuard<'_>>> {
+        let interval = match self.interval {
+            Some(interval) => interval,
+            None => return Ok(None),
+        };
+        let now = Instant::now();
+        let mut slots = self.slots.lock().unwrap();
+        slots.released.retain(|slot| *slot >= now);
+        let earliest_released = (0..slots.released.len()).min_by_key(|&i| slots.released[i]);
+        let slot = match earliest_released {
+            Some(i) => slots.released[i],
+            None => slots.next.max(now),
+        };
+        if now_only && slot > now {
+            return Err(Error::RateLimited {});
+        }
+        match earliest_released {
+            Some(i) => {
+                slots.released.swap_remove(i);
+            }
+            None => slots.next = slot + interval,
+        }
+        Ok(Some(SlotGuard {
+            slots: &self.slots,
+            slot,
+            interval,
+        }))
+    }
+
+    async fn wait(&self) -> Option<OwnedSemaphorePermit> {
+        if let Ok(Some(slot)) = self.reserve_slot(false) {
+            slot.wait().await;
+        }
+        match &self.in_flight {
+            Some(in_flight) => in_flight.clone().acquire_owned().await.ok(),
+            None => None,
+        }
+    }
+
+    fn try_acquire(&self) -> Result<Option<OwnedSemaphorePermit>> {
+        let permit = match &self.in_flight {
+            Some(in_flight) => Some(
+                in_flight
+                    .clone()
+                    .try_acquire_owned()
+                    .map_err(|_| Error::RateLimited {})?,
+            ),
+            None => None,
+        };
+        if let Some(slot) = self.reserve_slot(true)? {
+            std::mem::forget(slot);
+        }
+        Ok(permit)
+    }
+
+    async fn wait_or_drop(&self) -> Result<Option<OwnedSemaphorePermit>> {
+        let (cancel, cancelled) = oneshot::channel();
+        let id = {
+            let mut queue = self.queue.lock().unwrap();
+            let id = queue.next_id;
+            queue.next_id += 1;
+            queue.waiting.push_back((id, cancel));
+            if queue.waiting.len() > self.max_queued {
+                if let Some((_, oldest)) = queue.waiting.pop_front() {
+                    let _ = oldest.send(());
+                }
+            }
+            id
+        };
+        let result = tokio::select! {
+            permit = self.wait() => Ok(permit),
+            _ = cancelled => Err(Error::RateLimited {}),
+        };
+        self.queue
+            .lock()
+            .unwrap()
+            .waiting
+            .retain(|(waiting_id, _)| *waiting_id != id);
+        result
+    }
+}
+
+#[async_trait]
+impl<S: EventSink> EventSink for RateLimited<S> {
+    async fn send(&self, event: Event) -> Result<()> {
+        let _permit = match self.overflow {
+            OverflowPolicy::Block => self.wait().await,
+            OverflowPolicy::DropOldest => self.wait_or_drop().await?,
+            OverflowPolicy::Error => self.try_acquire()?,
+        };
+        self.sink.send(event).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Default)]
+    struct SlowSink {
+        in_flight: AtomicUsize,
+        max_in_flight: AtomicUsize,
+        sent: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EventSink for SlowSink {
+        async fn send(&self, _: Event) -> Result<()> {
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            self.sent.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn block() {
+        let sink = RateLimited::new(
+            SlowSink::default(),
+            RateLimitOptions::new().rate(100).max_in_flight(2),
+        );
+
+        let start = Instant::now();
+        let send = || sink.send(fixtures::v10::minimal());
+        let results = tokio::join!(send(), send(), send(), send(), send(), send());
+        assert!(results.0.is_ok() && results.5.is_ok());
+
+        // The 6th event is sent 50ms after the 1st one, at 100 events/sec
+        assert_eq!(start.elapsed(), Duration::from_millis(70));
+        assert_eq!(sink.sink().sent.load(Ordering::SeqCst), 6);
+        assert_eq!(sink.sink().max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn error() {
+        let sink = RateLimited::new(
+            SlowSink::default(),
+            RateLimitOptions::new()
+                .max_in_flight(1)
+                .overflow(OverflowPolicy::Error),
+        );
+
+        let send = || sink.send(fixtures::v10::minimal());
+        let results = tokio::join!(send(), send(), send());
+
+        assert!(results.0.is_ok());
+        assert!(matches!(results.1, Err(Error::RateLimited {})));
+        assert!(matches!(results.2, Err(Error::RateLimited {})));
+        sink.send(fixtures::v10::minimal()).await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn cancelled_send_releases_its_slot() {
+        let sink = RateLimited::new(SlowSink::default(), RateLimitOptions::new().rate(10));
+
+        let start = Instant::now();
+        sink.send(fixtures::v10::minimal()).await.unwrap();
+        // Reserves the slot at 100ms, then is cancelled
+        let cancelled = tokio::time::timeout(
+            Duration::from_millis(10),
+            sink.send(fixtures::v10::minimal()),
+        )
+        .await;
+        assert!(cancelled.is_err());
+
+        sink.send(fixtures::v10::minimal()).await.unwrap();
+        // Sent in the released slot at 100ms rather than at 200ms
+        assert_eq!(start.elapsed(), Duration::from_millis(120));
+        assert_eq!(sink.sink().sent.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn release_slots() {
+        let interval = Duration::from_millis(10);
+        let start = Instant::now();
+        let mut slots = Slots {
+            next: start + interval * 3,
+            released: Vec::new(),
+        };
+
+        slots.release(start + interval, interval);
+        assert_eq!(slots.next, start + interval * 3);
+        assert_eq!(slots.released, vec![start + interval]);
+
+        slots.release(start + interval * 2, interval);
+        assert_eq!(slots.next, start + interval);
+        assert!(slots.released.is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn drop_oldest() {
+        let sink = RateLimited::new(
+            SlowSink::default(),
+            RateLimitOptions::new()
+                .max_in_flight(1)
+                .max_queued(2)
+                .overflow(OverflowPolicy::DropOldest),
+        );
+
+        let send = || sink.send(fixtures::v10::minimal());
+        let results = tokio::join!(send(), send(), send(), send());
+
+        assert!(results.0.is_ok());
+        assert!(matches!(results.1, Err(Error::RateLimited {})));
+        assert!(results.2.is_ok());
+        assert!(results.3.is_ok());
+        assert_eq!(sink.sink().sent.load(Ordering::SeqCst), 3);
+    }
+}
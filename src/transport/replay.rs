@@ -0,0 +1,461 @@
+use super::{Error, EventSink, Result};
+use crate::event::WithSecrets;
+use crate::Event;
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::Mutex;
+
+/// Storage of the events sent to a [`ReplayingSink`] until their delivery is acknowledged.
+#[async_trait]
+pub trait ReplayBuffer: Send + Sync {
+    /// Store `event`, returning its sequence number. The sequence numbers are increasing.
+    async fn push(&self, event: &Event) -> Result<u64>;
+
+    /// Remove the event with the sequence number `seq`, once it has been delivered.
+    async fn ack(&self, seq: u64) -> Result<()>;
+
+    /// The events which were not acknowledged yet, ordered by sequence number.
+    async fn pending(&self) -> Result<Vec<(u64, Event)>>;
+
+    /// The first event which was not acknowledged yet with a sequence number greater than
+    /// `after`, if any. Used by [`ReplayingSink`] to replay the events one at a time, without
+    /// copying all the pending events on each send.
+    async fn next_pending(&self, after: Option<u64>) -> Result<Option<(u64, Event)>> {
+        Ok(self
+            .pending()
+            .await?
+            .into_iter()
+            .find(|(seq, _)| after.is_none_or(|after| *seq > after)))
+    }
+}
+
+#[derive(Debug, Default)]
+struct Pending {
+    events: BTreeMap<u64, Event>,
+    next_seq: u64,
+}
+
+impl Pending {
+    fn insert(&mut self, seq: u64, event: Event) {
+        self.events.insert(seq, event);
+        self.next_seq = self.next_seq.max(seq + 1);
+    }
+
+    fn next_after(&self, after: Option<u64>) -> Option<(u64, Event)> {
+        let start = after.map_or(Bound::Unbounded, Bound::Excluded);
+        self.events
+            .range((start, Bound::Unbounded))
+            .next()
+            .map(|(seq, event)| (*seq, event.clone()))
+    }
+
+    fn to_vec(&self) -> Vec<(u64, Event)> {
+        self.events
+            .iter()
+            .map(|(seq, event)| (*seq, event.clone()))
+            .collect()
+    }
+}
+
+/// [`ReplayBuffer`] keeping at most `capacity` events in memory.
+///
+/// The buffered events are lost when the process exits.
+#[derive(Debug)]
+pub struct MemoryReplayBuffer {
+    pending: std::sync::Mutex<Pending>,
+    capacity: usize,
+}
+
+impl MemoryReplayBuffer {
+    /// Create a new [`MemoryReplayBuffer`]. Pushing an event when `capacity` events are
+    /// buffered fails with [`Error::ReplayBufferFull`].
+    pub fn new(capacity: usize) -> Self {
+        MemoryReplayBuffer {
+            pending: Default::default(),
+            capacity,
+        }
+    }
+}
+
+#[async_trait]
+impl ReplayBuffer for MemoryReplayBuffer {
+    async fn push(&self, event: &Event) -> Result<u64> {
+        let mut pending = self.pending.lock().unwrap();
+        if pending.events.len() >= self.capacity {
+            return Err(Error::ReplayBufferFull {});
+        }
+        let seq = pending.next_seq;
+        pending.insert(seq, event.clone());
+        Ok(seq)
+    }
+
+    async fn ack(&self, seq: u64) -> Result<()> {
+        self.pending.lock().unwrap().events.remove(&seq);
+        Ok(())
+    }
+
+    async fn pending(&self) -> Result<Vec<(u64, Event)>> {
+        Ok(self.pending.lock().unwrap().to_vec())
+    }
+
+    async fn next_pending(&self, after: Option<u64>) -> Result<Option<(u64, Event)>> {
+        Ok(self.pending.lock().unwrap().next_after(after))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Entry {
+    Push { seq: u64, event: Box<Event> },
+    Ack { ack: u64 },
+}
+
+/// [`ReplayBuffer`] persisting the events in a journal file, so the events which were not
+/// delivered are replayed after a restart.
+///
+/// The journal is a [JSON lines](https://jsonlines.org/) file, with a line per buffered and
+/// per acknowledged event. It is truncated whenever all the events are acknowledged, and
+/// compacted when opened. The events are written to the file before [`ReplayBuffer::push`]
+/// returns, but not synced to the disk.
+#[derive(Debug)]
+pub struct FileReplayBuffer {
+    state: Mutex<(File, Pending)>,
+}
+
+impl FileReplayBuffer {
+    /// Open the journal file at `path`, creating it if it doesn't exist, and load the events
+    /// which were not acknowledged.
+    ///
+    /// A truncated last line, left by an interrupted write, is ignored.
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let pending = match File::open(path).await {
+            Ok(file) => load(file).await?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Pending::default(),
+            Err(e) => return Err(Error::transport(e)),
+        };
+
+        // Compact the journal, replacing it atomically
+        let mut compacted = path.as_os_str().to_owned();
+        compacted.push(".tmp");
+        let compacted = PathBuf::from(compacted);
+        let mut file = File::create(&compacted).await.map_err(Error::transport)?;
+        for (seq, event) in &pending.events {
+            write_line(
+                &mut file,
+                &serde_json::json!({ "seq": seq, "event": WithSecrets(event) }),
+            )
+            .await?;
+        }
+        file.sync_all().await.map_err(Error::transport)?;
+        tokio::fs::rename(&compacted, path)
+            .await
+            .map_err(Error::transport)?;
+
+        let file = OpenOptions::new()
+            .append(true)
+            .open(path)
+            .await
+            .map_err(Error::transport)?;
+        Ok(FileReplayBuffer {
+            state: Mutex::new((file, pending)),
+        })
+    }
+}
+
+async fn load(file: File) -> Result<Pending> {
+    let mut pending = Pending::default();
+    let mut lines = BufReader::new(file).lines();
+    let mut invalid = None;
+    while let Some(line) = lines.next_line().await.map_err(Error::transport)? {
+        if let Some(e) = invalid.take() {
+            return Err(Error::transport(e));
+        }
+        match serde_json::from_str(&line) {
+            Ok(Entry::Push { seq, event }) => pending.insert(seq, *event),
+            Ok(Entry::Ack { ack }) => {
+                pending.events.remove(&ack);
+            }
+            Err(e) => invalid = Some(e),
+        }
+    }
+    Ok(pending)
+}
+
+async fn write_line(file: &mut File, entry: &serde_json::Value) -> Result<()> {
+    let mut line = serde_json::to_vec(entry).map_err(crate::message::Error::from)?;
+    line.push(b'\n');
+    file.write_all(&line).await.map_err(Error::transport)?;
+    file.flush().await.map_err(Error::transport)
+}
+
+#[async_trait]
+impl ReplayBuffer for FileReplayBuffer {
+    async fn push(&self, event: &Event) -> Result<u64> {
+        let mut state = self.state.lock().await;
+        let (file, pending) = &mut *state;
+        let seq = pending.next_seq;
+        write_line(
+            file,
+            &serde_json::json!({ "seq": seq, "event": WithSecrets(event) }),
+        )
+        .await?;
+        pending.insert(seq, event.clone());
+        Ok(seq)
+    }
+
+    async fn ack(&self, seq: u64) -> Result<()> {
+        let mut state = self.state.lock().await;
+        let (file, pending) = &mut *state;
+        if pending.events.remove(&seq).is_none() {
+            return Ok(());
+        }
+        if pending.events.is_empty() {
+            file.set_len(0).await.map_err(Error::transport)
+        } else {
+            write_line(file, &serde_json::json!({ "ack": seq })).await
+        }
+    }
+
+    async fn pending(&self) -> Result<Vec<(u64, Event)>> {
+        Ok(self.state.lock().await.1.to_vec())
+    }
+
+    async fn next_pending(&self, after: Option<u64>) -> Result<Option<(u64, Event)>> {
+        Ok(self.state.lock().await.1.next_after(after))
+    }
+}
+
+/// [`EventSink`] wrapper storing the events in a [`ReplayBuffer`] until they are delivered,
+/// for at-least-once delivery over flaky broker connections, e.g. with the `NatsSink` or the
+/// `MqttSink`.
+///
+/// [`ReplayingSink::send`] buffers the event, then sends the pending events in order, stopping
+/// at the first failure. The events which could not be delivered stay in the buffer, to be
+/// replayed by the next send or by [`ReplayingSink::replay`], e.g. once the connection is
+/// re-established.
+///
+/// ```
+/// use cloudevents::transport::{EventSink, MemoryReplayBuffer, ReplayingSink, Result};
+/// use cloudevents::Event;
+///
+/// async fn publish(sink: impl EventSink, events: Vec<Event>) -> Result<()> {
+///     let sink = ReplayingSink::new(sink, MemoryReplayBuffer::new(10_000));
+///     for event in events {
+///         // Succeeds as long as the event could be buffered
+///         sink.send(event).await?;
+///     }
+///     // Fails if some events could still not be delivered
+///     sink.replay().await
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ReplayingSink<S, B> {
+    sink: S,
+    buffer: B,
+    delivery: Mutex<()>,
+}
+
+impl<S: EventSink, B: ReplayBuffer> ReplayingSink<S, B> {
+    /// Create a new [`ReplayingSink`] sending the events to `sink`, buffering them in `buffer`
+    /// until they are delivered.
+    pub fn new(sink: S, buffer: B) -> Self {
+        ReplayingSink {
+            sink,
+            buffer,
+            delivery: Mutex::new(()),
+        }
+    }
+
+    /// The wrapped sink.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// The replay buffer.
+    pub fn buffer(&self) -> &B {
+        &self.buffer
+    }
+
+    /// Send the pending events in order, acknowledging each delivered event, and returning the
+    /// first delivery failure.
+    pub async fn replay(&self) -> Result<()> {
+        self.deliver().await?
+    }
+
+    /// Send the pending events in order, stopping at the first delivery failure, returned in
+    /// the inner result, while the outer one carries the errors of the buffer.
+    async fn deliver(&self) -> Result<Result<()>> {
+        let _delivery = self.delivery.lock().await;
+        let mut after = None;
+        while let Some((seq, event)) = self.buffer.next_pending(after).await? {
+            if let Err(e) = self.sink.send(event).await {
+                return Ok(Err(e));
+            }
+            self.buffer.ack(seq).await?;
+            after = Some(seq);
+        }
+        Ok(Ok(()))
+    }
+}
+
+#[async_trait]
+impl<S: EventSink, B: ReplayBuffer> EventSink for ReplayingSink<S, B> {
+    /// Buffer `event` and send the pending events. Fails only on the errors of the buffer:
+    /// delivery failures leave the events in the buffer.
+    async fn send(&self, event: Event) -> Result<()> {
+        self.buffer.push(&event).await?;
+        let _delivery_failure = self.deliver().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::fixtures;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[derive(Default)]
+    struct FlakySink {
+        events: std::sync::Mutex<Vec<Event>>,
+        down: AtomicBool,
+    }
+
+    #[async_trait]
+    impl EventSink for FlakySink {
+        async fn send(&self, event: Event) -> Result<()> {
+            if self.down.load(Ordering::SeqCst) {
+                return Err(Error::transport("connection lost"));
+            }
+            self.events.lock().unwrap().push(event);
+            Ok(())
+        }
+    }
+
+    /// Buffer failing to acknowledge the events, e.g. because the disk is full.
+    struct UnackableBuffer(MemoryReplayBuffer);
+
+    #[async_trait]
+    impl ReplayBuffer for UnackableBuffer {
+        async fn push(&self, event: &Event) -> Result<u64> {
+            self.0.push(event).await
+        }
+
+        async fn ack(&self, _: u64) -> Result<()> {
+            Err(Error::transport("no space left on device"))
+        }
+
+        async fn pending(&self) -> Result<Vec<(u64, Event)>> {
+            self.0.pending().await
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "cloudevents-{}-{}-{}",
+            std::process::id(),
+            uuid::Uuid::new_v4(),
+            name
+        ))
+    }
+
+    #[tokio::test]
+    async fn replay_after_reconnect() {
+        let sink = ReplayingSink::new(FlakySink::default(), MemoryReplayBuffer::new(2));
+
+        sink.send(fixtures::v10::minimal()).await.unwrap();
+        sink.sink().down.store(true, Ordering::SeqCst);
+        sink.send(fixtures::v03::minimal()).await.unwrap();
+        sink.send(fixtures::v10::full_json_data()).await.unwrap();
+        assert!(matches!(
+            sink.send(fixtures::v03::full_json_data()).await,
+            Err(Error::ReplayBufferFull {})
+        ));
+        assert!(sink.replay().await.is_err());
+        assert_eq!(sink.buffer().pending().await.unwrap().len(), 2);
+
+        sink.sink().down.store(false, Ordering::SeqCst);
+        sink.replay().await.unwrap();
+
+        assert!(sink.buffer().pending().await.unwrap().is_empty());
+        assert_eq!(
+            *sink.sink().events.lock().unwrap(),
+            vec![
+                fixtures::v10::minimal(),
+                fixtures::v03::minimal(),
+                fixtures::v10::full_json_data()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn send_returns_buffer_errors() {
+        let sink = ReplayingSink::new(
+            FlakySink::default(),
+            UnackableBuffer(MemoryReplayBuffer::new(10)),
+        );
+
+        assert!(matches!(
+            sink.send(fixtures::v10::minimal()).await,
+            Err(Error::TransportError { .. })
+        ));
+        assert_eq!(sink.buffer().pending().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn file_buffer_survives_restart() {
+        let path = temp_path("replay.jsonl");
+        let buffer = FileReplayBuffer::open(&path).await.unwrap();
+        let first = buffer.push(&fixtures::v10::minimal()).await.unwrap();
+        let second = buffer.push(&fixtures::v03::minimal()).await.unwrap();
+        buffer.push(&fixtures::v10::full_json_data()).await.unwrap();
+        buffer.ack(second).await.unwrap();
+        drop(buffer);
+
+        // Simulate a write interrupted by a crash
+        let mut file = OpenOptions::new().append(true).open(&path).await.unwrap();
+        file.write_all(b"{\"seq\": 3, \"ev").await.unwrap();
+        drop(file);
+
+        let buffer = FileReplayBuffer::open(&path).await.unwrap();
+        let pending = buffer.pending().await.unwrap();
+        assert_eq!(
+            pending,
+            vec![
+                (first, fixtures::v10::minimal()),
+                (2, fixtures::v10::full_json_data())
+            ]
+        );
+        assert_eq!(buffer.push(&fixtures::v10::minimal()).await.unwrap(), 3);
+
+        for seq in [first, 2, 3] {
+            buffer.ack(seq).await.unwrap();
+        }
+        assert_eq!(tokio::fs::metadata(&path).await.unwrap().len(), 0);
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn file_buffer_keeps_sensitive_extensions() {
+        let path = temp_path("sensitive.jsonl");
+        let mut event = fixtures::v10::full_json_data_string_extension();
+        event.set_extension("authtoken", "s3cr3t");
+        event.mark_sensitive_extension("authtoken");
+
+        let buffer = FileReplayBuffer::open(&path).await.unwrap();
+        let seq = buffer.push(&event).await.unwrap();
+        drop(buffer);
+
+        let buffer = FileReplayBuffer::open(&path).await.unwrap();
+        assert_eq!(buffer.pending().await.unwrap(), vec![(seq, event.clone())]);
+        assert_eq!(buffer.next_pending(None).await.unwrap(), Some((seq, event)));
+        assert_eq!(buffer.next_pending(Some(seq)).await.unwrap(), None);
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+}
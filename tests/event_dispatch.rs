@@ -0,0 +1,76 @@
+#![cfg(feature = "derive")]
+
+use cloudevents::event::{EventDispatch, EventDispatchError};
+use cloudevents::{Event, EventBuilder, EventBuilderV10};
+use serde::Deserialize;
+use serde_json::json;
+use std::convert::TryFrom;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Created {
+    id: u64,
+}
+
+#[derive(Debug, PartialEq, EventDispatch)]
+enum OrderEvent {
+    #[cloudevents(ty = "order.created")]
+    Created(Created),
+    #[cloudevents(ty = "order.note")]
+    Note(Option<String>),
+    #[cloudevents(ty = "order.purged")]
+    Purged,
+}
+
+fn event(ty: &str, data: Option<serde_json::Value>) -> Event {
+    let builder = EventBuilderV10::new()
+        .id("0001")
+        .ty(ty)
+        .source("http://localhost/");
+    match data {
+        Some(data) => builder.data("application/json", data),
+        None => builder,
+    }
+    .build()
+    .unwrap()
+}
+
+#[test]
+fn event_types() {
+    assert_eq!(
+        OrderEvent::EVENT_TYPES,
+        &["order.created", "order.note", "order.purged"]
+    );
+    assert_eq!(OrderEvent::Purged.event_type(), "order.purged");
+}
+
+#[test]
+fn dispatch() {
+    assert_eq!(
+        OrderEvent::try_from(event("order.created", Some(json!({"id": 1})))).unwrap(),
+        OrderEvent::Created(Created { id: 1 })
+    );
+    assert_eq!(
+        OrderEvent::try_from(event("order.note", None)).unwrap(),
+        OrderEvent::Note(None)
+    );
+    assert_eq!(
+        OrderEvent::try_from(event("order.note", Some(json!("fragile")))).unwrap(),
+        OrderEvent::Note(Some("fragile".to_string()))
+    );
+    assert_eq!(
+        OrderEvent::try_from(event("order.purged", None)).unwrap(),
+        OrderEvent::Purged
+    );
+}
+
+#[test]
+fn dispatch_errors() {
+    assert!(matches!(
+        OrderEvent::try_from(event("order.shipped", None)),
+        Err(EventDispatchError::UnknownType { ty }) if ty == "order.shipped"
+    ));
+    assert!(matches!(
+        OrderEvent::try_from(event("order.created", Some(json!({"id": "1"})))),
+        Err(EventDispatchError::InvalidData { ty, .. }) if ty == "order.created"
+    ));
+}